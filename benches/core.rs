@@ -0,0 +1,132 @@
+//! Benchmarks for the hot paths: hashing, subtree checks, registry building
+//! and `TagContainer` queries.
+//!
+//! To catch regressions, record a baseline on the main branch and compare a
+//! change against it; criterion reports every benchmark that moved beyond
+//! its noise threshold:
+//!
+//! ```text
+//! git checkout main && cargo bench --bench core -- --save-baseline main
+//! git checkout my-branch && cargo bench --bench core -- --baseline main
+//! ```
+
+use std::hint::black_box;
+
+use bevy_tag::bevy::TagContainer;
+use bevy_tag::{gid_is_descendant_of, hierarchical_gid, NamespaceDef, NamespaceRegistry, GID};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+/// `Group{g}.Set{s}.Tag{t}` for a tree of about `count` nodes, parents first.
+fn tree_paths(count: usize) -> Vec<String> {
+    let (groups, sets) = (10, 30);
+    let tags = (count / (groups * sets)).max(1);
+    let mut paths = Vec::new();
+    for g in 0..groups {
+        paths.push(format!("Group{g}"));
+        for s in 0..sets {
+            paths.push(format!("Group{g}.Set{s}"));
+            for t in 0..tags {
+                paths.push(format!("Group{g}.Set{s}.Tag{t}"));
+            }
+        }
+    }
+    paths
+}
+
+/// Static definitions for `paths` (leaked, as `namespace!` tables are `'static`).
+fn definitions(paths: &[String]) -> &'static [NamespaceDef] {
+    let defs: Vec<NamespaceDef> = paths
+        .iter()
+        .map(|path| {
+            let path: &'static str = Box::leak(path.clone().into_boxed_str());
+            NamespaceDef::new(path, path.rsplit_once('.').map(|(parent, _)| parent))
+        })
+        .collect();
+    Box::leak(defs.into_boxed_slice())
+}
+
+fn hashing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hierarchical_gid");
+    let segments: [&[u8]; 8] = [
+        b"Combat", b"Melee", b"Sword", b"Heavy", b"Charged", b"Fire", b"Crit", b"Final",
+    ];
+    for depth in [1, 4, 8] {
+        group.bench_with_input(BenchmarkId::from_parameter(depth), &depth, |b, &depth| {
+            b.iter(|| hierarchical_gid(black_box(&segments[..depth])))
+        });
+    }
+    group.finish();
+}
+
+fn descendant_check(c: &mut Criterion) {
+    let ancestor = hierarchical_gid(&[b"Combat", b"Melee"]);
+    let inside = hierarchical_gid(&[b"Combat", b"Melee", b"Sword"]);
+    let outside = hierarchical_gid(&[b"Movement", b"Idle"]);
+    c.bench_function("gid_is_descendant_of", |b| {
+        b.iter(|| {
+            gid_is_descendant_of(black_box(inside), black_box(ancestor))
+                && !gid_is_descendant_of(black_box(outside), black_box(ancestor))
+        })
+    });
+}
+
+fn registry(c: &mut Criterion) {
+    let paths = tree_paths(10_000);
+    let defs = definitions(&paths);
+    let built = NamespaceRegistry::build(defs).unwrap();
+
+    let mut group = c.benchmark_group("registry");
+    group.throughput(Throughput::Elements(defs.len() as u64));
+    group.sample_size(20);
+    group.bench_function("build_10k", |b| {
+        b.iter(|| NamespaceRegistry::build(black_box(defs)).unwrap())
+    });
+    group.bench_function("register_10k", |b| {
+        b.iter_batched(
+            NamespaceRegistry::new,
+            |mut registry| {
+                for path in &paths {
+                    registry.register(path).unwrap();
+                }
+                registry
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+
+    let ancestor = built.gid_of("Group3.Set7").unwrap();
+    c.bench_function("registry/descendants_of_10k", |b| {
+        b.iter(|| built.descendants_of(black_box(ancestor)))
+    });
+}
+
+fn container(c: &mut Criterion) {
+    let paths = tree_paths(10_000);
+    let gids: Vec<GID> = paths
+        .iter()
+        .map(|p| {
+            let segments: Vec<&[u8]> = p.split('.').map(str::as_bytes).collect();
+            hierarchical_gid(&segments)
+        })
+        .collect();
+    let hit = hierarchical_gid(&[b"Group9"]);
+    let miss = hierarchical_gid(&[b"Group10"]);
+
+    let mut group = c.benchmark_group("TagContainer::has_descendant_of");
+    for size in [1, 4, 32] {
+        // The last tags are all under Group9: a hit stops at the first tag, a
+        // miss scans every one
+        let tags: TagContainer = gids[gids.len() - size..].iter().copied().collect();
+        group.bench_with_input(BenchmarkId::new("hit", size), &tags, |b, tags| {
+            b.iter(|| tags.has_descendant_of(black_box(hit)))
+        });
+        group.bench_with_input(BenchmarkId::new("miss", size), &tags, |b, tags| {
+            b.iter(|| tags.has_descendant_of(black_box(miss)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, hashing, descendant_check, registry, container);
+criterion_main!(benches);
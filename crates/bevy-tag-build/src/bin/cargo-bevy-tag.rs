@@ -0,0 +1,374 @@
+//! `cargo bevy-tag`: inspect `tags.toml` and `tags.lock.toml` without
+//! compiling the game.
+//!
+//! ```text
+//! cargo bevy-tag validate            # parse config, verify lock entries and GIDs
+//! cargo bevy-tag diff                # config vs lock (exit 1 on removals)
+//! cargo bevy-tag gid Item.Weapon     # GID of a path
+//! cargo bevy-tag path 0x2000...      # path of a GID
+//! cargo bevy-tag tree                # pretty-print the tree
+//! cargo bevy-tag export              # JSON GID manifest
+//! cargo bevy-tag dot | dot -Tsvg     # GraphViz diagram
+//! cargo bevy-tag mermaid             # Mermaid diagram
+//! cargo bevy-tag import-ue Config/DefaultGameplayTags.ini > tags.toml
+//! cargo bevy-tag merge-lock          # resolve conflict markers in the lock
+//! cargo bevy-tag migrations > tags.migrate  # old -> new GIDs for saved data
+//! cargo bevy-tag merge-lock other.lock.toml  # merge another lock into it
+//! ```
+//!
+//! All subcommands accept `--config <file>` (default `tags.toml`) and
+//! `--lock <file>` (default next to the config).
+
+use bevy_tag_build::{
+    detect_renames, export_dot, export_mermaid, find_collisions, format_gid, level_occupancy,
+    path_gid, ue_ini_to_toml, LockFile, Manifest, TagsConfig,
+};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+usage: cargo bevy-tag <command> [--config <file>] [--lock <file>]
+
+commands:
+  validate      parse the config and verify the lock entries and GIDs
+  diff          show paths added/removed relative to the lock
+  gid <path>    print the GID of a path
+  path <gid>    print the path of a GID (hex or decimal)
+  tree          print the tag tree
+  export        print the JSON GID manifest
+  dot           print the tag tree as a GraphViz digraph
+  mermaid       print the tag tree as a Mermaid flowchart
+  import-ue <ini>  convert an Unreal GameplayTags .ini to tags.toml
+  merge-lock [<other>]  merge another lock file into the lock, or resolve
+                the git conflict markers in it
+  migrations    print old -> new GIDs of redirected or rehashed locked paths,
+                for bevy_tag::migrate";
+
+/// Parsed command line.
+struct Args {
+    command: String,
+    operand: Option<String>,
+    config: PathBuf,
+    lock: Option<PathBuf>,
+}
+
+impl Args {
+    fn parse() -> Result<Self, String> {
+        let mut args = std::env::args().skip(1).peekable();
+        // Invoked as `cargo bevy-tag ...`, cargo passes the subcommand name first
+        if args.peek().map(String::as_str) == Some("bevy-tag") {
+            args.next();
+        }
+
+        let mut positional = Vec::new();
+        let mut config = PathBuf::from("tags.toml");
+        let mut lock = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--config" => config = args.next().ok_or("--config needs a file")?.into(),
+                "--lock" => lock = Some(args.next().ok_or("--lock needs a file")?.into()),
+                "-h" | "--help" => return Err(String::new()),
+                _ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let command = positional.next().ok_or_else(String::new)?;
+        let operand = positional.next();
+        if let Some(extra) = positional.next() {
+            return Err(format!("unexpected argument '{}'", extra));
+        }
+        Ok(Self {
+            command,
+            operand,
+            config,
+            lock,
+        })
+    }
+
+    fn lock_path(&self) -> PathBuf {
+        self.lock
+            .clone()
+            .unwrap_or_else(|| self.config.with_extension("lock.toml"))
+    }
+
+    fn load_config(&self) -> Result<TagsConfig, String> {
+        TagsConfig::from_file(&self.config).map_err(|e| e.to_string())
+    }
+
+    /// The lock file, if one exists.
+    fn load_lock(&self) -> Result<Option<LockFile>, String> {
+        let path = self.lock_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        LockFile::from_file(&path).map(Some).map_err(|e| e.to_string())
+    }
+
+    fn operand(&self, what: &str) -> Result<&str, String> {
+        self.operand
+            .as_deref()
+            .ok_or_else(|| format!("'{}' needs a {}", self.command, what))
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(msg) => {
+            if !msg.is_empty() {
+                eprintln!("error: {}\n", msg);
+            }
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match args.command.as_str() {
+        "validate" => validate(&args),
+        "diff" => diff(&args),
+        "gid" => gid(&args),
+        "path" => path(&args),
+        "tree" => tree(&args),
+        "merge-lock" => merge_lock(&args),
+        "migrations" => migrations(&args),
+        "export" => export(&args),
+        "dot" => diagram(&args, export_dot),
+        "mermaid" => diagram(&args, export_mermaid),
+        "import-ue" => import_ue(&args),
+        other => Err(format!("unknown command '{}'\n\n{}", other, USAGE)),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn validate(args: &Args) -> Result<ExitCode, String> {
+    let config = args.load_config()?;
+    println!("{}: {} tags, {} redirects", args.config.display(), config.len(), config.redirects().count());
+    for warning in config.policy_warnings() {
+        eprintln!("warning: [policy] {}", warning);
+    }
+    let paths: Vec<&str> = config.entries().map(|e| e.path.as_str()).collect();
+    let collisions = find_collisions(paths.iter().copied());
+    for c in &collisions {
+        eprintln!("GID collision: {} and {}: {}", c.first, c.second, format_gid(c.gid));
+    }
+    if !collisions.is_empty() {
+        return Err(format!("{} GID collision(s)", collisions.len()));
+    }
+    for level in level_occupancy(paths.iter().copied()) {
+        println!("{}", level);
+    }
+
+    if let Some(lock) = args.load_lock()? {
+        lock.verify_entries()
+            .map_err(|e| format!("{}: {}", args.lock_path().display(), e))?;
+        let mismatches = lock.verify_gids();
+        if !mismatches.is_empty() {
+            for m in &mismatches {
+                eprintln!("GID changed: {}: {} -> {}", m.path, m.locked, m.computed);
+            }
+            return Err(format!("{} locked GID(s) no longer match", mismatches.len()));
+        }
+        println!("{}: {} entries, GIDs verified", args.lock_path().display(), lock.entries.len());
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn diff(args: &Args) -> Result<ExitCode, String> {
+    let config = args.load_config()?;
+    let Some(lock) = args.load_lock()? else {
+        println!("no lock file at {}; every path is new", args.lock_path().display());
+        return Ok(ExitCode::SUCCESS);
+    };
+
+    let diff = lock.diff(&config);
+    let mut added = diff.added.clone();
+    let (mut allowed, mut removed): (Vec<_>, Vec<_>) = diff
+        .removed
+        .iter()
+        .filter(|p| lock.get(p).is_some_and(|e| !e.deprecated))
+        .cloned()
+        .partition(|p| config.is_removal_allowed(p));
+    added.sort();
+    removed.sort();
+    allowed.sort();
+
+    for path in &added {
+        println!("+ {}", path);
+    }
+    for path in &removed {
+        println!("- {}", path);
+    }
+    for path in &allowed {
+        println!("- {}  (allowed)", path);
+    }
+    for rename in detect_renames(&lock, &config, &diff) {
+        println!("~ {} -> {}  (suggest: {})", rename.from, rename.to, rename.redirect_entry());
+    }
+    if added.is_empty() && removed.is_empty() && allowed.is_empty() {
+        println!("config matches lock");
+    }
+
+    // Removals break the build under the default `on_remove = "error"`
+    Ok(if removed.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn gid(args: &Args) -> Result<ExitCode, String> {
+    let path = args.operand("path")?;
+    let gid = path_gid(path).ok_or_else(|| format!("'{}' is deeper than the GID layout allows", path))?;
+    println!("{}", format_gid(gid));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn path(args: &Args) -> Result<ExitCode, String> {
+    let raw = args.operand("GID")?;
+    let gid = parse_gid(raw).ok_or_else(|| format!("'{}' is not a GID", raw))?;
+
+    // Search everything the game could have been built with
+    let config = args.load_config()?;
+    let lock = args.load_lock()?;
+    let candidates = config
+        .entries()
+        .map(|e| e.path.as_str())
+        .chain(config.redirects().map(|r| r.from.as_str()))
+        .chain(lock.iter().flat_map(|l| l.entries.iter().map(|e| e.path.as_str())));
+
+    for candidate in candidates {
+        if path_gid(candidate) == Some(gid) {
+            println!("{}", candidate);
+            return Ok(ExitCode::SUCCESS);
+        }
+    }
+    Err(format!("no tag with GID {}", format_gid(gid)))
+}
+
+fn parse_gid(raw: &str) -> Option<u128> {
+    let raw = raw.replace('_', "");
+    match raw.strip_prefix("0x").or_else(|| raw.strip_prefix("0X")) {
+        Some(hex) => u128::from_str_radix(hex, 16).ok(),
+        None => raw.parse().ok(),
+    }
+}
+
+/// Tree node for printing, with an annotation per path.
+#[derive(Default)]
+struct TreeNode {
+    note: Option<String>,
+    children: BTreeMap<String, TreeNode>,
+}
+
+impl TreeNode {
+    fn insert(&mut self, path: &str) -> &mut TreeNode {
+        path.split('.')
+            .fold(self, |node, seg| node.children.entry(seg.to_string()).or_default())
+    }
+
+    fn print(&self, prefix: &str) {
+        let count = self.children.len();
+        for (i, (name, child)) in self.children.iter().enumerate() {
+            let last = i + 1 == count;
+            let note = child.note.as_deref().map(|n| format!("  ({})", n)).unwrap_or_default();
+            println!("{}{}{}{}", prefix, if last { "└── " } else { "├── " }, name, note);
+            child.print(&format!("{}{}", prefix, if last { "    " } else { "│   " }));
+        }
+    }
+}
+
+fn tree(args: &Args) -> Result<ExitCode, String> {
+    let config = args.load_config()?;
+    let lock = args.load_lock()?;
+
+    let mut root = TreeNode::default();
+    for entry in config.entries() {
+        let node = root.insert(&entry.path);
+        if let Some(ty) = config.data_of(&entry.path) {
+            node.note = Some(format!("data: {}", ty));
+        }
+        if config.deprecation_note(&entry.path).is_some() {
+            node.note = Some("deprecated".into());
+        }
+    }
+    for entry in lock.iter().flat_map(|l| l.deprecated_entries()) {
+        root.insert(&entry.path).note = Some(match &entry.equivalent_to {
+            Some(target) => format!("removed, redirects to {}", target),
+            None => "removed".into(),
+        });
+    }
+    for redirect in config.redirects() {
+        root.insert(&redirect.from).note = Some(format!("redirects to {}", redirect.to));
+    }
+
+    println!("{}", config.module_name);
+    root.print("");
+    Ok(ExitCode::SUCCESS)
+}
+
+fn export(args: &Args) -> Result<ExitCode, String> {
+    let config = args.load_config()?;
+    let lock = args.load_lock()?;
+    println!("{}", Manifest::new(&config, lock.as_ref()).to_json());
+    Ok(ExitCode::SUCCESS)
+}
+
+fn diagram(
+    args: &Args,
+    render: fn(&TagsConfig, Option<&LockFile>) -> String,
+) -> Result<ExitCode, String> {
+    let config = args.load_config()?;
+    let lock = args.load_lock()?;
+    print!("{}", render(&config, lock.as_ref()));
+    Ok(ExitCode::SUCCESS)
+}
+
+fn import_ue(args: &Args) -> Result<ExitCode, String> {
+    let ini_path = args.operand("GameplayTags .ini file")?;
+    let ini = std::fs::read_to_string(ini_path).map_err(|e| format!("{}: {}", ini_path, e))?;
+    let toml = ue_ini_to_toml(&ini).map_err(|e| e.to_string())?;
+    // Fail on tags that are not valid here (e.g. not Rust identifiers)
+    TagsConfig::from_str(&toml).map_err(|e| e.to_string())?;
+    print!("{}", toml);
+    Ok(ExitCode::SUCCESS)
+}
+
+fn merge_lock(args: &Args) -> Result<ExitCode, String> {
+    let lock_path = args.lock_path();
+    let read = |path: &std::path::Path| {
+        std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+    };
+    let content = read(&lock_path)?;
+
+    let merged = match &args.operand {
+        Some(other) => {
+            let ours = LockFile::from_str(&content).map_err(|e| e.to_string())?;
+            let theirs = LockFile::from_str(&read(other.as_ref())?).map_err(|e| e.to_string())?;
+            LockFile::merge(&ours, &theirs)
+        }
+        None => LockFile::resolve_conflicts(&content),
+    }
+    .map_err(|e| format!("{}: {}", lock_path.display(), e))?;
+
+    merged.write_to_file(&lock_path).map_err(|e| e.to_string())?;
+    println!("{}: {} entries", lock_path.display(), merged.entries.len());
+    Ok(ExitCode::SUCCESS)
+}
+
+fn migrations(args: &Args) -> Result<ExitCode, String> {
+    let config = args.load_config()?;
+    let Some(lock) = args.load_lock()? else {
+        return Err(format!("no lock file at {}", args.lock_path().display()));
+    };
+    println!("# GID migrations for {} (old new)", args.lock_path().display());
+    for migration in lock.gid_migrations(&config) {
+        println!("{}", migration);
+    }
+    Ok(ExitCode::SUCCESS)
+}
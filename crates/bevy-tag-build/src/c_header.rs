@@ -0,0 +1,176 @@
+//! C/C++ header generation for native plugins and middleware.
+//!
+//! GIDs are 128-bit, which C has no portable type for, so each tag is
+//! emitted as a pair of `uint64_t` halves plus a `bevy_tag_gid_t` value:
+//!
+//! ```c
+//! #define TAGS_Item_Weapon_HI 0x2000000000000000ULL
+//! #define TAGS_Item_Weapon_LO 0x000000000a1b2c3dULL
+//! #define TAGS_Item_Weapon BEVY_TAG_GID(TAGS_Item_Weapon_HI, TAGS_Item_Weapon_LO)
+//!
+//! if (bevy_tag_is_descendant_of(gid, TAGS_Item)) { ... }
+//! ```
+//!
+//! Compilers with `unsigned __int128` also get `bevy_tag_gid_to_u128`.
+
+use crate::gid::{path_gid, LEVEL_WIDTHS};
+use crate::lock::LockFile;
+use crate::manifest::Manifest;
+use crate::toml_parser::TagsConfig;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Generate a C/C++ header defining every tag's GID.
+///
+/// Covers the same tags as the [`Manifest`]: config paths, redirects (with
+/// their target's GID) and paths kept alive by `lock`. Constants are named
+/// `<MODULE>_<Path_With_Underscores>`; two paths that map to the same name
+/// produce an `#error`.
+pub fn generate_c_header(config: &TagsConfig, lock: Option<&LockFile>) -> String {
+    let prefix = config.module_name.to_uppercase();
+    let guard = format!("BEVY_TAG_{}_H", prefix);
+    let manifest = Manifest::new(config, lock);
+
+    let mut out = String::new();
+    writeln!(out, "// Generated by bevy-tag-build. Do not edit.").unwrap();
+    writeln!(out, "// GID scheme: {}", manifest.gid_scheme).unwrap();
+    writeln!(out, "#ifndef {}", guard).unwrap();
+    writeln!(out, "#define {}", guard).unwrap();
+    writeln!(out).unwrap();
+    out.push_str(&support_code());
+
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for entry in &manifest.tags {
+        let Some(gid) = path_gid(entry.redirect.as_deref().unwrap_or(&entry.path)) else {
+            continue;
+        };
+        let name = format!("{}_{}", prefix, entry.path.replace('.', "_"));
+        writeln!(out).unwrap();
+        if let Some(other) = seen.insert(name.clone(), &entry.path) {
+            writeln!(
+                out,
+                "#error \"{} and {} both map to {}\"",
+                other, entry.path, name
+            )
+            .unwrap();
+            continue;
+        }
+
+        match (&entry.redirect, entry.deprecated) {
+            (Some(target), _) => writeln!(out, "/* {} (deprecated, redirects to {}) */", entry.path, target),
+            (None, true) => writeln!(out, "/* {} (deprecated) */", entry.path),
+            (None, false) => writeln!(out, "/* {} */", entry.path),
+        }
+        .unwrap();
+        writeln!(out, "#define {}_HI 0x{:016x}ULL", name, (gid >> 64) as u64).unwrap();
+        writeln!(out, "#define {}_LO 0x{:016x}ULL", name, gid as u64).unwrap();
+        writeln!(out, "#define {0} BEVY_TAG_GID({0}_HI, {0}_LO)", name).unwrap();
+    }
+
+    writeln!(out).unwrap();
+    writeln!(out, "#endif /* {} */", guard).unwrap();
+    out
+}
+
+/// Shared GID type and helpers, guarded so several generated headers can be
+/// included together.
+fn support_code() -> String {
+    let mut prefix_bits = Vec::with_capacity(LEVEL_WIDTHS.len());
+    let mut total = 0u32;
+    for width in LEVEL_WIDTHS {
+        total += width as u32;
+        prefix_bits.push(total.to_string());
+    }
+
+    format!(
+        r#"#ifndef BEVY_TAG_GID_DEFINED
+#define BEVY_TAG_GID_DEFINED
+
+#include <stdint.h>
+
+/* 128-bit GID: depth in the top 3 bits of `hi`, path segments below. */
+typedef struct bevy_tag_gid_t {{
+    uint64_t hi;
+    uint64_t lo;
+}} bevy_tag_gid_t;
+
+#ifdef __cplusplus
+#define BEVY_TAG_GID(hi, lo) (bevy_tag_gid_t{{(hi), (lo)}})
+#else
+#define BEVY_TAG_GID(hi, lo) ((bevy_tag_gid_t){{(hi), (lo)}})
+#endif
+
+/* Tree depth (0 = root). */
+static inline unsigned bevy_tag_depth(bevy_tag_gid_t gid) {{
+    return (unsigned)(gid.hi >> 61);
+}}
+
+static inline int bevy_tag_gid_eq(bevy_tag_gid_t a, bevy_tag_gid_t b) {{
+    return a.hi == b.hi && a.lo == b.lo;
+}}
+
+/* Is `candidate` a descendant of (or equal to) `ancestor`? */
+static inline int bevy_tag_is_descendant_of(bevy_tag_gid_t candidate, bevy_tag_gid_t ancestor) {{
+    /* Payload bits used by levels 0..=depth */
+    static const unsigned prefix_bits[{len}] = {{{bits}}};
+    unsigned bits = prefix_bits[bevy_tag_depth(ancestor)];
+    uint64_t lo_mask = bits >= 64 ? UINT64_MAX : (UINT64_C(1) << bits) - 1;
+    uint64_t hi_mask = bits <= 64 ? 0 : (UINT64_C(1) << (bits - 64)) - 1;
+    return ((candidate.lo ^ ancestor.lo) & lo_mask) == 0
+        && ((candidate.hi ^ ancestor.hi) & hi_mask) == 0;
+}}
+
+#ifdef __SIZEOF_INT128__
+static inline unsigned __int128 bevy_tag_gid_to_u128(bevy_tag_gid_t gid) {{
+    return ((unsigned __int128)gid.hi << 64) | gid.lo;
+}}
+#endif
+
+#endif /* BEVY_TAG_GID_DEFINED */
+"#,
+        len = prefix_bits.len(),
+        bits = prefix_bits.join(", "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_split_gid_into_halves() {
+        let config = TagsConfig::from_str("[tags]\npaths = [\"Item.Weapon\"]").unwrap();
+        let header = generate_c_header(&config, None);
+        let gid = path_gid("Item.Weapon").unwrap();
+
+        assert!(header.contains(&format!("#define TAGS_Item_Weapon_HI 0x{:016x}ULL", (gid >> 64) as u64)));
+        assert!(header.contains(&format!("#define TAGS_Item_Weapon_LO 0x{:016x}ULL", gid as u64)));
+        assert!(header.contains("#define TAGS_Item BEVY_TAG_GID(TAGS_Item_HI, TAGS_Item_LO)"));
+        assert!(header.contains("prefix_bits[8] = {21, 39, 55, 71, 85, 99, 112, 125}"));
+        assert!(header.starts_with("// Generated"));
+        assert!(header.trim_end().ends_with("#endif /* BEVY_TAG_TAGS_H */"));
+    }
+
+    #[test]
+    fn redirects_use_target_gid() {
+        let toml = r#"
+[tags]
+paths = ["Item.Blade"]
+
+[redirects]
+"Item.Sword" = "Item.Blade"
+"#;
+        let header = generate_c_header(&TagsConfig::from_str(toml).unwrap(), None);
+        let gid = path_gid("Item.Blade").unwrap();
+
+        assert!(header.contains("/* Item.Sword (deprecated, redirects to Item.Blade) */"));
+        assert!(header.contains(&format!("#define TAGS_Item_Sword_LO 0x{:016x}ULL", gid as u64)));
+    }
+
+    #[test]
+    fn colliding_names_are_an_error() {
+        let config = TagsConfig::from_str("[tags]\npaths = [\"A.B_C\", \"A_B.C\"]").unwrap();
+        let header = generate_c_header(&config, None);
+        assert!(header.contains("#error \"A.B_C and A_B.C both map to TAGS_A_B_C\""));
+    }
+}
@@ -1,8 +1,9 @@
 //! Code generation for namespace! macro.
 
+use crate::gid::{format_gid, path_gid};
 use crate::lock::LockFile;
-use crate::toml_parser::TagsConfig;
-use std::collections::HashMap;
+use crate::toml_parser::{MetaValue, TagsConfig, STRING_META_KEYS};
+use std::collections::{BTreeMap, HashMap};
 
 /// Deprecation info for code generation.
 #[derive(Debug, Default, Clone)]
@@ -22,7 +23,9 @@ struct RedirectInfo {
 
 /// Generate Rust code that invokes the `namespace!` macro.
 ///
-/// Output looks like:
+/// The output is already formatted (rustfmt leaves it unchanged) and
+/// deterministic: one node per line with children sorted by name, so adding
+/// or removing a tag changes only its own lines in a diff. Output looks like:
 /// ```ignore
 /// // AUTO-GENERATED by bevy-tag-build - DO NOT EDIT
 /// use bevy_tag_macro::namespace;
@@ -48,7 +51,15 @@ pub fn generate_namespace_code(config: &TagsConfig) -> String {
         .map(|r| (r.from.as_str(), RedirectInfo { target: r.to.clone() }))
         .collect();
 
-    generate_namespace_code_internal(config, &HashMap::new(), &redirect_map)
+    let mut output = generate_namespace_code_internal(config, &HashMap::new(), &redirect_map);
+    let lock = LockFile::from_config(config);
+    if let Some(name) = &config.codegen.redirects {
+        generate_redirects_fn(config, &lock, name, &mut output);
+    }
+    if config.codegen.constants {
+        generate_constants(config, &lock, &mut output);
+    }
+    output
 }
 
 /// Generate Rust code from lock file (includes deprecated entries).
@@ -68,13 +79,28 @@ pub fn generate_namespace_code_from_lock(config: &TagsConfig, lock: &LockFile) -
         })
         .collect();
 
-    // Build redirect map from config
-    let redirect_map: HashMap<&str, RedirectInfo> = config
+    // Build redirect map from config, then add lock entries marked
+    // `equivalent_to` (config redirects take precedence)
+    let mut redirect_map: HashMap<&str, RedirectInfo> = config
         .redirects()
         .map(|r| (r.from.as_str(), RedirectInfo { target: r.to.clone() }))
         .collect();
+    for (path, info) in &deprecation_map {
+        if let Some(target) = &info.alias_of {
+            redirect_map
+                .entry(path)
+                .or_insert_with(|| RedirectInfo { target: target.clone() });
+        }
+    }
 
-    generate_namespace_code_internal(config, &deprecation_map, &redirect_map)
+    let mut output = generate_namespace_code_internal(config, &deprecation_map, &redirect_map);
+    if let Some(name) = &config.codegen.redirects {
+        generate_redirects_fn(config, lock, name, &mut output);
+    }
+    if config.codegen.constants {
+        generate_constants(config, lock, &mut output);
+    }
+    output
 }
 
 /// Generate code with optional deprecated markers and redirects.
@@ -88,7 +114,6 @@ fn generate_namespace_code_internal(
     // Header
     output.push_str("// AUTO-GENERATED by bevy-tag-build - DO NOT EDIT\n");
     output.push_str("// Source: tags.toml\n\n");
-    output.push_str("#![allow(non_snake_case)]\n\n");
     output.push_str("use bevy_tag_macro::namespace;\n\n");
 
     // Build tree structure (from config entries)
@@ -107,90 +132,275 @@ fn generate_namespace_code_internal(
     }
 
     // Generate namespace! macro call
+    let codegen = &config.codegen;
     output.push_str("namespace! {\n");
-    output.push_str(&format!("    pub mod {} {{\n", config.module_name));
+    if !codegen.derive.is_empty() {
+        output.push_str(&format!("    derive({});\n", codegen.derive.join(", ")));
+    }
+    if let Some(doc) = &codegen.doc {
+        for line in doc.lines() {
+            output.push_str(&format!("    ///{}{}\n", if line.is_empty() { "" } else { " " }, line));
+        }
+    }
+    if !codegen.allow.is_empty() {
+        output.push_str(&format!("    #[allow({})]\n", codegen.allow.join(", ")));
+    }
+    output.push_str(&format!("    {}mod {} {{\n", visibility_prefix(config), config.module_name));
 
     // Generate tree recursively
-    generate_tree_code(&tree, "", 2, deprecation_map, redirect_map, &mut output);
+    generate_tree_code(config, &tree, "", 2, deprecation_map, redirect_map, &mut output);
 
     output.push_str("    }\n");
     output.push_str("}\n");
 
-    // Generate type aliases for deprecated paths with equivalent_to
-    let aliases: Vec<_> = deprecation_map
-        .iter()
-        .filter_map(|(path, info)| {
-            info.alias_of.as_ref().map(|target| (*path, target.as_str()))
-        })
-        .collect();
+    if let Some(name) = &config.codegen.registry {
+        generate_registry_fn(config, name, &mut output);
+    }
+
+    output
+}
 
-    if !aliases.is_empty() {
-        output.push_str("\n// ══════════════════════════════════════════════════════════════════════════════\n");
-        output.push_str("// Redirects for renamed paths\n");
-        output.push_str("// ══════════════════════════════════════════════════════════════════════════════\n\n");
+/// Generate `[codegen] registry`: a lazily built `NamespaceRegistry` with the
+/// `[tags.meta]` values stored as metadata.
+///
+/// Integers are stored as `i32`, floats as `f64`, booleans as one byte and
+/// strings as UTF-8, matching the types of the macro's metadata consts.
+fn generate_registry_fn(config: &TagsConfig, name: &str, output: &mut String) {
+    let module = &config.module_name;
+    output.push_str(&format!(
+        "\n/// Runtime registry of every tag in `{}`, built on first use.\n",
+        module
+    ));
+    let mut lints = vec!["deprecated"];
+    lints.extend(config.codegen.allow.iter().map(String::as_str));
+    push_allow(output, &lints);
+    output.push_str(&format!(
+        "{}fn {}() -> &'static ::bevy_tag::NamespaceRegistry {{\n",
+        visibility_prefix(config),
+        name
+    ));
+    output.push_str("    static REGISTRY: ::std::sync::LazyLock<::bevy_tag::NamespaceRegistry> =\n");
+    output.push_str("        ::std::sync::LazyLock::new(|| {\n");
+    output.push_str("            #[allow(unused_mut)]\n");
+    output.push_str(&format!(
+        "            let mut registry = ::bevy_tag::NamespaceRegistry::build({}::DEFINITIONS)\n",
+        module
+    ));
+    output.push_str("                .expect(\"generated tags form a valid registry\");\n");
+
+    let mut paths: Vec<_> = config.entries().map(|e| e.path.as_str()).collect();
+    paths.sort_unstable();
+    for path in paths {
+        let node = format!("{}::{}", module, path.replace('.', "::"));
+        // Gated tags (and their descendants) only exist under their cfg
+        let gate = std::iter::successors(Some(path), |p| p.rsplit_once('.').map(|(parent, _)| parent))
+            .find_map(|p| config.cfg_of(p));
+        for meta in config.meta_of(path) {
+            if STRING_META_KEYS.contains(&meta.key.as_str()) {
+                continue;
+            }
+            if let Some(predicate) = gate {
+                output.push_str(&format!("            #[cfg({})]\n", predicate));
+            }
+            let gid = format!("{}::GID", node);
+            let key = format!("{:?}", meta.key);
+            let value = format!("{}::Tag::{}", node, meta.key.to_uppercase());
+            match meta.value {
+                MetaValue::String(_) => {
+                    let bytes = format!("{}.as_bytes().to_vec()", value);
+                    push_call(output, "registry.set_meta_raw", &[&gid, &key, &bytes]);
+                }
+                _ => push_call(output, "registry.set_meta", &[&gid, &key, &format!("&{}", value)]),
+            }
+        }
+    }
 
-        for (old_path, new_path) in aliases {
-            let old_rust_path = path_to_rust_path(old_path, &config.module_name);
-            let new_rust_path = path_to_rust_path(new_path, &config.module_name);
+    output.push_str("            registry\n");
+    output.push_str("        });\n");
+    output.push_str("    &REGISTRY\n");
+    output.push_str("}\n");
+}
 
-            output.push_str(&format!(
-                "#[deprecated(note = \"redirected to {}\")]\n",
-                new_rust_path
-            ));
-            output.push_str(&format!(
-                "pub type {} = bevy_tag::Redirect<{}>;\n\n",
-                old_rust_path.replace("::", "_"),  // Flatten for top-level type alias
-                new_rust_path
-            ));
+/// Generate `[codegen] redirects`: a lazily built `bevy_tag::migrate::RedirectMap`.
+///
+/// Path redirects come from `[redirects]` and lock entries marked
+/// `equivalent_to` (config first, as for the redirect modules). GID
+/// redirects map each redirected path's hashed GID to its target's, then
+/// every GID recorded in the lock that no longer matches (see
+/// [`LockFile::gid_migrations`]).
+fn generate_redirects_fn(config: &TagsConfig, lock: &LockFile, name: &str, output: &mut String) {
+    let mut paths: BTreeMap<&str, &str> = lock
+        .entries
+        .iter()
+        .filter_map(|e| Some((e.path.as_str(), e.equivalent_to.as_deref()?)))
+        .collect();
+    paths.extend(config.redirects().map(|r| (r.from.as_str(), r.to.as_str())));
+
+    let mut gids: BTreeMap<u128, u128> = paths
+        .iter()
+        .filter_map(|(from, to)| Some((path_gid(from)?, path_gid(to)?)))
+        .filter(|(from, to)| from != to)
+        .collect();
+    let parse = |hex: &str| u128::from_str_radix(hex.trim_start_matches("0x"), 16).ok();
+    for migration in lock.gid_migrations(config) {
+        if let (Some(from), Some(to)) = (parse(&migration.from), parse(&migration.to)) {
+            gids.insert(from, to);
         }
     }
 
-    output
+    output.push_str(&format!(
+        "\n/// Old paths and GIDs of `{}` mapped to the current ones, built on first use.\n",
+        config.module_name
+    ));
+    if !config.codegen.allow.is_empty() {
+        let lints: Vec<&str> = config.codegen.allow.iter().map(String::as_str).collect();
+        push_allow(output, &lints);
+    }
+    output.push_str(&format!(
+        "{}fn {}() -> &'static ::bevy_tag::migrate::RedirectMap {{\n",
+        visibility_prefix(config),
+        name
+    ));
+    output.push_str("    static REDIRECTS: ::std::sync::LazyLock<::bevy_tag::migrate::RedirectMap> =\n");
+    output.push_str("        ::std::sync::LazyLock::new(|| {\n");
+    output.push_str("            #[allow(unused_mut)]\n");
+    output.push_str("            let mut redirects = ::bevy_tag::migrate::RedirectMap::new();\n");
+    for (from, to) in &paths {
+        let (from, to) = (format!("{:?}", from), format!("{:?}", to));
+        push_call(output, "redirects.insert_path", &[&from, &to]);
+    }
+    for (from, to) in &gids {
+        push_call(output, "redirects.insert", &[&format_gid(*from), &format_gid(*to)]);
+    }
+    output.push_str("            redirects\n");
+    output.push_str("        });\n");
+    output.push_str("    &REDIRECTS\n");
+    output.push_str("}\n");
 }
 
-/// Convert CamelCase to snake_case.
-fn to_snake_case(s: &str) -> String {
-    let mut result = String::new();
-    for (i, c) in s.chars().enumerate() {
-        if c.is_uppercase() {
-            if i > 0 {
-                result.push('_');
+/// Generate `[codegen] constants`: the tag-set version and deprecated paths,
+/// for display and for checking saved data at runtime.
+fn generate_constants(config: &TagsConfig, lock: &LockFile, output: &mut String) {
+    let vis = visibility_prefix(config);
+    output.push_str("\n/// UTC date the tag set last changed when it was generated (`YYYY-MM-DD`).\n");
+    output.push_str(&format!(
+        "{}const {}: &str = \"{}\";\n",
+        vis,
+        GENERATED_AT,
+        crate::history::today()
+    ));
+    output.push_str("\n/// Hash of the locked tag set (paths, GIDs, deprecations, redirects).\n");
+    output.push_str(&format!(
+        "{}const CONFIG_HASH: u64 = 0x{:016x};\n",
+        vis,
+        lock.content_hash()
+    ));
+
+    let mut deprecated: Vec<&str> = lock
+        .entries
+        .iter()
+        .filter(|e| e.deprecated)
+        .map(|e| e.path.as_str())
+        .chain(config.deprecations().map(|(path, _)| path))
+        .collect();
+    deprecated.sort_unstable();
+    deprecated.dedup();
+    let items: Vec<String> = deprecated.iter().map(|p| format!("{:?}", p)).collect();
+    output.push_str("\n/// Deprecated paths, sorted.\n");
+    push_str_array(output, &format!("{}const DEPRECATED_PATHS: &[&str]", vis), &items);
+}
+
+/// Name of the `GENERATED_AT` constant.
+const GENERATED_AT: &str = "GENERATED_AT";
+
+/// Keep the `GENERATED_AT` of `previous` output if nothing else changed, so
+/// the date only moves with the tag set and unchanged builds don't rewrite
+/// the file.
+pub(crate) fn keep_generated_at(code: String, previous: Option<String>) -> String {
+    let marker = format!("const {}: &str = ", GENERATED_AT);
+    let date_line = |text: &str| text.lines().find(|l| l.contains(&marker)).map(str::to_string);
+    if let Some(previous) = previous
+        && let (Some(new_line), Some(old_line)) = (date_line(&code), date_line(&previous))
+        && code.replacen(&new_line, &old_line, 1) == previous
+    {
+        return previous;
+    }
+    code
+}
+
+/// Push `{declaration} = &[...];`, laid out the way rustfmt lays out arrays.
+fn push_str_array(output: &mut String, declaration: &str, items: &[String]) {
+    let joined = items.join(", ");
+    // rustfmt's defaults: array_width = 60, max_width = 100
+    let line = format!("{} = &[{}];", declaration, joined);
+    if joined.len() <= 60 && line.len() <= 100 {
+        output.push_str(&line);
+        output.push('\n');
+        return;
+    }
+
+    output.push_str(&format!("{} = &[\n", declaration));
+    // Short literals are packed several per line, anything longer one per line
+    if items.iter().all(|item| item.len() <= 10) {
+        let mut row = String::new();
+        for item in items {
+            if !row.is_empty() && 4 + row.len() + 2 + item.len() + 1 > 100 {
+                output.push_str(&format!("    {},\n", row));
+                row.clear();
             }
-            result.push(c.to_ascii_lowercase());
-        } else {
-            result.push(c);
+            if !row.is_empty() {
+                row.push_str(", ");
+            }
+            row.push_str(item);
+        }
+        output.push_str(&format!("    {},\n", row));
+    } else {
+        for item in items {
+            output.push_str(&format!("    {},\n", item));
         }
     }
-    result
+    output.push_str("];\n");
 }
 
-/// Convert dot-separated path to Rust module path.
-///
-/// Uses the new naming convention:
-/// - CamelCase struct for the tag itself
-/// - snake_case module for accessing children
-///
-/// Examples:
-/// - "Sword" -> "Tags::Sword" (leaf at root)
-/// - "Item.Weapon.Sword" -> "Tags::item::weapon::Sword" (nested leaf)
-/// - "Item" -> "Tags::Item" (branch at root)
-fn path_to_rust_path(path: &str, module_name: &str) -> String {
-    let segments: Vec<&str> = path.split('.').collect();
-
-    if segments.len() == 1 {
-        // Root level: direct access
-        format!("{}::{}", module_name, segments[0])
+/// `[codegen] visibility` followed by a space, or nothing for private items.
+fn visibility_prefix(config: &TagsConfig) -> String {
+    match config.codegen.visibility.as_deref() {
+        None => "pub ".to_string(),
+        Some("") => String::new(),
+        Some(vis) => format!("{} ", vis),
+    }
+}
+
+/// Push a top-level `#[allow(...)]`, wrapped the way rustfmt would.
+fn push_allow(output: &mut String, lints: &[&str]) {
+    let joined = lints.join(", ");
+    // rustfmt's default attr_fn_like_width = 70; wrapped lists get no trailing comma
+    if joined.len() <= 70 {
+        output.push_str(&format!("#[allow({})]\n", joined));
     } else {
-        // Nested: use snake_case modules for parents, CamelCase for the final segment
-        let parents: Vec<String> = segments[..segments.len() - 1]
-            .iter()
-            .map(|s| to_snake_case(s))
-            .collect();
-        let leaf = segments.last().unwrap();
-        format!("{}::{}::{}", module_name, parents.join("::"), leaf)
+        output.push_str(&format!("#[allow(\n    {}\n)]\n", lints.join(",\n    ")));
     }
 }
 
+/// Push a call statement in the registry closure, wrapped the way rustfmt
+/// would wrap it so the output stays rustfmt-stable.
+fn push_call(output: &mut String, function: &str, args: &[&str]) {
+    const INDENT: &str = "            ";
+    let joined = args.join(", ");
+    let line = format!("{}{}({});", INDENT, function, joined);
+    // rustfmt's defaults: max_width = 100, fn_call_width = 60
+    if line.len() <= 100 && joined.len() <= 60 {
+        output.push_str(&line);
+        output.push('\n');
+        return;
+    }
+    output.push_str(&format!("{}{}(\n", INDENT, function));
+    for arg in args {
+        output.push_str(&format!("{}    {},\n", INDENT, arg));
+    }
+    output.push_str(&format!("{});\n", INDENT));
+}
+
 /// Tree node for code generation.
 #[derive(Debug, Default)]
 struct TreeNode {
@@ -228,6 +438,7 @@ fn insert_path(node: &mut TreeNode, segments: &[&str]) {
 
 /// Generate code for a tree node recursively.
 fn generate_tree_code(
+    config: &TagsConfig,
     node: &TreeNode,
     current_path: &str,
     indent: usize,
@@ -247,8 +458,15 @@ fn generate_tree_code(
             format!("{}.{}", current_path, name)
         };
 
-        // Check if this path is a redirect
-        if let Some(redirect_info) = redirect_map.get(full_path.as_str()) {
+        // Check if this path is a redirect. Redirects must be leaves, so an
+        // aliased lock entry that still has children stays a deprecated node.
+        if let Some(redirect_info) = redirect_map.get(full_path.as_str())
+            && child.children.is_empty()
+        {
+            // The macro already deprecates redirects; only a custom note is added
+            if let Some(note) = config.deprecation_note(&full_path) {
+                output.push_str(&format!("{}#[deprecated(note = {:?})]\n", indent_str, note));
+            }
             // Generate #[redirect = "target"] attribute
             output.push_str(&format!(
                 "{}#[redirect = \"{}\"]\n",
@@ -258,37 +476,57 @@ fn generate_tree_code(
             continue;
         }
 
-        // Check if this path is deprecated
-        let is_deprecated = deprecation_map.get(full_path.as_str())
-            .map(|info| info.deprecated)
-            .unwrap_or(false);
+        // Gated by [tags.dev-only] / [tags.features]; children inherit it
+        if let Some(predicate) = config.cfg_of(&full_path) {
+            output.push_str(&format!("{}#[cfg({})]\n", indent_str, predicate));
+        }
+
+        // Check if this path is deprecated (removed via lock, or [deprecated])
+        let lock_info = deprecation_map.get(full_path.as_str());
+        let custom_note = config.deprecation_note(&full_path);
+        let is_deprecated =
+            lock_info.map(|info| info.deprecated).unwrap_or(false) || custom_note.is_some();
 
         // Generate #[deprecated] attribute for Rust native deprecation warnings
         if is_deprecated {
-            // Get the alias target if any
-            let note = if let Some(info) = deprecation_map.get(full_path.as_str()) {
-                if let Some(ref alias_of) = info.alias_of {
+            // A custom note wins; otherwise mention the alias target if any
+            let note = match (custom_note, lock_info.and_then(|info| info.alias_of.as_ref())) {
+                (Some(note), _) => note.to_string(),
+                (None, Some(alias_of)) => {
                     format!("This tag is deprecated. Use '{}' instead.", alias_of)
-                } else {
-                    "This tag is deprecated.".to_string()
                 }
-            } else {
-                "This tag is deprecated.".to_string()
+                (None, None) => "This tag is deprecated.".to_string(),
             };
 
             output.push_str(&format!(
-                "{}#[deprecated(note = \"{}\")]\n",
+                "{}#[deprecated(note = {:?})]\n",
                 indent_str, note
             ));
         }
 
+        // Static metadata from [tags.meta]
+        for meta in config.meta_of(&full_path) {
+            output.push_str(&format!(
+                "{}#[{} = {}]\n",
+                indent_str,
+                meta.key,
+                meta.value.to_rust_literal()
+            ));
+        }
+
+        // Data type from [tags.data]: Node<Type>
+        let data_type = config
+            .data_of(&full_path)
+            .map(|ty| format!("<{}>", ty))
+            .unwrap_or_default();
+
         if child.children.is_empty() {
             // Leaf node
-            output.push_str(&format!("{}{};\n", indent_str, name));
+            output.push_str(&format!("{}{}{};\n", indent_str, name, data_type));
         } else {
             // Branch node
-            output.push_str(&format!("{}{} {{\n", indent_str, name));
-            generate_tree_code(child, &full_path, indent + 1, deprecation_map, redirect_map, output);
+            output.push_str(&format!("{}{}{} {{\n", indent_str, name, data_type));
+            generate_tree_code(config, child, &full_path, indent + 1, deprecation_map, redirect_map, output);
             output.push_str(&format!("{}}}\n", indent_str));
         }
     }
@@ -369,6 +607,103 @@ paths = ["A"]
         assert!(a_pos < z_pos, "A should come before Z");
     }
 
+    #[test]
+    fn adding_a_tag_only_adds_its_line() {
+        let before = generate_namespace_code(&make_config(&["Item.Axe", "Item.Sword", "Skill"]));
+        let after = generate_namespace_code(&make_config(&["Item.Bow", "Skill", "Item.Sword", "Item.Axe"]));
+
+        // Dropping the new leaf's line gives back exactly the old output
+        let without_bow: Vec<_> = after.lines().filter(|line| line.trim() != "Bow;").collect();
+        assert_eq!(without_bow, before.lines().collect::<Vec<_>>());
+        assert_eq!(after.lines().count(), before.lines().count() + 1);
+    }
+
+    #[test]
+    fn gated_paths_get_cfg_attributes() {
+        let toml = r#"
+[tags.dev-only]
+paths = ["Debug.GodMode"]
+
+[tags.features.cheats]
+paths = ["Cheat"]
+"#;
+        let code = generate_namespace_code(&TagsConfig::from_str(toml).unwrap());
+
+        assert!(code.contains("        #[cfg(feature = \"cheats\")]\n        Cheat;"));
+        assert!(code.contains("            #[cfg(debug_assertions)]\n            GodMode;"));
+        assert!(code.contains("        Debug {"));
+        assert!(!code.contains("#[cfg(debug_assertions)]\n        Debug"));
+    }
+
+    #[test]
+    fn module_options_shape_the_root_module() {
+        let toml = r#"
+[codegen]
+visibility = "pub(crate)"
+doc = "Gameplay tags.\n\nGenerated from tags.toml."
+allow = ["missing_docs"]
+derive = ["PartialOrd", "Ord"]
+registry = true
+
+[tags]
+paths = ["A"]
+"#;
+        let code = generate_namespace_code(&TagsConfig::from_str(toml).unwrap());
+
+        assert!(code.contains(
+            "namespace! {\n    derive(PartialOrd, Ord);\n    /// Gameplay tags.\n    ///\n    \
+             /// Generated from tags.toml.\n    #[allow(missing_docs)]\n    pub(crate) mod Tags {\n"
+        ));
+        assert!(code.contains("#[allow(deprecated, missing_docs)]\npub(crate) fn registry()"));
+
+        let lints: Vec<_> = (0..8).map(|i| format!("\"clippy::lint_number_{}\"", i)).collect();
+        let toml = format!("[codegen]\nregistry = true\nallow = [{}]", lints.join(", "));
+        let code = generate_namespace_code(&TagsConfig::from_str(&toml).unwrap());
+        assert!(code.contains("#[allow(\n    deprecated,\n    clippy::lint_number_0,\n"));
+        assert!(code.contains("    clippy::lint_number_7\n)]\npub fn registry()"));
+    }
+
+    #[test]
+    fn constants_describe_the_tag_set() {
+        let toml = "[codegen]\nconstants = true\n[tags]\npaths = [\"A.B\"]\n[deprecated]\n\"A.B\" = \"x\"";
+        let config = TagsConfig::from_str(toml).unwrap();
+        let lock = LockFile::from_config(&config);
+        let code = generate_namespace_code_from_lock(&config, &lock);
+
+        let today = crate::history::today();
+        assert!(code.contains(&format!("pub const GENERATED_AT: &str = \"{}\";\n", today)));
+        let hash = format!("pub const CONFIG_HASH: u64 = 0x{:016x};\n", lock.content_hash());
+        assert!(code.contains(&hash));
+        assert!(code.contains("pub const DEPRECATED_PATHS: &[&str] = &[\"A.B\"];\n"));
+        assert_eq!(generate_namespace_code(&config), code);
+
+        // The date only moves when something else changed
+        let old = code.replace(&today, "2000-01-01");
+        assert_eq!(keep_generated_at(code.clone(), Some(old.clone())), old);
+        let changed = old.replace("B;", "C;");
+        assert_eq!(keep_generated_at(code.clone(), Some(changed)), code);
+        assert_eq!(keep_generated_at(code.clone(), None), code);
+    }
+
+    #[test]
+    fn str_arrays_follow_rustfmt_layout() {
+        let array = |items: &[&str]| {
+            let items: Vec<String> = items.iter().map(|i| format!("{:?}", i)).collect();
+            let mut out = String::new();
+            push_str_array(&mut out, "const X: &[&str]", &items);
+            out
+        };
+        assert_eq!(array(&[]), "const X: &[&str] = &[];\n");
+        assert_eq!(
+            array(&["Item.Sword.Long", "Item.Axe", "Item.Shield.Tower", "Skill.Fire.Ball"]),
+            "const X: &[&str] = &[\n    \"Item.Sword.Long\",\n    \"Item.Axe\",\n    \
+             \"Item.Shield.Tower\",\n    \"Skill.Fire.Ball\",\n];\n"
+        );
+        let short = array(&["A"; 30]);
+        assert!(short.starts_with("const X: &[&str] = &[\n    \"A\", \"A\","), "{}", short);
+        assert!(short.lines().all(|l| l.len() <= 100), "{}", short);
+    }
+
     #[test]
     fn generate_with_deprecated() {
         let config = make_config(&["A.B"]);
@@ -410,17 +745,43 @@ paths = ["A"]
             deprecated: true,
             alias_of: Some("New.Path".to_string()),
         });
+        let mut redirect_map = HashMap::new();
+        redirect_map.insert("Old.Path", RedirectInfo { target: "New.Path".to_string() });
 
-        let code = generate_namespace_code_internal(&config, &deprecation_map, &HashMap::new());
+        let code = generate_namespace_code_internal(&config, &deprecation_map, &redirect_map);
 
         println!("{}", code);
 
-        // Should have deprecated attribute with alias info
-        assert!(code.contains("Use 'New.Path' instead"));
+        // Aliased lock entries become redirect nodes inside the namespace
+        assert!(code.contains("#[redirect = \"New.Path\"]\n            Path;"));
+        assert!(!code.contains("pub type"));
+    }
+
+    #[test]
+    fn lock_aliases_become_redirects() {
+        let config = make_config(&["New.Path"]);
+        let mut lock = LockFile::from_config(&config);
+        lock.entries.push(crate::lock::LockEntry {
+            path: "Old".into(),
+            depth: 0,
+            gid: None,
+            parent: None,
+            deprecated: true,
+            equivalent_to: None,
+        });
+        lock.entries.push(crate::lock::LockEntry {
+            path: "Old.Path".into(),
+            depth: 1,
+            gid: None,
+            parent: Some("Old".into()),
+            deprecated: true,
+            equivalent_to: Some("New.Path".into()),
+        });
+
+        let code = generate_namespace_code_from_lock(&config, &lock);
 
-        // Should generate redirect section
-        assert!(code.contains("Redirects for renamed paths"));
-        assert!(code.contains("bevy_tag::Redirect<"));
+        assert!(code.contains("#[deprecated(note = \"This tag is deprecated.\")]\n        Old {"));
+        assert!(code.contains("#[redirect = \"New.Path\"]\n            Path;"));
     }
 
     #[test]
@@ -443,27 +804,59 @@ paths = ["Equipment.Weapon.Blade"]
     }
 
     #[test]
-    fn path_to_rust_path_conversion() {
-        // New naming convention: snake_case modules, CamelCase struct
-        assert_eq!(
-            path_to_rust_path("Item.Weapon.Sword", "Tags"),
-            "Tags::item::weapon::Sword"
-        );
-        assert_eq!(
-            path_to_rust_path("Movement", "GameTags"),
-            "GameTags::Movement"
-        );
-        assert_eq!(
-            path_to_rust_path("Combat.Attack", "Tags"),
-            "Tags::combat::Attack"
-        );
+    fn generate_with_metadata() {
+        let toml = r#"
+[tags]
+paths = ["Ability.Fireball"]
+
+[tags.meta."Ability.Fireball"]
+mana_cost = 10
+cooldown = 1.0
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+        let code = generate_namespace_code(&config);
+
+        assert!(code.contains("#[cooldown = 1.0]\n            #[mana_cost = 10]\n            Fireball;"));
     }
 
     #[test]
-    fn snake_case_conversion() {
-        assert_eq!(to_snake_case("Item"), "item");
-        assert_eq!(to_snake_case("CamelCase"), "camel_case");
-        assert_eq!(to_snake_case("HTMLParser"), "h_t_m_l_parser");
-        assert_eq!(to_snake_case("simple"), "simple");
+    fn generate_with_data_types() {
+        let toml = r#"
+[tags]
+paths = ["Ability.Fireball"]
+
+[tags.data]
+"Ability" = "crate::SchoolData"
+"Ability.Fireball" = "crate::AbilityData"
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+        let code = generate_namespace_code(&config);
+
+        assert!(code.contains("Ability<crate::SchoolData> {"));
+        assert!(code.contains("Fireball<crate::AbilityData>;"));
+    }
+
+    #[test]
+    fn generate_with_custom_deprecation_note() {
+        let toml = r#"
+[tags]
+paths = ["Item.Sword", "Item.Axe"]
+
+[deprecated]
+"Item.Sword" = "Use \"Equipment.Weapon.Blade\" instead"
+"Item.Gone" = "Removed in 0.3"
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+        let mut deprecation_map = HashMap::new();
+        deprecation_map.insert("Item.Gone", DeprecationInfo { deprecated: true, alias_of: None });
+
+        let code = generate_namespace_code_internal(&config, &deprecation_map, &HashMap::new());
+
+        assert!(code.contains(
+            "#[deprecated(note = \"Use \\\"Equipment.Weapon.Blade\\\" instead\")]\n            Sword;"
+        ));
+        // Custom note replaces the generic one for lock-removed paths
+        assert!(code.contains("#[deprecated(note = \"Removed in 0.3\")]\n            Gone;"));
+        assert!(!code.contains("This tag is deprecated."));
     }
 }
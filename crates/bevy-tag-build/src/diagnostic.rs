@@ -0,0 +1,211 @@
+//! Source locations and fix suggestions for `tags.toml` errors.
+//!
+//! Validation runs on the deserialized config, which has no spans, so
+//! errors are located afterwards: the quoted names in the message (`'B-C'`
+//! in "segment 'B-C' contains invalid character '-'") are looked up as keys
+//! and string values in a span-preserving [`toml_edit`] parse of each file.
+//!
+//! ```text
+//! Validation error: Invalid path 'Item.B-C': segment 'B-C' contains invalid character '-'
+//!   --> tags.toml:3:13
+//!   = help: rename the segment to 'BC'
+//! ```
+
+use crate::TagsConfigError;
+use std::ops::Range;
+use std::path::PathBuf;
+use toml_edit::{ImDocument, Item, TableLike, Value};
+
+/// Lowest [`similarity`](crate::rename::similarity) for a "did you mean"
+/// suggestion.
+const SUGGESTION_THRESHOLD: f64 = 0.5;
+
+/// Append `= help: ...` to an error message.
+pub(crate) fn with_help(message: String, help: Option<String>) -> String {
+    match help {
+        Some(help) => format!("{}\n  = help: {}", message, help),
+        None => message,
+    }
+}
+
+/// A valid segment name close to `segment`: PascalCase words, prefixed with
+/// `_` if it would start with a digit.
+///
+/// `"fire-ball"` becomes `"FireBall"`, `"1st"` becomes `"_1st"`.
+pub(crate) fn suggest_segment(segment: &str) -> Option<String> {
+    let mut name: String = segment
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_numeric()) {
+        name.insert(0, '_');
+    }
+    (!name.is_empty()).then_some(name)
+}
+
+/// "did you mean" help naming the known path most similar to `path`.
+pub(crate) fn did_you_mean<'a>(
+    path: &str,
+    known: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    known
+        .into_iter()
+        .map(|candidate| (crate::rename::similarity(path, candidate), candidate))
+        .filter(|&(score, _)| score >= SUGGESTION_THRESHOLD)
+        .max_by(|a, b| a.0.total_cmp(&b.0).then_with(|| b.1.cmp(a.1)))
+        .map(|(_, candidate)| format!("did you mean '{}'?", candidate))
+}
+
+/// Add the location of a validation error's subject, when it can be found
+/// in one of `sources` (file path, content), as a `--> file:line:column`
+/// line under the message.
+pub(crate) fn locate_error(
+    error: TagsConfigError,
+    sources: &[(Option<PathBuf>, String)],
+) -> TagsConfigError {
+    let TagsConfigError::Validation(message) = error else {
+        return error;
+    };
+    let (first_line, rest) = message.split_once('\n').unwrap_or((&message, ""));
+
+    // Quoted names, in order of appearance, are the candidates
+    let location = first_line
+        .split('\'')
+        .skip(1)
+        .step_by(2)
+        .find_map(|needle| {
+            sources.iter().find_map(|(path, content)| {
+                let offset = find_span(content, needle)?.start;
+                let file = path
+                    .as_ref()
+                    .map_or_else(|| "<string>".to_string(), |p| p.display().to_string());
+                let (line, column) = line_column(content, offset);
+                Some(format!("{}:{}:{}", file, line, column))
+            })
+        });
+
+    match location {
+        Some(location) => {
+            let rest = if rest.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", rest)
+            };
+            TagsConfigError::Validation(format!("{}\n  --> {}{}", first_line, location, rest))
+        }
+        None => TagsConfigError::Validation(message),
+    }
+}
+
+/// Span of the first key or string value equal to `needle`, or failing
+/// that, equal to a dotted suffix of it (a child name under its parent's
+/// table).
+fn find_span(content: &str, needle: &str) -> Option<Range<usize>> {
+    let document = ImDocument::parse(content).ok()?;
+    let mut exact = Vec::new();
+    let mut suffix = Vec::new();
+    let mut matches = |text: &str, span: Option<Range<usize>>| {
+        let Some(span) = span else { return };
+        if text == needle {
+            exact.push(span);
+        } else if needle
+            .strip_suffix(text)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+        {
+            suffix.push(span);
+        }
+    };
+    visit_item(document.as_item(), &mut matches);
+
+    let first = |spans: Vec<Range<usize>>| spans.into_iter().min_by_key(|s| s.start);
+    first(exact).or_else(|| first(suffix))
+}
+
+/// Call `f` with every key and string value under `item`.
+fn visit_item(item: &Item, f: &mut impl FnMut(&str, Option<Range<usize>>)) {
+    if let Some(table) = item.as_table_like() {
+        visit_table(table, f);
+    } else if let Some(tables) = item.as_array_of_tables() {
+        tables.iter().for_each(|table| visit_table(table, f));
+    } else if let Some(value) = item.as_value() {
+        visit_value(value, f);
+    }
+}
+
+fn visit_table(table: &dyn TableLike, f: &mut impl FnMut(&str, Option<Range<usize>>)) {
+    for (name, child) in table.iter() {
+        f(name, table.key(name).and_then(|k| k.span()));
+        visit_item(child, f);
+    }
+}
+
+fn visit_value(value: &Value, f: &mut impl FnMut(&str, Option<Range<usize>>)) {
+    match value {
+        Value::String(s) => f(s.value(), s.span()),
+        Value::Array(array) => array.iter().for_each(|v| visit_value(v, f)),
+        Value::InlineTable(table) => {
+            for (name, child) in table.iter() {
+                f(name, table.key(name).and_then(|k| k.span()));
+                visit_value(child, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 1-based line and column of a byte offset.
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_valid_segment_names() {
+        assert_eq!(suggest_segment("fire-ball").as_deref(), Some("FireBall"));
+        assert_eq!(suggest_segment("B-C").as_deref(), Some("BC"));
+        assert_eq!(suggest_segment("1st").as_deref(), Some("_1st"));
+        assert_eq!(suggest_segment("-"), None);
+    }
+
+    #[test]
+    fn did_you_mean_picks_the_closest_path() {
+        let known = ["Item.Sword", "Item.Shield", "Skill"];
+        assert_eq!(
+            did_you_mean("Item.Swrod", known).as_deref(),
+            Some("did you mean 'Item.Sword'?")
+        );
+        assert_eq!(did_you_mean("Zzz", known), None);
+    }
+
+    #[test]
+    fn locates_keys_values_and_child_names() {
+        let content = "[tags]\npaths = [\"A\", \"Item.B-C\"]\n\n[tags.Ability]\nchildren = [\"fire-ball\"]\n\n[tags.meta.\"A.X\"]\ncost = 1\n";
+        let sources = [(Some(PathBuf::from("tags.toml")), content.to_string())];
+        let locate = |message: &str| {
+            locate_error(TagsConfigError::Validation(message.into()), &sources).to_string()
+        };
+
+        assert_eq!(
+            locate("Invalid path 'Item.B-C': bad\n  = help: fix it"),
+            "Validation error: Invalid path 'Item.B-C': bad\n  --> tags.toml:2:15\n  = help: fix it"
+        );
+        assert!(locate("Invalid path 'Ability.fire-ball'").ends_with("--> tags.toml:5:13"));
+        assert!(locate("Metadata path 'A.X' not found").ends_with("--> tags.toml:7:12"));
+        assert_eq!(
+            locate("No 'Match' here"),
+            "Validation error: No 'Match' here"
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! GraphViz and Mermaid diagrams of the tag hierarchy.
+//!
+//! Both render the same tags as the [`Manifest`]: parent edges as solid
+//! arrows, deprecated tags dashed and grey, and redirects as dashed arrows to
+//! their target.
+//!
+//! ```text
+//! cargo bevy-tag dot | dot -Tsvg > tags.svg
+//! cargo bevy-tag mermaid > tags.mmd
+//! ```
+
+use crate::lock::LockFile;
+use crate::manifest::{Manifest, ManifestEntry};
+use crate::toml_parser::TagsConfig;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+/// Render the tag tree as a GraphViz `digraph`.
+///
+/// Nodes are identified by full path and labelled with their last segment.
+pub fn export_dot(config: &TagsConfig, lock: Option<&LockFile>) -> String {
+    let manifest = Manifest::new(config, lock);
+
+    let mut out = String::new();
+    writeln!(out, "digraph {} {{", quote(&manifest.module_name)).unwrap();
+    writeln!(out, "    rankdir=LR;").unwrap();
+    writeln!(out, "    node [shape=box];").unwrap();
+
+    for entry in &manifest.tags {
+        let style = if entry.deprecated {
+            ", style=dashed, fontcolor=gray, color=gray"
+        } else {
+            ""
+        };
+        writeln!(
+            out,
+            "    {} [label={}{}];",
+            quote(&entry.path),
+            quote(label(entry)),
+            style
+        )
+        .unwrap();
+    }
+    for entry in &manifest.tags {
+        if let Some(parent) = &entry.parent {
+            writeln!(out, "    {} -> {};", quote(parent), quote(&entry.path)).unwrap();
+        }
+        if let Some(target) = &entry.redirect {
+            writeln!(
+                out,
+                "    {} -> {} [style=dashed, label=\"redirect\", constraint=false];",
+                quote(&entry.path),
+                quote(target)
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// Write [`export_dot`] to `path`, e.g. from a build script.
+///
+/// An existing file with identical contents is left untouched.
+pub fn write_dot(
+    config: &TagsConfig,
+    lock: Option<&LockFile>,
+    path: impl AsRef<Path>,
+) -> io::Result<()> {
+    crate::write_if_changed(path.as_ref(), &export_dot(config, lock))
+}
+
+/// Render the tag tree as a Mermaid flowchart.
+///
+/// Mermaid ids cannot contain dots, so nodes get sequential ids and the
+/// segment name as label.
+pub fn export_mermaid(config: &TagsConfig, lock: Option<&LockFile>) -> String {
+    let manifest = Manifest::new(config, lock);
+    let ids: HashMap<&str, String> = manifest
+        .tags
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (e.path.as_str(), format!("n{}", i)))
+        .collect();
+
+    let mut out = String::new();
+    writeln!(out, "graph LR").unwrap();
+    writeln!(out, "    classDef deprecated stroke-dasharray: 5 5, color: #888").unwrap();
+
+    for entry in &manifest.tags {
+        let id = &ids[entry.path.as_str()];
+        writeln!(out, "    {}[{}]", id, quote(label(entry))).unwrap();
+        if entry.deprecated {
+            writeln!(out, "    class {} deprecated", id).unwrap();
+        }
+    }
+    for entry in &manifest.tags {
+        let id = &ids[entry.path.as_str()];
+        if let Some(parent) = &entry.parent {
+            writeln!(out, "    {} --> {}", ids[parent.as_str()], id).unwrap();
+        }
+        if let Some(target) = entry.redirect.as_deref().and_then(|t| ids.get(t)) {
+            writeln!(out, "    {} -.->|redirect| {}", id, target).unwrap();
+        }
+    }
+    out
+}
+
+/// Write [`export_mermaid`] to `path`, e.g. from a build script.
+///
+/// An existing file with identical contents is left untouched.
+pub fn write_mermaid(
+    config: &TagsConfig,
+    lock: Option<&LockFile>,
+    path: impl AsRef<Path>,
+) -> io::Result<()> {
+    crate::write_if_changed(path.as_ref(), &export_mermaid(config, lock))
+}
+
+/// Last path segment, shown as the node label.
+fn label(entry: &ManifestEntry) -> &str {
+    entry.path.rsplit('.').next().unwrap_or(&entry.path)
+}
+
+/// A double-quoted string, escaped for DOT and Mermaid.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> TagsConfig {
+        TagsConfig::from_str(
+            r#"
+[tags]
+paths = ["Item.Blade", "Item.Potion"]
+
+[redirects]
+"Item.Sword" = "Item.Blade"
+
+[deprecated]
+"Item.Potion" = "Use Consumable.Potion"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn dot_has_edges_and_annotations() {
+        let dot = export_dot(&config(), None);
+
+        assert!(dot.starts_with("digraph \"Tags\" {"));
+        assert!(dot.contains("    \"Item.Blade\" [label=\"Blade\"];"));
+        assert!(dot.contains("    \"Item.Potion\" [label=\"Potion\", style=dashed"));
+        assert!(dot.contains("    \"Item\" -> \"Item.Blade\";"));
+        assert!(dot.contains("    \"Item.Sword\" -> \"Item.Blade\" [style=dashed, label=\"redirect\""));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn mermaid_has_edges_and_annotations() {
+        let mermaid = export_mermaid(&config(), None);
+
+        // Sorted by path: Item, Item.Blade, Item.Potion, Item.Sword
+        assert!(mermaid.starts_with("graph LR\n"));
+        assert!(mermaid.contains("    n0[\"Item\"]"));
+        assert!(mermaid.contains("    n0 --> n1"));
+        assert!(mermaid.contains("    class n2 deprecated"));
+        assert!(mermaid.contains("    class n3 deprecated"));
+        assert!(mermaid.contains("    n3 -.->|redirect| n1"));
+    }
+
+    #[test]
+    fn writes_diagrams_to_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let (dot, mermaid) = (dir.path().join("tags.dot"), dir.path().join("tags.mmd"));
+        write_dot(&config(), None, &dot).unwrap();
+        write_mermaid(&config(), None, &mermaid).unwrap();
+        assert_eq!(std::fs::read_to_string(dot).unwrap(), export_dot(&config(), None));
+        assert_eq!(
+            std::fs::read_to_string(mermaid).unwrap(),
+            export_mermaid(&config(), None)
+        );
+        assert!(write_dot(&config(), None, dir.path().join("missing/tags.dot")).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+//! GID computation for lock file verification.
+//!
+//! Mirrors `bevy_tag`'s hash and bit layout so the build step can record
+//! each path's GID without depending on the runtime crate. A change to
+//! either side shows up as a [`GID_SCHEME`] or GID mismatch in the lock.
+//!
+//! It also lets the build find collisions and crowded levels before the
+//! `namespace!` macro's slower compile-time checks run.
+
+use std::collections::HashMap;
+
+/// Maximum supported tree depth (mirrors `bevy_tag::MAX_DEPTH`).
+pub(crate) const MAX_DEPTH: usize = 8;
+
+/// Segment bit widths per level (mirrors `bevy_tag::LEVEL_WIDTHS`).
+pub(crate) const LEVEL_WIDTHS: [u8; MAX_DEPTH] = [21, 18, 16, 16, 14, 14, 13, 13];
+
+/// Bit position where depth is stored.
+const DEPTH_SHIFT: u8 = 125;
+
+/// Segment hash version (mirrors `bevy_tag::HASH_VERSION`).
+pub const HASH_VERSION: u32 = 1;
+
+/// Bit layout version (mirrors `bevy_tag::LAYOUT_VERSION`).
+pub const LAYOUT_VERSION: u32 = 1;
+
+/// Identifies the hash function and bit layout GIDs were computed with
+/// (mirrors `bevy_tag::GID_SCHEME`).
+///
+/// Bump the hash version when the hash changes; the widths are included so a
+/// layout change is caught even if this string is forgotten.
+pub const GID_SCHEME: &str = "fnv1a64-mix-v1/21.18.16.16.14.14.13.13";
+
+/// FNV-1a 64-bit hash.
+pub(crate) fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Hash a segment into `width` bits, never 0.
+fn segment_hash(segment: &str, width: u8) -> u128 {
+    let full = fnv1a_64(segment.as_bytes());
+    let mixed = full ^ (full >> 32) ^ (full >> 17);
+    let val = (mixed as u128) & ((1u128 << width) - 1);
+    if val == 0 { 1 } else { val }
+}
+
+/// Bit offset of each level within the 125-bit payload (mirrors
+/// `LEVEL_OFFSETS`: each level starts after the widths of the ones above it).
+fn level_offset(level: usize) -> u8 {
+    LEVEL_WIDTHS[..level].iter().sum()
+}
+
+/// Compute the GID of a dot-separated path.
+///
+/// Returns `None` if the path is deeper than the layout allows (8 levels).
+pub fn path_gid(path: &str) -> Option<u128> {
+    let segments: Vec<&str> = path.split('.').collect();
+    if segments.is_empty() || segments.len() > MAX_DEPTH {
+        return None;
+    }
+
+    let payload = segments
+        .iter()
+        .enumerate()
+        .fold(0u128, |acc, (level, seg)| {
+            acc | segment_hash(seg, LEVEL_WIDTHS[level]) << level_offset(level)
+        });
+    let depth = (segments.len() - 1) as u128;
+    Some(payload | depth << DEPTH_SHIFT)
+}
+
+/// Percentage of a level's segment space above which the build warns
+/// (mirrors the `namespace!` macro's threshold).
+pub const OCCUPANCY_WARN_PERCENT: f64 = 25.0;
+
+/// Two paths that hash to the same GID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GidCollision {
+    /// First path, in sorted order
+    pub first: String,
+    /// Second path, in sorted order
+    pub second: String,
+    /// The shared GID
+    pub gid: u128,
+}
+
+/// Find every pair of paths with the same GID.
+///
+/// Siblings collide when their segments hash to the same bits; paths under
+/// different parents never do. Paths too deep for the layout are skipped.
+pub fn find_collisions<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<GidCollision> {
+    let mut paths: Vec<&str> = paths.into_iter().collect();
+    paths.sort_unstable();
+    paths.dedup();
+
+    let mut seen: HashMap<u128, &str> = HashMap::new();
+    let mut collisions = Vec::new();
+    for path in paths {
+        let Some(gid) = path_gid(path) else { continue };
+        if let Some(first) = seen.insert(gid, path) {
+            collisions.push(GidCollision {
+                first: first.to_string(),
+                second: path.to_string(),
+                gid,
+            });
+        }
+    }
+    collisions
+}
+
+/// How much of one level's segment space is used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelOccupancy {
+    /// Level (0 = roots)
+    pub level: usize,
+    /// Size of the widest sibling group at this level
+    pub used: usize,
+    /// Number of distinct segment values at this level
+    pub slots: u64,
+}
+
+impl LevelOccupancy {
+    /// `used` as a percentage of `slots`.
+    pub fn percent(&self) -> f64 {
+        self.used as f64 / self.slots as f64 * 100.0
+    }
+}
+
+impl std::fmt::Display for LevelOccupancy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "level {}: {}/{} slots used ({:.2}%)",
+            self.level,
+            self.used,
+            self.slots,
+            self.percent()
+        )
+    }
+}
+
+/// Occupancy of every level the paths reach.
+///
+/// Segments only need to be unique among siblings, so like the macro's
+/// `MAX_WIDTH_USED` this counts the widest sibling group of each level.
+pub fn level_occupancy<'a>(paths: impl IntoIterator<Item = &'a str>) -> Vec<LevelOccupancy> {
+    let mut siblings: HashMap<(usize, &str), usize> = HashMap::new();
+    let mut seen = std::collections::HashSet::new();
+    for path in paths {
+        if !seen.insert(path) {
+            continue;
+        }
+        let level = path.matches('.').count();
+        let parent = path.rsplit_once('.').map_or("", |(parent, _)| parent);
+        *siblings.entry((level, parent)).or_default() += 1;
+    }
+
+    let depth = siblings.keys().map(|&(level, _)| level + 1).max().unwrap_or(0);
+    (0..depth.min(MAX_DEPTH))
+        .map(|level| LevelOccupancy {
+            level,
+            used: siblings
+                .iter()
+                .filter(|((l, _), _)| *l == level)
+                .map(|(_, &count)| count)
+                .max()
+                .unwrap_or(0),
+            slots: 1u64 << LEVEL_WIDTHS[level],
+        })
+        .collect()
+}
+
+/// Format a GID as it is stored in the lock file.
+pub fn format_gid(gid: u128) -> String {
+    format!("0x{:032x}", gid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime_gid(path: &str) -> u128 {
+        let segments: Vec<&[u8]> = path.split('.').map(str::as_bytes).collect();
+        bevy_tag::hierarchical_gid(&segments)
+    }
+
+    #[test]
+    fn matches_runtime_layout() {
+        assert_eq!(LEVEL_WIDTHS, bevy_tag::LEVEL_WIDTHS);
+        assert_eq!(MAX_DEPTH, bevy_tag::MAX_DEPTH);
+        assert_eq!(HASH_VERSION, bevy_tag::HASH_VERSION);
+        assert_eq!(LAYOUT_VERSION, bevy_tag::LAYOUT_VERSION);
+        assert_eq!(GID_SCHEME, bevy_tag::GID_SCHEME);
+    }
+
+    #[test]
+    fn matches_runtime_hash() {
+        for path in [
+            "A",
+            "Item",
+            "Item.Weapon.Sword",
+            "Equipment.Weapon.Blade",
+            "L0.L1.L2.L3.L4.L5.L6.L7",
+        ] {
+            assert_eq!(path_gid(path), Some(runtime_gid(path)), "{}", path);
+        }
+    }
+
+    #[test]
+    fn rejects_too_deep() {
+        assert_eq!(path_gid("A.B.C.D.E.F.G.H.I"), None);
+    }
+
+    #[test]
+    fn finds_colliding_siblings() {
+        // Level 7 has 13 bits, so 1000 siblings are bound to share a segment
+        let paths: Vec<String> = (0..1000).map(|i| format!("A.B.C.D.E.F.G.N{}", i)).collect();
+        let collisions = find_collisions(paths.iter().map(String::as_str));
+        assert!(!collisions.is_empty());
+        for c in &collisions {
+            assert!(c.first < c.second);
+            assert_eq!(path_gid(&c.first), Some(c.gid));
+            assert_eq!(path_gid(&c.second), Some(c.gid));
+        }
+
+        assert!(find_collisions(["Item", "Item.Sword", "Item", "Skill"]).is_empty());
+    }
+
+    #[test]
+    fn measures_widest_sibling_group() {
+        let occupancy = level_occupancy(["A", "B", "A.X", "A.Y", "B.X", "A.Y"]);
+        assert_eq!(occupancy.len(), 2);
+        assert_eq!((occupancy[0].used, occupancy[0].slots), (2, 1 << 21));
+        assert_eq!((occupancy[1].used, occupancy[1].slots), (2, 1 << 18));
+        assert_eq!(
+            LevelOccupancy { level: 2, used: 431, slots: 65536 }.to_string(),
+            "level 2: 431/65536 slots used (0.66%)"
+        );
+    }
+
+    #[test]
+    fn format_is_fixed_width_hex() {
+        assert_eq!(format_gid(1), "0x00000000000000000000000000000001");
+    }
+}
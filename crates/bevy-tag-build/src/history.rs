@@ -0,0 +1,367 @@
+//! Tag change history (`tags.history.toml`).
+//!
+//! An append-only log, committed next to `tags.toml`, with one dated section
+//! per build that changed the tag set:
+//!
+//! ```toml
+//! [[changes]]
+//! date = "2026-10-17"
+//! added = [
+//!     "Item.Shield",
+//! ]
+//! deprecated = [
+//!     "Item.Buckler",
+//! ]
+//! redirected = [
+//!     { from = "Item.Buckler", to = "Item.Shield" },
+//! ]
+//! ```
+//!
+//! The log is its own baseline: replaying its sections gives the tag set of
+//! the last recorded build, and the next build records whatever differs
+//! from it. The first section therefore lists every tag that existed when
+//! the history was enabled.
+
+use crate::lock::{LockFile, LockFileError, toml_string};
+use crate::toml_parser::TagsConfig;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Comment at the top of a new history file.
+const HEADER: &str = "# AUTO-GENERATED by bevy-tag-build - DO NOT EDIT\n\
+                      # One section per build that changed the tag set, oldest first.\n";
+
+/// Parsed `tags.history.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct History {
+    /// Recorded changes, oldest first
+    #[serde(default)]
+    pub changes: Vec<HistoryChange>,
+}
+
+/// Changes recorded by one build.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct HistoryChange {
+    /// UTC date of the build (`YYYY-MM-DD`)
+    pub date: String,
+    /// New tags
+    #[serde(default)]
+    pub added: Vec<String>,
+    /// Tags and redirects that no longer exist
+    #[serde(default)]
+    pub removed: Vec<String>,
+    /// Newly deprecated tags
+    #[serde(default)]
+    pub deprecated: Vec<String>,
+    /// New or retargeted redirects
+    #[serde(default)]
+    pub redirected: Vec<HistoryRedirect>,
+}
+
+/// A redirect recorded in the history.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct HistoryRedirect {
+    /// Old path
+    pub from: String,
+    /// Path it now resolves to
+    pub to: String,
+}
+
+/// Tag set at some point in the history.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Snapshot {
+    tags: BTreeSet<String>,
+    deprecated: BTreeSet<String>,
+    redirects: BTreeMap<String, String>,
+}
+
+impl History {
+    /// Load a history file; a missing file is an empty history.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, LockFileError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| LockFileError::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        toml::from_str(&content)
+            .map_err(|e| LockFileError::Parse(format!("{}: {}", path.display(), e)))
+    }
+
+    /// Date of the build that last added `path`, if recorded.
+    pub fn added_on(&self, path: &str) -> Option<&str> {
+        self.changes
+            .iter()
+            .rev()
+            .find(|c| c.added.iter().any(|p| p == path))
+            .map(|c| c.date.as_str())
+    }
+
+    /// What changed since the last recorded build, or `None` if nothing did.
+    ///
+    /// Tags come from the lock (so deprecated and auto-redirected paths are
+    /// included); redirects and deprecations also from the config.
+    pub fn pending_change(
+        &self,
+        config: &TagsConfig,
+        lock: &LockFile,
+        date: &str,
+    ) -> Option<HistoryChange> {
+        let before = self.replay();
+        let after = Snapshot::current(config, lock);
+
+        let existed =
+            |s: &Snapshot, path: &str| s.tags.contains(path) || s.redirects.contains_key(path);
+        let change = HistoryChange {
+            date: date.to_string(),
+            added: after.tags.difference(&before.tags).cloned().collect(),
+            removed: before
+                .tags
+                .iter()
+                .chain(before.redirects.keys())
+                .filter(|p| !existed(&after, p))
+                .cloned()
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+            deprecated: after
+                .deprecated
+                .difference(&before.deprecated)
+                .cloned()
+                .collect(),
+            redirected: after
+                .redirects
+                .iter()
+                .filter(|&(from, to)| before.redirects.get(from) != Some(to))
+                .map(|(from, to)| HistoryRedirect {
+                    from: from.clone(),
+                    to: to.clone(),
+                })
+                .collect(),
+        };
+        (!change.is_empty()).then_some(change)
+    }
+
+    /// Tag set after applying every recorded change.
+    fn replay(&self) -> Snapshot {
+        let mut snapshot = Snapshot::default();
+        for change in &self.changes {
+            for path in &change.added {
+                snapshot.redirects.remove(path);
+                snapshot.tags.insert(path.clone());
+            }
+            for path in &change.removed {
+                snapshot.tags.remove(path);
+                snapshot.deprecated.remove(path);
+                snapshot.redirects.remove(path);
+            }
+            snapshot
+                .deprecated
+                .extend(change.deprecated.iter().cloned());
+            for redirect in &change.redirected {
+                snapshot.tags.remove(&redirect.from);
+                snapshot
+                    .redirects
+                    .insert(redirect.from.clone(), redirect.to.clone());
+            }
+        }
+        snapshot
+    }
+
+    /// Append `change` to the history file at `path`, creating it if needed.
+    pub fn append(path: impl AsRef<Path>, change: &HistoryChange) -> Result<(), LockFileError> {
+        let path = path.as_ref();
+        let mut content = std::fs::read_to_string(path).unwrap_or_else(|_| HEADER.to_string());
+        content.push('\n');
+        content.push_str(&change.to_toml_string());
+        std::fs::write(path, content)
+            .map_err(|e| LockFileError::Io(format!("Failed to write {}: {}", path.display(), e)))
+    }
+}
+
+impl Snapshot {
+    fn current(config: &TagsConfig, lock: &LockFile) -> Self {
+        let mut snapshot = Self::default();
+        for entry in &lock.entries {
+            match &entry.equivalent_to {
+                Some(target) => {
+                    snapshot
+                        .redirects
+                        .insert(entry.path.clone(), target.clone());
+                }
+                None => {
+                    snapshot.tags.insert(entry.path.clone());
+                    if entry.deprecated {
+                        snapshot.deprecated.insert(entry.path.clone());
+                    }
+                }
+            }
+        }
+        for redirect in config.redirects() {
+            snapshot
+                .redirects
+                .insert(redirect.from.clone(), redirect.to.clone());
+        }
+        snapshot.deprecated.extend(
+            config
+                .deprecations()
+                .map(|(path, _)| path)
+                .filter(|path| snapshot.tags.contains(*path))
+                .map(str::to_string),
+        );
+        snapshot
+    }
+}
+
+impl HistoryChange {
+    /// No additions, removals, deprecations or redirects.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.deprecated.is_empty()
+            && self.redirected.is_empty()
+    }
+
+    /// A `[[changes]]` section with one path per line, for readable diffs.
+    pub fn to_toml_string(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "[[changes]]").unwrap();
+        writeln!(out, "date = {}", toml_string(&self.date)).unwrap();
+        for (key, paths) in [
+            ("added", &self.added),
+            ("removed", &self.removed),
+            ("deprecated", &self.deprecated),
+        ] {
+            if !paths.is_empty() {
+                writeln!(out, "{} = [", key).unwrap();
+                for path in paths {
+                    writeln!(out, "    {},", toml_string(path)).unwrap();
+                }
+                writeln!(out, "]").unwrap();
+            }
+        }
+        if !self.redirected.is_empty() {
+            writeln!(out, "redirected = [").unwrap();
+            for r in &self.redirected {
+                let (from, to) = (toml_string(&r.from), toml_string(&r.to));
+                writeln!(out, "    {{ from = {}, to = {} }},", from, to).unwrap();
+            }
+            writeln!(out, "]").unwrap();
+        }
+        out
+    }
+}
+
+/// Today's UTC date as `YYYY-MM-DD`, or the date of `SOURCE_DATE_EPOCH`
+/// when set, for reproducible builds.
+pub(crate) fn today() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs() as i64)
+        });
+    let (year, month, day) = civil_date(seconds.div_euclid(86_400));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Proleptic Gregorian date of a day count since 1970-01-01.
+fn civil_date(days: i64) -> (i64, i64, i64) {
+    // Howard Hinnant's `civil_from_days`
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml: &str) -> TagsConfig {
+        TagsConfig::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn civil_dates() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(20_743), (2026, 10, 17));
+        assert_eq!(civil_date(-1), (1969, 12, 31));
+
+        // 2000 is a leap year (divisible by 400), 2100 is not
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+        assert_eq!(civil_date(11_017), (2000, 3, 1));
+        assert_eq!(civil_date(47_540), (2100, 2, 28));
+        assert_eq!(civil_date(47_541), (2100, 3, 1));
+
+        // Year rollover
+        assert_eq!(civil_date(10_956), (1999, 12, 31));
+        assert_eq!(civil_date(10_957), (2000, 1, 1));
+        assert_eq!(civil_date(20_818), (2026, 12, 31));
+        assert_eq!(civil_date(20_819), (2027, 1, 1));
+    }
+
+    #[test]
+    fn records_changes_since_the_last_section() {
+        let first = config("[tags]\npaths = [\"Item.Sword\", \"Item.Axe\"]");
+        let mut lock = LockFile::from_config(&first);
+        let mut history = History::default();
+
+        let change = history.pending_change(&first, &lock, "2026-01-01").unwrap();
+        assert_eq!(change.added, ["Item", "Item.Axe", "Item.Sword"]);
+        history.changes.push(change);
+        assert_eq!(history.pending_change(&first, &lock, "2026-01-02"), None);
+
+        // Axe deprecated, Sword renamed with a redirect, Shield added
+        let second = config(
+            "[tags]\npaths = [\"Item.Axe\", \"Item.Blade\", \"Item.Shield\"]\n\
+             [redirects]\n\"Item.Sword\" = \"Item.Blade\"\n[deprecated]\n\"Item.Axe\" = \"Use Blade\"",
+        );
+        lock.remove_entry("Item.Sword");
+        for entry in second.entries() {
+            lock.add_entry(entry.clone());
+        }
+        let change = history
+            .pending_change(&second, &lock, "2026-02-01")
+            .unwrap();
+        assert_eq!(change.added, ["Item.Blade", "Item.Shield"]);
+        assert_eq!(change.deprecated, ["Item.Axe"]);
+        assert!(change.removed.is_empty());
+        assert_eq!(
+            change.redirected,
+            [HistoryRedirect {
+                from: "Item.Sword".into(),
+                to: "Item.Blade".into()
+            }]
+        );
+        history.changes.push(change);
+
+        // Round-trips through the file format
+        let text: String = history
+            .changes
+            .iter()
+            .map(HistoryChange::to_toml_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let parsed: History = toml::from_str(&format!("{}\n{}", HEADER, text)).unwrap();
+        assert_eq!(parsed, history);
+        assert_eq!(parsed.added_on("Item.Shield"), Some("2026-02-01"));
+        assert_eq!(parsed.added_on("Item.Axe"), Some("2026-01-01"));
+        assert_eq!(parsed.pending_change(&second, &lock, "2026-02-02"), None);
+
+        // Dropping the redirect removes the old path
+        let third = config("[tags]\npaths = [\"Item.Axe\", \"Item.Blade\", \"Item.Shield\"]");
+        let change = parsed.pending_change(&third, &lock, "2026-03-01").unwrap();
+        assert_eq!(change.removed, ["Item.Sword"]);
+    }
+}
@@ -7,14 +7,32 @@
 //!
 //! # Usage in build.rs
 //!
+//! The recommended setup generates into `OUT_DIR`, so the working tree stays
+//! clean and `cargo publish` has nothing generated to package:
+//!
 //! ```ignore
 //! // build.rs
 //! fn main() {
-//!     bevy_tag_build::generate("tags.toml", "src/generated_tags.rs")
+//!     bevy_tag_build::generate_to_out_dir("tags.toml")
 //!         .expect("Failed to generate tags");
 //! }
+//!
+//! // src/lib.rs
+//! bevy_tag::include_tags!();
+//! ```
+//!
+//! `include_tags!` lives in `bevy_tag` because this crate is only a
+//! build-dependency. To commit the generated code instead, write it into
+//! `src/` with [`generate`] and include it as a module:
+//!
+//! ```ignore
+//! bevy_tag_build::generate("tags.toml", "src/generated_tags.rs")
+//!     .expect("Failed to generate tags");
 //! ```
 //!
+//! On docs.rs (`DOCS_RS` set) the source directory is read-only, so the lock
+//! file and `TAGS.md` are checked but not written.
+//!
 //! # Lock File Mechanism
 //!
 //! The lock file ensures that changes to `tags.toml` are intentional:
@@ -23,6 +41,15 @@
 //! - Subsequent builds: compares against lock file
 //! - Mismatch (path removed/renamed): **compile error** (default) or **warning** (with `on_remove = "warn"`)
 //! - New paths added: automatically appended to lock
+//! - Two tags hashing to the same GID fail the build with both paths named,
+//!   and levels over [`OCCUPANCY_WARN_PERCENT`] of their segment space are
+//!   reported as `cargo:warning`s
+//! - Every locked GID is recomputed; a changed GID for an unchanged path (hash
+//!   or bit-layout change) is always a **compile error**
+//! - A checksum over the entries turns hand edits of the lock (changed GIDs,
+//!   deleted lines) into a **compile error**
+//! - Entries are written one per line, sorted; a merge conflict in the lock is
+//!   resolved with `cargo bevy-tag merge-lock` (see [`LockFile::merge`])
 //!
 //! # Migration Modes
 //!
@@ -34,19 +61,131 @@
 //!
 //! # Or: warn (generates #[deprecated] for removed paths)
 //! on_remove = "warn"
+//!
+//! # Or: like warn, but removed paths that look renamed redirect to the new path
+//! on_remove = "auto-redirect"
+//! ```
+//!
+//! Whenever paths are removed and others added, likely renames are reported
+//! as `cargo:warning`s suggesting a `[redirects]` entry.
+//!
+//! To intentionally remove specific paths, list them in `allow_removed`; they
+//! are dropped from the lock while every other path stays protected:
+//!
+//! ```toml
+//! allow_removed = ["Item.Prototype"]
+//! ```
+//!
+//! To intentionally break compatibility everywhere, delete the lock file and
+//! rebuild.
+//!
+//! # GID Manifest
+//!
+//! With `[codegen] manifest = true`, a `tags_manifest.json` listing every
+//! path, GID, parent, deprecation and redirect target is written next to the
+//! generated Rust file, for services that need to resolve tags without Rust.
+//!
+//! With `[codegen] c_header = true`, a `tags.h` with every GID as `uint64_t`
+//! halves and an inline `bevy_tag_is_descendant_of` is written the same way,
+//! for native plugins; see [`generate_c_header`].
+//!
+//! With `[codegen] typescript = true`, a `tags.ts` module with typed paths,
+//! hex GIDs, parents and redirects is written for web tooling; see
+//! [`generate_typescript`].
+//!
+//! With `[codegen] markdown = true`, a `TAGS.md` table of every tag (GID,
+//! display name, description, deprecation) and redirect is written next to
+//! `tags.toml`, to be committed for non-programmers; see
+//! [`generate_markdown`].
+//!
+//! With `[codegen] registry = true`, the generated Rust file also gets a
+//! `pub fn registry() -> &'static NamespaceRegistry`, built on first use with
+//! every `[tags.meta]` value stored as registry metadata.
+//!
+//! With `[codegen] history = true`, every build that adds, removes,
+//! deprecates or redirects tags appends a dated section to
+//! `tags.history.toml` next to `tags.toml`, answering "when did this tag
+//! appear" without digging through git; see [`History`].
+//!
+//! With `[codegen] constants = true`, the generated file also gets
+//! `GENERATED_AT` (the UTC date the tag set last changed), `CONFIG_HASH`
+//! (see [`LockFile::content_hash`]) and a sorted `DEPRECATED_PATHS`, so the
+//! game can show its tag-set version and flag data saved under another one.
+//!
+//! `[codegen]` also sets the generated module's `visibility`, outer `doc`
+//! comment, `allow`ed lints and extra `Tag` `derive`s, so the file passes
+//! stricter workspace lints as-is.
+//!
+//! # Command Line
+//!
+//! The `cargo bevy-tag` binary (`cargo install bevy-tag-build`) inspects a
+//! config without compiling the game: `validate`, `diff`, `gid <path>`,
+//! `path <gid>`, `tree`, `export`, and `dot`/`mermaid` diagrams of the tree
+//! (also available as [`export_dot`] and [`export_mermaid`], or written to a
+//! file with [`write_dot`] and [`write_mermaid`]). `import-ue
+//! <ini>` converts an Unreal `DefaultGameplayTags.ini` into a `tags.toml`
+//! (see [`TagsConfig::from_ue_ini`]). `merge-lock` merges lock files and
+//! works as a git merge driver for `tags.lock.toml`.
+//!
+//! # Nested Tags
+//!
+//! Large trees can be written as nested tables instead of a flat list;
+//! both forms may be mixed and produce the same paths:
+//!
+//! ```toml
+//! [tags.Item.Weapon]
+//! children = ["Sword", "Axe"]
 //! ```
 //!
-//! To intentionally break compatibility, delete the lock file and rebuild.
+//! # Multiple Files
+//!
+//! The root config can pull in other files with `includes`, or scan
+//! directories for per-crate `tags.toml` files with `discover`. All files
+//! merge into one namespace; declaring the same tag in two files is an error.
+//! [`generate`] emits `cargo:rerun-if-changed` for every file it reads.
+//!
+//! ```toml
+//! includes = ["tags/combat.toml", "tags/items.toml"]
+//! discover = ["crates"]
+//! ```
 
+mod c_header;
 mod codegen;
+mod diagnostic;
+mod diagram;
+mod gid;
+mod history;
 mod lock;
+mod manifest;
+mod markdown;
+mod policy;
+mod rename;
 mod toml_parser;
+mod typescript;
+mod ue_ini;
 
+pub use c_header::generate_c_header;
 pub use codegen::{generate_namespace_code, generate_namespace_code_from_lock};
-pub use lock::{LockFile, LockFileError};
-pub use toml_parser::{OnRemove, RedirectEntry, TagsConfig, TagsConfigError};
+pub use diagram::{export_dot, export_mermaid, write_dot, write_mermaid};
+pub use gid::{
+    find_collisions, format_gid, level_occupancy, path_gid, GidCollision, LevelOccupancy,
+    GID_SCHEME, HASH_VERSION, LAYOUT_VERSION, OCCUPANCY_WARN_PERCENT,
+};
+pub use history::{History, HistoryChange, HistoryRedirect};
+pub use lock::{GidMigration, GidMismatch, LockDiff, LockFile, LockFileError};
+pub use manifest::{Manifest, ManifestEntry};
+pub use markdown::generate_markdown;
+pub use policy::{Policy, PolicyLevel};
+pub use rename::{detect_renames, RenameCandidate};
+pub use toml_parser::{CodegenOptions, MetaEntry, MetaValue, OnRemove, RedirectEntry, TagsConfig, TagsConfigError};
+pub use typescript::generate_typescript;
+pub use ue_ini::ue_ini_to_toml;
+
+use std::path::{Path, PathBuf};
 
-use std::path::Path;
+/// File name [`generate_to_out_dir`] writes and `bevy_tag::include_tags!`
+/// includes.
+pub const OUT_DIR_FILE: &str = "bevy_tags.rs";
 
 /// Main entry point for build.rs integration.
 ///
@@ -62,6 +201,7 @@ use std::path::Path;
 /// Returns an error if:
 /// - `tags.toml` cannot be read or parsed
 /// - Lock file mismatch detected (paths removed) and `on_remove = "error"`
+/// - Two tags hash to the same GID
 /// - Output file cannot be written
 ///
 /// # Example
@@ -87,6 +227,37 @@ pub fn generate(
     generate_with_lock(config_path, &lock_path, output_path)
 }
 
+/// Generate into `$OUT_DIR/bevy_tags.rs`, for `bevy_tag::include_tags!()`.
+///
+/// The lock file still lives next to `tags.toml` and should be committed.
+/// Optional outputs from `[codegen]` other than `markdown` land in `OUT_DIR`
+/// too. Returns the path of the generated file.
+///
+/// # Errors
+///
+/// As [`generate`], and if `OUT_DIR` is not set (not running in build.rs).
+///
+/// # Example
+///
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     bevy_tag_build::generate_to_out_dir("tags.toml")
+///         .expect("Failed to generate tags");
+/// }
+/// ```
+pub fn generate_to_out_dir(config_path: impl AsRef<Path>) -> Result<PathBuf, GenerateError> {
+    let out_dir = std::env::var_os("OUT_DIR").ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "OUT_DIR is not set; generate_to_out_dir must run from build.rs",
+        )
+    })?;
+    let output_path = Path::new(&out_dir).join(OUT_DIR_FILE);
+    generate(config_path, &output_path)?;
+    Ok(output_path)
+}
+
 /// Generate with explicit lock file path.
 pub fn generate_with_lock(
     config_path: impl AsRef<Path>,
@@ -97,13 +268,44 @@ pub fn generate_with_lock(
     let lock_path = lock_path.as_ref();
     let output_path = output_path.as_ref();
 
-    // 1. Parse tags.toml
+    // 1. Parse tags.toml (and any included files)
     let config = TagsConfig::from_file(config_path)?;
+    for source in config.sources() {
+        println!("cargo:rerun-if-changed={}", source.display());
+    }
+    for warning in config.policy_warnings() {
+        println!("cargo:warning=bevy-tag: [policy] {}", warning);
+    }
 
     // 2. Load or create lock file
     let (lock, diff) = if lock_path.exists() {
-        let existing_lock = LockFile::from_file(lock_path)?;
-        let diff = existing_lock.diff(&config);
+        let mut existing_lock = LockFile::from_file(lock_path)?;
+        existing_lock.verify_entries()?;
+
+        // Recorded GIDs must still hash the same; anything else would
+        // silently corrupt saved data
+        let mismatches = existing_lock.verify_gids();
+        if !mismatches.is_empty() {
+            return Err(GenerateError::GidMismatch(format_gid_error(
+                existing_lock.gid_scheme.as_deref(),
+                &mismatches,
+            )));
+        }
+        existing_lock.fill_gids();
+
+        // Reviewed removals leave the lock; every other removal is checked below
+        let mut diff = existing_lock.diff(&config);
+        diff.removed.retain(|path| {
+            if !config.is_removal_allowed(path) {
+                return true;
+            }
+            existing_lock.remove_entry(path);
+            println!(
+                "cargo:warning=bevy-tag: '{}' was removed (allowed by allow_removed) and dropped from the lock",
+                path
+            );
+            false
+        });
         (existing_lock, Some(diff))
     } else {
         (LockFile::from_config(&config), None)
@@ -114,11 +316,21 @@ pub fn generate_with_lock(
     if let Some(ref diff) = diff
         && !diff.removed.is_empty()
     {
+        let renames = rename::detect_renames(&updated_lock, &config, diff);
+        if config.on_remove != OnRemove::AutoRedirect {
+            for rename in &renames {
+                println!(
+                    "cargo:warning=bevy-tag: '{}' looks renamed to '{}'; add `{}` under [redirects] to keep the old path working",
+                    rename.from, rename.to, rename.redirect_entry()
+                );
+            }
+        }
+
         match config.on_remove {
             OnRemove::Error => {
-                return Err(GenerateError::LockMismatch(format_lock_error(diff)));
+                return Err(GenerateError::LockMismatch(format_lock_error(diff, &renames)));
             }
-            OnRemove::Warn => {
+            OnRemove::Warn | OnRemove::AutoRedirect => {
                 // Mark removed paths as deprecated instead of erroring
                 for path in &diff.removed {
                     updated_lock.mark_deprecated(path);
@@ -132,6 +344,16 @@ pub fn generate_with_lock(
                 }
             }
         }
+
+        if config.on_remove == OnRemove::AutoRedirect {
+            for rename in &renames {
+                updated_lock.mark_equivalent(&rename.from, &rename.to);
+                println!(
+                    "cargo:warning=bevy-tag: '{}' looks renamed and now redirects to '{}'",
+                    rename.from, rename.to
+                );
+            }
+        }
     }
 
     // 4. Update lock file with new entries
@@ -143,27 +365,138 @@ pub fn generate_with_lock(
         }
     }
 
-    // 5. Write updated lock file
-    updated_lock.write_to_file(lock_path)?;
+    // 5. Every [deprecated] path must be a current tag or a removed one
+    for (path, _) in config.deprecations() {
+        let known = config.entries().any(|e| e.path == path)
+            || updated_lock.get(path).is_some_and(|e| e.deprecated);
+        if !known {
+            return Err(GenerateError::ConfigError(TagsConfigError::Validation(format!(
+                "Deprecated path '{}' is neither in [tags] nor a removed path in the lock file",
+                path
+            ))));
+        }
+    }
+
+    // 6. Catch GID collisions here rather than in the macro's const asserts,
+    //    and report crowded levels. Redirects have no GID of their own.
+    let gid_paths: Vec<&str> = updated_lock
+        .entries
+        .iter()
+        .filter(|e| e.equivalent_to.is_none())
+        .map(|e| e.path.as_str())
+        .collect();
+    if let Some(collision) = find_collisions(gid_paths.iter().copied()).first() {
+        return Err(GenerateError::GidCollision(format!(
+            "bevy-tag: '{}' and '{}' both hash to GID {}; rename one of them",
+            collision.first,
+            collision.second,
+            format_gid(collision.gid)
+        )));
+    }
+    let occupancy = level_occupancy(gid_paths.iter().copied());
+    if occupancy.iter().any(|level| level.percent() > OCCUPANCY_WARN_PERCENT) {
+        for level in &occupancy {
+            println!("cargo:warning=bevy-tag: {}", level);
+        }
+        println!(
+            "cargo:warning=bevy-tag: levels above {}% make hash collisions likely; consider restructuring",
+            OCCUPANCY_WARN_PERCENT
+        );
+    }
+
+    // 7. Write updated lock file (not on docs.rs, where sources are read-only)
+    let read_only_sources = std::env::var_os("DOCS_RS").is_some();
+    if !read_only_sources {
+        updated_lock.write_to_file(lock_path)?;
+    }
 
-    // 6. Generate Rust code (include deprecated entries from lock)
+    // 8. Generate Rust code (include deprecated entries from lock)
     let code = generate_namespace_code_from_lock(&config, &updated_lock);
-    std::fs::write(output_path, code)?;
+    let code = codegen::keep_generated_at(code, std::fs::read_to_string(output_path).ok());
+    write_if_changed(output_path, &code)?;
+
+    // 9. Optional JSON manifest next to the Rust file
+    if let Some(file_name) = &config.codegen.manifest {
+        let manifest_path = output_path.with_file_name(file_name);
+        write_if_changed(&manifest_path, &Manifest::new(&config, Some(&updated_lock)).to_json())?;
+    }
+
+    // 10. Optional C/C++ header next to the Rust file
+    if let Some(file_name) = &config.codegen.c_header {
+        let header_path = output_path.with_file_name(file_name);
+        write_if_changed(&header_path, &generate_c_header(&config, Some(&updated_lock)))?;
+    }
+
+    // 11. Optional TypeScript module next to the Rust file
+    if let Some(file_name) = &config.codegen.typescript {
+        let ts_path = output_path.with_file_name(file_name);
+        write_if_changed(&ts_path, &generate_typescript(&config, Some(&updated_lock)))?;
+    }
+
+    // 12. Optional Markdown docs next to tags.toml, to be committed with it
+    if let Some(file_name) = config.codegen.markdown.as_ref().filter(|_| !read_only_sources) {
+        let docs_path = config_path.with_file_name(file_name);
+        write_if_changed(&docs_path, &generate_markdown(&config, Some(&updated_lock)))?;
+    }
+
+    // 13. Optional change history next to tags.toml, appended when the tags changed
+    if let Some(file_name) = config.codegen.history.as_ref().filter(|_| !read_only_sources) {
+        let history_path = config_path.with_file_name(file_name);
+        let history = History::from_file(&history_path)?;
+        if let Some(change) = history.pending_change(&config, &updated_lock, &history::today()) {
+            History::append(&history_path, &change)?;
+        }
+    }
 
     Ok(())
 }
 
-fn format_lock_error(diff: &lock::LockDiff) -> String {
+/// Write `contents` unless the file already holds exactly that.
+///
+/// Keeps the mtime of unchanged outputs, so a build that changes nothing
+/// does not make cargo recompile the crate including them.
+fn write_if_changed(path: &Path, contents: &str) -> std::io::Result<()> {
+    if std::fs::read_to_string(path).is_ok_and(|existing| existing == contents) {
+        return Ok(());
+    }
+    std::fs::write(path, contents)
+}
+
+fn format_lock_error(diff: &lock::LockDiff, renames: &[RenameCandidate]) -> String {
     let mut msg = String::new();
     msg.push_str("bevy-tag: Lock file mismatch!\n\n");
     msg.push_str("  Missing in tags.toml (existed in lock):\n");
     for path in &diff.removed {
         msg.push_str(&format!("    - {}\n", path));
     }
+    if !renames.is_empty() {
+        msg.push_str("\n  Possible renames (add under [redirects] in tags.toml):\n");
+        for rename in renames {
+            msg.push_str(&format!("    {}\n", rename.redirect_entry()));
+        }
+    }
     msg.push_str("\n  To fix:\n");
     msg.push_str("    1. Add the path(s) back to tags.toml, OR\n");
     msg.push_str("    2. Set `on_remove = \"warn\"` in tags.toml to deprecate instead, OR\n");
-    msg.push_str("    3. Delete tags.lock.toml to regenerate (BREAKING CHANGE!)\n");
+    msg.push_str("    3. Set `on_remove = \"auto-redirect\"` to also redirect likely renames, OR\n");
+    msg.push_str("    4. List reviewed removals in `allow_removed` to drop them from the lock, OR\n");
+    msg.push_str("    5. Delete tags.lock.toml to regenerate (BREAKING CHANGE!)\n");
+    msg
+}
+
+fn format_gid_error(locked_scheme: Option<&str>, mismatches: &[GidMismatch]) -> String {
+    let mut msg = String::new();
+    msg.push_str("bevy-tag: GIDs changed for unchanged paths!\n\n");
+    msg.push_str(&format!(
+        "  Lock file scheme: {}\n  Current scheme:   {}\n\n",
+        locked_scheme.unwrap_or("(unrecorded)"),
+        gid::GID_SCHEME
+    ));
+    for m in mismatches {
+        msg.push_str(&format!("    - {}: {} -> {}\n", m.path, m.locked, m.computed));
+    }
+    msg.push_str("\n  The hash function or bit layout changed; saved GIDs would no longer match.\n");
+    msg.push_str("  Revert the change, or delete tags.lock.toml to accept it (BREAKING CHANGE!)\n");
     msg
 }
 
@@ -176,6 +509,10 @@ pub enum GenerateError {
     LockError(LockFileError),
     /// Lock file mismatch (paths removed)
     LockMismatch(String),
+    /// A locked GID no longer matches the computed one
+    GidMismatch(String),
+    /// Two paths hash to the same GID
+    GidCollision(String),
     /// IO error
     Io(std::io::Error),
 }
@@ -186,6 +523,8 @@ impl std::fmt::Display for GenerateError {
             Self::ConfigError(e) => write!(f, "Config error: {}", e),
             Self::LockError(e) => write!(f, "Lock file error: {}", e),
             Self::LockMismatch(msg) => write!(f, "{}", msg),
+            Self::GidMismatch(msg) => write!(f, "{}", msg),
+            Self::GidCollision(msg) => write!(f, "{}", msg),
             Self::Io(e) => write!(f, "IO error: {}", e),
         }
     }
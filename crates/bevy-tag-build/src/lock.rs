@@ -2,26 +2,61 @@
 //!
 //! The lock file records all paths and their computed GIDs.
 //! On subsequent builds, we compare the current config against the lock
-//! to detect breaking changes (removed paths).
+//! to detect breaking changes (removed paths), and recompute every locked
+//! GID to catch hash or bit-layout changes that would corrupt saved data.
+//!
+//! Entries are written sorted, one per line, so branches that add different
+//! tags usually merge cleanly. When they do conflict, [`LockFile::merge`]
+//! (or `cargo bevy-tag merge-lock`) resolves it mechanically.
+//!
+//! A `checksum` trailer over the entries catches hand edits (changed GIDs,
+//! deleted lines) that would otherwise be silently accepted; see
+//! [`LockFile::verify_entries`]. It is the last line of the file, so two
+//! branches adding tags conflict only there; merging recomputes it. As a git
+//! merge driver:
+//!
+//! ```text
+//! # .gitattributes
+//! tags.lock.toml merge=bevy-tag-lock
+//!
+//! # .git/config
+//! [merge "bevy-tag-lock"]
+//!     driver = cargo bevy-tag merge-lock %B --lock %A
+//! ```
 
+use crate::gid::{fnv1a_64, format_gid, path_gid, GID_SCHEME};
 use crate::toml_parser::{TagEntry, TagsConfig};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
 use std::path::Path;
 
 /// Current lock file schema version.
-const SCHEMA_VERSION: u32 = 1;
+///
+/// Version 2 added `gid_scheme` and per-entry `gid`.
+const SCHEMA_VERSION: u32 = 2;
 
 /// Lock file contents.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LockFile {
     /// Schema version for forward compatibility
     pub schema_version: u32,
-    /// When the lock file was generated
+    /// Hash function and bit layout the GIDs were computed with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gid_scheme: Option<String>,
+    /// When an older lock was written. No longer written (it changed on
+    /// every build and conflicted on every merge); read from old locks and
+    /// otherwise empty.
+    #[deprecated(note = "no longer written to the lock file")]
+    #[serde(default, skip_serializing)]
     pub generated_at: String,
     /// All locked entries
     #[serde(default)]
     pub entries: Vec<LockEntry>,
+    /// Checksum of the entries when the file was written (None in locks
+    /// written without one). Recomputed on every write.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
 }
 
 /// A single locked entry.
@@ -31,6 +66,10 @@ pub struct LockEntry {
     pub path: String,
     /// Tree depth
     pub depth: u8,
+    /// Computed GID as fixed-width hex (None in locks written before GIDs
+    /// were recorded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gid: Option<String>,
     /// Parent path (None for root)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent: Option<String>,
@@ -42,6 +81,42 @@ pub struct LockEntry {
     pub equivalent_to: Option<String>,
 }
 
+/// A locked GID that differs from the one computed now.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GidMismatch {
+    /// Path of the entry
+    pub path: String,
+    /// GID recorded in the lock file
+    pub locked: String,
+    /// GID computed by this build
+    pub computed: String,
+}
+
+/// A locked GID that saved data must be rewritten to, because the path now
+/// redirects elsewhere or hashes differently.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GidMigration {
+    /// Locked path
+    pub path: String,
+    /// Path whose GID it now resolves to (itself unless redirected)
+    pub target: String,
+    /// GID recorded in the lock file
+    pub from: String,
+    /// GID the game uses now
+    pub to: String,
+}
+
+/// One `old new` line of the map read by `bevy_tag::migrate::RedirectMap`.
+impl std::fmt::Display for GidMigration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}  # {}", self.from, self.to, self.path)?;
+        if self.target != self.path {
+            write!(f, " -> {}", self.target)?;
+        }
+        Ok(())
+    }
+}
+
 /// Difference between lock file and current config.
 #[derive(Debug, Default)]
 pub struct LockDiff {
@@ -59,16 +134,20 @@ impl LockFile {
             .map(|e| LockEntry {
                 path: e.path.clone(),
                 depth: e.depth,
+                gid: path_gid(&e.path).map(format_gid),
                 parent: e.parent.clone(),
                 deprecated: false,
                 equivalent_to: None,
             })
             .collect();
 
+        #[allow(deprecated)]
         Self {
             schema_version: SCHEMA_VERSION,
-            generated_at: chrono::Utc::now().to_rfc3339(),
+            gid_scheme: Some(GID_SCHEME.to_string()),
+            generated_at: String::new(),
             entries,
+            checksum: None,
         }
     }
 
@@ -81,6 +160,7 @@ impl LockFile {
     }
 
     /// Parse from a TOML string.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(content: &str) -> Result<Self, LockFileError> {
         let lock: LockFile =
             toml::from_str(content).map_err(|e| LockFileError::Parse(e.to_string()))?;
@@ -97,20 +177,237 @@ impl LockFile {
     }
 
     /// Write to a TOML file.
+    ///
+    /// An existing file with identical contents is left untouched, so its
+    /// modification time does not trigger rebuilds.
     pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), LockFileError> {
-        let header = "# AUTO-GENERATED by bevy-tag-build - DO NOT EDIT MANUALLY\n\
-                      # To update: delete this file and rebuild\n\n";
-
-        let content =
-            toml::to_string_pretty(self).map_err(|e| LockFileError::Serialize(e.to_string()))?;
-
-        std::fs::write(path.as_ref(), format!("{}{}", header, content)).map_err(|e| {
+        crate::write_if_changed(path.as_ref(), &self.to_toml_string()).map_err(|e| {
             LockFileError::Io(format!("Failed to write {}: {}", path.as_ref().display(), e))
         })?;
 
         Ok(())
     }
 
+    /// Render the lock file as written to disk.
+    ///
+    /// Each entry is an inline table on its own line with a trailing comma,
+    /// sorted by path, so adding a tag only adds a line. The `checksum`
+    /// trailer follows the entries.
+    pub fn to_toml_string(&self) -> String {
+        let mut out = String::from(
+            "# AUTO-GENERATED by bevy-tag-build - DO NOT EDIT MANUALLY\n\
+             # To update: delete this file and rebuild\n\
+             # To resolve merge conflicts: cargo bevy-tag merge-lock\n\n",
+        );
+        writeln!(out, "schema_version = {}", self.schema_version).unwrap();
+        if let Some(scheme) = &self.gid_scheme {
+            writeln!(out, "gid_scheme = {}", toml_string(scheme)).unwrap();
+        }
+        writeln!(out).unwrap();
+
+        let entries = self.entry_lines();
+        writeln!(out, "entries = [").unwrap();
+        out.push_str(&entries);
+        writeln!(out, "]").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "checksum = {}", toml_string(&Self::checksum_of(&entries))).unwrap();
+        out
+    }
+
+    /// The `entries` array body: one sorted inline table per line.
+    fn entry_lines(&self) -> String {
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let mut out = String::new();
+        for entry in entries {
+            write!(out, "    {{ path = {}, depth = {}", toml_string(&entry.path), entry.depth).unwrap();
+            if let Some(gid) = &entry.gid {
+                write!(out, ", gid = {}", toml_string(gid)).unwrap();
+            }
+            if let Some(parent) = &entry.parent {
+                write!(out, ", parent = {}", toml_string(parent)).unwrap();
+            }
+            if entry.deprecated {
+                write!(out, ", deprecated = true").unwrap();
+            }
+            if let Some(target) = &entry.equivalent_to {
+                write!(out, ", equivalent_to = {}", toml_string(target)).unwrap();
+            }
+            writeln!(out, " }},").unwrap();
+        }
+        out
+    }
+
+    fn checksum_of(entry_lines: &str) -> String {
+        format!("fnv1a64:{:016x}", fnv1a_64(entry_lines.as_bytes()))
+    }
+
+    /// Hash of the entries, as recorded (in hex) in the `checksum` trailer.
+    ///
+    /// Changes whenever a path, GID, deprecation or redirect changes.
+    pub fn content_hash(&self) -> u64 {
+        fnv1a_64(self.entry_lines().as_bytes())
+    }
+
+    /// Check the entries against the recorded checksum and their paths.
+    ///
+    /// A failure means the file was edited by hand. The error lists what
+    /// can be pinpointed: entries whose parent or depth don't match their
+    /// path and entries whose parent is gone. Any other change (a deleted
+    /// leaf, deprecated or redirect line) is caught by the checksum. Locks
+    /// without a checksum are only checked entry by entry. GIDs are checked
+    /// separately by [`LockFile::verify_gids`].
+    pub fn verify_entries(&self) -> Result<(), LockFileError> {
+        let paths: HashSet<&str> = self.entries.iter().map(|e| e.path.as_str()).collect();
+        let mut details = Vec::new();
+        for entry in &self.entries {
+            let prefix = entry.path.rsplit_once('.').map(|(prefix, _)| prefix);
+            let depth = entry.path.matches('.').count();
+            if entry.parent.as_deref() != prefix || entry.depth as usize != depth {
+                details.push(format!("  '{}' has a parent or depth that doesn't match its path", entry.path));
+            } else if let Some(parent) = prefix.filter(|p| !paths.contains(p)) {
+                details.push(format!("  '{}' is missing (parent of '{}')", parent, entry.path));
+            }
+        }
+        let checksum_ok = self
+            .checksum
+            .as_ref()
+            .is_none_or(|recorded| *recorded == Self::checksum_of(&self.entry_lines()));
+        if details.is_empty() {
+            if checksum_ok {
+                return Ok(());
+            }
+            details.push("  an entry was added, removed or changed".to_string());
+        }
+        Err(LockFileError::Tampered(details.join("\n")))
+    }
+
+    /// Union two lock files, e.g. both sides of a merge conflict.
+    ///
+    /// An entry on either side is kept; deprecation from either side wins.
+    /// The checksums of the two sides are ignored; the result gets a fresh
+    /// one when written.
+    /// Entries recorded differently on the two sides (GID, parent or alias
+    /// target), a different GID scheme, or a result whose GIDs no longer
+    /// verify are errors.
+    pub fn merge(ours: &LockFile, theirs: &LockFile) -> Result<LockFile, LockFileError> {
+        if let (Some(a), Some(b)) = (&ours.gid_scheme, &theirs.gid_scheme)
+            && a != b
+        {
+            return Err(LockFileError::Conflict(format!(
+                "GID schemes differ: '{}' vs '{}'",
+                a, b
+            )));
+        }
+
+        let mut entries: BTreeMap<&str, LockEntry> = BTreeMap::new();
+        for entry in ours.entries.iter().chain(&theirs.entries) {
+            let Some(existing) = entries.get_mut(entry.path.as_str()) else {
+                entries.insert(&entry.path, entry.clone());
+                continue;
+            };
+            let conflict = |what: &str| {
+                LockFileError::Conflict(format!("'{}' has a different {} on each side", entry.path, what))
+            };
+            if existing.depth != entry.depth || existing.parent != entry.parent {
+                return Err(conflict("parent"));
+            }
+            match (&existing.gid, &entry.gid) {
+                (Some(a), Some(b)) if !a.eq_ignore_ascii_case(b) => return Err(conflict("GID")),
+                (None, Some(_)) => existing.gid = entry.gid.clone(),
+                _ => {}
+            }
+            match (&existing.equivalent_to, &entry.equivalent_to) {
+                (Some(a), Some(b)) if a != b => return Err(conflict("redirect target")),
+                (None, Some(_)) => existing.equivalent_to = entry.equivalent_to.clone(),
+                _ => {}
+            }
+            existing.deprecated |= entry.deprecated;
+        }
+
+        #[allow(deprecated)]
+        let merged = LockFile {
+            schema_version: ours.schema_version.max(theirs.schema_version),
+            gid_scheme: ours.gid_scheme.clone().or_else(|| theirs.gid_scheme.clone()),
+            generated_at: String::new(),
+            entries: entries.into_values().collect(),
+            checksum: None,
+        };
+
+        if let Some(entry) = merged
+            .entries
+            .iter()
+            .find(|e| e.parent.as_deref().is_some_and(|p| merged.get(p).is_none()))
+        {
+            return Err(LockFileError::Conflict(format!(
+                "'{}' has no parent entry '{}'",
+                entry.path,
+                entry.parent.as_deref().unwrap_or_default()
+            )));
+        }
+        if let Some(mismatch) = merged.verify_gids().first() {
+            return Err(LockFileError::Conflict(format!(
+                "'{}' is locked as {} but hashes to {}",
+                mismatch.path, mismatch.locked, mismatch.computed
+            )));
+        }
+        Ok(merged)
+    }
+
+    /// Resolve a lock file containing git conflict markers by merging both
+    /// sides with [`LockFile::merge`].
+    ///
+    /// Lines outside the markers belong to both sides; a diff3 base section
+    /// is ignored.
+    pub fn resolve_conflicts(content: &str) -> Result<LockFile, LockFileError> {
+        #[derive(PartialEq)]
+        enum Side {
+            Both,
+            Ours,
+            Base,
+            Theirs,
+        }
+
+        let (mut ours, mut theirs) = (String::new(), String::new());
+        let mut side = Side::Both;
+        for line in content.lines() {
+            let next = if line.starts_with("<<<<<<<") {
+                Some(Side::Ours)
+            } else if line.starts_with("|||||||") {
+                Some(Side::Base)
+            } else if line.starts_with("=======") {
+                Some(Side::Theirs)
+            } else if line.starts_with(">>>>>>>") {
+                Some(Side::Both)
+            } else {
+                None
+            };
+            if let Some(next) = next {
+                side = next;
+                continue;
+            }
+            if matches!(side, Side::Both | Side::Ours) {
+                ours.push_str(line);
+                ours.push('\n');
+            }
+            if matches!(side, Side::Both | Side::Theirs) {
+                theirs.push_str(line);
+                theirs.push('\n');
+            }
+        }
+        if side != Side::Both {
+            return Err(LockFileError::Parse("unterminated conflict marker".into()));
+        }
+
+        let side = |text: &str, name: &str| {
+            Self::from_str(text).map_err(|e| match e {
+                LockFileError::Parse(msg) => LockFileError::Parse(format!("{} side: {}", name, msg)),
+                other => other,
+            })
+        };
+        Self::merge(&side(&ours, "our")?, &side(&theirs, "their")?)
+    }
+
     /// Compare against current config.
     pub fn diff(&self, config: &TagsConfig) -> LockDiff {
         let lock_paths: HashSet<_> = self.entries.iter().map(|e| e.path.as_str()).collect();
@@ -137,6 +434,7 @@ impl LockFile {
         }
 
         self.entries.push(LockEntry {
+            gid: path_gid(&entry.path).map(format_gid),
             path: entry.path,
             depth: entry.depth,
             parent: entry.parent,
@@ -146,9 +444,11 @@ impl LockFile {
 
         // Keep sorted for deterministic output
         self.entries.sort_by(|a, b| a.path.cmp(&b.path));
+    }
 
-        // Update timestamp
-        self.generated_at = chrono::Utc::now().to_rfc3339();
+    /// Drop an entry, e.g. a removal allowed by `allow_removed`.
+    pub fn remove_entry(&mut self, path: &str) {
+        self.entries.retain(|e| e.path != path);
     }
 
     /// Mark an entry as deprecated.
@@ -156,7 +456,76 @@ impl LockFile {
         if let Some(entry) = self.entries.iter_mut().find(|e| e.path == path) {
             entry.deprecated = true;
         }
-        self.generated_at = chrono::Utc::now().to_rfc3339();
+    }
+
+    /// Mark an entry as equivalent to (redirected to) another path.
+    pub fn mark_equivalent(&mut self, path: &str, target: &str) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.path == path) {
+            entry.equivalent_to = Some(target.to_string());
+        }
+    }
+
+    /// Recompute every recorded GID and report the ones that changed.
+    ///
+    /// Paths are never renamed in the lock, so any difference means the hash
+    /// function or bit layout changed.
+    pub fn verify_gids(&self) -> Vec<GidMismatch> {
+        self.entries
+            .iter()
+            .filter_map(|e| {
+                let locked = e.gid.as_ref()?;
+                let computed = path_gid(&e.path).map(format_gid).unwrap_or_default();
+                (!locked.eq_ignore_ascii_case(&computed)).then(|| GidMismatch {
+                    path: e.path.clone(),
+                    locked: locked.clone(),
+                    computed,
+                })
+            })
+            .collect()
+    }
+
+    /// Locked GIDs that changed meaning under `config`: redirected paths
+    /// (from the config or the lock) map to their target's GID, and paths
+    /// whose hash or layout changed map to their recomputed GID.
+    ///
+    /// Feed the result to `bevy_tag::migrate` to upgrade saved data.
+    pub fn gid_migrations(&self, config: &TagsConfig) -> Vec<GidMigration> {
+        let redirects: HashMap<&str, &str> = config
+            .redirects()
+            .map(|r| (r.from.as_str(), r.to.as_str()))
+            .collect();
+        self.entries
+            .iter()
+            .filter_map(|e| {
+                let from = e.gid.as_ref()?;
+                let target = redirects
+                    .get(e.path.as_str())
+                    .copied()
+                    .or(e.equivalent_to.as_deref())
+                    .unwrap_or(&e.path);
+                let to = format_gid(path_gid(target)?);
+                (!from.eq_ignore_ascii_case(&to)).then(|| GidMigration {
+                    path: e.path.clone(),
+                    target: target.to_string(),
+                    from: from.clone(),
+                    to,
+                })
+            })
+            .collect()
+    }
+
+    /// Record GIDs for entries that have none and stamp the current scheme.
+    ///
+    /// Upgrades locks written before GIDs were recorded. Call after
+    /// [`LockFile::verify_gids`] passes.
+    pub fn fill_gids(&mut self) {
+        for entry in &mut self.entries {
+            if entry.gid.is_none() {
+                entry.gid = path_gid(&entry.path).map(format_gid);
+            }
+        }
+        self.gid_scheme = Some(GID_SCHEME.to_string());
+        self.schema_version = SCHEMA_VERSION;
     }
 
     /// Get all deprecated entries.
@@ -191,6 +560,10 @@ pub enum LockFileError {
     Serialize(String),
     /// Unsupported schema version
     SchemaVersion { found: u32, supported: u32 },
+    /// Two lock files that cannot be merged
+    Conflict(String),
+    /// Entries that don't match the recorded checksum or their paths
+    Tampered(String),
 }
 
 impl std::fmt::Display for LockFileError {
@@ -199,6 +572,14 @@ impl std::fmt::Display for LockFileError {
             Self::Io(msg) => write!(f, "IO error: {}", msg),
             Self::Parse(msg) => write!(f, "Parse error: {}", msg),
             Self::Serialize(msg) => write!(f, "Serialize error: {}", msg),
+            Self::Conflict(msg) => write!(f, "Merge conflict: {}", msg),
+            Self::Tampered(details) => write!(
+                f,
+                "Lock file entries do not match its checksum; it was edited by hand:\n{}\n\
+                 Restore it from version control (or run `cargo bevy-tag merge-lock` after a \
+                 merge). To accept the edit, delete the `checksum` line.",
+                details
+            ),
             Self::SchemaVersion { found, supported } => {
                 write!(
                     f,
@@ -213,6 +594,11 @@ impl std::fmt::Display for LockFileError {
 
 impl std::error::Error for LockFileError {}
 
+/// A TOML basic string.
+pub(crate) fn toml_string(s: &str) -> String {
+    toml::Value::String(s.to_string()).to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +641,128 @@ paths = [{}]
         }
     }
 
+    #[test]
+    fn writes_one_entry_per_line() {
+        let mut lock = LockFile::from_config(&make_config(&["B", "A.X"]));
+        lock.mark_deprecated("B");
+        lock.mark_equivalent("B", "A.X");
+
+        let text = lock.to_toml_string();
+        let lines: Vec<_> = text.lines().filter(|l| l.starts_with("    {")).collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("    { path = \"A\", depth = 0, gid = "));
+        assert!(lines[1].contains("path = \"A.X\"") && lines[1].ends_with("parent = \"A\" },"));
+        assert!(lines[2].ends_with(", deprecated = true, equivalent_to = \"A.X\" },"));
+
+        let parsed = LockFile::from_str(&text).unwrap();
+        assert_eq!(parsed.gid_scheme, lock.gid_scheme);
+        assert_eq!(parsed.get("B"), lock.get("B"));
+        assert_eq!(parsed.entries.len(), 3);
+    }
+
+    #[test]
+    fn verify_entries_detects_hand_edits() {
+        let text = LockFile::from_config(&make_config(&["A.B", "C"])).to_toml_string();
+        let lock = LockFile::from_str(&text).unwrap();
+        assert!(lock.checksum.as_deref().unwrap().starts_with("fnv1a64:"));
+        assert!(text.trim_end().lines().last().unwrap().starts_with("checksum = "));
+        assert!(lock.verify_entries().is_ok());
+
+        // Edited GID
+        let gid = lock.get("C").unwrap().gid.clone().unwrap();
+        let edited = LockFile::from_str(&text.replace(&gid, &format_gid(42))).unwrap();
+        assert_eq!(edited.verify_gids()[0].path, "C");
+
+        // Deleted parent line
+        let a_line = text.lines().find(|l| l.contains("path = \"A\",")).unwrap();
+        let deleted = LockFile::from_str(&text.replace(&format!("{}\n", a_line), "")).unwrap();
+        let err = deleted.verify_entries().unwrap_err().to_string();
+        assert!(err.contains("'A' is missing (parent of 'A.B')"), "{}", err);
+
+        // Parent that isn't the path prefix
+        let moved = LockFile::from_str(&text.replace("parent = \"A\"", "parent = \"C\"")).unwrap();
+        assert!(matches!(moved.verify_entries(), Err(LockFileError::Tampered(_))));
+
+        // Deleted leaf and deprecated lines: nothing to pinpoint, still caught
+        let mut lock = LockFile::from_config(&make_config(&["A.B", "C", "D"]));
+        lock.mark_deprecated("D");
+        let text = lock.to_toml_string();
+        for path in ["C", "D"] {
+            let line = text.lines().find(|l| l.contains(&format!("path = \"{}\",", path))).unwrap();
+            let deleted = LockFile::from_str(&text.replace(&format!("{}\n", line), "")).unwrap();
+            assert!(matches!(deleted.verify_entries(), Err(LockFileError::Tampered(_))), "{}", path);
+        }
+        let undeprecated = LockFile::from_str(&text.replace(", deprecated = true", "")).unwrap();
+        assert!(matches!(undeprecated.verify_entries(), Err(LockFileError::Tampered(_))));
+
+        // Locks without a checksum are checked entry by entry only
+        let unchecked = LockFile::from_str(&text.replace("checksum", "# checksum")).unwrap();
+        assert!(unchecked.verify_entries().is_ok());
+    }
+
+    #[test]
+    fn merge_unions_entries() {
+        let base = make_config(&["A.B"]);
+        let mut ours = LockFile::from_config(&make_config(&["A.B", "A.C"]));
+        let mut theirs = LockFile::from_config(&make_config(&["A.B", "X"]));
+        ours.mark_deprecated("A.B");
+        theirs.gid_scheme = None;
+        theirs.entries.iter_mut().for_each(|e| e.gid = None);
+
+        let merged = LockFile::merge(&ours, &theirs).unwrap();
+        let paths: Vec<_> = merged.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["A", "A.B", "A.C", "X"]);
+        assert!(merged.get("A.B").unwrap().deprecated);
+        assert_eq!(merged.gid_scheme.as_deref(), Some(GID_SCHEME));
+        assert!(merged.diff(&base).added.is_empty());
+    }
+
+    #[test]
+    fn merge_rejects_conflicting_entries() {
+        let ours = LockFile::from_config(&make_config(&["A.B"]));
+        let mut theirs = ours.clone();
+        theirs.entries[1].gid = Some(format_gid(42));
+        let err = LockFile::merge(&ours, &theirs).unwrap_err();
+        assert!(err.to_string().contains("'A.B' has a different GID"), "{}", err);
+
+        let mut theirs = ours.clone();
+        theirs.gid_scheme = Some("other".into());
+        assert!(matches!(LockFile::merge(&ours, &theirs), Err(LockFileError::Conflict(_))));
+
+        // A child whose parent is on neither side
+        let mut orphan = ours.clone();
+        orphan.entries.remove(0);
+        let err = LockFile::merge(&orphan, &orphan).unwrap_err();
+        assert!(err.to_string().contains("no parent entry 'A'"), "{}", err);
+    }
+
+    #[test]
+    fn resolves_conflict_markers() {
+        let ours = LockFile::from_config(&make_config(&["A.B"])).to_toml_string();
+        let theirs = LockFile::from_config(&make_config(&["A.C"])).to_toml_string();
+        // What git produces when both sides add a line after the same entry
+        // (and the checksum trailers differ)
+        let conflict = |line: &dyn Fn(&str) -> bool, text: &str| {
+            let ours_line = ours.lines().find(|l| line(l)).unwrap();
+            let theirs_line = theirs.lines().find(|l| line(l)).unwrap();
+            text.replace(
+                &format!("{}\n", ours_line),
+                &format!("<<<<<<< HEAD\n{}\n=======\n{}\n>>>>>>> branch\n", ours_line, theirs_line),
+            )
+        };
+        let conflicted = conflict(&|l| l.contains("\"A.B\"") || l.contains("\"A.C\""), &ours);
+        let conflicted = conflict(&|l| l.starts_with("checksum"), &conflicted);
+
+        let merged = LockFile::resolve_conflicts(&conflicted).unwrap();
+        let paths: Vec<_> = merged.entries.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths, ["A", "A.B", "A.C"]);
+        let written = LockFile::from_str(&merged.to_toml_string()).unwrap();
+        assert!(written.verify_entries().is_ok());
+
+        let err = LockFile::resolve_conflicts("<<<<<<< HEAD\nentries = []\n").unwrap_err();
+        assert!(err.to_string().contains("unterminated"), "{}", err);
+    }
+
     #[test]
     fn diff_detects_removed() {
         let config_v1 = make_config(&["A.B", "X.Y"]);
@@ -311,6 +819,75 @@ paths = [{}]
         assert_eq!(lock.entries.len(), original_len);
     }
 
+    #[test]
+    fn records_gids() {
+        let config = make_config(&["A.B"]);
+        let lock = LockFile::from_config(&config);
+
+        assert_eq!(lock.gid_scheme.as_deref(), Some(GID_SCHEME));
+        let b = lock.get("A.B").unwrap();
+        assert_eq!(b.gid, path_gid("A.B").map(format_gid));
+        assert!(lock.verify_gids().is_empty());
+    }
+
+    #[test]
+    fn verify_detects_changed_gid() {
+        let config = make_config(&["A.B"]);
+        let mut lock = LockFile::from_config(&config);
+        lock.entries[1].gid = Some(format_gid(42));
+
+        let mismatches = lock.verify_gids();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].path, "A.B");
+        assert_eq!(mismatches[0].locked, format_gid(42));
+    }
+
+    #[test]
+    fn migrations_cover_redirects_and_changed_gids() {
+        let config = make_config(&["A.B", "A.C", "D"]);
+        let mut lock = LockFile::from_config(&config);
+        assert!(lock.gid_migrations(&config).is_empty());
+
+        lock.mark_equivalent("A.C", "A.B");
+        lock.entries[3].gid = Some(format_gid(42));
+        let migrations = lock.gid_migrations(&make_config(&["A.B"]));
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[0].from, format_gid(path_gid("A.C").unwrap()));
+        assert_eq!(migrations[0].to, format_gid(path_gid("A.B").unwrap()));
+        assert_eq!(
+            migrations[0].to_string(),
+            format!("{} {}  # A.C -> A.B", migrations[0].from, migrations[0].to)
+        );
+        assert_eq!(migrations[1].from, format_gid(42));
+        assert_eq!(migrations[1].to, format_gid(path_gid("D").unwrap()));
+        assert_eq!(migrations[1].target, "D");
+    }
+
+    #[test]
+    fn fill_upgrades_old_locks() {
+        let toml = r#"
+schema_version = 1
+generated_at = "2025-01-01T00:00:00Z"
+
+[[entries]]
+path = "A"
+depth = 0
+"#;
+        let mut lock = LockFile::from_str(toml).unwrap();
+        assert!(lock.verify_gids().is_empty());
+
+        lock.fill_gids();
+
+        assert_eq!(lock.schema_version, SCHEMA_VERSION);
+        assert_eq!(lock.get("A").unwrap().gid, path_gid("A").map(format_gid));
+
+        // The old timestamp is read but never written back
+        #[allow(deprecated)]
+        let generated_at = lock.generated_at.clone();
+        assert_eq!(generated_at, "2025-01-01T00:00:00Z");
+        assert!(!lock.to_toml_string().contains("generated_at"));
+    }
+
     #[test]
     fn rejects_future_schema() {
         let toml = r#"
@@ -0,0 +1,177 @@
+//! Machine-readable GID manifest (`tags_manifest.json`).
+//!
+//! Lets backend services, analytics pipelines and scripting runtimes resolve
+//! tags exactly as the game does, without linking Rust code:
+//!
+//! ```json
+//! {
+//!   "gid_scheme": "fnv1a64-mix-v1/21.18.16.16.14.14.13.13",
+//!   "module_name": "Tags",
+//!   "tags": [
+//!     { "path": "Item", "gid": "0x...", "depth": 0, "parent": null,
+//!       "deprecated": false, "redirect": null }
+//!   ]
+//! }
+//! ```
+
+use crate::gid::{format_gid, path_gid, GID_SCHEME};
+use crate::lock::LockFile;
+use crate::toml_parser::TagsConfig;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Every tag the generated code defines, with its GID.
+#[derive(Debug, Clone, Serialize)]
+pub struct Manifest {
+    /// Hash function and bit layout of the GIDs
+    pub gid_scheme: String,
+    /// Name of the generated namespace module
+    pub module_name: String,
+    /// Entries sorted by path
+    pub tags: Vec<ManifestEntry>,
+}
+
+/// One tag in the manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ManifestEntry {
+    /// Full dot-separated path
+    pub path: String,
+    /// GID as fixed-width hex; a redirect has its target's GID
+    pub gid: Option<String>,
+    /// Tree depth (0 = root)
+    pub depth: u8,
+    /// Parent path (None for root nodes)
+    pub parent: Option<String>,
+    /// Deprecated via `[deprecated]`, removal, or redirect
+    pub deprecated: bool,
+    /// Canonical path this one redirects to
+    pub redirect: Option<String>,
+}
+
+impl ManifestEntry {
+    fn new(path: &str, deprecated: bool, redirect: Option<&str>) -> Self {
+        let gid_path = redirect.unwrap_or(path);
+        Self {
+            path: path.to_string(),
+            gid: path_gid(gid_path).map(format_gid),
+            depth: path.matches('.').count() as u8,
+            parent: path.rsplit_once('.').map(|(parent, _)| parent.to_string()),
+            deprecated,
+            redirect: redirect.map(str::to_string),
+        }
+    }
+}
+
+impl Manifest {
+    /// Build the manifest for `config`, including paths kept alive by `lock`
+    /// (removed paths and their redirects).
+    pub fn new(config: &TagsConfig, lock: Option<&LockFile>) -> Self {
+        let mut tags = BTreeMap::new();
+
+        for entry in lock.iter().flat_map(|l| l.deprecated_entries()) {
+            let redirect = entry.equivalent_to.as_deref();
+            tags.insert(entry.path.clone(), ManifestEntry::new(&entry.path, true, redirect));
+        }
+        for entry in config.entries() {
+            let deprecated = config.deprecation_note(&entry.path).is_some();
+            tags.insert(entry.path.clone(), ManifestEntry::new(&entry.path, deprecated, None));
+        }
+        for redirect in config.redirects() {
+            tags.insert(
+                redirect.from.clone(),
+                ManifestEntry::new(&redirect.from, true, Some(&redirect.to)),
+            );
+        }
+        // Parents of redirects exist as plain nodes in the generated code
+        let parents: Vec<String> = tags.values().filter_map(|e| e.parent.clone()).collect();
+        for parent in parents {
+            let mut path = Some(parent.as_str());
+            while let Some(p) = path {
+                if tags.contains_key(p) {
+                    break;
+                }
+                tags.insert(p.to_string(), ManifestEntry::new(p, false, None));
+                path = p.rsplit_once('.').map(|(parent, _)| parent);
+            }
+        }
+
+        Self {
+            gid_scheme: GID_SCHEME.to_string(),
+            module_name: config.module_name.clone(),
+            tags: tags.into_values().collect(),
+        }
+    }
+
+    /// Pretty-printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("manifest is always serializable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn includes_redirects_and_removed_paths() {
+        let toml = r#"
+[tags]
+paths = ["Equipment.Weapon.Blade"]
+
+[redirects]
+"Legacy.OldSword" = "Equipment.Weapon.Blade"
+
+[deprecated]
+"Equipment.Weapon" = "Use Equipment.Gear"
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+        let mut lock = LockFile::from_config(&config);
+        lock.entries.push(crate::lock::LockEntry {
+            path: "Gone".into(),
+            depth: 0,
+            gid: None,
+            parent: None,
+            deprecated: true,
+            equivalent_to: None,
+        });
+
+        let manifest = Manifest::new(&config, Some(&lock));
+        let by_path: BTreeMap<_, _> = manifest.tags.iter().map(|e| (e.path.as_str(), e)).collect();
+
+        let paths: Vec<_> = by_path.keys().copied().collect();
+        assert_eq!(
+            paths,
+            [
+                "Equipment",
+                "Equipment.Weapon",
+                "Equipment.Weapon.Blade",
+                "Gone",
+                "Legacy",
+                "Legacy.OldSword",
+            ]
+        );
+
+        let old = by_path["Legacy.OldSword"];
+        assert!(old.deprecated);
+        assert_eq!(old.redirect.as_deref(), Some("Equipment.Weapon.Blade"));
+        assert_eq!(old.gid, by_path["Equipment.Weapon.Blade"].gid);
+        assert_eq!(old.parent.as_deref(), Some("Legacy"));
+        assert_eq!(old.depth, 1);
+
+        assert!(by_path["Equipment.Weapon"].deprecated);
+        assert!(by_path["Gone"].deprecated);
+        assert!(!by_path["Legacy"].deprecated);
+    }
+
+    #[test]
+    fn json_shape() {
+        let config = TagsConfig::from_str("[tags]\npaths = [\"A\"]").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&Manifest::new(&config, None).to_json()).unwrap();
+
+        assert_eq!(json["gid_scheme"], GID_SCHEME);
+        assert_eq!(json["module_name"], "Tags");
+        assert_eq!(json["tags"][0]["path"], "A");
+        assert_eq!(json["tags"][0]["gid"], format_gid(path_gid("A").unwrap()));
+        assert!(json["tags"][0]["parent"].is_null());
+    }
+}
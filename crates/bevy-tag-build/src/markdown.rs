@@ -0,0 +1,111 @@
+//! Markdown documentation of the tag set (`TAGS.md`).
+//!
+//! Meant to be committed next to `tags.toml` so designers and other
+//! non-programmers can browse the taxonomy on a code host.
+
+use crate::lock::LockFile;
+use crate::manifest::Manifest;
+use crate::toml_parser::TagsConfig;
+use std::fmt::Write;
+
+/// Generate a Markdown page listing every tag and redirect.
+///
+/// The tag table covers the same tags as the [`Manifest`], with display name
+/// and description from `[tags.meta]` and the deprecation note, if any.
+pub fn generate_markdown(config: &TagsConfig, lock: Option<&LockFile>) -> String {
+    let manifest = Manifest::new(config, lock);
+
+    let mut out = String::new();
+    writeln!(out, "# {}", manifest.module_name).unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "<!-- Generated by bevy-tag-build. Do not edit; change tags.toml instead. -->"
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "GID scheme: `{}`", manifest.gid_scheme).unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "## Tags").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "| Path | Depth | GID | Name | Description | Status |").unwrap();
+    writeln!(out, "|------|-------|-----|------|-------------|--------|").unwrap();
+    for entry in manifest.tags.iter().filter(|e| e.redirect.is_none()) {
+        // Deprecated entries without a note are removed paths kept by the lock
+        let status = match (entry.deprecated, config.deprecation_note(&entry.path)) {
+            (true, Some(note)) => format!("Deprecated: {}", note),
+            (true, None) => "Deprecated: removed".to_string(),
+            (false, _) => String::new(),
+        };
+        writeln!(
+            out,
+            "| `{}` | {} | `{}` | {} | {} | {} |",
+            entry.path,
+            entry.depth,
+            entry.gid.as_deref().unwrap_or("-"),
+            cell(config.display_name_of(&entry.path).unwrap_or("")),
+            cell(config.description_of(&entry.path).unwrap_or("")),
+            cell(&status),
+        )
+        .unwrap();
+    }
+
+    let redirects: Vec<_> = manifest
+        .tags
+        .iter()
+        .filter_map(|e| Some((&e.path, e.redirect.as_ref()?)))
+        .collect();
+    if !redirects.is_empty() {
+        writeln!(out).unwrap();
+        writeln!(out, "## Redirects").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "| Old path | Redirects to |").unwrap();
+        writeln!(out, "|----------|--------------|").unwrap();
+        for (from, to) in redirects {
+            writeln!(out, "| `{}` | `{}` |", from, to).unwrap();
+        }
+    }
+    out
+}
+
+/// Escape text for a single Markdown table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gid::{format_gid, path_gid};
+
+    #[test]
+    fn lists_tags_and_redirects() {
+        let toml = r#"
+[tags]
+paths = ["Ability.Fireball", "Ability.Frost"]
+
+[tags.meta."Ability.Fireball"]
+display_name = "Fireball"
+description = "Hurls fire | burns"
+
+[redirects]
+"Ability.Fire" = "Ability.Fireball"
+
+[deprecated]
+"Ability.Frost" = "Use Ability.Ice"
+"#;
+        let md = generate_markdown(&TagsConfig::from_str(toml).unwrap(), None);
+
+        assert!(md.starts_with("# Tags\n"));
+        assert!(md.contains(&format!(
+            "| `Ability.Fireball` | 1 | `{}` | Fireball | Hurls fire \\| burns |  |",
+            format_gid(path_gid("Ability.Fireball").unwrap())
+        )));
+        assert!(md.contains("| Deprecated: Use Ability.Ice |"));
+        assert!(md.contains("## Redirects"));
+        assert!(md.contains("| `Ability.Fire` | `Ability.Fireball` |"));
+        // Redirects are only listed in their own table
+        assert!(!md.contains("| `Ability.Fire` | 1 |"));
+    }
+}
@@ -0,0 +1,134 @@
+//! Taxonomy policy checks from `[policy]` in `tags.toml`.
+//!
+//! ```toml
+//! [policy]
+//! level = "warn"               # or "error" (default)
+//! forbidden = ["Temp", "Test"] # segment names, case-insensitive
+//! pascal_case = true
+//! max_children = 32
+//! max_depth = 4
+//! ```
+//!
+//! Violations fail parsing at `level = "error"`; at `level = "warn"` they are
+//! kept in [`TagsConfig::policy_warnings`](crate::TagsConfig::policy_warnings)
+//! and reported as `cargo:warning`s by the build.
+
+use crate::toml_parser::TagEntry;
+use std::collections::BTreeMap;
+
+/// How policy violations are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PolicyLevel {
+    /// Fail parsing (default)
+    #[default]
+    Error,
+    /// Keep the violations as warnings
+    Warn,
+}
+
+/// Naming and shape rules every tag must follow.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Policy {
+    /// How violations are reported
+    pub level: PolicyLevel,
+    /// Segment names no tag may use (compared case-insensitively)
+    pub forbidden: Vec<String>,
+    /// Require every segment to be PascalCase (`FireBall`, not `fire_ball`)
+    pub pascal_case: bool,
+    /// Maximum number of direct children of any node (roots included)
+    pub max_children: Option<usize>,
+    /// Maximum number of segments in a path
+    pub max_depth: Option<usize>,
+}
+
+impl Policy {
+    /// Check `entries` and describe every violation, in path order.
+    pub(crate) fn violations(&self, entries: &[TagEntry]) -> Vec<String> {
+        let mut sorted: Vec<_> = entries.iter().collect();
+        sorted.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut violations = Vec::new();
+        let mut children: BTreeMap<&str, usize> = BTreeMap::new();
+        for entry in sorted {
+            let segment = entry.path.rsplit('.').next().unwrap_or(&entry.path);
+            if self.forbidden.iter().any(|f| f.eq_ignore_ascii_case(segment)) {
+                violations.push(format!("'{}' uses the forbidden name '{}'", entry.path, segment));
+            }
+            if self.pascal_case && !is_pascal_case(segment) {
+                violations.push(format!("'{}': segment '{}' is not PascalCase", entry.path, segment));
+            }
+            if let Some(max) = self.max_depth
+                && entry.depth as usize >= max
+            {
+                violations.push(format!(
+                    "'{}' is {} levels deep (max_depth = {})",
+                    entry.path,
+                    entry.depth + 1,
+                    max
+                ));
+            }
+            *children.entry(entry.parent.as_deref().unwrap_or("")).or_default() += 1;
+        }
+
+        if let Some(max) = self.max_children {
+            for (parent, count) in children.into_iter().filter(|&(_, count)| count > max) {
+                let node = if parent.is_empty() {
+                    "The root".to_string()
+                } else {
+                    format!("'{}'", parent)
+                };
+                violations.push(format!(
+                    "{} has {} children (max_children = {})",
+                    node, count, max
+                ));
+            }
+        }
+        violations
+    }
+}
+
+/// Starts with an uppercase letter and contains only letters and digits.
+fn is_pascal_case(segment: &str) -> bool {
+    let mut chars = segment.chars();
+    matches!(chars.next(), Some(c) if c.is_uppercase()) && chars.all(char::is_alphanumeric)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str) -> TagEntry {
+        TagEntry {
+            path: path.to_string(),
+            depth: path.matches('.').count() as u8,
+            parent: path.rsplit_once('.').map(|(parent, _)| parent.to_string()),
+        }
+    }
+
+    #[test]
+    fn reports_each_rule() {
+        let entries: Vec<_> = ["Item", "Item.temp", "Item.Axe", "Item.Bow", "Item.Bow.Long_Bow"]
+            .into_iter()
+            .map(entry)
+            .collect();
+        let policy = Policy {
+            forbidden: vec!["Temp".into()],
+            pascal_case: true,
+            max_children: Some(2),
+            max_depth: Some(2),
+            ..Policy::default()
+        };
+
+        assert_eq!(
+            policy.violations(&entries),
+            [
+                "'Item.Bow.Long_Bow': segment 'Long_Bow' is not PascalCase",
+                "'Item.Bow.Long_Bow' is 3 levels deep (max_depth = 2)",
+                "'Item.temp' uses the forbidden name 'temp'",
+                "'Item.temp': segment 'temp' is not PascalCase",
+                "'Item' has 3 children (max_children = 2)",
+            ]
+        );
+        assert!(Policy::default().violations(&entries).is_empty());
+    }
+}
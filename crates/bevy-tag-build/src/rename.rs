@@ -0,0 +1,235 @@
+//! Rename detection between removed and added paths.
+//!
+//! When a build removes some paths and adds others, likely renames are
+//! paired up so the user can be pointed at a `[redirects]` entry (or, with
+//! `on_remove = "auto-redirect"`, have one applied for them).
+
+use crate::lock::{LockDiff, LockFile};
+use crate::toml_parser::TagsConfig;
+use std::collections::HashSet;
+
+/// Minimum similarity for a removed/added pair to count as a rename.
+const SIMILARITY_THRESHOLD: f64 = 0.6;
+
+/// A removed path that was probably renamed to an added one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenameCandidate {
+    /// Path removed from the config
+    pub from: String,
+    /// Path added to the config
+    pub to: String,
+    /// Similarity score in `0.0..=1.0`
+    pub similarity: f64,
+}
+
+impl RenameCandidate {
+    /// The `[redirects]` line that would preserve the old path.
+    pub fn redirect_entry(&self) -> String {
+        format!("\"{}\" = \"{}\"", self.from, self.to)
+    }
+}
+
+/// Pair removed leaves with added leaves that look like renames.
+///
+/// Only leaves are considered: removed lock entries that are not already
+/// deprecated or redirected, and new config entries without children. Pairs
+/// are taken greedily by similarity; if exactly one removed and one added
+/// leaf remain unpaired, they are paired as well, since a lone removal
+/// alongside a lone addition is almost always a move.
+pub fn detect_renames(
+    lock: &LockFile,
+    config: &TagsConfig,
+    diff: &LockDiff,
+) -> Vec<RenameCandidate> {
+    let lock_parents: HashSet<&str> = lock
+        .entries
+        .iter()
+        .filter_map(|e| e.parent.as_deref())
+        .collect();
+    let config_parents: HashSet<&str> = config
+        .entries()
+        .filter_map(|e| e.parent.as_deref())
+        .collect();
+    let redirected: HashSet<&str> = config.redirects().map(|r| r.from.as_str()).collect();
+
+    let mut removed: Vec<&str> = diff
+        .removed
+        .iter()
+        .map(String::as_str)
+        .filter(|p| !lock_parents.contains(p) && !redirected.contains(p))
+        .filter(|p| lock.get(p).is_some_and(|e| !e.deprecated))
+        .collect();
+    let mut added: Vec<&str> = diff
+        .added
+        .iter()
+        .map(String::as_str)
+        .filter(|p| !config_parents.contains(p))
+        .collect();
+    removed.sort_unstable();
+    added.sort_unstable();
+
+    pair_renames(&removed, &added)
+}
+
+/// Greedy pairing of removed and added paths by [`similarity`].
+fn pair_renames(removed: &[&str], added: &[&str]) -> Vec<RenameCandidate> {
+    let mut scored: Vec<(f64, &str, &str)> = removed
+        .iter()
+        .flat_map(|from| {
+            added
+                .iter()
+                .map(move |to| (similarity(from, to), *from, *to))
+        })
+        .collect();
+    // Highest score first; ties broken by path for deterministic output
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(b.1)).then(a.2.cmp(b.2)));
+
+    let mut used_from = HashSet::new();
+    let mut used_to = HashSet::new();
+    let mut renames = Vec::new();
+    for (score, from, to) in scored {
+        if score < SIMILARITY_THRESHOLD {
+            break;
+        }
+        if used_from.contains(from) || used_to.contains(to) {
+            continue;
+        }
+        used_from.insert(from);
+        used_to.insert(to);
+        renames.push(RenameCandidate {
+            from: from.to_string(),
+            to: to.to_string(),
+            similarity: score,
+        });
+    }
+
+    let rest_from: Vec<_> = removed.iter().filter(|p| !used_from.contains(*p)).collect();
+    let rest_to: Vec<_> = added.iter().filter(|p| !used_to.contains(*p)).collect();
+    if let ([from], [to]) = (rest_from.as_slice(), rest_to.as_slice()) {
+        renames.push(RenameCandidate {
+            from: from.to_string(),
+            to: to.to_string(),
+            similarity: similarity(from, to),
+        });
+    }
+
+    renames.sort_by(|a, b| a.from.cmp(&b.from));
+    renames
+}
+
+/// Similarity of two paths in `0.0..=1.0`.
+///
+/// Averages how alike the leaf names are (edit distance) with how many
+/// segments the paths share.
+pub(crate) fn similarity(a: &str, b: &str) -> f64 {
+    let a_segments: Vec<&str> = a.split('.').collect();
+    let b_segments: Vec<&str> = b.split('.').collect();
+
+    let leaf = name_similarity(
+        a_segments[a_segments.len() - 1],
+        b_segments[b_segments.len() - 1],
+    );
+
+    let shared = a_segments.iter().filter(|s| b_segments.contains(s)).count();
+    let overlap = shared as f64 / a_segments.len().max(b_segments.len()) as f64;
+
+    (leaf + overlap) / 2.0
+}
+
+/// `1 - levenshtein / max_len`, case-insensitive.
+fn name_similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            row[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        prev = row;
+    }
+
+    1.0 - prev[b.len()] as f64 / max_len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_config(paths: &[&str]) -> TagsConfig {
+        let toml = format!(
+            "[tags]\npaths = [{}]",
+            paths
+                .iter()
+                .map(|p| format!("\"{}\"", p))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        TagsConfig::from_str(&toml).unwrap()
+    }
+
+    fn renames(old: &[&str], new: &[&str]) -> Vec<(String, String)> {
+        let lock = LockFile::from_config(&make_config(old));
+        let config = make_config(new);
+        let diff = lock.diff(&config);
+        detect_renames(&lock, &config, &diff)
+            .into_iter()
+            .map(|r| (r.from, r.to))
+            .collect()
+    }
+
+    #[test]
+    fn lone_move_is_paired() {
+        assert_eq!(
+            renames(
+                &["Item.Sword", "Item.Axe"],
+                &["Item.Axe", "Equipment.Weapon.Blade"]
+            ),
+            [(
+                "Item.Sword".to_string(),
+                "Equipment.Weapon.Blade".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn bulk_move_pairs_by_similarity() {
+        let found = renames(
+            &["Item.Sword", "Item.Shield", "Item.Potion"],
+            &[
+                "Equipment.Weapon.Sword",
+                "Equipment.Armor.Shield",
+                "Consumable.Elixir",
+            ],
+        );
+        assert!(found.contains(&("Item.Sword".into(), "Equipment.Weapon.Sword".into())));
+        assert!(found.contains(&("Item.Shield".into(), "Equipment.Armor.Shield".into())));
+        // Potion/Elixir are unrelated by name, but the only pair left over
+        assert!(found.contains(&("Item.Potion".into(), "Consumable.Elixir".into())));
+    }
+
+    #[test]
+    fn unrelated_changes_are_not_paired() {
+        let found = renames(&["A.Sword", "A.Bow"], &["A.Potion", "A.Scroll"]);
+        assert!(found.is_empty(), "{:?}", found);
+    }
+
+    #[test]
+    fn only_removals_yield_nothing() {
+        assert!(renames(&["A.B", "A.C"], &["A.B"]).is_empty());
+    }
+
+    #[test]
+    fn similarity_scores() {
+        assert_eq!(similarity("A.B", "A.B"), 1.0);
+        assert!(similarity("Item.Sword", "Equipment.Weapon.Sword") > SIMILARITY_THRESHOLD);
+        assert!(similarity("Item.Sword", "Item.Bow") < SIMILARITY_THRESHOLD);
+        assert_eq!(name_similarity("Sword", "sword"), 1.0);
+    }
+}
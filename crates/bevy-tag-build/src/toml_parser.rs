@@ -1,8 +1,134 @@
 //! TOML configuration parser for tags.toml.
-
+//!
+//! Tags can be listed flat, nested as tables, or mixed:
+//!
+//! ```toml
+//! [tags]
+//! paths = ["Skill.Combat"]
+//!
+//! [tags.Item.Weapon]
+//! children = ["Sword", "Axe"]
+//!
+//! [tags.Ability]
+//! Fire = { children = ["Fireball"] }
+//! Ice = {}
+//! ```
+//!
+//! Inside a nested table, `children` lists leaf names (or dotted relative
+//! paths) and every other key is a child node whose value is a table.
+//!
+//! Large projects can split the tree across files. `includes` lists files
+//! relative to the including file; `discover` lists directories whose
+//! immediate subdirectories are scanned for a `tags.toml` (e.g. one per
+//! workspace crate):
+//!
+//! ```toml
+//! includes = ["tags/combat.toml", "tags/items.toml"]
+//! discover = ["crates"]
+//! ```
+//!
+//! Static metadata goes in `[tags.meta."Path"]` and is emitted as
+//! `#[key = value]` attributes on the node, exactly like hand-written
+//! `namespace!` metadata. Values may be integers, floats, booleans or
+//! strings:
+//!
+//! ```toml
+//! [tags.meta."Ability.Fireball"]
+//! mana_cost = 10
+//! cooldown = 1.5
+//! display_name = "Fireball"
+//! description = "Hurls a ball of fire"
+//! ```
+//!
+//! `display_name` and `description` are strings passed to the macro's own
+//! attributes of the same name (the registry's UI fields) rather than
+//! becoming metadata consts.
+//!
+//! `[tags.data]` associates a Rust data type with a path, emitted as
+//! `Node<Type>` so the tag implements `HasData`. Types are written as they
+//! would be in the macro, usually as absolute paths:
+//!
+//! ```toml
+//! [tags.data]
+//! "Ability.Fireball" = "crate::AbilityData"
+//! ```
+//!
+//! Tags that only exist in some builds are listed under `[tags.dev-only]`
+//! (compiled with `debug_assertions`) or `[tags.features.<name>]` (compiled
+//! with the cargo feature `<name>`). The lock file still tracks them, so
+//! their GIDs never move between builds:
+//!
+//! ```toml
+//! [tags.dev-only]
+//! paths = ["Debug.GodMode", "Debug.NoClip"]
+//!
+//! [tags.features.cheats]
+//! paths = ["Cheat.InfiniteAmmo"]
+//! ```
+//!
+//! Their descendants are gated with them; a gated path cannot also be
+//! declared ungated or be a redirect target.
+//!
+//! `paths`, `meta`, `data`, `dev-only` and `features` are therefore reserved
+//! at the top of `[tags]` and cannot be used as root node names.
+//!
+//! `[deprecated]` marks tags as deprecated with a custom note, emitted as
+//! `#[deprecated(note = "...")]`. The path must still be declared in
+//! `[tags]`, or be a removed path kept by `on_remove = "warn"`, whose
+//! generic note it replaces:
+//!
+//! ```toml
+//! [deprecated]
+//! "Item.Sword" = "Use Equipment.Weapon.Blade instead"
+//! ```
+//!
+//! `allow_removed` lists paths (with their descendants) whose removal was
+//! reviewed: they are dropped from the lock instead of failing the build,
+//! while every other removed path stays protected:
+//!
+//! ```toml
+//! allow_removed = ["Item.Prototype"]
+//! ```
+//!
+//! Optional outputs are enabled in `[codegen]`:
+//!
+//! ```toml
+//! [codegen]
+//! manifest = true   # or a file name; written next to the generated Rust file
+//! c_header = "tags.h"
+//! typescript = true  # tags.ts
+//! markdown = true    # TAGS.md, written next to this file instead
+//! registry = true    # or a function name; emits `pub fn registry()`
+//! redirects = true   # or a function name; emits `pub fn redirects()`
+//! history = true     # tags.history.toml, next to this file
+//! constants = true   # GENERATED_AT, CONFIG_HASH and DEPRECATED_PATHS consts
+//! ```
+//!
+//! The same table shapes the generated module itself, so it can pass
+//! stricter workspace lints without post-editing. `allow` is emitted as an
+//! outer `#[allow(...)]` on the module (the file stays usable with
+//! `include!`), and `derive` is forwarded to the macro for every `Tag`:
+//!
+//! ```toml
+//! [codegen]
+//! visibility = "pub(crate)"  # default "pub"; also used for `registry`
+//! doc = "Gameplay tags."
+//! allow = ["missing_docs", "clippy::module_name_repetitions"]
+//! derive = ["PartialOrd", "Ord"]
+//! ```
+//!
+//! Naming and shape rules go in `[policy]`; see [`Policy`].
+//!
+//! Included files use the same format but may not set `module_name`,
+//! `on_remove`, `[codegen]` or `[policy]`. A tag or redirect declared in
+//! two different files is an error naming both files.
+
+use crate::diagnostic;
+use crate::gid::{GID_SCHEME, MAX_DEPTH};
+use crate::policy::{Policy, PolicyLevel};
 use serde::Deserialize;
-use std::collections::HashSet;
-use std::path::Path;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 /// Behavior when a path is removed from config but exists in lock.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -12,6 +138,9 @@ pub enum OnRemove {
     Error,
     /// Emit a warning via #[deprecated], but allow compilation
     Warn,
+    /// Like `Warn`, but paths that look renamed become redirects to their
+    /// new path
+    AutoRedirect,
 }
 
 /// Parsed tags configuration.
@@ -21,12 +150,81 @@ pub struct TagsConfig {
     pub module_name: String,
     /// Behavior when paths are removed
     pub on_remove: OnRemove,
+    /// Extra outputs, from `[codegen]`
+    pub codegen: CodegenOptions,
+    /// Naming and shape rules, from `[policy]`
+    pub policy: Policy,
+    /// Policy violations reported as warnings (`level = "warn"`)
+    policy_warnings: Vec<String>,
     /// All tag entries (including auto-generated parents)
     entries: Vec<TagEntry>,
     /// Redirect declarations (old_path -> new_path)
     redirects: Vec<RedirectEntry>,
+    /// Static metadata per path, from `[tags.meta]`
+    meta: BTreeMap<String, Vec<MetaEntry>>,
+    /// Data type per path, from `[tags.data]`
+    data: BTreeMap<String, String>,
+    /// Deprecation note per path, from `[deprecated]`
+    deprecated: BTreeMap<String, String>,
+    /// Paths whose removal is allowed, from `allow_removed`
+    allow_removed: BTreeSet<String>,
+    /// `cfg` predicate per gated path, from `[tags.dev-only]` and
+    /// `[tags.features]`
+    cfg: BTreeMap<String, String>,
+    /// Files the config was read from (root first), for `rerun-if-changed`
+    sources: Vec<PathBuf>,
+}
+
+/// Extra outputs written alongside the generated Rust file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodegenOptions {
+    /// File name of the JSON GID manifest, if enabled
+    pub manifest: Option<String>,
+    /// File name of the C/C++ header, if enabled
+    pub c_header: Option<String>,
+    /// File name of the TypeScript module, if enabled
+    pub typescript: Option<String>,
+    /// File name of the Markdown docs (relative to the config), if enabled
+    pub markdown: Option<String>,
+    /// Name of the generated `fn() -> &'static NamespaceRegistry`, if enabled
+    pub registry: Option<String>,
+    /// Name of the generated `fn() -> &'static RedirectMap`, if enabled
+    pub redirects: Option<String>,
+    /// File name of the change history (relative to the config), if enabled
+    pub history: Option<String>,
+    /// Emit `GENERATED_AT`, `CONFIG_HASH` and `DEPRECATED_PATHS` constants
+    pub constants: bool,
+    /// Visibility of the generated module and registry function (`None` is `pub`)
+    pub visibility: Option<String>,
+    /// Outer doc comment on the generated module
+    pub doc: Option<String>,
+    /// Lints allowed on the generated module
+    pub allow: Vec<String>,
+    /// Extra derives for every `Tag`
+    pub derive: Vec<String>,
 }
 
+/// Default file name for `manifest = true`.
+const DEFAULT_MANIFEST_FILE: &str = "tags_manifest.json";
+
+/// Default file name for `c_header = true`.
+const DEFAULT_C_HEADER_FILE: &str = "tags.h";
+
+/// Default file name for `typescript = true`.
+const DEFAULT_TYPESCRIPT_FILE: &str = "tags.ts";
+
+/// Default file name for `markdown = true`.
+const DEFAULT_MARKDOWN_FILE: &str = "TAGS.md";
+
+/// Default function name for `registry = true`.
+const DEFAULT_REGISTRY_FN: &str = "registry";
+
+/// Default function name for `redirects = true`.
+const DEFAULT_REDIRECTS_FN: &str = "redirects";
+
+/// Default file name for `history = true`.
+const DEFAULT_HISTORY_FILE: &str = "tags.history.toml";
+
 /// A single tag entry with computed properties.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TagEntry {
@@ -47,6 +245,41 @@ pub struct RedirectEntry {
     pub to: String,
 }
 
+/// A static metadata attribute attached to a tag (`mana_cost = 10`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetaEntry {
+    /// Attribute name; becomes `#[key = ...]` and the `KEY` const
+    pub key: String,
+    /// Attribute value
+    pub value: MetaValue,
+}
+
+/// A metadata value from `[tags.meta]`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaValue {
+    /// Integer literal
+    Integer(i64),
+    /// Float literal
+    Float(f64),
+    /// Boolean literal
+    Bool(bool),
+    /// String literal
+    String(String),
+}
+
+impl MetaValue {
+    /// Render as a Rust literal for the generated attribute.
+    pub fn to_rust_literal(&self) -> String {
+        match self {
+            Self::Integer(v) => v.to_string(),
+            // Debug keeps a decimal point so the macro infers a float type
+            Self::Float(v) => format!("{:?}", v),
+            Self::Bool(v) => v.to_string(),
+            Self::String(v) => format!("{:?}", v),
+        }
+    }
+}
+
 /// Raw TOML structure.
 #[derive(Debug, Deserialize)]
 struct RawTagsConfig {
@@ -54,79 +287,726 @@ struct RawTagsConfig {
     module_name: Option<String>,
     /// Behavior when paths are removed: "error" (default) or "warn"
     on_remove: Option<String>,
+    /// Extra outputs (root config only)
+    codegen: Option<RawCodegen>,
+    /// Naming and shape rules (root config only)
+    policy: Option<RawPolicy>,
+    /// Other config files to merge, relative to this file
+    #[serde(default)]
+    includes: Vec<String>,
+    /// Directories whose subdirectories are scanned for `tags.toml`
+    #[serde(default)]
+    discover: Vec<String>,
     /// Tag definitions
+    #[serde(default)]
     tags: RawTags,
     /// Redirect declarations: { "OldPath" = "NewPath" }
     #[serde(default)]
-    redirects: std::collections::HashMap<String, String>,
+    redirects: HashMap<String, String>,
+    /// Deprecation notes: { "Path" = "note" }
+    #[serde(default)]
+    deprecated: HashMap<String, String>,
+    /// Removed paths that may be dropped from the lock
+    #[serde(default)]
+    allow_removed: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCodegen {
+    /// `true` for the default file name, or a file name
+    manifest: Option<RawOutput>,
+    /// `true` for the default file name, or a file name
+    c_header: Option<RawOutput>,
+    /// `true` for the default file name, or a file name
+    typescript: Option<RawOutput>,
+    /// `true` for the default file name, or a file name
+    markdown: Option<RawOutput>,
+    /// `true` for the default function name, or a function name
+    registry: Option<RawOutput>,
+    /// `true` for the default function name, or a function name
+    redirects: Option<RawOutput>,
+    /// `true` for the default file name, or a file name
+    history: Option<RawOutput>,
+    #[serde(default)]
+    constants: bool,
+    visibility: Option<String>,
+    doc: Option<String>,
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    derive: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawPolicy {
+    /// "error" (default) or "warn"
+    level: Option<String>,
+    #[serde(default)]
+    forbidden: Vec<String>,
+    #[serde(default)]
+    pascal_case: bool,
+    max_children: Option<usize>,
+    max_depth: Option<usize>,
 }
 
+/// An optional output: enabled with the default name, or a file name.
 #[derive(Debug, Deserialize)]
-struct RawTags {
-    /// List of dot-separated paths
-    paths: Vec<String>,
+#[serde(untagged)]
+enum RawOutput {
+    Enabled(bool),
+    File(String),
 }
 
+impl RawOutput {
+    fn file_name(self, default: &str) -> Option<String> {
+        match self {
+            Self::Enabled(true) => Some(default.to_string()),
+            Self::Enabled(false) => None,
+            Self::File(name) => Some(name),
+        }
+    }
+}
+
+/// A parsed config file and where it came from.
+struct RawSource {
+    /// File path (`None` for configs parsed from a string)
+    path: Option<PathBuf>,
+    /// File content, for locating errors
+    content: String,
+    raw: RawTagsConfig,
+}
+
+impl RawSource {
+    fn name(&self) -> String {
+        match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "<string>".to_string(),
+        }
+    }
+}
+
+/// Tag definitions: an optional flat `paths` list plus nested node tables.
+type RawTags = toml::Table;
+
+/// Key holding the flat path list at the top of `[tags]`.
+const PATHS_KEY: &str = "paths";
+/// Key holding the metadata tables at the top of `[tags]`.
+const META_KEY: &str = "meta";
+/// Key holding the path-to-type table at the top of `[tags]`.
+const DATA_KEY: &str = "data";
+/// Key holding the `debug_assertions`-only paths at the top of `[tags]`.
+const DEV_ONLY_KEY: &str = "dev-only";
+/// Key holding the per-feature path tables at the top of `[tags]`.
+const FEATURES_KEY: &str = "features";
+/// Attribute names the `namespace!` macro interprets itself; they would not
+/// become metadata consts.
+const RESERVED_META_KEYS: &[&str] = &[
+    "bundle",
+    "cfg",
+    "deprecated",
+    "doc",
+    "flags",
+    "gid",
+    "loc_key",
+    "no_registry",
+    "redirect",
+    "segment",
+];
+/// Macro attributes that are accepted in `[tags.meta]` but must be strings.
+pub(crate) const STRING_META_KEYS: &[&str] = &["description", "display_name"];
+/// Key holding leaf names inside a nested node table.
+const CHILDREN_KEY: &str = "children";
+/// File name looked up in `discover` directories.
+const CONFIG_FILE_NAME: &str = "tags.toml";
+
 impl TagsConfig {
-    /// Parse from a TOML file.
+    /// Parse from a TOML file, following `includes` and `discover`.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self, TagsConfigError> {
-        let content = std::fs::read_to_string(path.as_ref()).map_err(|e| {
-            TagsConfigError::Io(format!("Failed to read {}: {}", path.as_ref().display(), e))
-        })?;
-        Self::from_str(&content)
+        let mut sources = Vec::new();
+        Self::load_file(path.as_ref(), &mut Vec::new(), &mut sources)?;
+        Self::from_sources(sources)
     }
 
     /// Parse from a TOML string.
+    ///
+    /// `includes` and `discover` need a base directory, so they are only
+    /// supported through [`TagsConfig::from_file`].
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(content: &str) -> Result<Self, TagsConfigError> {
-        let raw: RawTagsConfig =
-            toml::from_str(content).map_err(|e| TagsConfigError::Parse(e.to_string()))?;
+        let raw = Self::parse_raw(content)?;
+        if !raw.includes.is_empty() || !raw.discover.is_empty() {
+            return Err(TagsConfigError::Validation(
+                "'includes' and 'discover' require loading the config with from_file".into(),
+            ));
+        }
+        Self::from_sources(vec![RawSource {
+            path: None,
+            content: content.to_string(),
+            raw,
+        }])
+    }
+
+    /// Parse an Unreal Engine `DefaultGameplayTags.ini`.
+    ///
+    /// Tags become entries, `DevComment`s become `display_name` metadata and
+    /// tag redirects become `[redirects]`; see [`crate::ue_ini_to_toml`] to
+    /// write the result out as a `tags.toml`.
+    pub fn from_ue_ini(path: impl AsRef<Path>) -> Result<Self, TagsConfigError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            TagsConfigError::Io(format!("Failed to read '{}': {}", path.display(), e))
+        })?;
+        let mut config = Self::from_str(&crate::ue_ini::ue_ini_to_toml(&content)?)?;
+        config.sources = vec![path.to_path_buf()];
+        Ok(config)
+    }
+
+    /// Files the config was read from, root first.
+    ///
+    /// Empty for configs parsed with [`TagsConfig::from_str`].
+    pub fn sources(&self) -> impl Iterator<Item = &Path> {
+        self.sources.iter().map(PathBuf::as_path)
+    }
 
-        let module_name = raw.module_name.unwrap_or_else(|| "Tags".to_string());
+    fn parse_raw(content: &str) -> Result<RawTagsConfig, TagsConfigError> {
+        toml::from_str(content).map_err(|e| TagsConfigError::Parse(e.to_string()))
+    }
+
+    /// Read `path` and, depth-first, every file it includes or discovers.
+    ///
+    /// `stack` holds the chain of files currently being loaded, for cycle
+    /// detection.
+    fn load_file(
+        path: &Path,
+        stack: &mut Vec<PathBuf>,
+        sources: &mut Vec<RawSource>,
+    ) -> Result<(), TagsConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            TagsConfigError::Io(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let canonical = path.canonicalize().map_err(|e| {
+            TagsConfigError::Io(format!("Failed to resolve {}: {}", path.display(), e))
+        })?;
+
+        if stack.contains(&canonical) {
+            let chain: Vec<_> = stack
+                .iter()
+                .chain(std::iter::once(&canonical))
+                .map(|p| p.display().to_string())
+                .collect();
+            return Err(TagsConfigError::Validation(format!(
+                "Include cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+        if sources
+            .iter()
+            .any(|s| s.path.as_deref() == Some(canonical.as_path()))
+        {
+            return Err(TagsConfigError::Validation(format!(
+                "'{}' is included more than once",
+                path.display()
+            )));
+        }
+
+        let raw = Self::parse_raw(&content).map_err(|e| match e {
+            TagsConfigError::Parse(msg) => {
+                TagsConfigError::Parse(format!("{}: {}", path.display(), msg))
+            }
+            other => other,
+        })?;
+
+        if !sources.is_empty()
+            && (raw.module_name.is_some()
+                || raw.on_remove.is_some()
+                || raw.codegen.is_some()
+                || raw.policy.is_some())
+        {
+            return Err(TagsConfigError::Validation(format!(
+                "'{}': module_name, on_remove, [codegen] and [policy] may only be set in the root config",
+                path.display()
+            )));
+        }
+
+        let base = canonical.parent().unwrap_or(Path::new("")).to_path_buf();
+        let mut children: Vec<PathBuf> = raw.includes.iter().map(|p| base.join(p)).collect();
+        for dir in &raw.discover {
+            children.extend(Self::discover_in(&base.join(dir))?);
+        }
+
+        sources.push(RawSource {
+            path: Some(canonical.clone()),
+            content,
+            raw,
+        });
+
+        stack.push(canonical);
+        for child in children {
+            Self::load_file(&child, stack, sources)?;
+        }
+        stack.pop();
+
+        Ok(())
+    }
+
+    /// `tags.toml` files in the immediate subdirectories of `dir`, sorted.
+    fn discover_in(dir: &Path) -> Result<Vec<PathBuf>, TagsConfigError> {
+        let read_dir = std::fs::read_dir(dir).map_err(|e| {
+            TagsConfigError::Io(format!("Failed to read directory {}: {}", dir.display(), e))
+        })?;
+
+        let mut found = Vec::new();
+        for entry in read_dir {
+            let entry = entry.map_err(|e| {
+                TagsConfigError::Io(format!("Failed to read directory {}: {}", dir.display(), e))
+            })?;
+            let candidate = entry.path().join(CONFIG_FILE_NAME);
+            if candidate.is_file() {
+                found.push(candidate);
+            }
+        }
+        found.sort();
+        Ok(found)
+    }
+
+    /// Merge parsed files (root first) into a validated config, pointing
+    /// validation errors at the file, line and column they come from.
+    fn from_sources(mut sources: Vec<RawSource>) -> Result<Self, TagsConfigError> {
+        let contents: Vec<_> = sources
+            .iter_mut()
+            .map(|s| (s.path.clone(), std::mem::take(&mut s.content)))
+            .collect();
+        Self::validate_sources(sources).map_err(|e| diagnostic::locate_error(e, &contents))
+    }
+
+    /// Merge parsed files (root first) into a validated config.
+    fn validate_sources(mut sources: Vec<RawSource>) -> Result<Self, TagsConfigError> {
+        let codegen = match sources[0].raw.codegen.take() {
+            Some(raw) => CodegenOptions {
+                manifest: raw
+                    .manifest
+                    .and_then(|m| m.file_name(DEFAULT_MANIFEST_FILE)),
+                c_header: raw
+                    .c_header
+                    .and_then(|h| h.file_name(DEFAULT_C_HEADER_FILE)),
+                typescript: raw
+                    .typescript
+                    .and_then(|t| t.file_name(DEFAULT_TYPESCRIPT_FILE)),
+                markdown: raw
+                    .markdown
+                    .and_then(|m| m.file_name(DEFAULT_MARKDOWN_FILE)),
+                registry: raw
+                    .registry
+                    .and_then(|r| r.file_name(DEFAULT_REGISTRY_FN)),
+                redirects: raw
+                    .redirects
+                    .and_then(|r| r.file_name(DEFAULT_REDIRECTS_FN)),
+                history: raw
+                    .history
+                    .and_then(|h| h.file_name(DEFAULT_HISTORY_FILE)),
+                constants: raw.constants,
+                visibility: raw.visibility,
+                doc: raw.doc,
+                allow: raw.allow,
+                derive: raw.derive,
+            },
+            None => CodegenOptions::default(),
+        };
+        let policy = match sources[0].raw.policy.take() {
+            Some(raw) => Policy {
+                level: match raw.level.as_deref() {
+                    None | Some("error") => PolicyLevel::Error,
+                    Some("warn") => PolicyLevel::Warn,
+                    Some(other) => {
+                        return Err(TagsConfigError::Validation(format!(
+                            "Invalid [policy] level '{}': expected 'error' or 'warn'",
+                            other
+                        )));
+                    }
+                },
+                forbidden: raw.forbidden,
+                pascal_case: raw.pascal_case,
+                max_children: raw.max_children,
+                max_depth: raw.max_depth,
+            },
+            None => Policy::default(),
+        };
+        if let Some(name) = codegen.registry.as_deref().filter(|n| !Self::is_identifier(n)) {
+            return Err(TagsConfigError::Validation(format!(
+                "Invalid [codegen] registry '{}': expected a function name",
+                name
+            )));
+        }
+        if let Some(name) = codegen.redirects.as_deref().filter(|n| !Self::is_identifier(n)) {
+            return Err(TagsConfigError::Validation(format!(
+                "Invalid [codegen] redirects '{}': expected a function name",
+                name
+            )));
+        }
+        if let Some(vis) = codegen.visibility.as_deref().filter(|v| !Self::is_visibility(v)) {
+            return Err(TagsConfigError::Validation(format!(
+                "Invalid [codegen] visibility '{}': expected e.g. 'pub', 'pub(crate)' or ''",
+                vis
+            )));
+        }
+        let bad_path = |key: &str, paths: &[String]| {
+            paths.iter().find(|p| !Self::is_rust_path(p)).map(|p| {
+                TagsConfigError::Validation(format!(
+                    "Invalid [codegen] {} entry '{}': expected a path like 'a::b'",
+                    key, p
+                ))
+            })
+        };
+        if let Some(err) = bad_path("allow", &codegen.allow)
+            .or_else(|| bad_path("derive", &codegen.derive))
+        {
+            return Err(err);
+        }
+        let root = &sources[0].raw;
+        let module_name = root.module_name.clone().unwrap_or_else(|| "Tags".to_string());
 
         // Parse on_remove strategy
-        let on_remove = match raw.on_remove.as_deref() {
+        let on_remove = match root.on_remove.as_deref() {
             None | Some("error") => OnRemove::Error,
             Some("warn") => OnRemove::Warn,
+            Some("auto-redirect") => OnRemove::AutoRedirect,
             Some(other) => {
                 return Err(TagsConfigError::Validation(format!(
-                    "Invalid on_remove value '{}': expected 'error' or 'warn'",
+                    "Invalid on_remove value '{}': expected 'error', 'warn' or 'auto-redirect'",
                     other
                 )));
             }
         };
 
-        // Validate and expand paths
-        let entries = Self::expand_paths(&raw.tags.paths)?;
+        // Flatten nested tables of every file, rejecting cross-file duplicates
+        let mut paths = Vec::new();
+        let mut declared_in: HashMap<String, usize> = HashMap::new();
+        for (index, source) in sources.iter().enumerate() {
+            for path in Self::collect_paths(&source.raw.tags)? {
+                Self::check_duplicate(&sources, &mut declared_in, "Tag", &path, index)?;
+                paths.push(path);
+            }
+        }
 
-        // Parse and validate redirects
-        let mut redirects = Vec::new();
-        for (from, to) in raw.redirects {
-            // Validate paths
-            Self::validate_path(&from)?;
-            Self::validate_path(&to)?;
+        // Gated paths are declared like any other, plus their cfg predicate
+        let mut cfg: BTreeMap<String, String> = BTreeMap::new();
+        for (index, source) in sources.iter().enumerate() {
+            for (path, predicate) in Self::collect_gated(&source.raw.tags)? {
+                if paths.contains(&path) {
+                    return Err(TagsConfigError::Validation(format!(
+                        "Tag '{}' is declared both gated ({}) and ungated",
+                        path, predicate
+                    )));
+                }
+                if let Some(other) = cfg.get(&path).filter(|other| **other != predicate) {
+                    return Err(TagsConfigError::Validation(format!(
+                        "Tag '{}' is gated by both {} and {}",
+                        path, other, predicate
+                    )));
+                }
+                Self::check_duplicate(&sources, &mut declared_in, "Tag", &path, index)?;
+                cfg.insert(path, predicate);
+            }
+        }
+        // An ungated tag below a gated one would not exist without its parent
+        for path in &paths {
+            if let Some(ancestor) = Self::gated_ancestor(&cfg, path) {
+                return Err(TagsConfigError::Validation(format!(
+                    "Tag '{}' is not gated but its parent '{}' is ({})",
+                    path, ancestor, cfg[ancestor]
+                )));
+            }
+        }
+        paths.extend(cfg.keys().cloned());
+        let entries = Self::expand_paths(&paths)?;
 
-            // Check that target exists in entries
-            if !entries.iter().any(|e| e.path == to) {
+        let violations = policy.violations(&entries);
+        let policy_warnings = match policy.level {
+            PolicyLevel::Error if !violations.is_empty() => {
                 return Err(TagsConfigError::Validation(format!(
-                    "Redirect target '{}' not found in [tags].paths",
-                    to
+                    "[policy] violated:\n  {}",
+                    violations.join("\n  ")
                 )));
             }
+            PolicyLevel::Error => Vec::new(),
+            PolicyLevel::Warn => violations,
+        };
 
-            redirects.push(RedirectEntry { from, to });
+        // Parse and validate redirects
+        let mut redirects = Vec::new();
+        let mut redirected_in: HashMap<String, usize> = HashMap::new();
+        for (index, source) in sources.iter().enumerate() {
+            for (from, to) in &source.raw.redirects {
+                // Validate paths
+                Self::validate_path(from)?;
+                Self::validate_path(to)?;
+                Self::check_duplicate(&sources, &mut redirected_in, "Redirect", from, index)?;
+
+                // Check that target exists in entries
+                if !entries.iter().any(|e| &e.path == to) {
+                    return Err(TagsConfigError::Validation(diagnostic::with_help(
+                        format!("Redirect target '{}' not found in [tags]", to),
+                        diagnostic::did_you_mean(to, entries.iter().map(|e| e.path.as_str())),
+                    )));
+                }
+                if cfg.contains_key(to) || Self::gated_ancestor(&cfg, to).is_some() {
+                    return Err(TagsConfigError::Validation(format!(
+                        "Redirect target '{}' is gated and may not exist in every build",
+                        to
+                    )));
+                }
+
+                redirects.push(RedirectEntry {
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
         }
 
         // Sort redirects for deterministic output
         redirects.sort_by(|a, b| a.from.cmp(&b.from));
 
+        // Parse and validate metadata
+        let mut meta = BTreeMap::new();
+        let mut meta_in: HashMap<String, usize> = HashMap::new();
+        for (index, source) in sources.iter().enumerate() {
+            let Some(tables) = source.raw.tags.get(META_KEY) else {
+                continue;
+            };
+            for (path, attrs) in Self::expect_table(tables, "tags.meta")? {
+                Self::validate_path(path)?;
+                Self::check_duplicate(&sources, &mut meta_in, "Metadata for", path, index)?;
+                if !entries.iter().any(|e| &e.path == path) {
+                    return Err(TagsConfigError::Validation(diagnostic::with_help(
+                        format!("Metadata path '{}' not found in [tags]", path),
+                        diagnostic::did_you_mean(path, entries.iter().map(|e| e.path.as_str())),
+                    )));
+                }
+                let context = format!("tags.meta.\"{}\"", path);
+                meta.insert(path.clone(), Self::parse_meta(attrs, &context)?);
+            }
+        }
+
+        // Parse and validate data type associations
+        let mut data = BTreeMap::new();
+        let mut data_in: HashMap<String, usize> = HashMap::new();
+        for (index, source) in sources.iter().enumerate() {
+            let Some(table) = source.raw.tags.get(DATA_KEY) else {
+                continue;
+            };
+            for (path, ty) in Self::expect_table(table, "tags.data")? {
+                Self::validate_path(path)?;
+                Self::check_duplicate(&sources, &mut data_in, "Data type for", path, index)?;
+                if !entries.iter().any(|e| &e.path == path) {
+                    return Err(TagsConfigError::Validation(diagnostic::with_help(
+                        format!("Data path '{}' not found in [tags]", path),
+                        diagnostic::did_you_mean(path, entries.iter().map(|e| e.path.as_str())),
+                    )));
+                }
+                data.insert(path.clone(), Self::parse_type(path, ty)?);
+            }
+        }
+
+        // Parse and validate deprecations. Paths missing from [tags] may
+        // still be removed paths in the lock file; that is checked during
+        // generation.
+        let mut deprecated = BTreeMap::new();
+        let mut deprecated_in: HashMap<String, usize> = HashMap::new();
+        for (index, source) in sources.iter().enumerate() {
+            for (path, note) in &source.raw.deprecated {
+                Self::validate_path(path)?;
+                Self::check_duplicate(&sources, &mut deprecated_in, "Deprecation of", path, index)?;
+                if redirects.iter().any(|r| &r.from == path) {
+                    return Err(TagsConfigError::Validation(format!(
+                        "Deprecated path '{}' is a redirect, which is already deprecated",
+                        path
+                    )));
+                }
+                deprecated.insert(path.clone(), note.clone());
+            }
+        }
+
+        let mut allow_removed = BTreeSet::new();
+        for source in &sources {
+            for path in &source.raw.allow_removed {
+                Self::validate_path(path)?;
+                allow_removed.insert(path.clone());
+            }
+        }
+
+        let sources = sources.into_iter().filter_map(|s| s.path).collect();
+
         Ok(Self {
             module_name,
             on_remove,
+            codegen,
+            policy,
+            policy_warnings,
             entries,
             redirects,
+            meta,
+            data,
+            deprecated,
+            allow_removed,
+            cfg,
+            sources,
+        })
+    }
+
+    /// Parse one `[tags.data]` value as a Rust type path.
+    ///
+    /// Only a character-level check; the macro reports real type errors.
+    fn parse_type(path: &str, value: &toml::Value) -> Result<String, TagsConfigError> {
+        let ty = value.as_str().ok_or_else(|| {
+            TagsConfigError::Validation(format!(
+                "Invalid data type for '{}': expected a string, found {}",
+                path,
+                value.type_str()
+            ))
+        })?;
+        let ty = ty.trim();
+        let valid = !ty.is_empty()
+            && ty
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_:<>, &'[];".contains(c));
+        if !valid {
+            return Err(TagsConfigError::Validation(format!(
+                "Invalid data type '{}' for '{}': expected a Rust type path",
+                ty, path
+            )));
+        }
+        Ok(ty.to_string())
+    }
+
+    /// Parse one `[tags.meta."Path"]` table.
+    fn parse_meta(value: &toml::Value, context: &str) -> Result<Vec<MetaEntry>, TagsConfigError> {
+        let mut entries = Vec::new();
+        for (key, value) in Self::expect_table(value, context)? {
+            if !Self::is_identifier(key) || RESERVED_META_KEYS.contains(&key.as_str()) {
+                return Err(TagsConfigError::Validation(format!(
+                    "Invalid metadata key '{}' in '{}'",
+                    key, context
+                )));
+            }
+            if STRING_META_KEYS.contains(&key.as_str()) && !value.is_str() {
+                return Err(TagsConfigError::Validation(format!(
+                    "Invalid metadata '{}' in '{}': expected a string, found {}",
+                    key,
+                    context,
+                    value.type_str()
+                )));
+            }
+            let value = match value {
+                // The generated literal is untyped, so it must fit `i32`
+                toml::Value::Integer(v) if i32::try_from(*v).is_err() => {
+                    return Err(TagsConfigError::Validation(format!(
+                        "Invalid metadata '{}' in '{}': integer {} does not fit in i32",
+                        key, context, v
+                    )));
+                }
+                toml::Value::Integer(v) => MetaValue::Integer(*v),
+                // `nan` and `inf` have no Rust literal
+                toml::Value::Float(v) if !v.is_finite() => {
+                    return Err(TagsConfigError::Validation(format!(
+                        "Invalid metadata '{}' in '{}': float must be finite, found {}",
+                        key, context, v
+                    )));
+                }
+                toml::Value::Float(v) => MetaValue::Float(*v),
+                toml::Value::Boolean(v) => MetaValue::Bool(*v),
+                toml::Value::String(v) => MetaValue::String(v.clone()),
+                other => {
+                    return Err(TagsConfigError::Validation(format!(
+                        "Invalid metadata '{}' in '{}': expected integer, float, boolean or string, found {}",
+                        key,
+                        context,
+                        other.type_str()
+                    )));
+                }
+            };
+            entries.push(MetaEntry {
+                key: key.clone(),
+                value,
+            });
+        }
+        Ok(entries)
+    }
+
+    fn expect_table<'a>(
+        value: &'a toml::Value,
+        context: &str,
+    ) -> Result<&'a toml::Table, TagsConfigError> {
+        value.as_table().ok_or_else(|| {
+            TagsConfigError::Validation(format!(
+                "Invalid '{}': expected a table, found {}",
+                context,
+                value.type_str()
+            ))
         })
     }
 
+    /// True for a lowercase-friendly Rust identifier (`mana_cost`).
+    fn is_identifier(key: &str) -> bool {
+        let mut chars = key.chars();
+        matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || c == '_')
+    }
+
+    /// A Rust visibility: `pub`, `pub(crate)`, `pub(super)`, `pub(self)`,
+    /// `pub(in path)`, or empty for private.
+    fn is_visibility(vis: &str) -> bool {
+        match vis.strip_prefix("pub") {
+            None => vis.is_empty(),
+            Some("") => true,
+            Some(rest) => match rest.strip_prefix('(').and_then(|r| r.strip_suffix(')')) {
+                Some("crate" | "super" | "self") => true,
+                Some(scope) => scope
+                    .strip_prefix("in ")
+                    .is_some_and(|path| Self::is_rust_path(path.trim())),
+                None => false,
+            },
+        }
+    }
+
+    /// `::`-separated identifiers, optionally with a leading `::`.
+    fn is_rust_path(path: &str) -> bool {
+        let path = path.strip_prefix("::").unwrap_or(path);
+        path.split("::").all(Self::is_identifier)
+    }
+
+    /// Record that `path` is declared in `sources[index]`, erroring if a
+    /// different file already declared it.
+    fn check_duplicate(
+        sources: &[RawSource],
+        declared_in: &mut HashMap<String, usize>,
+        kind: &str,
+        path: &str,
+        index: usize,
+    ) -> Result<(), TagsConfigError> {
+        match declared_in.get(path) {
+            Some(&first) if first != index => Err(TagsConfigError::Validation(format!(
+                "{} '{}' is declared in both {} and {}",
+                kind,
+                path,
+                sources[first].name(),
+                sources[index].name()
+            ))),
+            Some(_) => Ok(()),
+            None => {
+                declared_in.insert(path.to_string(), index);
+                Ok(())
+            }
+        }
+    }
+
+    /// `[policy]` violations when `level = "warn"` (empty otherwise, since
+    /// violations at `level = "error"` fail parsing).
+    pub fn policy_warnings(&self) -> &[String] {
+        &self.policy_warnings
+    }
+
     /// Get all entries.
     pub fn entries(&self) -> impl Iterator<Item = &TagEntry> {
         self.entries.iter()
@@ -142,6 +1022,60 @@ impl TagsConfig {
         self.entries.is_empty()
     }
 
+    /// Metadata attributes declared for `path` in `[tags.meta]`.
+    pub fn meta_of(&self, path: &str) -> &[MetaEntry] {
+        self.meta.get(path).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Human-readable name declared for `path` (`display_name` in `[tags.meta]`).
+    pub fn display_name_of(&self, path: &str) -> Option<&str> {
+        self.string_meta(path, "display_name")
+    }
+
+    /// Description declared for `path` (`description` in `[tags.meta]`).
+    pub fn description_of(&self, path: &str) -> Option<&str> {
+        self.string_meta(path, "description")
+    }
+
+    fn string_meta(&self, path: &str, key: &str) -> Option<&str> {
+        self.meta_of(path).iter().find_map(|m| match &m.value {
+            MetaValue::String(s) if m.key == key => Some(s.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Data type declared for `path` in `[tags.data]`.
+    pub fn data_of(&self, path: &str) -> Option<&str> {
+        self.data.get(path).map(String::as_str)
+    }
+
+    /// Custom deprecation note declared for `path` in `[deprecated]`.
+    pub fn deprecation_note(&self, path: &str) -> Option<&str> {
+        self.deprecated.get(path).map(String::as_str)
+    }
+
+    /// Whether `path` may be dropped from the lock when it is removed: it or
+    /// an ancestor is listed in `allow_removed`.
+    pub fn is_removal_allowed(&self, path: &str) -> bool {
+        self.allow_removed.iter().any(|allowed| {
+            path.strip_prefix(allowed.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+
+    /// All `[deprecated]` declarations as `(path, note)`, sorted by path.
+    pub fn deprecations(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.deprecated.iter().map(|(p, n)| (p.as_str(), n.as_str()))
+    }
+
+    /// `cfg` predicate gating `path` itself, from `[tags.dev-only]` or
+    /// `[tags.features]` (e.g. `feature = "cheats"`).
+    ///
+    /// Descendants of a gated path return `None` but are compiled out with it.
+    pub fn cfg_of(&self, path: &str) -> Option<&str> {
+        self.cfg.get(path).map(String::as_str)
+    }
+
     /// Get all redirect entries.
     pub fn redirects(&self) -> impl Iterator<Item = &RedirectEntry> {
         self.redirects.iter()
@@ -164,6 +1098,13 @@ impl TagsConfig {
                 path
             )));
         }
+        let depth = path.split('.').count();
+        if depth > MAX_DEPTH {
+            return Err(TagsConfigError::Validation(format!(
+                "Invalid path '{}': {} levels deep, but the GID layout ({}) supports at most {}",
+                path, depth, GID_SCHEME, MAX_DEPTH
+            )));
+        }
 
         for seg in path.split('.') {
             if seg.is_empty() {
@@ -172,19 +1113,29 @@ impl TagsConfig {
                     path
                 )));
             }
+            let rename = || {
+                diagnostic::suggest_segment(seg)
+                    .map(|name| format!("rename the segment to '{}'", name))
+            };
             let mut chars = seg.chars();
             if let Some(first) = chars.next()
                 && !first.is_alphabetic() && first != '_' {
-                    return Err(TagsConfigError::Validation(format!(
-                        "Invalid path '{}': segment '{}' must start with letter or underscore",
-                        path, seg
+                    return Err(TagsConfigError::Validation(diagnostic::with_help(
+                        format!(
+                            "Invalid path '{}': segment '{}' must start with letter or underscore",
+                            path, seg
+                        ),
+                        rename(),
                     )));
                 }
             for c in chars {
                 if !c.is_alphanumeric() && c != '_' {
-                    return Err(TagsConfigError::Validation(format!(
-                        "Invalid path '{}': segment '{}' contains invalid character '{}'",
-                        path, seg, c
+                    return Err(TagsConfigError::Validation(diagnostic::with_help(
+                        format!(
+                            "Invalid path '{}': segment '{}' contains invalid character '{}'",
+                            path, seg, c
+                        ),
+                        rename(),
                     )));
                 }
             }
@@ -193,60 +1144,155 @@ impl TagsConfig {
         Ok(())
     }
 
-    /// Expand paths to include all parent nodes.
-    ///
-    /// e.g., "A.B.C" expands to ["A", "A.B", "A.B.C"]
-    fn expand_paths(paths: &[String]) -> Result<Vec<TagEntry>, TagsConfigError> {
-        let mut seen: HashSet<String> = HashSet::new();
-        let mut entries: Vec<TagEntry> = Vec::new();
-
-        for path in paths {
-            // Validate path
-            if path.is_empty() {
-                return Err(TagsConfigError::Validation("Empty path not allowed".into()));
+    /// Collect every path declared under `[tags]`, flat or nested.
+    fn collect_paths(tags: &RawTags) -> Result<Vec<String>, TagsConfigError> {
+        let mut paths = Vec::new();
+        for (key, value) in tags {
+            if key == PATHS_KEY {
+                Self::collect_strings(value, "[tags].paths", "", &mut paths)?;
+            } else if key == META_KEY
+                || key == DATA_KEY
+                || key == DEV_ONLY_KEY
+                || key == FEATURES_KEY
+            {
+                continue;
+            } else {
+                Self::collect_node(key, value, &mut paths)?;
             }
-            if path.starts_with('.') || path.ends_with('.') {
+        }
+        Ok(paths)
+    }
+
+    /// Collect the gated paths of `[tags.dev-only]` and `[tags.features]`
+    /// with their `cfg` predicates.
+    fn collect_gated(tags: &RawTags) -> Result<Vec<(String, String)>, TagsConfigError> {
+        let mut gated = Vec::new();
+        let mut collect = |value: &toml::Value, context: &str, predicate: String| {
+            let table = Self::expect_table(value, context)?;
+            if let Some(key) = table.keys().find(|key| *key != PATHS_KEY) {
                 return Err(TagsConfigError::Validation(format!(
-                    "Invalid path '{}': cannot start or end with '.'",
-                    path
+                    "Invalid key '{}' in '{}': expected only 'paths'",
+                    key, context
                 )));
             }
-            if path.contains("..") {
-                return Err(TagsConfigError::Validation(format!(
-                    "Invalid path '{}': contains '..'",
-                    path
-                )));
+            let mut paths = Vec::new();
+            if let Some(list) = table.get(PATHS_KEY) {
+                Self::collect_strings(list, &format!("{}.paths", context), "", &mut paths)?;
             }
+            gated.extend(paths.into_iter().map(|path| (path, predicate.clone())));
+            Ok(())
+        };
 
-            // Split into segments
-            let segments: Vec<&str> = path.split('.').collect();
-
-            // Validate segments
-            for seg in &segments {
-                if seg.is_empty() {
+        if let Some(value) = tags.get(DEV_ONLY_KEY) {
+            collect(value, "tags.dev-only", "debug_assertions".to_string())?;
+        }
+        if let Some(features) = tags.get(FEATURES_KEY) {
+            for (feature, value) in Self::expect_table(features, "tags.features")? {
+                if !Self::is_feature_name(feature) {
                     return Err(TagsConfigError::Validation(format!(
-                        "Invalid path '{}': empty segment",
-                        path
+                        "Invalid feature name '{}' in [tags.features]",
+                        feature
                     )));
                 }
-                // Check valid identifier (starts with letter/underscore, contains alphanumeric/_)
-                let mut chars = seg.chars();
-                if let Some(first) = chars.next()
-                    && !first.is_alphabetic() && first != '_' {
-                        return Err(TagsConfigError::Validation(format!(
-                            "Invalid path '{}': segment '{}' must start with letter or underscore",
-                            path, seg
-                        )));
-                    }
-                for c in chars {
-                    if !c.is_alphanumeric() && c != '_' {
-                        return Err(TagsConfigError::Validation(format!(
-                            "Invalid path '{}': segment '{}' contains invalid character '{}'",
-                            path, seg, c
-                        )));
-                    }
-                }
+                let context = format!("tags.features.{}", feature);
+                collect(value, &context, format!("feature = {:?}", feature))?;
+            }
+        }
+        Ok(gated)
+    }
+
+    /// True for a name cargo accepts as a feature (`cheats`, `dev-tools`).
+    fn is_feature_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_alphanumeric() || c == '_')
+            && chars.all(|c| c.is_alphanumeric() || "_-+.".contains(c))
+    }
+
+    /// Closest strict ancestor of `path` that is gated.
+    fn gated_ancestor<'a>(cfg: &'a BTreeMap<String, String>, path: &str) -> Option<&'a str> {
+        let mut ancestor = path;
+        while let Some((parent, _)) = ancestor.rsplit_once('.') {
+            if let Some((key, _)) = cfg.get_key_value(parent) {
+                return Some(key);
             }
+            ancestor = parent;
+        }
+        None
+    }
+
+    /// Collect a nested node table and everything below it.
+    fn collect_node(
+        path: &str,
+        value: &toml::Value,
+        paths: &mut Vec<String>,
+    ) -> Result<(), TagsConfigError> {
+        let table = value.as_table().ok_or_else(|| {
+            TagsConfigError::Validation(format!(
+                "Invalid node 'tags.{}': expected a table, found {}",
+                path,
+                value.type_str()
+            ))
+        })?;
+
+        // Intermediate tables are implied by their children; only tables
+        // without any declare a node themselves.
+        let before = paths.len();
+        for (key, child) in table {
+            if key == CHILDREN_KEY {
+                let context = format!("tags.{}.{}", path, CHILDREN_KEY);
+                Self::collect_strings(child, &context, path, paths)?;
+            } else {
+                Self::collect_node(&format!("{}.{}", path, key), child, paths)?;
+            }
+        }
+        if paths.len() == before {
+            paths.push(path.to_string());
+        }
+        Ok(())
+    }
+
+    /// Collect a string array, prefixing each entry with `prefix`.
+    fn collect_strings(
+        value: &toml::Value,
+        context: &str,
+        prefix: &str,
+        paths: &mut Vec<String>,
+    ) -> Result<(), TagsConfigError> {
+        let items = value.as_array().ok_or_else(|| {
+            TagsConfigError::Validation(format!(
+                "Invalid '{}': expected an array of strings, found {}",
+                context,
+                value.type_str()
+            ))
+        })?;
+
+        for item in items {
+            let name = item.as_str().ok_or_else(|| {
+                TagsConfigError::Validation(format!(
+                    "Invalid '{}': expected strings, found {}",
+                    context,
+                    item.type_str()
+                ))
+            })?;
+            if prefix.is_empty() || name.is_empty() {
+                paths.push(name.to_string());
+            } else {
+                paths.push(format!("{}.{}", prefix, name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Expand paths to include all parent nodes.
+    ///
+    /// e.g., "A.B.C" expands to ["A", "A.B", "A.B.C"]
+    fn expand_paths(paths: &[String]) -> Result<Vec<TagEntry>, TagsConfigError> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut entries: Vec<TagEntry> = Vec::new();
+
+        for path in paths {
+            Self::validate_path(path)?;
+            let segments: Vec<&str> = path.split('.').collect();
 
             // Add all ancestors and the path itself
             for depth in 0..segments.len() {
@@ -388,12 +1434,13 @@ paths = [""]
     #[test]
     fn rejects_invalid_path() {
         let cases = [
-            ".A",        // starts with dot
-            "A.",        // ends with dot
-            "A..B",      // double dot
-            "A.1B",      // segment starts with number
-            "A.B-C",     // contains hyphen
-            "A.B C",     // contains space
+            ".A",                // starts with dot
+            "A.",                // ends with dot
+            "A..B",              // double dot
+            "A.1B",              // segment starts with number
+            "A.B-C",             // contains hyphen
+            "A.B C",             // contains space
+            "A.B.C.D.E.F.G.H.I", // deeper than MAX_DEPTH
         ];
 
         for case in cases {
@@ -455,6 +1502,128 @@ paths = ["A"]
         assert_eq!(config.on_remove, OnRemove::Warn);
     }
 
+    #[test]
+    fn on_remove_auto_redirect() {
+        let toml = r#"
+on_remove = "auto-redirect"
+
+[tags]
+paths = ["A"]
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+        assert_eq!(config.on_remove, OnRemove::AutoRedirect);
+    }
+
+    #[test]
+    fn codegen_manifest_option() {
+        let config = TagsConfig::from_str("[tags]\npaths = [\"A\"]").unwrap();
+        assert_eq!(config.codegen.manifest, None);
+
+        let config =
+            TagsConfig::from_str("[codegen]\nmanifest = true\n[tags]\npaths = [\"A\"]").unwrap();
+        assert_eq!(config.codegen.manifest.as_deref(), Some("tags_manifest.json"));
+
+        let config =
+            TagsConfig::from_str("[codegen]\nmanifest = \"ids.json\"\n[tags]\npaths = [\"A\"]")
+                .unwrap();
+        assert_eq!(config.codegen.manifest.as_deref(), Some("ids.json"));
+
+        let config =
+            TagsConfig::from_str("[codegen]\nc_header = true\n[tags]\npaths = [\"A\"]").unwrap();
+        assert_eq!(config.codegen.c_header.as_deref(), Some("tags.h"));
+
+        let config =
+            TagsConfig::from_str("[codegen]\ntypescript = \"web/tags.ts\"\n[tags]\npaths = [\"A\"]")
+                .unwrap();
+        assert_eq!(config.codegen.typescript.as_deref(), Some("web/tags.ts"));
+
+        let config =
+            TagsConfig::from_str("[codegen]\nmarkdown = true\n[tags]\npaths = [\"A\"]").unwrap();
+        assert_eq!(config.codegen.markdown.as_deref(), Some("TAGS.md"));
+
+        let config =
+            TagsConfig::from_str("[codegen]\nhistory = true\n[tags]\npaths = [\"A\"]").unwrap();
+        assert_eq!(config.codegen.history.as_deref(), Some("tags.history.toml"));
+
+        let config =
+            TagsConfig::from_str("[codegen]\nregistry = \"tag_registry\"\n[tags]\npaths = [\"A\"]")
+                .unwrap();
+        assert_eq!(config.codegen.registry.as_deref(), Some("tag_registry"));
+        assert!(TagsConfig::from_str("[codegen]\nregistry = \"not a fn\"").is_err());
+
+        let config = TagsConfig::from_str("[codegen]\nredirects = true").unwrap();
+        assert_eq!(config.codegen.redirects.as_deref(), Some("redirects"));
+        assert!(TagsConfig::from_str("[codegen]\nredirects = \"not a fn\"").is_err());
+
+        assert!(TagsConfig::from_str("[codegen]\nmanifset = true\n[tags]\npaths = [\"A\"]").is_err());
+    }
+
+    #[test]
+    fn codegen_module_options() {
+        let toml = r#"
+[codegen]
+visibility = "pub(in crate::game)"
+doc = "Gameplay tags."
+allow = ["missing_docs", "clippy::module_name_repetitions"]
+derive = ["PartialOrd", "::serde::Serialize"]
+
+[tags]
+paths = ["A"]
+"#;
+        let codegen = TagsConfig::from_str(toml).unwrap().codegen;
+        assert_eq!(codegen.visibility.as_deref(), Some("pub(in crate::game)"));
+        assert_eq!(codegen.doc.as_deref(), Some("Gameplay tags."));
+        assert_eq!(codegen.allow, ["missing_docs", "clippy::module_name_repetitions"]);
+        assert_eq!(codegen.derive, ["PartialOrd", "::serde::Serialize"]);
+
+        for vis in ["", "pub", "pub(crate)", "pub(super)"] {
+            let toml = format!("[codegen]\nvisibility = \"{}\"\n[tags]\npaths = [\"A\"]", vis);
+            assert!(TagsConfig::from_str(&toml).is_ok(), "{}", vis);
+        }
+        for bad in [
+            "visibility = \"public\"",
+            "visibility = \"pub(world)\"",
+            "allow = [\"missing docs\"]",
+            "derive = [\"Ord)] struct X; #[derive(Eq\"]",
+        ] {
+            let toml = format!("[codegen]\n{}\n[tags]\npaths = [\"A\"]", bad);
+            assert!(TagsConfig::from_str(&toml).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn policy_error_and_warn_levels() {
+        let tags = "[tags]\npaths = [\"Item.temp_sword\", \"Item.Axe\"]";
+        let policy = "[policy]\npascal_case = true\nforbidden = [\"Axe\"]";
+
+        let err = TagsConfig::from_str(&format!("{}\n{}", policy, tags)).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("'Item.Axe' uses the forbidden name 'Axe'"), "{}", msg);
+        assert!(msg.contains("segment 'temp_sword' is not PascalCase"), "{}", msg);
+
+        let config = TagsConfig::from_str(&format!("{}\nlevel = \"warn\"\n{}", policy, tags)).unwrap();
+        assert_eq!(config.policy.level, PolicyLevel::Warn);
+        assert_eq!(config.policy_warnings().len(), 2);
+
+        let config = TagsConfig::from_str(tags).unwrap();
+        assert!(config.policy_warnings().is_empty());
+
+        assert!(TagsConfig::from_str("[policy]\nlevel = \"loud\"").is_err());
+        assert!(TagsConfig::from_str("[policy]\nmax_dpeth = 3").is_err());
+    }
+
+    #[test]
+    fn allow_removed_covers_subtrees() {
+        let config =
+            TagsConfig::from_str("allow_removed = [\"Item.Proto\"]\n[tags]\npaths = [\"A\"]").unwrap();
+        assert!(config.is_removal_allowed("Item.Proto"));
+        assert!(config.is_removal_allowed("Item.Proto.Sword"));
+        assert!(!config.is_removal_allowed("Item.Prototype"));
+        assert!(!config.is_removal_allowed("Item"));
+
+        assert!(TagsConfig::from_str("allow_removed = [\"Bad..Path\"]").is_err());
+    }
+
     #[test]
     fn on_remove_invalid_value() {
         let toml = r#"
@@ -519,6 +1688,300 @@ paths = ["A.B"]
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_nested_tables() {
+        let toml = r#"
+[tags.Item.Weapon]
+children = ["Sword", "Axe"]
+
+[tags.Item.Armor]
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+
+        let paths: Vec<_> = config.entries().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "Item",
+                "Item.Armor",
+                "Item.Weapon",
+                "Item.Weapon.Axe",
+                "Item.Weapon.Sword",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_inline_tree_tables() {
+        let toml = r#"
+[tags]
+Ability = { Fire = { children = ["Fireball", "Burn.Dot"] }, Ice = {} }
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+
+        let paths: Vec<_> = config.entries().map(|e| e.path.as_str()).collect();
+        assert!(paths.contains(&"Ability.Fire.Fireball"));
+        assert!(paths.contains(&"Ability.Fire.Burn"));
+        assert!(paths.contains(&"Ability.Fire.Burn.Dot"));
+        assert!(paths.contains(&"Ability.Ice"));
+    }
+
+    #[test]
+    fn flat_and_nested_forms_merge() {
+        let toml = r#"
+[tags]
+paths = ["Item.Weapon.Sword", "Skill"]
+
+[tags.Item.Weapon]
+children = ["Sword", "Axe"]
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+
+        // Item, Item.Weapon, Item.Weapon.Sword, Item.Weapon.Axe, Skill
+        assert_eq!(config.len(), 5);
+    }
+
+    #[test]
+    fn nested_redirect_target_resolves() {
+        let toml = r#"
+[tags.Equipment.Weapon]
+children = ["Blade"]
+
+[redirects]
+"Legacy.OldSword" = "Equipment.Weapon.Blade"
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+        assert_eq!(config.redirects().count(), 1);
+    }
+
+    #[test]
+    fn rejects_malformed_nested_nodes() {
+        let cases = [
+            "[tags]\nItem = \"Sword\"",              // node is not a table
+            "[tags.Item]\nchildren = \"Sword\"",     // children is not an array
+            "[tags.Item]\nchildren = [1, 2]",        // children are not strings
+            "[tags.Item]\nchildren = [\"Bad-Name\"]", // invalid segment
+            "[tags]\npaths = \"A.B\"",               // paths is not an array
+        ];
+
+        for case in cases {
+            assert!(TagsConfig::from_str(case).is_err(), "Should reject: {}", case);
+        }
+    }
+
+    #[test]
+    fn from_str_rejects_includes() {
+        let toml = r#"
+includes = ["other.toml"]
+
+[tags]
+paths = ["A"]
+"#;
+        let err = TagsConfig::from_str(toml).unwrap_err();
+        assert!(err.to_string().contains("from_file"));
+    }
+
+    #[test]
+    fn root_may_omit_tags_table() {
+        let config = TagsConfig::from_str("module_name = \"Empty\"").unwrap();
+        assert!(config.is_empty());
+        assert_eq!(config.sources().count(), 0);
+    }
+
+    #[test]
+    fn parse_metadata() {
+        let toml = r#"
+[tags.Ability]
+children = ["Fireball"]
+
+[tags.meta."Ability.Fireball"]
+mana_cost = 10
+cooldown = 1.5
+school = "fire"
+aoe = true
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+
+        // `meta` is not a node
+        assert!(config.entries().all(|e| !e.path.starts_with("meta")));
+
+        let meta = config.meta_of("Ability.Fireball");
+        let rendered: Vec<_> = meta
+            .iter()
+            .map(|m| format!("{} = {}", m.key, m.value.to_rust_literal()))
+            .collect();
+        assert_eq!(
+            rendered,
+            ["aoe = true", "cooldown = 1.5", "mana_cost = 10", "school = \"fire\""]
+        );
+        assert!(config.meta_of("Ability").is_empty());
+    }
+
+    #[test]
+    fn display_name_and_description() {
+        let toml = r#"
+[tags]
+paths = ["Ability.Fireball"]
+
+[tags.meta."Ability.Fireball"]
+display_name = "Fireball"
+description = "Hurls a ball of fire"
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+        assert_eq!(config.display_name_of("Ability.Fireball"), Some("Fireball"));
+        assert_eq!(config.description_of("Ability.Fireball"), Some("Hurls a ball of fire"));
+        assert_eq!(config.display_name_of("Ability"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_metadata() {
+        let cases = [
+            // unknown path
+            "[tags]\npaths = [\"A\"]\n[tags.meta.\"B\"]\nx = 1",
+            // unsupported value type
+            "[tags]\npaths = [\"A\"]\n[tags.meta.\"A\"]\nx = [1]",
+            // key the macro interprets itself
+            "[tags]\npaths = [\"A\"]\n[tags.meta.\"A\"]\ngid = 1",
+            // UI attributes must be strings
+            "[tags]\npaths = [\"A\"]\n[tags.meta.\"A\"]\ndisplay_name = 1",
+            // not an identifier
+            "[tags]\npaths = [\"A\"]\n[tags.meta.\"A\"]\n\"mana-cost\" = 1",
+            // no Rust literal for these
+            "[tags]\npaths = [\"A\"]\n[tags.meta.\"A\"]\nx = 2147483648",
+            "[tags]\npaths = [\"A\"]\n[tags.meta.\"A\"]\nx = nan",
+            "[tags]\npaths = [\"A\"]\n[tags.meta.\"A\"]\nx = inf",
+        ];
+
+        for case in cases {
+            assert!(TagsConfig::from_str(case).is_err(), "Should reject: {}", case);
+        }
+    }
+
+    #[test]
+    fn parse_data_types() {
+        let toml = r#"
+[tags]
+paths = ["Ability.Fireball", "Item.Weapon.Sword"]
+
+[tags.data]
+"Ability.Fireball" = "crate::AbilityData"
+"Item.Weapon" = " crate::WeaponData<u32> "
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+
+        assert!(config.entries().all(|e| !e.path.starts_with("data")));
+        assert_eq!(config.data_of("Ability.Fireball"), Some("crate::AbilityData"));
+        assert_eq!(config.data_of("Item.Weapon"), Some("crate::WeaponData<u32>"));
+        assert_eq!(config.data_of("Item.Weapon.Sword"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_data_types() {
+        let cases = [
+            // unknown path
+            "[tags]\npaths = [\"A\"]\n[tags.data]\nB = \"crate::X\"",
+            // not a string
+            "[tags]\npaths = [\"A\"]\n[tags.data]\nA = 1",
+            // not a type
+            "[tags]\npaths = [\"A\"]\n[tags.data]\nA = \"crate::X { }\"",
+            "[tags]\npaths = [\"A\"]\n[tags.data]\nA = \"\"",
+        ];
+
+        for case in cases {
+            assert!(TagsConfig::from_str(case).is_err(), "Should reject: {}", case);
+        }
+    }
+
+    #[test]
+    fn parse_deprecations() {
+        let toml = r#"
+[tags]
+paths = ["Item.Sword", "Equipment.Weapon.Blade"]
+
+[deprecated]
+"Item.Sword" = "Use Equipment.Weapon.Blade instead"
+"Item.Removed" = "Gone since 0.3"
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+
+        assert_eq!(
+            config.deprecation_note("Item.Sword"),
+            Some("Use Equipment.Weapon.Blade instead")
+        );
+        assert_eq!(config.deprecation_note("Equipment.Weapon.Blade"), None);
+        let paths: Vec<_> = config.deprecations().map(|(p, _)| p).collect();
+        assert_eq!(paths, ["Item.Removed", "Item.Sword"]);
+    }
+
+    #[test]
+    fn deprecating_a_redirect_is_rejected() {
+        let toml = r#"
+[tags]
+paths = ["A"]
+
+[redirects]
+"Old" = "A"
+
+[deprecated]
+"Old" = "already redirected"
+"#;
+        assert!(TagsConfig::from_str(toml).is_err());
+    }
+
+    #[test]
+    fn parse_gated_paths() {
+        let toml = r#"
+[tags]
+paths = ["Debug.Log"]
+
+[tags.dev-only]
+paths = ["Debug.GodMode", "Debug.NoClip"]
+
+[tags.features.cheats]
+paths = ["Cheat.InfiniteAmmo"]
+"#;
+        let config = TagsConfig::from_str(toml).unwrap();
+
+        let paths: Vec<_> = config.entries().map(|e| e.path.as_str()).collect();
+        assert!(paths.contains(&"Debug.GodMode"));
+        assert!(paths.contains(&"Cheat"));
+        assert_eq!(config.cfg_of("Debug.GodMode"), Some("debug_assertions"));
+        assert_eq!(config.cfg_of("Cheat.InfiniteAmmo"), Some("feature = \"cheats\""));
+        assert_eq!(config.cfg_of("Debug"), None);
+        assert_eq!(config.cfg_of("Debug.Log"), None);
+    }
+
+    #[test]
+    fn rejects_conflicting_gates() {
+        let cases = [
+            // Declared ungated as well
+            ("paths = [\"A.B\"]\n[tags.dev-only]\npaths = [\"A.B\"]", "both gated"),
+            // Two different gates
+            (
+                "[tags.dev-only]\npaths = [\"A\"]\n[tags.features.x]\npaths = [\"A\"]",
+                "gated by both",
+            ),
+            // Ungated child of a gated tag
+            ("paths = [\"A.B\"]\n[tags.dev-only]\npaths = [\"A\"]", "its parent 'A'"),
+            ("[tags.features.\"-x\"]\npaths = [\"A\"]", "Invalid feature name"),
+            ("[tags.dev-only]\nchildren = [\"A\"]", "expected only 'paths'"),
+        ];
+        for (tags, expected) in cases {
+            let err = TagsConfig::from_str(&format!("[tags]\n{}", tags)).unwrap_err();
+            assert!(err.to_string().contains(expected), "{}: {}", tags, err);
+        }
+
+        let toml = r#"
+[tags.dev-only]
+paths = ["Debug.GodMode"]
+
+[redirects]
+"Debug.God" = "Debug.GodMode"
+"#;
+        let err = TagsConfig::from_str(toml).unwrap_err();
+        assert!(err.to_string().contains("is gated"), "{}", err);
+    }
+
     #[test]
     fn empty_redirects_allowed() {
         let toml = r#"
@@ -0,0 +1,116 @@
+//! TypeScript module generation for web tooling.
+//!
+//! Emits the same tags as the [`Manifest`] as `as const` object literals, so
+//! dashboards get typed paths and hex GIDs without parsing JSON at runtime:
+//!
+//! ```ts
+//! import { Tags, TAG_PARENTS, tagPathOf } from "./tags";
+//!
+//! const gid = Tags["Item.Weapon"];        // "0x2000..."
+//! const parent = TAG_PARENTS["Item.Weapon"]; // "Item"
+//! ```
+
+use crate::lock::LockFile;
+use crate::manifest::Manifest;
+use crate::toml_parser::TagsConfig;
+use std::fmt::Write;
+
+/// Generate a TypeScript module with every tag's GID, parent and redirect.
+///
+/// `<module_name>` maps path to hex GID (a redirect has its target's GID),
+/// `TAG_PARENTS` maps path to parent, `TAG_REDIRECTS` maps redirected paths
+/// to their targets, and `tagPathOf` resolves a GID to its canonical path.
+pub fn generate_typescript(config: &TagsConfig, lock: Option<&LockFile>) -> String {
+    let manifest = Manifest::new(config, lock);
+    let tags: Vec<_> = manifest.tags.iter().filter(|e| e.gid.is_some()).collect();
+    let name = &manifest.module_name;
+
+    let mut out = String::new();
+    writeln!(out, "// Generated by bevy-tag-build. Do not edit.").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "export const GID_SCHEME = {};", quote(&manifest.gid_scheme)).unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "/** Hex GID of every tag path. */").unwrap();
+    writeln!(out, "export const {} = {{", name).unwrap();
+    for entry in &tags {
+        let gid = entry.gid.as_deref().unwrap_or_default();
+        if entry.deprecated {
+            writeln!(out, "  /** @deprecated */").unwrap();
+        }
+        writeln!(out, "  {}: {},", quote(&entry.path), quote(gid)).unwrap();
+    }
+    writeln!(out, "}} as const;").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "export type TagPath = keyof typeof {};", name).unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "/** Parent of every tag path (null for roots). */").unwrap();
+    writeln!(out, "export const TAG_PARENTS: Record<TagPath, TagPath | null> = {{").unwrap();
+    for entry in &tags {
+        let parent = entry.parent.as_deref().map_or("null".to_string(), quote);
+        writeln!(out, "  {}: {},", quote(&entry.path), parent).unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "/** Old paths and the canonical path they redirect to. */").unwrap();
+    writeln!(out, "export const TAG_REDIRECTS: Partial<Record<TagPath, TagPath>> = {{").unwrap();
+    for entry in &tags {
+        if let Some(target) = &entry.redirect {
+            writeln!(out, "  {}: {},", quote(&entry.path), quote(target)).unwrap();
+        }
+    }
+    writeln!(out, "}};").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "const PATH_BY_GID: Record<string, TagPath> = {{").unwrap();
+    for entry in tags.iter().filter(|e| e.redirect.is_none()) {
+        let gid = entry.gid.as_deref().unwrap_or_default();
+        writeln!(out, "  {}: {},", quote(gid), quote(&entry.path)).unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "/** Canonical path of a hex GID, if it is a known tag. */").unwrap();
+    writeln!(out, "export function tagPathOf(gid: string): TagPath | undefined {{").unwrap();
+    writeln!(out, "  return PATH_BY_GID[gid.toLowerCase()];").unwrap();
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// A string literal valid in both JSON and TypeScript.
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).expect("strings are always serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gid::{format_gid, path_gid};
+
+    #[test]
+    fn emits_gids_parents_and_redirects() {
+        let toml = r#"
+module_name = "GameTags"
+
+[tags]
+paths = ["Item.Blade"]
+
+[redirects]
+"Item.Sword" = "Item.Blade"
+"#;
+        let ts = generate_typescript(&TagsConfig::from_str(toml).unwrap(), None);
+        let blade = format_gid(path_gid("Item.Blade").unwrap());
+
+        assert!(ts.contains("export const GameTags = {"));
+        assert!(ts.contains("export type TagPath = keyof typeof GameTags;"));
+        assert!(ts.contains(&format!("  \"Item.Blade\": \"{}\",", blade)));
+        assert!(ts.contains(&format!("  /** @deprecated */\n  \"Item.Sword\": \"{}\",", blade)));
+        assert!(ts.contains("  \"Item\": null,"));
+        assert!(ts.contains("  \"Item.Blade\": \"Item\","));
+        assert!(ts.contains("  \"Item.Sword\": \"Item.Blade\","));
+        // Reverse lookup only resolves to the canonical path
+        assert!(ts.contains(&format!("  \"{}\": \"Item.Blade\",", blade)));
+        assert!(!ts.contains(&format!("  \"{}\": \"Item.Sword\",", blade)));
+    }
+}
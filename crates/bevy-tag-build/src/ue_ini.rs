@@ -0,0 +1,179 @@
+//! Import of Unreal Engine GameplayTags `.ini` files.
+//!
+//! Converts the entries of a `DefaultGameplayTags.ini` (or a
+//! `Config/Tags/*.ini` list) into `tags.toml` text:
+//!
+//! ```ini
+//! [/Script/GameplayTags.GameplayTagsSettings]
+//! +GameplayTagList=(Tag="Ability.Fireball",DevComment="Hurls fire")
+//! +GameplayTagRedirects=(OldTagName="Ability.Fire",NewTagName="Ability.Fireball")
+//! ```
+//!
+//! becomes
+//!
+//! ```toml
+//! [tags]
+//! paths = ["Ability.Fireball"]
+//!
+//! [tags.meta."Ability.Fireball"]
+//! display_name = "Hurls fire"
+//!
+//! [redirects]
+//! "Ability.Fire" = "Ability.Fireball"
+//! ```
+//!
+//! Other settings in the file are ignored.
+
+use crate::toml_parser::TagsConfigError;
+use std::collections::BTreeMap;
+
+/// Convert the contents of a GameplayTags `.ini` file into `tags.toml` text.
+///
+/// `DevComment`s become `display_name` metadata and `GameplayTagRedirects`
+/// become `[redirects]`. Redirects to an empty tag (removals in Unreal) are
+/// dropped.
+pub fn ue_ini_to_toml(ini: &str) -> Result<String, TagsConfigError> {
+    let mut paths = Vec::new();
+    let mut display_names = BTreeMap::new();
+    let mut redirects = BTreeMap::new();
+
+    for (index, line) in ini.lines().enumerate() {
+        let line = line.trim();
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().trim_start_matches(['+', '-', '.', '!']);
+        let invalid = |msg: &str| {
+            TagsConfigError::Parse(format!("line {}: {}: {}", index + 1, msg, line))
+        };
+
+        match key {
+            "GameplayTagList" => {
+                let fields = parse_struct(value).ok_or_else(|| invalid("malformed entry"))?;
+                let tag = fields
+                    .get("Tag")
+                    .filter(|t| !t.is_empty())
+                    .ok_or_else(|| invalid("missing Tag"))?;
+                if !paths.contains(tag) {
+                    paths.push(tag.clone());
+                }
+                if let Some(comment) = fields.get("DevComment").filter(|c| !c.is_empty()) {
+                    display_names.insert(tag.clone(), comment.clone());
+                }
+            }
+            "GameplayTagRedirects" => {
+                let fields = parse_struct(value).ok_or_else(|| invalid("malformed redirect"))?;
+                let old = fields
+                    .get("OldTagName")
+                    .ok_or_else(|| invalid("missing OldTagName"))?;
+                if let Some(new) = fields.get("NewTagName").filter(|n| !n.is_empty()) {
+                    redirects.insert(old.clone(), new.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut tags = toml::Table::new();
+    tags.insert("paths".into(), paths.into());
+    if !display_names.is_empty() {
+        let meta: toml::Table = display_names
+            .into_iter()
+            .map(|(path, display_name)| {
+                let mut attrs = toml::Table::new();
+                attrs.insert("display_name".into(), display_name.into());
+                (path, attrs.into())
+            })
+            .collect();
+        tags.insert("meta".into(), meta.into());
+    }
+
+    let mut root = toml::Table::new();
+    root.insert("tags".into(), tags.into());
+    if !redirects.is_empty() {
+        let redirects: toml::Table = redirects.into_iter().map(|(k, v)| (k, v.into())).collect();
+        root.insert("redirects".into(), redirects.into());
+    }
+    toml::to_string(&root).map_err(|e| TagsConfigError::Parse(e.to_string()))
+}
+
+/// Parse an Unreal struct literal `(Key="value",Other=bare)` into its fields.
+fn parse_struct(text: &str) -> Option<BTreeMap<String, String>> {
+    let inner = text.trim().strip_prefix('(')?.strip_suffix(')')?;
+    let mut chars = inner.chars().peekable();
+    let mut fields = BTreeMap::new();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+        if chars.peek().is_none() {
+            return Some(fields);
+        }
+
+        let key: String = std::iter::from_fn(|| chars.next_if(|c| *c != '=')).collect();
+        chars.next()?; // '='
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    '\\' => value.push(chars.next()?),
+                    c => value.push(c),
+                }
+            }
+        } else {
+            value.extend(std::iter::from_fn(|| chars.next_if(|c| *c != ',')));
+        }
+        fields.insert(key.trim().to_string(), value.trim_end().to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toml_parser::TagsConfig;
+
+    const INI: &str = r#"
+[/Script/GameplayTags.GameplayTagsSettings]
+ImportTagsFromConfig=True
+WarnOnInvalidTags=True
++GameplayTagList=(Tag="Ability",DevComment="All abilities")
++GameplayTagList=(Tag="Ability.Fireball",DevComment="Hurls fire, \"hot\"")
++GameplayTagList=(Tag="Ability.Frost",DevComment="")
++GameplayTagList=(Tag="Status.Burning")
++GameplayTagRedirects=(OldTagName="Ability.Fire",NewTagName="Ability.Fireball")
++GameplayTagRedirects=(OldTagName="Ability.Removed",NewTagName="")
+"#;
+
+    #[test]
+    fn parses_struct_literals() {
+        let fields = parse_struct(r#"(Tag="A.B", DevComment="x, \"y\"",Flag=True)"#).unwrap();
+        assert_eq!(fields["Tag"], "A.B");
+        assert_eq!(fields["DevComment"], "x, \"y\"");
+        assert_eq!(fields["Flag"], "True");
+
+        assert!(parse_struct("Tag=\"A\"").is_none());
+        assert!(parse_struct("(Tag=\"A)").is_none());
+    }
+
+    #[test]
+    fn converts_to_config() {
+        let config = TagsConfig::from_str(&ue_ini_to_toml(INI).unwrap()).unwrap();
+
+        let paths: Vec<_> = config.entries().map(|e| e.path.as_str()).collect();
+        assert!(paths.contains(&"Ability.Fireball"));
+        assert!(paths.contains(&"Status.Burning"));
+        assert_eq!(config.display_name_of("Ability.Fireball"), Some("Hurls fire, \"hot\""));
+        assert_eq!(config.display_name_of("Ability.Frost"), None);
+        assert_eq!(config.description_of("Ability.Fireball"), None);
+
+        let redirects: Vec<_> = config.redirects().map(|r| (r.from.as_str(), r.to.as_str())).collect();
+        assert_eq!(redirects, [("Ability.Fire", "Ability.Fireball")]);
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let err = ue_ini_to_toml("+GameplayTagList=(DevComment=\"x\")").unwrap_err();
+        assert!(err.to_string().contains("line 1: missing Tag"), "{}", err);
+    }
+}
@@ -0,0 +1,196 @@
+//! Tests for the `cargo bevy-tag` binary.
+
+use bevy_tag_build::{format_gid, path_gid};
+use std::fs;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn setup(config: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    fs::write(dir.path().join("tags.toml"), config).unwrap();
+    dir
+}
+
+fn run(dir: &TempDir, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cargo-bevy-tag"))
+        .current_dir(dir.path())
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn gid_and_path_roundtrip() {
+    let dir = setup("[tags]\npaths = [\"Item.Weapon.Sword\"]\n");
+
+    // Accepts the `cargo` subcommand name as the first argument
+    let output = run(&dir, &["bevy-tag", "gid", "Item.Weapon.Sword"]);
+    assert!(output.status.success());
+    let gid = stdout(&output).trim().to_string();
+    assert_eq!(gid, format_gid(path_gid("Item.Weapon.Sword").unwrap()));
+
+    let output = run(&dir, &["path", &gid]);
+    assert!(output.status.success());
+    assert_eq!(stdout(&output).trim(), "Item.Weapon.Sword");
+
+    let output = run(&dir, &["path", "0x1"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn validate_reports_bad_config() {
+    let dir = setup("[tags]\npaths = [\"Bad-Name\"]\n");
+    let output = run(&dir, &["validate"]);
+    assert!(!output.status.success());
+
+    let dir = setup("[tags]\npaths = [\"A.B\"]\n");
+    let output = run(&dir, &["validate"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("2 tags"));
+    assert!(stdout(&output).contains("level 1: 1/262144 slots used"));
+}
+
+#[test]
+fn diff_fails_on_removed_paths() {
+    let dir = setup("[tags]\npaths = [\"Item.Sword\", \"Item.Axe\"]\n");
+    bevy_tag_build::generate(dir.path().join("tags.toml"), dir.path().join("out.rs")).unwrap();
+
+    let output = run(&dir, &["diff"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("config matches lock"));
+
+    fs::write(
+        dir.path().join("tags.toml"),
+        "[tags]\npaths = [\"Item.Axe\", \"Item.Blade\"]\n",
+    )
+    .unwrap();
+    let output = run(&dir, &["diff"]);
+    assert!(!output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("+ Item.Blade"), "{}", out);
+    assert!(out.contains("- Item.Sword"), "{}", out);
+    assert!(out.contains("~ Item.Sword -> Item.Blade"), "{}", out);
+}
+
+#[test]
+fn tree_and_export() {
+    let dir = setup(
+        "module_name = \"Game\"\n[tags]\npaths = [\"A.B\", \"A.C\"]\n[redirects]\n\"Old\" = \"A.B\"\n",
+    );
+
+    let output = run(&dir, &["tree"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.starts_with("Game\n"), "{}", out);
+    assert!(out.contains("├── B"), "{}", out);
+    assert!(out.contains("└── C"), "{}", out);
+    assert!(out.contains("Old  (redirects to A.B)"), "{}", out);
+
+    let output = run(&dir, &["export"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    let tags = &json["tags"];
+    assert_eq!(tags.as_array().unwrap().len(), 4);
+    assert_eq!(tags[1]["path"], "A.B");
+    assert_eq!(tags[1]["parent"], "A");
+    assert_eq!(tags[3]["path"], "Old");
+    assert_eq!(tags[3]["redirect"], "A.B");
+}
+
+#[test]
+fn diagrams() {
+    let dir = setup("[tags]\npaths = [\"A.B\"]\n[redirects]\n\"Old\" = \"A.B\"\n");
+
+    let output = run(&dir, &["dot"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("\"A\" -> \"A.B\";"), "{}", out);
+    assert!(out.contains("\"Old\" -> \"A.B\" [style=dashed"), "{}", out);
+
+    let output = run(&dir, &["mermaid"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("graph LR\n"));
+}
+
+#[test]
+fn import_ue_prints_toml() {
+    let dir = setup("[tags]\npaths = [\"A\"]\n");
+    fs::write(
+        dir.path().join("DefaultGameplayTags.ini"),
+        "[/Script/GameplayTags.GameplayTagsSettings]\n+GameplayTagList=(Tag=\"Ability.Fireball\",DevComment=\"Hot\")\n",
+    )
+    .unwrap();
+
+    let output = run(&dir, &["import-ue", "DefaultGameplayTags.ini"]);
+    assert!(output.status.success());
+    let out = stdout(&output);
+    assert!(out.contains("paths = [\"Ability.Fireball\"]"), "{}", out);
+    assert!(out.contains("display_name = \"Hot\""), "{}", out);
+}
+
+#[test]
+fn merge_lock_merges_and_resolves() {
+    let dir = setup("[tags]\npaths = [\"Item.Sword\"]\n");
+    let lock = |paths: &str| {
+        let config = bevy_tag_build::TagsConfig::from_str(&format!("[tags]\npaths = [{}]", paths));
+        bevy_tag_build::LockFile::from_config(&config.unwrap())
+    };
+    let lock_path = dir.path().join("tags.lock.toml");
+
+    // As a git merge driver: merge the other side into the lock
+    lock(r#""Item.Sword""#).write_to_file(&lock_path).unwrap();
+    lock(r#""Item.Axe""#).write_to_file(dir.path().join("theirs.toml")).unwrap();
+    let output = run(&dir, &["merge-lock", "theirs.toml"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let merged = bevy_tag_build::LockFile::from_file(&lock_path).unwrap();
+    assert!(merged.get("Item.Axe").is_some() && merged.get("Item.Sword").is_some());
+
+    // After a conflicted merge: resolve the markers in place
+    let content = fs::read_to_string(&lock_path).unwrap();
+    let conflicted = content.replace(
+        "]\n",
+        "<<<<<<< HEAD\n=======\n    { path = \"Skill\", depth = 0 },\n>>>>>>> other\n]\n",
+    );
+    fs::write(&lock_path, conflicted).unwrap();
+    assert!(run(&dir, &["merge-lock"]).status.success());
+    let resolved = fs::read_to_string(&lock_path).unwrap();
+    assert!(!resolved.contains("<<<<<<<"));
+    assert!(resolved.contains("    { path = \"Skill\", depth = 0 },"), "{}", resolved);
+
+    // Lock files that disagree on a GID are not merged
+    let mut bad = lock(r#""Item.Axe""#);
+    bad.entries[1].gid = Some(format_gid(42));
+    bad.write_to_file(dir.path().join("theirs.toml")).unwrap();
+    assert!(!run(&dir, &["merge-lock", "theirs.toml"]).status.success());
+}
+
+#[test]
+fn migrations_map_redirected_gids() {
+    let dir =
+        setup("[tags]\npaths = [\"Item.Sword\"]\n[redirects]\n\"Item.Blade\" = \"Item.Sword\"\n");
+    assert!(!run(&dir, &["migrations"]).status.success());
+
+    let config =
+        bevy_tag_build::TagsConfig::from_str("[tags]\npaths = [\"Item.Blade\", \"Item.Sword\"]");
+    bevy_tag_build::LockFile::from_config(&config.unwrap())
+        .write_to_file(dir.path().join("tags.lock.toml"))
+        .unwrap();
+    let output = run(&dir, &["migrations"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let out = stdout(&output);
+    let lines: Vec<&str> = out.lines().filter(|l| !l.starts_with('#')).collect();
+    let blade = format_gid(path_gid("Item.Blade").unwrap());
+    let sword = format_gid(path_gid("Item.Sword").unwrap());
+    assert_eq!(lines, [format!("{} {}  # Item.Blade -> Item.Sword", blade, sword)]);
+}
+
+#[test]
+fn unknown_command_fails() {
+    let dir = setup("[tags]\npaths = [\"A\"]\n");
+    assert!(!run(&dir, &["frobnicate"]).status.success());
+    assert!(!run(&dir, &[]).status.success());
+}
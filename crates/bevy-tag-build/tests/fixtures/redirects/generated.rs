@@ -0,0 +1,56 @@
+// AUTO-GENERATED by bevy-tag-build - DO NOT EDIT
+// Source: tags.toml
+
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Equipment {
+            Weapon {
+                Blade;
+                Bow;
+            }
+        }
+        #[deprecated(note = "This tag is deprecated.")]
+        Item {
+            #[redirect = "Equipment.Weapon.Blade"]
+            Sword;
+        }
+        Legacy {
+            #[redirect = "Equipment.Weapon.Blade"]
+            OldSword;
+        }
+        #[redirect = "Equipment.Weapon.Bow"]
+        OldBow;
+    }
+}
+
+/// Old paths and GIDs of `Tags` mapped to the current ones, built on first use.
+pub fn redirects() -> &'static ::bevy_tag::migrate::RedirectMap {
+    static REDIRECTS: ::std::sync::LazyLock<::bevy_tag::migrate::RedirectMap> =
+        ::std::sync::LazyLock::new(|| {
+            #[allow(unused_mut)]
+            let mut redirects = ::bevy_tag::migrate::RedirectMap::new();
+            redirects.insert_path("Item.Sword", "Equipment.Weapon.Blade");
+            redirects.insert_path("Legacy.OldSword", "Equipment.Weapon.Blade");
+            redirects.insert_path("OldBow", "Equipment.Weapon.Bow");
+            redirects.insert(
+                0x000000000000000000000000001a8b8b,
+                0x4000000000000000003ff9bbecc1b275,
+            );
+            redirects.insert(
+                0x20000000000000000000001138a005f4,
+                0x4000000000000000007518bbecc1b275,
+            );
+            redirects.insert(
+                0x20000000000000000000005a6d41ddd3,
+                0x4000000000000000007518bbecc1b275,
+            );
+            redirects.insert(
+                0x40000000000000000000000000001234,
+                0x4000000000000000003ff9bbecc1b275,
+            );
+            redirects
+        });
+    &REDIRECTS
+}
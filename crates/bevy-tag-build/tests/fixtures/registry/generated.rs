@@ -0,0 +1,72 @@
+// AUTO-GENERATED by bevy-tag-build - DO NOT EDIT
+// Source: tags.toml
+
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Ability {
+            #[channeled = true]
+            #[cooldown = 1.5]
+            #[display_name = "Fireball"]
+            #[mana_cost = 10]
+            #[school = "fire"]
+            Fireball;
+            #[deprecated(note = "Use Ability.Ice")]
+            #[mana_cost = 5]
+            Frost;
+        }
+        Debug {
+            #[cfg(debug_assertions)]
+            #[mana_cost = 0]
+            GodMode;
+        }
+        Status {
+            Burning;
+        }
+    }
+}
+
+/// Runtime registry of every tag in `Tags`, built on first use.
+#[allow(deprecated)]
+pub fn registry() -> &'static ::bevy_tag::NamespaceRegistry {
+    static REGISTRY: ::std::sync::LazyLock<::bevy_tag::NamespaceRegistry> =
+        ::std::sync::LazyLock::new(|| {
+            #[allow(unused_mut)]
+            let mut registry = ::bevy_tag::NamespaceRegistry::build(Tags::DEFINITIONS)
+                .expect("generated tags form a valid registry");
+            registry.set_meta(
+                Tags::Ability::Fireball::GID,
+                "channeled",
+                &Tags::Ability::Fireball::Tag::CHANNELED,
+            );
+            registry.set_meta(
+                Tags::Ability::Fireball::GID,
+                "cooldown",
+                &Tags::Ability::Fireball::Tag::COOLDOWN,
+            );
+            registry.set_meta(
+                Tags::Ability::Fireball::GID,
+                "mana_cost",
+                &Tags::Ability::Fireball::Tag::MANA_COST,
+            );
+            registry.set_meta_raw(
+                Tags::Ability::Fireball::GID,
+                "school",
+                Tags::Ability::Fireball::Tag::SCHOOL.as_bytes().to_vec(),
+            );
+            registry.set_meta(
+                Tags::Ability::Frost::GID,
+                "mana_cost",
+                &Tags::Ability::Frost::Tag::MANA_COST,
+            );
+            #[cfg(debug_assertions)]
+            registry.set_meta(
+                Tags::Debug::GodMode::GID,
+                "mana_cost",
+                &Tags::Debug::GodMode::Tag::MANA_COST,
+            );
+            registry
+        });
+    &REGISTRY
+}
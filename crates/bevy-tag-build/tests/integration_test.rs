@@ -1,6 +1,6 @@
 //! Integration tests for bevy-tag-build.
 
-use bevy_tag_build::{generate_with_lock, GenerateError, LockFile};
+use bevy_tag_build::{generate, generate_with_lock, GenerateError, History, LockFile, TagsConfig};
 use std::fs;
 use tempfile::TempDir;
 
@@ -114,6 +114,70 @@ paths = ["Item.Weapon"]
     }
 }
 
+#[test]
+fn allow_removed_drops_only_listed_paths() {
+    let (dir, config_path) = setup_config(&["Item.Weapon", "Item.Proto.Sword", "Skill.Combat"]);
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    // Removing the allowed subtree passes and drops it from the lock
+    fs::write(
+        &config_path,
+        "allow_removed = [\"Item.Proto\"]\n[tags]\npaths = [\"Item.Weapon\", \"Skill.Combat\"]\n",
+    )
+    .unwrap();
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    let lock = LockFile::from_file(&lock_path).unwrap();
+    assert!(lock.get("Item.Proto").is_none());
+    assert!(lock.get("Item.Proto.Sword").is_none());
+    assert!(!fs::read_to_string(&output_path).unwrap().contains("Proto"));
+
+    // Any other removal is still an error
+    fs::write(
+        &config_path,
+        "allow_removed = [\"Item.Proto\"]\n[tags]\npaths = [\"Item.Weapon\"]\n",
+    )
+    .unwrap();
+    match generate_with_lock(&config_path, &lock_path, &output_path) {
+        Err(GenerateError::LockMismatch(msg)) => assert!(msg.contains("Skill.Combat"), "{}", msg),
+        other => panic!("Expected LockMismatch, got: {:?}", other),
+    }
+}
+
+#[test]
+fn gid_collisions_fail_before_codegen() {
+    // 2000 siblings in an 18-bit level are all but certain to share a segment
+    let names: Vec<String> = (0..2000).map(|i| format!("\"Item.N{}\"", i)).collect();
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", &format!("[tags]\npaths = [{}]\n", names.join(", ")));
+    let output_path = dir.path().join("generated.rs");
+
+    match generate(&config_path, &output_path) {
+        Err(GenerateError::GidCollision(msg)) => {
+            assert!(msg.contains("both hash to GID"), "{}", msg);
+        }
+        other => panic!("Expected GidCollision, got: {:?}", other),
+    }
+    assert!(!output_path.exists());
+}
+
+#[test]
+fn too_deep_paths_fail_before_codegen() {
+    let (dir, config_path) = setup_config(&["A.B.C.D.E.F.G.H", "A.B.C.D.E.F.G.H.I"]);
+    let output_path = dir.path().join("generated.rs");
+
+    match generate(&config_path, &output_path) {
+        Err(GenerateError::ConfigError(e)) => {
+            let msg = e.to_string();
+            assert!(msg.contains("'A.B.C.D.E.F.G.H.I': 9 levels deep"), "{}", msg);
+            assert!(msg.contains("at most 8"), "{}", msg);
+        }
+        other => panic!("Expected ConfigError, got: {:?}", other),
+    }
+    assert!(!output_path.exists());
+}
+
 #[test]
 fn generated_code_has_correct_structure() {
     let (dir, config_path) = setup_config(&["Item.Weapon.Sword", "Item.Armor.Helmet"]);
@@ -358,3 +422,491 @@ paths = ["Item.Weapon"]
     assert!(!active.contains(&"Skill"));
     assert!(!active.contains(&"Skill.Combat"));
 }
+
+#[test]
+fn switching_to_nested_form_keeps_lock_stable() {
+    let (dir, config_path) = setup_config(&["Item.Weapon.Sword", "Item.Weapon.Axe"]);
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    // First build with the flat form
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    let before = LockFile::from_file(&lock_path).unwrap();
+
+    // Same tree, nested form
+    fs::write(
+        &config_path,
+        r#"
+[tags.Item.Weapon]
+children = ["Sword", "Axe"]
+"#,
+    )
+    .unwrap();
+
+    // Second build - no paths removed, GIDs unchanged
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    let after = LockFile::from_file(&lock_path).unwrap();
+
+    assert_eq!(before.get("Item.Weapon.Axe"), after.get("Item.Weapon.Axe"));
+    assert_eq!(before.get("Item.Weapon.Sword"), after.get("Item.Weapon.Sword"));
+}
+
+/// Write `content` to `dir/relative`, creating parent directories.
+fn write_file(dir: &TempDir, relative: &str, content: &str) -> std::path::PathBuf {
+    let path = dir.path().join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn includes_merge_into_one_config() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(
+        &dir,
+        "tags.toml",
+        r#"
+includes = ["tags/combat.toml", "tags/items.toml"]
+
+[tags]
+paths = ["Core"]
+"#,
+    );
+    write_file(
+        &dir,
+        "tags/combat.toml",
+        r#"
+includes = ["status.toml"]
+
+[tags.Combat]
+children = ["Attack", "Block"]
+
+[redirects]
+"Legacy.Hit" = "Combat.Attack"
+"#,
+    );
+    write_file(&dir, "tags/status.toml", "[tags.Status]\nchildren = [\"Stun\"]\n");
+    write_file(&dir, "tags/items.toml", "[tags.Item.Weapon]\nchildren = [\"Sword\"]\n");
+
+    let config = TagsConfig::from_file(&config_path).unwrap();
+
+    let paths: Vec<_> = config.entries().map(|e| e.path.as_str()).collect();
+    for expected in ["Core", "Combat.Attack", "Combat.Block", "Status.Stun", "Item.Weapon.Sword"] {
+        assert!(paths.contains(&expected), "missing {}", expected);
+    }
+    assert_eq!(config.redirects().count(), 1);
+    assert_eq!(config.sources().count(), 4);
+}
+
+#[test]
+fn shared_parents_across_files_are_not_duplicates() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "includes = [\"a.toml\", \"b.toml\"]\n");
+    write_file(&dir, "a.toml", "[tags.Item.Weapon]\nchildren = [\"Sword\"]\n");
+    write_file(&dir, "b.toml", "[tags.Item.Armor]\nchildren = [\"Helm\"]\n");
+
+    let config = TagsConfig::from_file(&config_path).unwrap();
+    assert!(config.entries().any(|e| e.path == "Item.Armor.Helm"));
+}
+
+#[test]
+fn cross_file_duplicate_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "includes = [\"a.toml\", \"b.toml\"]\n");
+    write_file(&dir, "a.toml", "[tags]\npaths = [\"Item.Sword\"]\n");
+    write_file(&dir, "b.toml", "[tags.Item]\nchildren = [\"Sword\"]\n");
+
+    let err = TagsConfig::from_file(&config_path).unwrap_err().to_string();
+    assert!(err.contains("Item.Sword"), "{}", err);
+    assert!(err.contains("a.toml") && err.contains("b.toml"), "{}", err);
+}
+
+#[test]
+fn cross_file_duplicate_redirect_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(
+        &dir,
+        "tags.toml",
+        "includes = [\"a.toml\"]\n[tags]\npaths = [\"A\", \"B\"]\n[redirects]\n\"Old\" = \"A\"\n",
+    );
+    write_file(&dir, "a.toml", "[redirects]\n\"Old\" = \"B\"\n");
+
+    let err = TagsConfig::from_file(&config_path).unwrap_err().to_string();
+    assert!(err.contains("Redirect 'Old'"), "{}", err);
+}
+
+#[test]
+fn include_cycle_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "includes = [\"a.toml\"]\n");
+    write_file(&dir, "a.toml", "includes = [\"tags.toml\"]\n");
+
+    let err = TagsConfig::from_file(&config_path).unwrap_err().to_string();
+    assert!(err.contains("Include cycle"), "{}", err);
+}
+
+#[test]
+fn validation_errors_point_at_the_source() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "includes = [\"a.toml\"]\n");
+    write_file(&dir, "a.toml", "[tags.Ability]\nchildren = [\"Heal\", \"fire-ball\"]\n");
+
+    let err = TagsConfig::from_file(&config_path).unwrap_err().to_string();
+    let location = format!("--> {}:2:21", dir.path().canonicalize().unwrap().join("a.toml").display());
+    assert!(err.contains(&location), "{}", err);
+    assert!(err.ends_with("= help: rename the segment to 'FireBall'"), "{}", err);
+
+    write_file(&dir, "a.toml", "[tags]\npaths = [\"Item.Sword\"]\n[redirects]\n\"Old\" = \"Item.Swrod\"\n");
+    let err = TagsConfig::from_file(&config_path).unwrap_err().to_string();
+    assert!(err.contains("a.toml:4:9"), "{}", err);
+    assert!(err.ends_with("= help: did you mean 'Item.Sword'?"), "{}", err);
+}
+
+#[test]
+fn included_file_cannot_set_root_options() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "includes = [\"a.toml\"]\n");
+    write_file(&dir, "a.toml", "on_remove = \"warn\"\n[tags]\npaths = [\"A\"]\n");
+
+    let err = TagsConfig::from_file(&config_path).unwrap_err().to_string();
+    assert!(err.contains("root config"), "{}", err);
+}
+
+#[test]
+fn discover_finds_per_crate_tag_files() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "discover = [\"crates\"]\n");
+    write_file(&dir, "crates/combat/tags.toml", "[tags.Combat]\nchildren = [\"Attack\"]\n");
+    write_file(&dir, "crates/items/tags.toml", "[tags.Item]\nchildren = [\"Sword\"]\n");
+    fs::create_dir_all(dir.path().join("crates/no_tags")).unwrap();
+
+    let config = TagsConfig::from_file(&config_path).unwrap();
+
+    assert!(config.entries().any(|e| e.path == "Combat.Attack"));
+    assert!(config.entries().any(|e| e.path == "Item.Sword"));
+    assert_eq!(config.sources().count(), 3);
+}
+
+#[test]
+fn deprecation_notes_apply_to_removed_paths() {
+    let (dir, config_path) = setup_config_with_on_remove(&["Item.Weapon", "Item.Sword"], "warn");
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    // Remove Item.Sword but keep a note for it
+    fs::write(
+        &config_path,
+        r#"
+on_remove = "warn"
+
+[tags]
+paths = ["Item.Weapon"]
+
+[deprecated]
+"Item.Sword" = "Use Item.Weapon instead"
+"#,
+    )
+    .unwrap();
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    let code = fs::read_to_string(&output_path).unwrap();
+    assert!(code.contains("#[deprecated(note = \"Use Item.Weapon instead\")]"));
+}
+
+#[test]
+fn deprecation_of_unknown_path_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(
+        &dir,
+        "tags.toml",
+        "[tags]\npaths = [\"A\"]\n[deprecated]\n\"Nope\" = \"never existed\"\n",
+    );
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    let result = generate_with_lock(&config_path, &lock_path, &output_path);
+    assert!(matches!(result, Err(GenerateError::ConfigError(_))));
+}
+
+#[test]
+fn rename_is_suggested_in_mismatch_error() {
+    let (dir, config_path) = setup_config(&["Item.Sword", "Item.Axe"]);
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    fs::write(
+        &config_path,
+        "[tags]\npaths = [\"Item.Axe\", \"Equipment.Weapon.Blade\"]\n",
+    )
+    .unwrap();
+
+    let Err(GenerateError::LockMismatch(msg)) =
+        generate_with_lock(&config_path, &lock_path, &output_path)
+    else {
+        panic!("expected a lock mismatch");
+    };
+    assert!(msg.contains("\"Item.Sword\" = \"Equipment.Weapon.Blade\""), "{}", msg);
+}
+
+#[test]
+fn auto_redirect_applies_detected_renames() {
+    let (dir, config_path) = setup_config_with_on_remove(&["Item.Sword", "Item.Axe"], "auto-redirect");
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    fs::write(
+        &config_path,
+        r#"
+on_remove = "auto-redirect"
+
+[tags]
+paths = ["Item.Axe", "Equipment.Weapon.Sword"]
+"#,
+    )
+    .unwrap();
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    let lock = LockFile::from_file(&lock_path).unwrap();
+    let sword = lock.get("Item.Sword").unwrap();
+    assert!(sword.deprecated);
+    assert_eq!(sword.equivalent_to.as_deref(), Some("Equipment.Weapon.Sword"));
+
+    let code = fs::read_to_string(&output_path).unwrap();
+    assert!(code.contains("#[redirect = \"Equipment.Weapon.Sword\"]\n            Sword;"));
+}
+
+#[test]
+fn changed_gid_in_lock_is_rejected() {
+    let (dir, config_path) = setup_config(&["Item.Weapon"]);
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    // Simulate a hash/layout change by corrupting one recorded GID
+    let mut lock = LockFile::from_file(&lock_path).unwrap();
+    let weapon = lock.entries.iter_mut().find(|e| e.path == "Item.Weapon").unwrap();
+    weapon.gid = Some("0x00000000000000000000000000000001".into());
+    lock.write_to_file(&lock_path).unwrap();
+
+    let Err(GenerateError::GidMismatch(msg)) =
+        generate_with_lock(&config_path, &lock_path, &output_path)
+    else {
+        panic!("expected a GID mismatch");
+    };
+    assert!(msg.contains("Item.Weapon"), "{}", msg);
+}
+
+#[test]
+fn lock_without_gids_is_upgraded() {
+    let (dir, config_path) = setup_config(&["Item.Weapon"]);
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    fs::write(
+        &lock_path,
+        r#"
+schema_version = 1
+generated_at = "2025-01-01T00:00:00Z"
+
+[[entries]]
+path = "Item"
+depth = 0
+
+[[entries]]
+path = "Item.Weapon"
+depth = 1
+parent = "Item"
+"#,
+    )
+    .unwrap();
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    let lock = LockFile::from_file(&lock_path).unwrap();
+    assert!(lock.gid_scheme.is_some());
+    assert!(lock.entries.iter().all(|e| e.gid.is_some()));
+}
+
+#[test]
+fn manifest_is_written_next_to_output() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(
+        &dir,
+        "tags.toml",
+        "[codegen]\nmanifest = true\n\n[tags]\npaths = [\"Item.Weapon\"]\n",
+    );
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("src/generated.rs");
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    let manifest = fs::read_to_string(dir.path().join("src/tags_manifest.json")).unwrap();
+    assert!(manifest.contains("\"path\": \"Item.Weapon\""), "{}", manifest);
+}
+
+#[test]
+fn c_header_is_written_next_to_output() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(
+        &dir,
+        "tags.toml",
+        "[codegen]\nc_header = true\n\n[tags]\npaths = [\"Item.Weapon.Sword\", \"Item.Potion\"]\n",
+    );
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    let header = fs::read_to_string(dir.path().join("tags.h")).unwrap();
+    let gid = bevy_tag_build::path_gid("Item.Weapon.Sword").unwrap();
+    assert!(header.contains(&format!("#define TAGS_Item_Weapon_Sword_LO 0x{:016x}ULL", gid as u64)));
+    assert!(header.contains("bevy_tag_is_descendant_of"));
+}
+
+#[test]
+fn typescript_module_is_written_next_to_output() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(
+        &dir,
+        "tags.toml",
+        "[codegen]\ntypescript = true\n\n[tags]\npaths = [\"Item.Weapon\"]\n",
+    );
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    let ts = fs::read_to_string(dir.path().join("tags.ts")).unwrap();
+    assert!(ts.contains("export const Tags = {"), "{}", ts);
+    assert!(ts.contains("  \"Item.Weapon\": \"Item\","), "{}", ts);
+}
+
+#[test]
+fn markdown_is_written_next_to_config() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(
+        &dir,
+        "config/tags.toml",
+        "[codegen]\nmarkdown = true\n\n[tags]\npaths = [\"Item.Weapon\"]\n\n[tags.meta.\"Item.Weapon\"]\ndisplay_name = \"Weapon\"\n",
+    );
+    let lock_path = dir.path().join("config/tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    let md = fs::read_to_string(dir.path().join("config/TAGS.md")).unwrap();
+    assert!(md.contains("| `Item.Weapon` | 1 |"), "{}", md);
+    assert!(md.contains("| Weapon |"), "{}", md);
+
+    // The macro accepts display_name from the config
+    let code = fs::read_to_string(&output_path).unwrap();
+    assert!(code.contains("#[display_name = \"Weapon\"]"), "{}", code);
+}
+
+#[test]
+fn config_from_ue_ini() {
+    let dir = TempDir::new().unwrap();
+    let ini_path = write_file(
+        &dir,
+        "Config/DefaultGameplayTags.ini",
+        "[/Script/GameplayTags.GameplayTagsSettings]\n+GameplayTagList=(Tag=\"Ability.Fireball\",DevComment=\"Hot\")\n",
+    );
+
+    let config = TagsConfig::from_ue_ini(&ini_path).unwrap();
+    assert!(config.entries().any(|e| e.path == "Ability.Fireball"));
+    assert_eq!(config.display_name_of("Ability.Fireball"), Some("Hot"));
+    assert_eq!(config.sources().collect::<Vec<_>>(), [ini_path.as_path()]);
+}
+
+#[test]
+fn hand_edited_lock_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "[tags]\npaths = [\"Item.Weapon\", \"Skill\"]\n");
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+
+    // Deleting a line would otherwise just re-add the entry
+    let lock = fs::read_to_string(&lock_path).unwrap();
+    let skill = lock.lines().find(|l| l.contains("path = \"Skill\"")).unwrap();
+    fs::write(&lock_path, lock.replace(&format!("{}\n", skill), "")).unwrap();
+
+    let err = generate_with_lock(&config_path, &lock_path, &output_path).unwrap_err();
+    assert!(err.to_string().contains("edited by hand"), "{}", err);
+}
+
+#[test]
+fn unchanged_output_is_not_rewritten() {
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "[tags]\npaths = [\"Item.Weapon\"]\n");
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    let modified = |path: &std::path::Path| fs::metadata(path).unwrap().modified().unwrap();
+    let (output_mtime, lock_mtime) = (modified(&output_path), modified(&lock_path));
+    std::thread::sleep(std::time::Duration::from_millis(20));
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    assert_eq!(modified(&output_path), output_mtime);
+    assert_eq!(modified(&lock_path), lock_mtime);
+
+    // A new tag rewrites both
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    write_file(&dir, "tags.toml", "[tags]\npaths = [\"Item.Weapon\", \"Item.Armor\"]\n");
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    assert_ne!(modified(&output_path), output_mtime);
+    assert_ne!(modified(&lock_path), lock_mtime);
+}
+
+#[test]
+fn generated_code_has_no_inner_attributes() {
+    // Inner attributes would break `include!` (and `bevy_tag::include_tags!`)
+    let dir = TempDir::new().unwrap();
+    let config_path = write_file(&dir, "tags.toml", "[tags]\npaths = [\"Item.Weapon\"]\n");
+    let output_path = dir.path().join("bevy_tags.rs");
+
+    generate(&config_path, &output_path).unwrap();
+
+    let code = fs::read_to_string(&output_path).unwrap();
+    assert!(!code.contains("#!["), "{}", code);
+}
+
+#[test]
+fn history_records_builds_that_change_tags() {
+    let dir = TempDir::new().unwrap();
+    let config = |paths: &str| {
+        format!("on_remove = \"warn\"\n[codegen]\nhistory = true\n[tags]\npaths = [{}]\n", paths)
+    };
+    let config_path = write_file(&dir, "tags.toml", &config(r#""Item.Sword", "Item.Axe""#));
+    let lock_path = dir.path().join("tags.lock.toml");
+    let output_path = dir.path().join("generated.rs");
+    let history_path = dir.path().join("tags.history.toml");
+
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    let first = fs::read_to_string(&history_path).unwrap();
+    assert!(first.contains("added = [\n    \"Item\",\n    \"Item.Axe\",\n"), "{}", first);
+
+    // Unchanged builds add nothing
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    assert_eq!(fs::read_to_string(&history_path).unwrap(), first);
+
+    write_file(&dir, "tags.toml", &config(r#""Item.Sword", "Item.Shield""#));
+    generate_with_lock(&config_path, &lock_path, &output_path).unwrap();
+    let history = History::from_file(&history_path).unwrap();
+    assert_eq!(history.changes.len(), 2);
+    assert_eq!(history.changes[1].added, ["Item.Shield"]);
+    assert_eq!(history.changes[1].deprecated, ["Item.Axe"]);
+    assert_eq!(history.added_on("Item.Shield"), Some(history.changes[1].date.as_str()));
+    assert!(fs::read_to_string(&history_path).unwrap().starts_with(&first));
+}
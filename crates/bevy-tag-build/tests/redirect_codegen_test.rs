@@ -0,0 +1,78 @@
+//! Redirects from `tags.toml` and lock aliases compile to `Redirect<Target>`
+//! modules that share the target's GID, and `[codegen] redirects = true`
+//! collects them (plus stale lock GIDs) into one runtime `RedirectMap`.
+//!
+//! `fixtures/redirects/generated.rs` is the checked-in output of the fixture
+//! config; `generated_code_matches_fixture` keeps it in sync. It is pulled
+//! in with `include!`, as `bevy_tag::include_tags!` does.
+
+#[allow(deprecated)]
+mod generated {
+    include!("fixtures/redirects/generated.rs");
+}
+
+use bevy_tag::{hierarchical_gid, NamespaceRegistry, NamespaceTag};
+use bevy_tag_build::{generate_namespace_code_from_lock, LockFile, TagsConfig};
+use generated::{redirects, Tags};
+
+const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/redirects");
+
+#[test]
+fn generated_code_matches_fixture() {
+    let config = TagsConfig::from_file(format!("{}/tags.toml", FIXTURE_DIR)).unwrap();
+    let lock = LockFile::from_file(format!("{}/tags.lock.toml", FIXTURE_DIR)).unwrap();
+
+    let code = generate_namespace_code_from_lock(&config, &lock);
+
+    assert_eq!(code, include_str!("fixtures/redirects/generated.rs"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn config_redirects_share_target_gid() {
+    assert_eq!(Tags::Legacy::OldSword::GID, Tags::Equipment::Weapon::Blade::GID);
+    assert_eq!(Tags::OldBow::GID, Tags::Equipment::Weapon::Bow::GID);
+    assert_eq!(
+        <Tags::Legacy::OldSword::Tag as NamespaceTag>::PATH,
+        "Equipment.Weapon.Blade"
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn lock_aliases_share_target_gid() {
+    assert_eq!(Tags::Item::Sword::GID, Tags::Equipment::Weapon::Blade::GID);
+    assert_eq!(
+        <Tags::Item::Sword::Tag as NamespaceTag>::GID,
+        <Tags::Equipment::Weapon::Blade::Tag as NamespaceTag>::GID
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn redirect_map_covers_paths_and_gids() {
+    let map = redirects();
+    assert!(std::ptr::eq(map, redirects()));
+    assert_eq!(
+        map.get_path("Legacy.OldSword"),
+        Some("Equipment.Weapon.Blade")
+    );
+    assert_eq!(map.get_path("Item.Sword"), Some("Equipment.Weapon.Blade"));
+    assert_eq!(map.get_path("Equipment.Weapon.Blade"), None);
+
+    // Saves written before the rename hold the old path's own hash
+    let old_sword = hierarchical_gid(&[b"Legacy", b"OldSword"]);
+    assert_eq!(map.resolve(old_sword), Tags::Equipment::Weapon::Blade::GID);
+    // Stale GIDs recorded in the lock map to the current hash
+    assert_eq!(
+        map.resolve(0x4000_0000_0000_0000_0000_0000_0000_1234),
+        Tags::Equipment::Weapon::Bow::GID
+    );
+
+    let registry = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    assert_eq!(
+        registry.gid_of_redirected("OldBow", map),
+        Some(Tags::Equipment::Weapon::Bow::GID)
+    );
+    assert_eq!(registry.verify_redirects(map), Ok(()));
+}
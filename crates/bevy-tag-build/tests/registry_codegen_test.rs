@@ -0,0 +1,48 @@
+//! `[codegen] registry = true` emits a `registry()` function backed by a
+//! `LazyLock`, with `[tags.meta]` values stored as registry metadata.
+//!
+//! `fixtures/registry/generated.rs` is the checked-in output of the fixture
+//! config; `generated_code_matches_fixture` keeps it in sync.
+
+mod generated {
+    include!("fixtures/registry/generated.rs");
+}
+
+use bevy_tag_build::{generate_namespace_code, TagsConfig};
+use generated::{registry, Tags};
+
+const FIXTURE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/registry");
+
+#[test]
+fn generated_code_matches_fixture() {
+    let config = TagsConfig::from_file(format!("{}/tags.toml", FIXTURE_DIR)).unwrap();
+
+    let code = generate_namespace_code(&config);
+
+    assert_eq!(code, include_str!("fixtures/registry/generated.rs"));
+}
+
+#[test]
+fn registry_is_built_once() {
+    assert!(std::ptr::eq(registry(), registry()));
+    assert_eq!(registry().len(), Tags::NODE_COUNT);
+    assert_eq!(
+        registry().display_name_of(Tags::Ability::Fireball::GID),
+        Some("Fireball")
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn metadata_is_prepopulated() {
+    let reg = registry();
+    let fireball = Tags::Ability::Fireball::GID;
+
+    assert_eq!(reg.get_meta::<i32>(fireball, "mana_cost"), Some(&10));
+    assert_eq!(reg.get_meta::<f64>(fireball, "cooldown"), Some(&1.5));
+    assert_eq!(reg.get_meta_raw(fireball, "channeled"), Some(&[1u8][..]));
+    assert_eq!(reg.get_meta_raw(fireball, "school"), Some(&b"fire"[..]));
+    assert_eq!(reg.get_meta::<i32>(Tags::Ability::Frost::GID, "mana_cost"), Some(&5));
+    // UI fields are registry fields, not metadata
+    assert!(!reg.has_meta(fireball, "display_name"));
+}
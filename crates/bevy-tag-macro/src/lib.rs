@@ -1,6 +1,7 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::quote;
+use quote::{format_ident, quote, ToTokens};
+use std::collections::HashMap;
 use syn::parse::{Parse, ParseStream};
 use syn::{braced, token, Expr, Ident, Result, Token, Type, Visibility};
 
@@ -9,6 +10,13 @@ use proc_macro_crate::{crate_name, FoundCrate};
 /// Maximum supported tree depth (0-7, encoded in 3 bits).
 const MAX_DEPTH: usize = 8;
 
+/// Segment bit widths per level (mirrors `bevy_tag::LEVEL_WIDTHS`).
+const LEVEL_WIDTHS: [u8; MAX_DEPTH] = [21, 18, 16, 16, 14, 14, 13, 13];
+
+/// Occupancy (% of a level's hash space used by one sibling group) above
+/// which the macro emits a compile-time warning.
+const OCCUPANCY_WARN_PERCENT: f64 = 25.0;
+
 /// Metadata attribute: #[key = value]
 #[derive(Clone)]
 struct MetaAttr {
@@ -16,15 +24,73 @@ struct MetaAttr {
     value: Expr,
 }
 
-/// Deprecation attribute: #[deprecated(note = "...")]
+/// Deprecation attribute: #[deprecated(since = "...", note = "...")]
 #[derive(Clone, Default)]
 struct DeprecationAttr {
     /// Whether the node is deprecated
     is_deprecated: bool,
+    /// Optional version the node was deprecated in
+    since: Option<String>,
     /// Optional deprecation note
     note: Option<String>,
 }
 
+impl DeprecationAttr {
+    /// Build the `#[deprecated(...)]` attribute.
+    ///
+    /// For redirects the replacement is always named in the note, so the
+    /// compiler warning tells users where the tag went.
+    fn to_attr(&self, redirect: Option<&RedirectTarget>) -> TokenStream2 {
+        if !self.is_deprecated && redirect.is_none() {
+            return quote! {};
+        }
+
+        let note = match (redirect, &self.note) {
+            (Some(target), Some(note)) => {
+                Some(format!("{} (redirected to {})", note, target.describe()))
+            }
+            (Some(target), None) => Some(format!("redirected to {}", target.describe())),
+            (None, note) => note.clone(),
+        };
+
+        let since = self.since.as_ref().map(|since| {
+            let lit = syn::LitStr::new(since, Span::call_site());
+            quote!(since = #lit)
+        });
+        let note = note.map(|note| {
+            let lit = syn::LitStr::new(&note, Span::call_site());
+            quote!(note = #lit)
+        });
+
+        match (since, note) {
+            (None, None) => quote! { #[deprecated] },
+            (Some(since), None) => quote! { #[deprecated(#since)] },
+            (None, Some(note)) => quote! { #[deprecated(#note)] },
+            (Some(since), Some(note)) => quote! { #[deprecated(#since, #note)] },
+        }
+    }
+}
+
+/// Where a redirect node points.
+#[derive(Clone)]
+enum RedirectTarget {
+    /// Dot path within the same invocation (`#[redirect = "Path.To.Target"]`)
+    Path(syn::LitStr),
+    /// Any `NamespaceTag` type, e.g. from another crate
+    /// (`#[redirect(type = other_crate::Tags::Path::Tag)]`)
+    Type(Box<Type>),
+}
+
+impl RedirectTarget {
+    /// Human-readable target for deprecation notes.
+    fn describe(&self) -> String {
+        match self {
+            RedirectTarget::Path(path) => path.value(),
+            RedirectTarget::Type(ty) => quote!(#ty).to_string().replace(' ', ""),
+        }
+    }
+}
+
 /// Parsed attributes for a node.
 #[derive(Clone, Default)]
 struct NodeAttrs {
@@ -32,8 +98,48 @@ struct NodeAttrs {
     meta: Vec<MetaAttr>,
     /// Deprecation attribute (#[deprecated] or #[deprecated(note = "...")])
     deprecation: DeprecationAttr,
-    /// Redirect target path (#[redirect = "Path.To.Target"])
-    redirect_to: Option<String>,
+    /// Redirect target (#[redirect = "Path.To.Target"] or #[redirect(type = ...)])
+    redirect_to: Option<RedirectTarget>,
+    /// `redirect_to` came from #[alias = ...]: a permanent second name that
+    /// keeps its own path, rather than a deprecated redirect
+    is_alias: bool,
+    /// UI display name (#[display_name = "Heavy Attack"])
+    display_name: Option<String>,
+    /// Free-form description (#[description = "..."], or the doc comments
+    /// with a bare #[description])
+    description: Option<String>,
+    /// Localization key (#[loc_key = "tag-combat-attack"]); derived from the path if absent
+    loc_key: Option<String>,
+    /// Category flags (#[flags(Cosmetic | Replicated | crate::MY_FLAG)])
+    flags: Vec<syn::Path>,
+    /// Explicitly pinned full GID (#[gid = 0x...])
+    pinned_gid: Option<Expr>,
+    /// Explicitly pinned segment value for this node's level (#[segment = 0x...])
+    pinned_segment: Option<Expr>,
+    /// Compile-time only: keep the type and GID but leave the node (and its
+    /// subtree) out of `DEFINITIONS` and the collision table (#[no_registry])
+    no_registry: bool,
+    /// Generate a marker + default-data `TagBundle` and `spawn` helper (#[bundle])
+    bundle: Option<Span>,
+    /// Conditional compilation predicates (#[cfg(...)]); after `propagate_cfg`
+    /// this also holds every ancestor's, since flat tables live at the root
+    cfg: Vec<TokenStream2>,
+    /// External `DEFINITIONS` tables mounted under the node
+    /// (`include_defs!(path);` in its body)
+    mounts: Vec<syn::Path>,
+}
+
+impl NodeAttrs {
+    /// Whether this node's GID is pinned rather than hashed from its path.
+    fn is_pinned(&self) -> bool {
+        self.pinned_gid.is_some() || self.pinned_segment.is_some()
+    }
+
+    /// `#[cfg(...)]` attributes gating this node, empty if it is unconditional.
+    fn cfg_attrs(&self) -> TokenStream2 {
+        let predicates = &self.cfg;
+        quote! { #(#[cfg(#predicates)])* }
+    }
 }
 
 struct Node {
@@ -45,7 +151,87 @@ struct Node {
     children: Vec<Node>,
 }
 
+/// Options given before the module: `namespace!(bevy_components; pub mod Tags { ... })`.
+#[derive(Default)]
+struct MacroOptions {
+    /// Derive Bevy `Component` + `Reflect` on every `Tag`
+    bevy_components: bool,
+    /// Path to bevy-tag used by generated code (`crate = my_engine::tags`)
+    crate_path: Option<syn::Path>,
+    /// Extra derives for every `Tag` (`derive(PartialOrd, Ord)`)
+    derives: Vec<syn::Path>,
+}
+
+impl MacroOptions {
+    /// Parse a single option key.
+    fn parse_option(&mut self, input: ParseStream) -> Result<()> {
+        if input.peek(Token![crate]) {
+            // crate = path::to::bevy_tag
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            self.crate_path = Some(input.parse()?);
+            return Ok(());
+        }
+
+        let key: Ident = input.parse()?;
+        if key == "bevy_components" {
+            self.bevy_components = true;
+            Ok(())
+        } else if key == "derive" {
+            // derive(Trait, path::to::Trait)
+            let content;
+            syn::parenthesized!(content in input);
+            let derives = content.parse_terminated(syn::Path::parse_mod_style, Token![,])?;
+            self.derives.extend(derives);
+            Ok(())
+        } else {
+            Err(syn::Error::new(
+                key.span(),
+                format!("unknown namespace option '{}'", key),
+            ))
+        }
+    }
+
+    /// Parse attribute arguments: `#[namespace_mod(bevy_components)]`.
+    fn parse_attr_args(input: ParseStream) -> Result<Self> {
+        let mut options = Self::default();
+        while !input.is_empty() {
+            options.parse_option(input)?;
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+        Ok(options)
+    }
+}
+
+impl Parse for MacroOptions {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut options = Self::default();
+
+        // No options: input starts directly with the module declaration
+        if input.peek(Token![pub]) || input.peek(Token![mod]) || input.peek(Token![#]) {
+            return Ok(options);
+        }
+
+        loop {
+            options.parse_option(input)?;
+
+            if input.peek(Token![;]) {
+                input.parse::<Token![;]>()?;
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(options)
+    }
+}
+
 struct NamespaceInput {
+    options: MacroOptions,
+    /// Outer attributes of the root module (attribute form only)
+    root_attrs: Vec<syn::Attribute>,
     vis: Visibility,
     root: Ident,
     nodes: Vec<Node>,
@@ -53,21 +239,265 @@ struct NamespaceInput {
 
 impl Parse for NamespaceInput {
     fn parse(input: ParseStream) -> Result<Self> {
+        let options: MacroOptions = input.parse()?;
+        let root_attrs = input.call(syn::Attribute::parse_outer)?;
         let vis: Visibility = input.parse()?;
         input.parse::<Token![mod]>()?;
         let root: Ident = input.parse()?;
         let content;
         braced!(content in input);
-        let nodes = parse_nodes(&content)?;
-        Ok(Self { vis, root, nodes })
+        let (mut nodes, mounts) = parse_body(&content)?;
+        reject_root_mounts(&mounts)?;
+        validate_root(&nodes)?;
+        resolve_redirects(&mut nodes)?;
+        propagate_cfg(&mut nodes, &[]);
+        Ok(Self {
+            options,
+            root_attrs,
+            vis,
+            root,
+            nodes,
+        })
+    }
+}
+
+impl NamespaceInput {
+    /// Build the input from a real module tree (`#[namespace_mod] pub mod Tags { ... }`).
+    fn from_item_mod(options: MacroOptions, module: syn::ItemMod) -> Result<Self> {
+        let Some((_, ref items)) = module.content else {
+            return Err(syn::Error::new_spanned(
+                &module,
+                "#[namespace_mod] requires an inline module: `mod Tags { ... }`",
+            ));
+        };
+        let (mut nodes, mounts) = nodes_from_mod_items(items)?;
+        reject_root_mounts(&mounts)?;
+        validate_root(&nodes)?;
+        resolve_redirects(&mut nodes)?;
+        propagate_cfg(&mut nodes, &[]);
+        Ok(Self {
+            options,
+            root_attrs: module.attrs,
+            vis: module.vis,
+            root: module.ident,
+            nodes,
+        })
+    }
+}
+
+/// Prepend each node's inherited `#[cfg]` predicates to its own.
+///
+/// Modules nest, so the inherited ones are redundant there, but the flat
+/// root tables (`DEFINITIONS`, `PATH_TABLE`, ...) need the full condition.
+fn propagate_cfg(nodes: &mut [Node], inherited: &[TokenStream2]) {
+    for node in nodes {
+        let mut cfg = inherited.to_vec();
+        cfg.append(&mut node.attrs.cfg);
+        node.attrs.cfg = cfg;
+        propagate_cfg(&mut node.children, &node.attrs.cfg);
+    }
+}
+
+/// `include_defs!` is only meaningful under a node, which gives the prefix.
+fn reject_root_mounts(mounts: &[syn::Path]) -> Result<()> {
+    match mounts.first() {
+        Some(path) => Err(syn::Error::new_spanned(
+            path,
+            "include_defs! must be inside a node, which becomes the mount point",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Convert the nested `mod` items of the attribute form into nodes, and
+/// collect `include_defs!(...)` mounts.
+fn nodes_from_mod_items(items: &[syn::Item]) -> Result<(Vec<Node>, Vec<syn::Path>)> {
+    let mut nodes = Vec::new();
+    let mut mounts = Vec::new();
+    for item in items {
+        match item {
+            syn::Item::Mod(module) => nodes.push(node_from_mod(module)?),
+            syn::Item::Macro(item) if item.mac.path.is_ident("include_defs") => {
+                mounts.push(item.mac.parse_body()?);
+            }
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "#[namespace_mod] modules may only contain nested `mod` items \
+                     and `include_defs!(...)`",
+                ))
+            }
+        }
+    }
+    validate_siblings(&nodes)?;
+    Ok((nodes, mounts))
+}
+
+/// Convert one `mod Name { ... }` into a node.
+///
+/// Node attributes are the same as in `namespace!`; the data type is given
+/// with `#[data(Type)]` instead of `Name<Type>`.
+fn node_from_mod(module: &syn::ItemMod) -> Result<Node> {
+    let Some((_, ref items)) = module.content else {
+        return Err(syn::Error::new_spanned(
+            module,
+            format!("namespace node must be inline: write `mod {} {{}}`", module.ident),
+        ));
+    };
+
+    let mut data_type = None;
+    let mut node_attrs = Vec::new();
+    for attr in &module.attrs {
+        if attr.path().is_ident("data") {
+            data_type = Some(attr.parse_args::<Type>()?);
+        } else {
+            node_attrs.push(attr);
+        }
+    }
+
+    let mut attrs = syn::parse::Parser::parse2(parse_all_attrs, quote!(#(#node_attrs)*))?;
+    let (children, mounts) = nodes_from_mod_items(items)?;
+    attrs.mounts = mounts;
+    validate_attrs(&module.ident, &attrs)?;
+
+    Ok(Node {
+        name: module.ident.clone(),
+        attrs,
+        data_type,
+        children,
+    })
+}
+
+/// Reject attribute combinations that can't be generated.
+fn validate_attrs(name: &Ident, attrs: &NodeAttrs) -> Result<()> {
+    if attrs.pinned_gid.is_some() && attrs.pinned_segment.is_some() {
+        return Err(syn::Error::new(
+            name.span(),
+            format!("'{}' cannot have both #[gid] and #[segment]", name),
+        ));
+    }
+    if attrs.redirect_to.is_some() && !attrs.mounts.is_empty() {
+        return Err(syn::Error::new(
+            name.span(),
+            format!("'{}' is a redirect and cannot mount include_defs!", name),
+        ));
+    }
+    if attrs.redirect_to.is_some() && attrs.is_pinned() {
+        return Err(syn::Error::new(
+            name.span(),
+            format!(
+                "'{}' is {} and cannot pin its own GID",
+                name,
+                if attrs.is_alias { "an alias" } else { "a redirect" }
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// Record every node path and its (unresolved) redirect target.
+fn collect_redirect_table(
+    nodes: &[Node],
+    prefix: &str,
+    out: &mut HashMap<String, Option<RedirectTarget>>,
+) {
+    for node in nodes {
+        let path = if prefix.is_empty() {
+            node.name.to_string()
+        } else {
+            format!("{}.{}", prefix, node.name)
+        };
+        out.insert(path.clone(), node.attrs.redirect_to.clone());
+        collect_redirect_table(&node.children, &path, out);
+    }
+}
+
+/// Follow a redirect to its final, non-redirect target.
+///
+/// Errors (spanned on the offending `#[redirect]`) if a target in the chain
+/// doesn't exist in this invocation or the chain loops back on itself.
+fn resolve_redirect_chain(
+    start: &str,
+    target: &RedirectTarget,
+    table: &HashMap<String, Option<RedirectTarget>>,
+) -> Result<RedirectTarget> {
+    let mut chain = vec![start.to_string()];
+    let mut current = target.clone();
+
+    while let RedirectTarget::Path(ref lit) = current {
+        let path = lit.value();
+        let Some(next) = table.get(&path) else {
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("redirect target '{}' (from '{}') does not exist", path, start),
+            ));
+        };
+        if chain.contains(&path) {
+            chain.push(path);
+            return Err(syn::Error::new(
+                lit.span(),
+                format!("redirect cycle: {}", chain.join(" -> ")),
+            ));
+        }
+        chain.push(path);
+
+        match next {
+            Some(next) => current = next.clone(),
+            None => break,
+        }
+    }
+
+    Ok(current)
+}
+
+/// Validate every `#[redirect]` and rewrite chains (A -> B -> C) to point
+/// directly at their final target, so generated types are never nested
+/// `Redirect<Redirect<..>>`.
+fn resolve_redirects(nodes: &mut [Node]) -> Result<()> {
+    let mut table = HashMap::new();
+    collect_redirect_table(nodes, "", &mut table);
+    resolve_redirects_in(nodes, "", &table)
+}
+
+fn resolve_redirects_in(
+    nodes: &mut [Node],
+    prefix: &str,
+    table: &HashMap<String, Option<RedirectTarget>>,
+) -> Result<()> {
+    for node in nodes {
+        let path = if prefix.is_empty() {
+            node.name.to_string()
+        } else {
+            format!("{}.{}", prefix, node.name)
+        };
+        if let Some(ref target) = node.attrs.redirect_to {
+            node.attrs.redirect_to = Some(resolve_redirect_chain(&path, target, table)?);
+        }
+        resolve_redirects_in(&mut node.children, &path, table)?;
     }
+    Ok(())
 }
 
-fn parse_nodes(input: ParseStream) -> Result<Vec<Node>> {
+/// Parse a node body: child nodes and `include_defs!(path);` mounts.
+fn parse_body(input: ParseStream) -> Result<(Vec<Node>, Vec<syn::Path>)> {
     let mut nodes = Vec::new();
+    let mut mounts = Vec::new();
     while !input.is_empty() {
+        if input.peek(Ident) && input.peek2(Token![!]) {
+            let fork = input.fork();
+            if fork.parse::<Ident>()? == "include_defs" {
+                input.parse::<Ident>()?;
+                input.parse::<Token![!]>()?;
+                let content;
+                syn::parenthesized!(content in input);
+                mounts.push(content.parse()?);
+                input.parse::<Token![;]>()?;
+                continue;
+            }
+        }
+
         // Parse attributes
-        let attrs = parse_all_attrs(input)?;
+        let mut attrs = parse_all_attrs(input)?;
 
         // Parse node name
         let name: Ident = input.parse()?;
@@ -86,7 +516,9 @@ fn parse_nodes(input: ParseStream) -> Result<Vec<Node>> {
         if input.peek(token::Brace) {
             let content;
             braced!(content in input);
-            let children = parse_nodes(&content)?;
+            let (children, node_mounts) = parse_body(&content)?;
+            attrs.mounts = node_mounts;
+            validate_attrs(&name, &attrs)?;
             nodes.push(Node {
                 name,
                 attrs,
@@ -95,6 +527,7 @@ fn parse_nodes(input: ParseStream) -> Result<Vec<Node>> {
             });
         } else {
             input.parse::<Token![;]>()?;
+            validate_attrs(&name, &attrs)?;
             nodes.push(Node {
                 name,
                 attrs,
@@ -103,17 +536,77 @@ fn parse_nodes(input: ParseStream) -> Result<Vec<Node>> {
             });
         }
     }
-    Ok(nodes)
+    validate_siblings(&nodes)?;
+    Ok((nodes, mounts))
+}
+
+/// Names generated inside every node module's type namespace.
+const RESERVED_NODE_NAMES: &[&str] = &["Tag", "TagBundle"];
+
+/// Names generated in the root module's type namespace.
+const RESERVED_ROOT_NAMES: &[&str] = &["prelude"];
+
+/// Reject duplicate siblings and names that clash with generated items.
+///
+/// Only the type namespace matters: node modules can't clash with the
+/// generated consts and functions (`GID`, `lookup`, ...).
+fn validate_siblings(nodes: &[Node]) -> Result<()> {
+    let mut seen: HashMap<String, &Ident> = HashMap::new();
+    for node in nodes {
+        let name = node.name.to_string();
+        if RESERVED_NODE_NAMES.contains(&name.as_str()) {
+            return Err(syn::Error::new(
+                node.name.span(),
+                format!("'{}' is reserved for generated items and can't be a node name", name),
+            ));
+        }
+        if let Some(first) = seen.get(&name) {
+            let mut err = syn::Error::new(
+                node.name.span(),
+                format!("duplicate node '{}': sibling names must be unique", name),
+            );
+            err.combine(syn::Error::new(first.span(), format!("'{}' first declared here", name)));
+            return Err(err);
+        }
+        seen.insert(name, &node.name);
+    }
+    Ok(())
+}
+
+/// Reject top-level names that clash with items generated in the root module.
+fn validate_root(nodes: &[Node]) -> Result<()> {
+    for node in nodes {
+        let name = node.name.to_string();
+        if RESERVED_ROOT_NAMES.contains(&name.as_str()) {
+            return Err(syn::Error::new(
+                node.name.span(),
+                format!("'{}' is reserved at the top level of a namespace", name),
+            ));
+        }
+    }
+    Ok(())
 }
 
 /// Parse all attributes into NodeAttrs.
 ///
 /// Handles:
-/// - `#[deprecated]` or `#[deprecated(note = "...")]`
+/// - `#[deprecated]` or `#[deprecated(since = "...", note = "...")]`
 /// - `#[redirect = "Path.To.Target"]`
+/// - `#[redirect(type = other_crate::Tags::Path::Tag)]`
+/// - `#[alias = "Path.To.Target"]` or `#[alias(type = ...)]`
+/// - `#[loc_key = "tag-combat-attack"]`
+/// - `#[flags(Cosmetic | Replicated | crate::MY_FLAG)]`
+/// - `#[no_registry]`
+/// - `#[bundle]`
+/// - `#[cfg(...)]` (conditional nodes)
+/// - `#[display_name = "..."]` and `#[description = "..."]`
+/// - `#[description]` (the node's `/// doc comments` as description)
+/// - `#[gid = 0x...]` or `#[segment = 0x...]` (GID pinning)
 /// - `#[key = value]` (metadata)
 fn parse_all_attrs(input: ParseStream) -> Result<NodeAttrs> {
     let mut result = NodeAttrs::default();
+    let mut doc_lines = Vec::new();
+    let mut docs_as_description = None;
 
     while input.peek(Token![#]) {
         input.parse::<Token![#]>()?;
@@ -125,26 +618,100 @@ fn parse_all_attrs(input: ParseStream) -> Result<NodeAttrs> {
         if key == "deprecated" {
             result.deprecation.is_deprecated = true;
 
-            // Check for (note = "...")
+            // Check for (since = "...", note = "...") in any order
             if content.peek(syn::token::Paren) {
                 let inner;
                 syn::parenthesized!(inner in content);
 
-                // Parse note = "..."
-                if !inner.is_empty() {
-                    let note_key: Ident = inner.parse()?;
-                    if note_key == "note" {
-                        inner.parse::<Token![=]>()?;
-                        let note_value: syn::LitStr = inner.parse()?;
-                        result.deprecation.note = Some(note_value.value());
+                while !inner.is_empty() {
+                    let field: Ident = inner.parse()?;
+                    inner.parse::<Token![=]>()?;
+                    let value: syn::LitStr = inner.parse()?;
+                    if field == "note" {
+                        result.deprecation.note = Some(value.value());
+                    } else if field == "since" {
+                        result.deprecation.since = Some(value.value());
+                    } else {
+                        return Err(syn::Error::new(
+                            field.span(),
+                            "expected `since` or `note` in #[deprecated(...)]",
+                        ));
+                    }
+                    if !inner.is_empty() {
+                        inner.parse::<Token![,]>()?;
                     }
                 }
             }
-        } else if key == "redirect" {
-            // #[redirect = "Path.To.Target"]
+        } else if key == "redirect" || key == "alias" {
+            if result.redirect_to.is_some() {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "a node can have only one #[redirect] or #[alias]",
+                ));
+            }
+            result.is_alias = key == "alias";
+            if content.peek(syn::token::Paren) {
+                // #[redirect(type = other_crate::Tags::Path::Tag)]
+                let inner;
+                syn::parenthesized!(inner in content);
+                inner.parse::<Token![type]>()?;
+                inner.parse::<Token![=]>()?;
+                let ty: Type = inner.parse()?;
+                result.redirect_to = Some(RedirectTarget::Type(Box::new(ty)));
+            } else {
+                // #[redirect = "Path.To.Target"]
+                content.parse::<Token![=]>()?;
+                let target: syn::LitStr = content.parse()?;
+                result.redirect_to = Some(RedirectTarget::Path(target));
+            }
+        } else if key == "doc" {
+            // `/// ...` doc comments; the description only with a bare #[description]
+            content.parse::<Token![=]>()?;
+            let line: syn::LitStr = content.parse()?;
+            doc_lines.push(line.value().trim().to_string());
+        } else if key == "bundle" {
+            // #[bundle] — flag, requires a data type and `bevy_components`
+            result.bundle = Some(key.span());
+        } else if key == "cfg" {
+            // #[cfg(feature = "cheats")] — node and subtree only exist when it holds
+            let inner;
+            syn::parenthesized!(inner in content);
+            result.cfg.push(inner.parse()?);
+        } else if key == "no_registry" {
+            // #[no_registry] — flag, no value
+            result.no_registry = true;
+        } else if key == "display_name" {
+            // #[display_name = "Heavy Attack"]
+            content.parse::<Token![=]>()?;
+            let name: syn::LitStr = content.parse()?;
+            result.display_name = Some(name.value());
+        } else if key == "flags" {
+            // #[flags(Cosmetic | Replicated | crate::MY_FLAG)]
+            let inner;
+            syn::parenthesized!(inner in content);
+            let flags = syn::punctuated::Punctuated::<syn::Path, Token![|]>::parse_separated_nonempty(&inner)?;
+            result.flags.extend(flags);
+        } else if key == "loc_key" {
+            // #[loc_key = "tag-combat-attack"]
             content.parse::<Token![=]>()?;
-            let target: syn::LitStr = content.parse()?;
-            result.redirect_to = Some(target.value());
+            let loc_key: syn::LitStr = content.parse()?;
+            result.loc_key = Some(loc_key.value());
+        } else if key == "description" && content.is_empty() {
+            // #[description] — opt in to doc comments as the description
+            docs_as_description = Some(key.span());
+        } else if key == "description" {
+            // #[description = "..."]
+            content.parse::<Token![=]>()?;
+            let desc: syn::LitStr = content.parse()?;
+            result.description = Some(desc.value());
+        } else if key == "gid" {
+            // #[gid = 0x...] — full GID, validated at compile time
+            content.parse::<Token![=]>()?;
+            result.pinned_gid = Some(content.parse()?);
+        } else if key == "segment" {
+            // #[segment = 0x...] — this level's segment value only
+            content.parse::<Token![=]>()?;
+            result.pinned_segment = Some(content.parse()?);
         } else {
             // Regular metadata attribute: #[key = value]
             content.parse::<Token![=]>()?;
@@ -153,6 +720,17 @@ fn parse_all_attrs(input: ParseStream) -> Result<NodeAttrs> {
         }
     }
 
+    if let Some(span) = docs_as_description {
+        if result.description.is_some() || doc_lines.is_empty() {
+            return Err(syn::Error::new(
+                span,
+                "a bare #[description] takes the node's doc comments; \
+                 add `///` comments or use #[description = \"...\"] alone",
+            ));
+        }
+        result.description = Some(doc_lines.join("\n"));
+    }
+
     Ok(result)
 }
 
@@ -166,14 +744,16 @@ struct FlatNode {
     segments: Vec<String>,
     /// Depth: 0 for roots, 1 for children, etc.
     depth: u8,
+    /// `#[cfg]` attributes gating the node
+    cfg: TokenStream2,
 }
 
 /// Flatten the parsed tree into a list with depth/path info.
-/// Skips redirect nodes (they don't have their own GID).
+/// Skips redirect nodes (they don't have their own GID) and `#[no_registry]` subtrees.
 fn flatten_nodes(nodes: &[Node], prefix: &str, depth: u8, out: &mut Vec<FlatNode>) {
     for node in nodes {
         // Skip redirect nodes - they use target's GID
-        if node.attrs.redirect_to.is_some() {
+        if node.attrs.redirect_to.is_some() || node.attrs.no_registry {
             continue;
         }
 
@@ -185,7 +765,11 @@ fn flatten_nodes(nodes: &[Node], prefix: &str, depth: u8, out: &mut Vec<FlatNode
 
         let segments: Vec<String> = path.split('.').map(String::from).collect();
 
-        out.push(FlatNode { segments, depth });
+        out.push(FlatNode {
+            segments,
+            depth,
+            cfg: node.attrs.cfg_attrs(),
+        });
 
         flatten_nodes(&node.children, &path, depth + 1, out);
     }
@@ -278,11 +862,198 @@ fn path_to_rust_type_path(path: &str) -> TokenStream2 {
     quote! { #(#modules::)*Tag }
 }
 
+/// Convert a dot-separated path to its module path relative to the module root.
+///
+/// Example: "Equipment.Weapon.Blade" -> Equipment::Weapon::Blade
+fn path_to_rust_module_path(path: &str) -> TokenStream2 {
+    let modules: Vec<Ident> = path
+        .split('.')
+        .map(|s| Ident::new(s, Span::call_site()))
+        .collect();
+    quote! { #(#modules)::* }
+}
+
+/// Default localization key for a path: `Combat.HeavyAttack` -> `tag-combat-heavy-attack`.
+///
+/// Must match `bevy_tag::default_loc_key` character for character.
+fn default_loc_key(path: &str) -> String {
+    let mut key = String::from("tag");
+    for segment in path.split('.') {
+        key.push('-');
+        let start = key.len();
+        let chars: Vec<char> = segment.chars().collect();
+        for (i, &c) in chars.iter().enumerate() {
+            if c == '_' || c == '-' {
+                if key.len() > start && !key.ends_with('-') {
+                    key.push('-');
+                }
+                continue;
+            }
+            // A capital starts a word after a lowercase letter or digit, and
+            // ends an acronym when a lowercase letter follows: `HUDIcon`
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let boundary = c.is_uppercase()
+                && prev.is_some_and(|p| {
+                    p.is_lowercase()
+                        || p.is_ascii_digit()
+                        || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+                });
+            if boundary && !key.ends_with('-') {
+                key.push('-');
+            }
+            key.extend(c.to_lowercase());
+        }
+    }
+    key
+}
+
+/// Convert CamelCase to snake_case (same rule as bevy-tag-build's codegen).
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Generate a hidden snake_case alias for a node module, so both
+/// `Tags::Movement::Idle` and `Tags::movement::Idle` resolve.
+///
+/// Skipped when the name is already snake_case, when the alias would clash
+/// with a sibling, or when it isn't a usable identifier (e.g. `Type` -> `type`).
+fn generate_snake_alias(node: &Node, siblings: &[Node]) -> TokenStream2 {
+    let name = node.name.to_string();
+    let snake = to_snake_case(&name);
+    if snake == name {
+        return quote! {};
+    }
+
+    let clashes = siblings.iter().any(|other| {
+        let other_name = other.name.to_string();
+        other_name == snake || (other_name != name && to_snake_case(&other_name) == snake)
+    });
+    if clashes {
+        return quote! {};
+    }
+
+    match syn::parse_str::<Ident>(&snake) {
+        Ok(alias) => {
+            let node_ident = &node.name;
+            let cfg = node.attrs.cfg_attrs();
+            quote! {
+                #cfg
+                #[doc(hidden)]
+                #[allow(deprecated)]
+                pub use #node_ident as #alias;
+            }
+        }
+        Err(_) => quote! {},
+    }
+}
+
+/// `#[cfg(test)]` module checking that a redirect resolves to its canonical
+/// target, with `module` the path to the redirect's module from where the
+/// test is emitted.
+///
+/// These run in the user's crate, so refactors that break a target fail in
+/// CI rather than at runtime.
+fn redirect_test(
+    node: &Node,
+    path: &str,
+    module: TokenStream2,
+    test_mod: Ident,
+    ns_crate: &TokenStream2,
+) -> TokenStream2 {
+    let Some(ref target) = node.attrs.redirect_to else {
+        return quote! {};
+    };
+    let target_type = match target {
+        RedirectTarget::Path(target_path) => path_to_rust_type_path(&target_path.value()),
+        RedirectTarget::Type(ty) => quote!(#ty),
+    };
+    let old_path_lit = syn::LitStr::new(path, Span::call_site());
+
+    // Aliases keep their own path; redirects report the target's
+    let path_check = if node.attrs.is_alias {
+        quote! {
+            assert_eq!(#module::PATH, #old_path_lit, "alias must keep its own path");
+        }
+    } else {
+        quote! {
+            assert_eq!(#module::PATH, <Target as #ns_crate::NamespaceTag>::PATH);
+            assert_ne!(#module::PATH, #old_path_lit, "redirect must not point at itself");
+        }
+    };
+    let cfg = node.attrs.cfg_attrs();
+    quote! {
+        #[cfg(test)]
+        #cfg
+        #[allow(deprecated, non_snake_case)]
+        mod #test_mod {
+            use super::*;
+
+            #[test]
+            fn redirect_resolves_to_canonical_target() {
+                type Target = #target_type;
+                assert_eq!(#module::GID, <Target as #ns_crate::NamespaceTag>::GID);
+                #path_check
+                assert!(
+                    #ns_crate::is_well_formed(#module::GID),
+                    "redirect target has a malformed GID"
+                );
+            }
+        }
+    }
+}
+
+/// Redirect tests for redirects below a `#[deprecated]` node, emitted at the
+/// namespace root.
+///
+/// Every other redirect gets its test next to its module. Inside a
+/// deprecated node the test function would inherit the deprecation, which
+/// the test harness then trips over.
+fn generate_deprecated_redirect_tests(
+    nodes: &[Node],
+    prefix: &str,
+    in_deprecated: bool,
+    ns_crate: &TokenStream2,
+    output: &mut Vec<TokenStream2>,
+) {
+    for node in nodes {
+        let path = if prefix.is_empty() {
+            node.name.to_string()
+        } else {
+            format!("{}.{}", prefix, node.name)
+        };
+        if node.attrs.redirect_to.is_none() {
+            let deprecated = in_deprecated || node.attrs.deprecation.is_deprecated;
+            generate_deprecated_redirect_tests(&node.children, &path, deprecated, ns_crate, output);
+        } else if in_deprecated {
+            let test_mod = Ident::new(
+                &format!("__redirect_test_{}", path.replace('.', "_")),
+                Span::call_site(),
+            );
+            let module = path_to_rust_module_path(&path);
+            output.push(redirect_test(node, &path, module, test_mod, ns_crate));
+        }
+    }
+}
+
 fn generate_tags_recursive(
     nodes: &[Node],
     prefix: &str,
     depth: u8,
+    in_deprecated: bool,
     ns_crate: &TokenStream2,
+    options: &MacroOptions,
 ) -> Vec<TokenStream2> {
     if depth as usize >= MAX_DEPTH {
         panic!(
@@ -300,50 +1071,83 @@ fn generate_tags_recursive(
         } else {
             format!("{}.{}", prefix, node.name)
         };
-
-        // Generate deprecation attribute if present
-        let deprecation_attr = if node.attrs.deprecation.is_deprecated {
-            if let Some(ref note) = node.attrs.deprecation.note {
-                let note_lit = syn::LitStr::new(note, Span::call_site());
-                quote! { #[deprecated(note = #note_lit)] }
-            } else {
-                quote! { #[deprecated] }
-            }
-        } else {
+        // `prelude` is reserved for the generated leaf prelude at the root
+        let snake_alias = if depth == 0 && to_snake_case(&node.name.to_string()) == "prelude" {
             quote! {}
+        } else {
+            generate_snake_alias(node, nodes)
         };
 
+        // Generate deprecation attribute if present
+        let deprecation_attr = node.attrs.deprecation.to_attr(None);
+        let cfg = node.attrs.cfg_attrs();
+
         // Check if this node is a redirect
-        if let Some(ref target_path) = node.attrs.redirect_to {
+        if let Some(ref target) = node.attrs.redirect_to {
             // Generate module with type alias: pub mod OldName { pub type Tag = Redirect<...>; }
-            let target_type = path_to_rust_type_path(target_path);
+            let target_type = match target {
+                RedirectTarget::Path(target_path) => path_to_rust_type_path(&target_path.value()),
+                RedirectTarget::Type(ty) => quote!(#ty),
+            };
 
-            // Add deprecation note about redirect if not already deprecated
-            let redirect_deprecation = if node.attrs.deprecation.is_deprecated {
-                deprecation_attr.clone()
+            let module = if node.attrs.is_alias {
+                // Aliases keep their own path and are only deprecated if marked
+                let path_lit = syn::LitStr::new(&path, Span::call_site());
+                quote! {
+                    #cfg
+                    #deprecation_attr
+                    #[allow(non_snake_case)]
+                    pub mod #node_ident {
+                        use super::*;
+                        #[doc(hidden)]
+                        #[derive(Clone, Copy)]
+                        pub struct __AliasPath;
+                        impl #ns_crate::AliasPath for __AliasPath {
+                            const PATH: &'static str = #path_lit;
+                        }
+                        pub type Tag = #ns_crate::Alias<#target_type, __AliasPath>;
+                        pub const GID: #ns_crate::GID = <Tag as #ns_crate::NamespaceTag>::GID;
+                        pub const PATH: &'static str = <Tag as #ns_crate::NamespaceTag>::PATH;
+                        pub const DEPTH: u8 = <Tag as #ns_crate::NamespaceTag>::DEPTH;
+                    }
+                }
+            } else {
+                // Redirects are always deprecated and name their replacement
+                let redirect_deprecation = node.attrs.deprecation.to_attr(Some(target));
+                quote! {
+                    #cfg
+                    #redirect_deprecation
+                    #[allow(non_snake_case)]
+                    pub mod #node_ident {
+                        use super::*;
+                        pub type Tag = #ns_crate::Redirect<#target_type>;
+                        pub const GID: #ns_crate::GID = <Tag as #ns_crate::NamespaceTag>::GID;
+                        pub const PATH: &'static str = <Tag as #ns_crate::NamespaceTag>::PATH;
+                        pub const DEPTH: u8 = <Tag as #ns_crate::NamespaceTag>::DEPTH;
+                    }
+                }
+            };
+            // Below a deprecated node, the test goes to the namespace root
+            // instead (see `generate_deprecated_redirect_tests`)
+            let redirect_test = if in_deprecated {
+                quote! {}
             } else {
-                let note = format!("redirected to {}", target_path);
-                let note_lit = syn::LitStr::new(&note, Span::call_site());
-                quote! { #[deprecated(note = #note_lit)] }
+                let test_mod =
+                    Ident::new(&format!("__redirect_test_{}", node_ident), Span::call_site());
+                redirect_test(node, &path, quote!(#node_ident), test_mod, ns_crate)
             };
-
             output.push(quote! {
-                #redirect_deprecation
-                #[allow(non_snake_case)]
-                pub mod #node_ident {
-                    use super::*;
-                    pub type Tag = #ns_crate::Redirect<#target_type>;
-                    pub const GID: #ns_crate::GID = <Tag as #ns_crate::NamespaceTag>::GID;
-                    pub const PATH: &'static str = <Tag as #ns_crate::NamespaceTag>::PATH;
-                    pub const DEPTH: u8 = <Tag as #ns_crate::NamespaceTag>::DEPTH;
-                }
+                #module
+                #redirect_test
+                #snake_alias
             });
 
-            // Redirects cannot have children
+            // Redirects and aliases cannot have children
             if !node.children.is_empty() {
                 panic!(
-                    "Node '{}' has #[redirect] but also has children. Redirects must be leaf nodes.",
-                    path
+                    "Node '{}' has #[{}] but also has children. Redirects must be leaf nodes.",
+                    path,
+                    if node.attrs.is_alias { "alias" } else { "redirect" }
                 );
             }
 
@@ -363,9 +1167,65 @@ fn generate_tags_recursive(
 
         let depth_lit = depth;
 
+        // Children derive their GID from the parent's so pinned ancestors propagate
+        let parent_gid = if depth == 0 {
+            quote!(#ns_crate::ROOT_GID)
+        } else {
+            quote!(super::Tag::GID)
+        };
+        let gid_expr = if let Some(ref pinned) = node.attrs.pinned_gid {
+            let depth_msg = format!("pinned GID for '{}' must encode depth {}", path, depth);
+            let shape_msg = format!("pinned GID for '{}' has empty or stray level bits", path);
+            let parent_check = if depth == 0 {
+                quote! {}
+            } else {
+                let parent_msg = format!("pinned GID for '{}' is not under its parent's GID", path);
+                quote! {
+                    assert!(#ns_crate::gid_is_descendant_of(PINNED, #parent_gid), #parent_msg);
+                }
+            };
+            quote! {{
+                const PINNED: #ns_crate::GID = #pinned;
+                assert!(#ns_crate::depth_of(PINNED) == #depth_lit, #depth_msg);
+                assert!(#ns_crate::is_well_formed(PINNED), #shape_msg);
+                #parent_check
+                PINNED
+            }}
+        } else if let Some(ref value) = node.attrs.pinned_segment {
+            quote! { #ns_crate::gid_with_segment(#parent_gid, #depth_lit, #value) }
+        } else if depth == 0 {
+            quote! {{
+                const SEGS: [&[u8]; #seg_count] = [#(#seg_lits),*];
+                #ns_crate::hierarchical_gid(&SEGS)
+            }}
+        } else {
+            let seg_lit = seg_lits.last().unwrap();
+            quote! { #ns_crate::child_gid(#parent_gid, #depth_lit, #seg_lit) }
+        };
+
         // Generate metadata constants from attributes
         let metadata = generate_metadata_consts(&node.attrs.meta);
 
+        // Display name falls back to the node's own segment
+        let display_name = node
+            .attrs
+            .display_name
+            .clone()
+            .unwrap_or_else(|| node.name.to_string());
+        let display_name_lit = syn::LitStr::new(&display_name, Span::call_site());
+        let name_lit = syn::LitStr::new(&node.name.to_string(), Span::call_site());
+        let description_lit = syn::LitStr::new(
+            node.attrs.description.as_deref().unwrap_or(""),
+            Span::call_site(),
+        );
+        let loc_key = node
+            .attrs
+            .loc_key
+            .clone()
+            .unwrap_or_else(|| default_loc_key(&path));
+        let loc_key_lit = syn::LitStr::new(&loc_key, Span::call_site());
+        let flags_expr = generate_flags_expr(&node.attrs.flags, ns_crate);
+
         // Generate data type association if present
         let data_type_impl = if let Some(ref ty) = node.data_type {
             quote! {
@@ -377,22 +1237,100 @@ fn generate_tags_recursive(
             quote! {}
         };
 
-        // Generate children recursively
-        let children_output = if !node.children.is_empty() {
-            generate_tags_recursive(&node.children, &path, depth + 1, ns_crate)
-        } else {
-            Vec::new()
-        };
+        // Optional marker + data bundle
+        let bundle_impl = match (node.attrs.bundle, &node.data_type) {
+            (None, _) => quote! {},
+            (Some(span), None) => {
+                let msg = format!("#[bundle] on '{}' requires a data type: {}<Data>", path, node.name);
+                syn::Error::new(span, msg).to_compile_error()
+            }
+            (Some(span), Some(_)) if !options.bevy_components => syn::Error::new(
+                span,
+                "#[bundle] requires the `bevy_components` namespace option",
+            )
+            .to_compile_error(),
+            (Some(_), Some(ty)) => {
+                let bevy = quote!(#ns_crate::__private::bevy);
+                quote! {
+                    /// This tag's marker component together with its data.
+                    #[derive(#bevy::prelude::Bundle)]
+                    pub struct TagBundle {
+                        pub tag: Tag,
+                        pub data: #ty,
+                    }
 
-        // Generate the module containing Tag struct and children
-        output.push(quote! {
-            #deprecation_attr
-            #[allow(non_snake_case)]
-            pub mod #node_ident {
-                use super::*;
+                    impl TagBundle {
+                        /// Bundle with the given data.
+                        pub fn new(data: #ty) -> Self {
+                            Self { tag: Tag, data }
+                        }
+                    }
+
+                    impl Default for TagBundle {
+                        fn default() -> Self {
+                            Self::new(Default::default())
+                        }
+                    }
+
+                    /// Spawn an entity with this tag and default data.
+                    pub fn spawn<'a>(
+                        commands: &'a mut #bevy::prelude::Commands,
+                    ) -> #bevy::ecs::system::EntityCommands<'a> {
+                        commands.spawn(TagBundle::default())
+                    }
+                }
+            }
+        };
+
+        // Optional Bevy marker-component derives
+        let bevy_derives = if options.bevy_components {
+            let bevy = quote!(#ns_crate::__private::bevy);
+            quote! {
+                #[derive(#bevy::prelude::Component, #bevy::prelude::Reflect)]
+                #[reflect(Component, Default)]
+            }
+        } else {
+            quote! {}
+        };
+        let bevy_imports = if options.bevy_components {
+            quote! {
+                use #ns_crate::__private::bevy::prelude::{ReflectComponent, ReflectDefault};
+            }
+        } else {
+            quote! {}
+        };
+
+        let derives = &options.derives;
+        let extra_derives = if derives.is_empty() {
+            quote! {}
+        } else {
+            quote! { #[derive(#(#derives),*)] }
+        };
+
+        // Generate children recursively
+        let children_output = if !node.children.is_empty() {
+            let deprecated = in_deprecated || node.attrs.deprecation.is_deprecated;
+            generate_tags_recursive(&node.children, &path, depth + 1, deprecated, ns_crate, options)
+        } else {
+            Vec::new()
+        };
+        // Emitted on leaves too (empty), so they don't pick up the parent's via `use super::*`
+        let child_arrays =
+            generate_child_arrays(&node.children, &path, !node.attrs.mounts.is_empty(), ns_crate);
+
+        // Generate the module containing Tag struct and children
+        output.push(quote! {
+            #cfg
+            #deprecation_attr
+            #[allow(non_snake_case)]
+            pub mod #node_ident {
+                use super::*;
+                #bevy_imports
 
                 /// Zero-sized tag type for this namespace node.
                 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+                #bevy_derives
+                #extra_derives
                 pub struct Tag;
 
                 impl Tag {
@@ -402,11 +1340,23 @@ fn generate_tags_recursive(
                     /// Depth in the namespace tree (0 = top-level).
                     pub const DEPTH: u8 = #depth_lit;
 
+                    /// The node's own name (last path segment).
+                    pub const NAME: &'static str = #name_lit;
+
+                    /// Human-readable name (defaults to the node name).
+                    pub const DISPLAY_NAME: &'static str = #display_name_lit;
+
+                    /// Description (empty if none was declared).
+                    pub const DESCRIPTION: &'static str = #description_lit;
+
+                    /// Localization key (Fluent/gettext id) for the display string.
+                    pub const LOC_KEY: &'static str = #loc_key_lit;
+
+                    /// Category flags (`#[flags(...)]`).
+                    pub const FLAGS: #ns_crate::TagFlags = #flags_expr;
+
                     /// Stable hierarchical GID, computed at compile time.
-                    pub const GID: #ns_crate::GID = {
-                        const SEGS: [&[u8]; #seg_count] = [#(#seg_lits),*];
-                        #ns_crate::hierarchical_gid(&SEGS)
-                    };
+                    pub const GID: #ns_crate::GID = #gid_expr;
 
                     /// Get the GID (convenience method).
                     #[inline]
@@ -414,6 +1364,12 @@ fn generate_tags_recursive(
                         Self::GID
                     }
 
+                    /// Get the node's own name (strum-style `name()`).
+                    #[inline]
+                    pub const fn name() -> &'static str {
+                        Self::NAME
+                    }
+
                     #metadata
                 }
 
@@ -441,22 +1397,200 @@ fn generate_tags_recursive(
                     const GID: #ns_crate::GID = Tag::GID;
                 }
 
+                // Serializes as PATH when bevy-tag's `serde` feature is enabled
+                #ns_crate::__impl_tag_serde!(Tag);
+
                 #data_type_impl
 
+                #bundle_impl
+
                 // Module-level convenience constants
                 pub const GID: #ns_crate::GID = Tag::GID;
                 pub const PATH: &'static str = Tag::PATH;
                 pub const DEPTH: u8 = Tag::DEPTH;
 
+                #child_arrays
+
                 // Nested child modules
                 #(#children_output)*
             }
+            #snake_alias
         });
     }
 
     output
 }
 
+/// Collect the segment paths of leaves that belong in the prelude.
+///
+/// Redirects and deprecated nodes (or leaves under a deprecated ancestor)
+/// are left out: the prelude is for tags new code should use.
+fn collect_prelude_leaves<'a>(
+    nodes: &'a [Node],
+    prefix: &[String],
+    out: &mut Vec<(Vec<String>, &'a NodeAttrs)>,
+) {
+    for node in nodes {
+        if node.attrs.redirect_to.is_some() || node.attrs.deprecation.is_deprecated {
+            continue;
+        }
+
+        let mut segments = prefix.to_vec();
+        segments.push(node.name.to_string());
+
+        if node.children.is_empty() {
+            out.push((segments, &node.attrs));
+        } else {
+            collect_prelude_leaves(&node.children, &segments, out);
+        }
+    }
+}
+
+/// Generate the `prelude` module re-exporting every leaf `Tag`.
+///
+/// A leaf is exported under its own name when that name is unique among
+/// leaves; otherwise its full path is concatenated (`Combat.Attack` ->
+/// `CombatAttack`). Names that still clash are left out of the prelude.
+fn generate_prelude(nodes: &[Node]) -> TokenStream2 {
+    let mut leaves = Vec::new();
+    collect_prelude_leaves(nodes, &[], &mut leaves);
+
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    for (segments, _) in &leaves {
+        *name_counts.entry(segments.last().unwrap()).or_default() += 1;
+    }
+
+    let names: Vec<String> = leaves
+        .iter()
+        .map(|(segments, _)| {
+            let leaf = segments.last().unwrap();
+            if name_counts[leaf.as_str()] == 1 {
+                leaf.clone()
+            } else {
+                segments.concat()
+            }
+        })
+        .collect();
+
+    let mut final_counts: HashMap<&str, usize> = HashMap::new();
+    for name in &names {
+        *final_counts.entry(name).or_default() += 1;
+    }
+
+    let exports = leaves.iter().zip(&names).filter_map(|((segments, attrs), name)| {
+        if final_counts[name.as_str()] > 1 {
+            return None;
+        }
+        let modules: Vec<Ident> = segments
+            .iter()
+            .map(|s| Ident::new(s, Span::call_site()))
+            .collect();
+        let alias = Ident::new(name, Span::call_site());
+        let cfg = attrs.cfg_attrs();
+        Some(quote! { #cfg pub use super::#(#modules)::*::Tag as #alias; })
+    });
+
+    quote! {
+        /// Leaf tags re-exported under short names: `use Tags::prelude::*;`
+        pub mod prelude {
+            #(#exports)*
+        }
+    }
+}
+
+/// Build the `TagFlags` expression for a node.
+///
+/// Bare names are built-in flags (`DesignerVisible` -> `TagFlags::DESIGNER_VISIBLE`);
+/// longer paths are user-defined `TagFlags` constants used as-is.
+fn generate_flags_expr(flags: &[syn::Path], ns_crate: &TokenStream2) -> TokenStream2 {
+    let parts = flags.iter().map(|flag| match flag.get_ident() {
+        Some(name) => {
+            let konst = Ident::new(&to_snake_case(&name.to_string()).to_uppercase(), name.span());
+            quote!(#ns_crate::TagFlags::#konst)
+        }
+        None => quote!(#flag),
+    });
+    quote! { #ns_crate::TagFlags::EMPTY #(.union(#parts))* }
+}
+
+/// Generate `CHILD_GIDS` / `CHILD_PATHS` for a node, in declaration order.
+///
+/// Redirects are skipped (they aren't nodes of their own), so index `i` of
+/// both arrays always names the same child. So are the roots of
+/// `include_defs!` tables, which have no modules; `HAS_MOUNTS` says whether
+/// there are any.
+fn generate_child_arrays(
+    children: &[Node],
+    prefix: &str,
+    mounted: bool,
+    ns_crate: &TokenStream2,
+) -> TokenStream2 {
+    let own: Vec<&Node> = children
+        .iter()
+        .filter(|child| child.attrs.redirect_to.is_none())
+        .collect();
+    let cfgs: Vec<TokenStream2> = own.iter().map(|child| child.attrs.cfg_attrs()).collect();
+    // With `#[cfg]` children the length depends on which are compiled in
+    let count = if own.iter().any(|child| !child.attrs.cfg.is_empty()) {
+        quote!([#(#cfgs ()),*].len())
+    } else {
+        let count = own.len();
+        quote!(#count)
+    };
+    let idents = own.iter().map(|child| &child.name);
+    let descriptor_idents = idents.clone();
+    let paths = own.iter().map(|child| {
+        let path = if prefix.is_empty() {
+            child.name.to_string()
+        } else {
+            format!("{}.{}", prefix, child.name)
+        };
+        syn::LitStr::new(&path, Span::call_site())
+    });
+
+    quote! {
+        /// GIDs of the direct children, in declaration order.
+        #[allow(deprecated)]
+        pub const CHILD_GIDS: [#ns_crate::GID; VARIANT_COUNT] = [#(#cfgs #idents::GID),*];
+
+        /// Paths of the direct children, in the same order as `CHILD_GIDS`.
+        pub const CHILD_PATHS: [&'static str; VARIANT_COUNT] = [#(#cfgs #paths),*];
+
+        /// Number of direct children (strum-style `VARIANT_COUNT`).
+        pub const VARIANT_COUNT: usize = #count;
+
+        /// `include_defs!` tables are mounted here. Their roots are children
+        /// too but are not listed in `CHILD_GIDS`, which only names declared
+        /// nodes.
+        pub const HAS_MOUNTS: bool = #mounted;
+
+        /// Descriptors of the direct children, in the same order as `CHILD_GIDS`.
+        #[allow(deprecated)]
+        pub const CHILDREN: [#ns_crate::TagDescriptor; VARIANT_COUNT] = [#(
+            #cfgs
+            #ns_crate::TagDescriptor::of::<#descriptor_idents::Tag>()
+                .with_display_name(#descriptor_idents::Tag::DISPLAY_NAME)
+        ),*];
+
+        /// Iterate the direct children's descriptors (strum-style `iter()`).
+        pub fn iter() -> impl Iterator<Item = #ns_crate::TagDescriptor> {
+            CHILDREN.into_iter()
+        }
+
+        /// Position of a direct child in `CHILD_GIDS`, if `gid` is one.
+        pub const fn child_index(gid: #ns_crate::GID) -> Option<usize> {
+            let mut i = 0;
+            while i < CHILD_GIDS.len() {
+                if CHILD_GIDS[i] == gid {
+                    return Some(i);
+                }
+                i += 1;
+            }
+            None
+        }
+    }
+}
+
 /// Generate const fields from metadata attributes.
 fn generate_metadata_consts(attrs: &[MetaAttr]) -> TokenStream2 {
     let consts: Vec<TokenStream2> = attrs
@@ -496,18 +1630,26 @@ fn infer_type_from_expr(expr: &Expr) -> TokenStream2 {
     }
 }
 
+/// One `DEFINITIONS` entry: its `#[cfg]` gate, `NamespaceDef` expression and GID.
+struct DefTokens {
+    cfg: TokenStream2,
+    def: TokenStream2,
+    gid: TokenStream2,
+}
+
 /// Generate `NamespaceDef` entries.
-/// Skips redirect nodes (they don't have their own definition).
+/// Skips redirect nodes (they don't have their own definition) and `#[no_registry]` subtrees.
 fn collect_defs(
     nodes: &[Node],
     prefix: &str,
     parent: Option<&str>,
     ns_crate: &TokenStream2,
-    out: &mut Vec<TokenStream2>,
+    out: &mut Vec<DefTokens>,
 ) {
     for node in nodes {
-        // Skip redirect nodes - they point to another definition
-        if node.attrs.redirect_to.is_some() {
+        // Skip redirect nodes - they point to another definition.
+        // `#[no_registry]` drops the whole subtree: children need their parent's def.
+        if node.attrs.redirect_to.is_some() || node.attrs.no_registry {
             continue;
         }
 
@@ -526,19 +1668,330 @@ fn collect_defs(
             None => quote!(None),
         };
 
-        out.push(quote! {
-            #ns_crate::NamespaceDef {
-                path: #path_lit,
-                parent: #parent_tokens,
+        let display_name = node.attrs.display_name.as_ref().map(|name| {
+            let lit = syn::LitStr::new(name, Span::call_site());
+            quote!(.with_display_name(#lit))
+        });
+        let description = node.attrs.description.as_ref().map(|desc| {
+            let lit = syn::LitStr::new(desc, Span::call_site());
+            quote!(.with_description(#lit))
+        });
+
+        let loc_key = node
+            .attrs
+            .loc_key
+            .clone()
+            .unwrap_or_else(|| default_loc_key(&path));
+        let loc_key_lit = syn::LitStr::new(&loc_key, Span::call_site());
+
+        let pinned_gid = node.attrs.is_pinned().then(|| {
+            let module_path = path_to_rust_module_path(&path);
+            quote!(.with_gid(#module_path::GID))
+        });
+
+        let flags = (!node.attrs.flags.is_empty()).then(|| {
+            let module_path = path_to_rust_module_path(&path);
+            quote!(.with_flags(#module_path::Tag::FLAGS))
+        });
+
+        let deprecation = &node.attrs.deprecation;
+        let deprecated = deprecation.is_deprecated.then(|| {
+            let opt_lit = |value: &Option<String>| match value {
+                Some(v) => {
+                    let lit = syn::LitStr::new(v, Span::call_site());
+                    quote!(Some(#lit))
+                }
+                None => quote!(None),
+            };
+            let since = opt_lit(&deprecation.since);
+            let note = opt_lit(&deprecation.note);
+            quote!(.with_deprecation(#ns_crate::Deprecation { since: #since, note: #note }))
+        });
+
+        let data_type = node.data_type.as_ref().map(|ty| {
+            let lit = syn::LitStr::new(&type_name(ty), Span::call_site());
+            quote!(.with_data_type(#lit))
+        });
+
+        let module_path = path_to_rust_module_path(&path);
+        out.push(DefTokens {
+            cfg: node.attrs.cfg_attrs(),
+            def: quote! {
+                #ns_crate::NamespaceDef::new(#path_lit, #parent_tokens)
+                    #display_name
+                    #description
+                    .with_loc_key(#loc_key_lit)
+                    #flags
+                    #pinned_gid
+                    #deprecated
+                    #data_type
             },
+            gid: quote!(#module_path::GID),
         });
 
         collect_defs(&node.children, &path, Some(&path), ns_crate, out);
     }
 }
 
+/// One `include_defs!` mount: the node it is under and the mounted table.
+struct MountTokens {
+    cfg: TokenStream2,
+    prefix: String,
+    defs: syn::Path,
+}
+
+/// Collect `include_defs!` mounts, skipping the subtrees `collect_defs` skips.
+fn collect_mounts(nodes: &[Node], prefix: &str, out: &mut Vec<MountTokens>) {
+    for node in nodes {
+        if node.attrs.redirect_to.is_some() || node.attrs.no_registry {
+            continue;
+        }
+
+        let path = if prefix.is_empty() {
+            node.name.to_string()
+        } else {
+            format!("{}.{}", prefix, node.name)
+        };
+        out.extend(node.attrs.mounts.iter().map(|defs| MountTokens {
+            cfg: node.attrs.cfg_attrs(),
+            prefix: path.clone(),
+            defs: defs.clone(),
+        }));
+
+        collect_mounts(&node.children, &path, out);
+    }
+}
+
+/// Generate one re-rooted `StaticEntry` table per mount and `MOUNTED`, the
+/// list of them that the root tables are extended with.
+fn generate_mounts(mounts: &[MountTokens], ns_crate: &TokenStream2) -> TokenStream2 {
+    let tables = mounts.iter().enumerate().map(|(i, mount)| {
+        let MountTokens { cfg, prefix, defs } = mount;
+        let prefix_lit = syn::LitStr::new(prefix, Span::call_site());
+        let prefix_gid = path_to_rust_module_path(prefix);
+        let bytes = format_ident!("MOUNT_{}_BYTES", i);
+        let paths = format_ident!("MOUNT_{}_PATHS", i);
+        let table = format_ident!("MOUNT_{}", i);
+        quote! {
+            #cfg
+            const #bytes: usize = #ns_crate::__private::mounted_path_bytes(#prefix_lit, #defs);
+            #cfg
+            const #paths: [u8; #bytes] = #ns_crate::__private::mounted_paths(#prefix_lit, #defs);
+            #cfg
+            #[allow(deprecated)]
+            const #table: [#ns_crate::StaticEntry; #defs.len()] =
+                #ns_crate::__private::mount(#prefix_lit, #prefix_gid::GID, #defs, &#paths);
+        }
+    });
+    let refs = mounts.iter().enumerate().map(|(i, MountTokens { cfg, .. })| {
+        let table = format_ident!("MOUNT_{}", i);
+        quote!(#cfg &#table)
+    });
+
+    quote! {
+        #(#tables)*
+
+        /// Every `include_defs!` table, re-rooted under its node.
+        const MOUNTED: &[&[#ns_crate::StaticEntry]] = &[#(#refs),*];
+
+        const MOUNTED_LEN: usize = #ns_crate::__private::mounted_len(MOUNTED);
+    }
+}
+
+/// `ty` as written, without the spaces token printing adds
+/// (`Vec < u8 >` becomes `Vec<u8>`).
+fn type_name(ty: &Type) -> String {
+    let spaced = ty.to_token_stream().to_string();
+    let chars: Vec<char> = spaced.chars().collect();
+    let word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '_');
+    let mut name = String::with_capacity(spaced.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let keep = c != ' '
+            || (i > 0 && matches!(chars[i - 1], ',' | ';'))
+            || (i > 0 && word(chars.get(i - 1)) && word(chars.get(i + 1)));
+        if keep {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// Collect every declared path, including redirects (which resolve to their
+/// target), with the `#[cfg]` attributes gating it.
+fn collect_all_paths(nodes: &[Node], prefix: &str, out: &mut Vec<(String, TokenStream2)>) {
+    for node in nodes {
+        let path = if prefix.is_empty() {
+            node.name.to_string()
+        } else {
+            format!("{}.{}", prefix, node.name)
+        };
+        out.push((path.clone(), node.attrs.cfg_attrs()));
+        collect_all_paths(&node.children, &path, out);
+    }
+}
+
+/// Generate `PATH_TABLE` (sorted by path) and a const `lookup(path)`.
+///
+/// With `mounted`, the declared paths are merged with `MOUNTED`'s.
+fn generate_path_lookup(nodes: &[Node], mounted: bool, ns_crate: &TokenStream2) -> TokenStream2 {
+    let mut paths = Vec::new();
+    collect_all_paths(nodes, "", &mut paths);
+    paths.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let entries: Vec<_> = paths
+        .iter()
+        .map(|(path, cfg)| {
+            let lit = syn::LitStr::new(path, Span::call_site());
+            let module_path = path_to_rust_module_path(path);
+            quote!(#cfg (#lit, #module_path::GID))
+        })
+        .collect();
+    let table = if mounted {
+        quote! {
+            &#ns_crate::__private::merge_path_table::<{ LOCAL_PATH_TABLE.len() + MOUNTED_LEN }>(
+                LOCAL_PATH_TABLE,
+                MOUNTED,
+            )
+        }
+    } else {
+        quote!(&[#(#entries),*])
+    };
+    let local = mounted.then(|| {
+        quote! {
+            #[allow(deprecated)]
+            const LOCAL_PATH_TABLE: &[(&str, #ns_crate::GID)] = &[#(#entries),*];
+        }
+    });
+
+    quote! {
+        #local
+
+        /// Every declared path (redirects included) with its GID, sorted by path.
+        #[allow(deprecated)]
+        pub const PATH_TABLE: &'static [(&'static str, #ns_crate::GID)] = #table;
+
+        const _: () = assert!(#ns_crate::is_sorted_by_path(PATH_TABLE));
+
+        /// Path -> GID without a runtime registry (const binary search over `PATH_TABLE`).
+        ///
+        /// Redirected paths resolve to their target's GID.
+        pub const fn lookup(path: &str) -> Option<#ns_crate::GID> {
+            #ns_crate::lookup_path(PATH_TABLE, path)
+        }
+    }
+}
+
+/// Generate `STATIC_REGISTRY`: `DEFINITIONS` with their GIDs, sorted by GID
+/// and indexed by path during const evaluation.
+///
+/// With `mounted`, `MOUNTED`'s entries follow the declared ones.
+fn generate_static_registry(
+    defs: &[DefTokens],
+    mounted: bool,
+    ns_crate: &TokenStream2,
+) -> TokenStream2 {
+    let entries = defs.iter().map(|DefTokens { cfg, def, gid }| {
+        quote!(#cfg #ns_crate::StaticEntry { gid: #gid, def: #def })
+    });
+    let entries = if mounted {
+        quote!(#ns_crate::__private::concat_entries(&[#(#entries),*], MOUNTED))
+    } else {
+        quote!([#(#entries),*])
+    };
+
+    quote! {
+        #[allow(deprecated)]
+        const STATIC_ENTRIES: [#ns_crate::StaticEntry; NODE_COUNT] =
+            #ns_crate::StaticRegistry::sort_by_gid(#entries);
+
+        const STATIC_PATH_INDEX: [usize; NODE_COUNT] =
+            #ns_crate::StaticRegistry::path_index(&STATIC_ENTRIES);
+
+        /// Read-only registry over const tables: same lookups as a
+        /// `NamespaceRegistry` built from `DEFINITIONS`, with no startup cost.
+        pub const STATIC_REGISTRY: #ns_crate::StaticRegistry =
+            #ns_crate::StaticRegistry::new(&STATIC_ENTRIES, &STATIC_PATH_INDEX);
+    }
+}
+
+/// Per-level statistics: `(nodes at level, widest sibling group at level)`.
+fn level_stats(flat: &[FlatNode], tree_depth: usize) -> Vec<(usize, usize)> {
+    let mut nodes_per_level = vec![0usize; tree_depth];
+    let mut siblings: HashMap<&[String], usize> = HashMap::new();
+    for node in flat {
+        nodes_per_level[node.depth as usize] += 1;
+        *siblings
+            .entry(&node.segments[..node.segments.len() - 1])
+            .or_default() += 1;
+    }
+
+    let mut max_width = vec![0usize; tree_depth];
+    for (parent, count) in siblings {
+        let level = parent.len();
+        max_width[level] = max_width[level].max(count);
+    }
+
+    nodes_per_level.into_iter().zip(max_width).collect()
+}
+
+/// Percentage of a level's segment space used by `width` siblings.
+fn occupancy_percent(level: usize, width: usize) -> f64 {
+    width as f64 / (1u64 << LEVEL_WIDTHS[level]) as f64 * 100.0
+}
+
+/// Generate `NODES_PER_LEVEL`, `MAX_WIDTH_USED`, `LEVEL_OCCUPANCY_PERCENT`
+/// and a deprecation-style warning for crowded levels.
+///
+/// Segments only need to be unique among siblings, so occupancy is measured
+/// on the widest sibling group of each level.
+fn generate_level_stats(flat: &[FlatNode], tree_depth: usize) -> TokenStream2 {
+    let stats = level_stats(flat, tree_depth);
+    let nodes_per_level = stats.iter().map(|&(nodes, _)| nodes);
+    let max_width = stats.iter().map(|&(_, width)| width);
+    let occupancy: Vec<f64> = stats
+        .iter()
+        .enumerate()
+        .map(|(level, &(_, width))| occupancy_percent(level, width))
+        .collect();
+
+    // No `warning!` on stable: using a deprecated const makes rustc print the note
+    let warnings = occupancy.iter().enumerate().filter_map(|(level, &percent)| {
+        if percent <= OCCUPANCY_WARN_PERCENT {
+            return None;
+        }
+        let note = format!(
+            "namespace level {} uses {:.1}% of its {}-bit segment space (> {}%); \
+             hash collisions become likely, consider pinning segments or restructuring",
+            level, percent, LEVEL_WIDTHS[level], OCCUPANCY_WARN_PERCENT
+        );
+        let name = Ident::new(&format!("LEVEL_{}_OCCUPANCY_WARNING", level), Span::call_site());
+        Some(quote! {
+            #[deprecated(note = #note)]
+            #[doc(hidden)]
+            const #name: () = ();
+            const _: () = #name;
+        })
+    });
+
+    quote! {
+        /// Number of nodes at each level.
+        pub const NODES_PER_LEVEL: [usize; TREE_DEPTH] = [#(#nodes_per_level),*];
+
+        /// Size of the widest sibling group at each level.
+        pub const MAX_WIDTH_USED: [usize; TREE_DEPTH] = [#(#max_width),*];
+
+        /// `MAX_WIDTH_USED` as a percentage of each level's segment space.
+        pub const LEVEL_OCCUPANCY_PERCENT: [f64; TREE_DEPTH] = [#(#occupancy),*];
+
+        #(#warnings)*
+    }
+}
+
 /// Generate compile-time collision detection with detailed error messages.
-fn generate_collision_check(flat: &[FlatNode], ns_crate: &TokenStream2) -> TokenStream2 {
+///
+/// Compares the generated `GID` constants, so pinned GIDs/segments are
+/// checked the same way as hashed ones.
+fn generate_collision_check(flat: &[FlatNode]) -> TokenStream2 {
     // Generate individual collision checks for each pair with specific error messages
     let mut checks = Vec::new();
 
@@ -547,37 +2000,20 @@ fn generate_collision_check(flat: &[FlatNode], ns_crate: &TokenStream2) -> Token
             let path_i = flat[i].segments.join(".");
             let path_j = flat[j].segments.join(".");
 
-            let seg_count_i = flat[i].segments.len();
-            let seg_lits_i: Vec<syn::LitByteStr> = flat[i]
-                .segments
-                .iter()
-                .map(|s| syn::LitByteStr::new(s.as_bytes(), Span::call_site()))
-                .collect();
-
-            let seg_count_j = flat[j].segments.len();
-            let seg_lits_j: Vec<syn::LitByteStr> = flat[j]
-                .segments
-                .iter()
-                .map(|s| syn::LitByteStr::new(s.as_bytes(), Span::call_site()))
-                .collect();
+            let gid_i = path_to_rust_module_path(&path_i);
+            let gid_j = path_to_rust_module_path(&path_j);
 
             let error_msg = format!(
                 "GID collision detected: '{}' and '{}' hash to the same value",
                 path_i, path_j
             );
 
+            let (cfg_i, cfg_j) = (&flat[i].cfg, &flat[j].cfg);
             checks.push(quote! {
-                const _: () = {
-                    const GID_A: #ns_crate::GID = {
-                        const SEGS: [&[u8]; #seg_count_i] = [#(#seg_lits_i),*];
-                        #ns_crate::hierarchical_gid(&SEGS)
-                    };
-                    const GID_B: #ns_crate::GID = {
-                        const SEGS: [&[u8]; #seg_count_j] = [#(#seg_lits_j),*];
-                        #ns_crate::hierarchical_gid(&SEGS)
-                    };
-                    assert!(GID_A != GID_B, #error_msg);
-                };
+                #cfg_i
+                #cfg_j
+                #[allow(deprecated)]
+                const _: () = assert!(#gid_i::GID != #gid_j::GID, #error_msg);
             });
         }
     }
@@ -591,10 +2027,75 @@ fn generate_collision_check(flat: &[FlatNode], ns_crate: &TokenStream2) -> Token
 // Entry point
 // =============================================================================
 
+/// Define a hierarchical tag namespace.
+///
+/// Options may precede the module, separated by commas and terminated by `;`:
+///
+/// - `bevy_components` — derive Bevy `Component` and `Reflect` on every `Tag`,
+///   so tags can be used as marker components (`With<Tags::Status::Stunned::Tag>`).
+///   Nodes with data can then add `#[bundle]` to get a `TagBundle` (marker + default
+///   data) and a `spawn(&mut commands)` helper.
+/// - `crate = path` — path to bevy-tag used by the generated code, for when it is
+///   re-exported through a facade crate (`crate = ::my_engine::tags`). The path
+///   must be absolute and re-export all of bevy-tag (`pub use bevy_tag::*;`).
+/// - `derive(Trait, ...)` — extra derives for every `Tag` (`derive(PartialOrd, Ord)`).
+///
+/// `include_defs!(path::to::DEFINITIONS);` inside a node mounts another
+/// namespace's table under it: `Weapon.Blade` mounted under `Game.Engine`
+/// becomes `Game.Engine.Weapon.Blade`, with its GID rehashed to match. The
+/// path is resolved inside the generated module, so make it absolute
+/// (`crate::...` or an external crate). Mounted nodes appear in
+/// `DEFINITIONS`, `PATH_TABLE` and `STATIC_REGISTRY` but get no modules or
+/// `Tag` types, and tables with pinned GIDs cannot be mounted.
+///
+/// Outer attributes before the module (doc comments, `#[allow(...)]`) are kept
+/// on the generated root module.
+///
+/// ```ignore
+/// namespace!(bevy_components; pub mod Tags {
+///     Status { Stunned; Burning; }
+/// });
+/// ```
 #[proc_macro]
 pub fn namespace(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as NamespaceInput);
-    let ns_crate = namespace_crate_path();
+    expand_namespace(input).into()
+}
+
+/// Attribute form of [`namespace!`], applied to a real module tree.
+///
+/// Each nested inline `mod` is a node; node attributes are the same as in
+/// `namespace!` (doc comments become the description only with a bare
+/// `#[description]`), and a data type is attached with `#[data(Type)]`.
+/// Options go in the attribute arguments.
+///
+/// ```ignore
+/// #[bevy_tag::namespace_mod(bevy_components)]
+/// pub mod Tags {
+///     pub mod Movement {
+///         /// Standing still.
+///         #[description]
+///         pub mod Idle {}
+///         #[data(f32)]
+///         pub mod Running {}
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn namespace_mod(args: TokenStream, item: TokenStream) -> TokenStream {
+    let options = syn::parse_macro_input!(args with MacroOptions::parse_attr_args);
+    let module = syn::parse_macro_input!(item as syn::ItemMod);
+    match NamespaceInput::from_item_mod(options, module) {
+        Ok(input) => expand_namespace(input).into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand_namespace(input: NamespaceInput) -> TokenStream2 {
+    let ns_crate = match input.options.crate_path {
+        Some(ref path) => quote!(#path),
+        None => namespace_crate_path(),
+    };
 
     // 1. Flatten tree and analyze shape
     let mut flat = Vec::new();
@@ -611,43 +2112,94 @@ pub fn namespace(input: TokenStream) -> TokenStream {
     }
 
     let tree_depth = (max_depth + 1) as usize;
-    let node_count = flat.len();
 
     // 2. Generate tags
-    let tags = generate_tags_recursive(&input.nodes, "", 0, &ns_crate);
+    let tags = generate_tags_recursive(&input.nodes, "", 0, false, &ns_crate, &input.options);
 
     // 3. Generate NamespaceDef entries
     let mut defs = Vec::new();
     collect_defs(&input.nodes, "", None, &ns_crate, &mut defs);
+    let mut mounts = Vec::new();
+    collect_mounts(&input.nodes, "", &mut mounts);
+    let mounted = !mounts.is_empty();
+    let static_registry = generate_static_registry(&defs, mounted, &ns_crate);
+    let defs = defs.iter().map(|DefTokens { cfg, def, .. }| quote!(#cfg #def,));
+    let definitions = if mounted {
+        quote! {
+            #[allow(deprecated)]
+            const LOCAL_DEFINITIONS: &[#ns_crate::NamespaceDef] = &[#(#defs)*];
+
+            /// Flat NamespaceDef table (for runtime registry), `include_defs!`
+            /// tables included.
+            pub const DEFINITIONS: &'static [#ns_crate::NamespaceDef] =
+                &#ns_crate::__private::concat_defs::<{ LOCAL_DEFINITIONS.len() + MOUNTED_LEN }>(
+                    LOCAL_DEFINITIONS,
+                    MOUNTED,
+                );
+        }
+    } else {
+        quote! {
+            /// Flat NamespaceDef table (for runtime registry).
+            #[allow(deprecated)]
+            pub const DEFINITIONS: &'static [#ns_crate::NamespaceDef] = &[
+                #(#defs)*
+            ];
+        }
+    };
+    let mounts = mounted.then(|| generate_mounts(&mounts, &ns_crate));
+
+    // 4. Generate collision detection and level statistics
+    let collision_check = generate_collision_check(&flat);
+    let level_stats = generate_level_stats(&flat, tree_depth);
+    let path_lookup = generate_path_lookup(&input.nodes, mounted, &ns_crate);
+
+    // 5. Leaf prelude (`prelude` is a reserved top-level name)
+    let prelude = generate_prelude(&input.nodes);
 
-    // 4. Generate collision detection
-    let collision_check = generate_collision_check(&flat, &ns_crate);
+    // 6. Top-level child arrays
+    let root_child_arrays = generate_child_arrays(&input.nodes, "", false, &ns_crate);
 
-    // 5. Assemble
+    // 7. Checks for redirects below deprecated nodes
+    let mut redirect_tests = Vec::new();
+    generate_deprecated_redirect_tests(&input.nodes, "", false, &ns_crate, &mut redirect_tests);
+
+    // 8. Assemble
+    let root_attrs = input.root_attrs;
     let vis = input.vis;
     let root = input.root;
 
-    let expanded = quote! {
+    quote! {
+        #(#root_attrs)*
         #[allow(non_snake_case, non_camel_case_types)]
         #vis mod #root {
             /// Number of tree levels in this namespace.
             pub const TREE_DEPTH: usize = #tree_depth;
 
-            /// Total number of namespace nodes.
-            pub const NODE_COUNT: usize = #node_count;
+            /// Number of namespace nodes in `DEFINITIONS` (excludes redirects, `#[no_registry]`
+            /// and nodes whose `#[cfg]` is off).
+            pub const NODE_COUNT: usize = DEFINITIONS.len();
 
-            /// Flat NamespaceDef table (for runtime registry).
-            pub const DEFINITIONS: &'static [#ns_crate::NamespaceDef] = &[
-                #(#defs)*
-            ];
+            #mounts
+
+            #definitions
 
             #collision_check
 
+            #level_stats
+
+            #path_lookup
+
+            #static_registry
+
+            #root_child_arrays
+
+            #prelude
+
             #(#tags)*
-        }
-    };
 
-    expanded.into()
+            #(#redirect_tests)*
+        }
+    }
 }
 
 #[cfg(test)]
@@ -685,7 +2237,7 @@ mod tests {
         ];
 
         let ns_crate = quote!(::bevy_tag);
-        let output = generate_tags_recursive(&nodes, "", 0, &ns_crate);
+        let output = generate_tags_recursive(&nodes, "", 0, false, &ns_crate, &MacroOptions::default());
 
         // Should generate 2 top-level modules (Combat and Movement)
         assert_eq!(output.len(), 2);
@@ -702,7 +2254,12 @@ mod tests {
 
         // Verify no flat re-exports that would cause conflicts
         // The old buggy code would have generated conflicting `pub use combat::Attack`
-        assert!(!code.contains("pub use"));
+        assert!(!code.contains("pub use combat :: Attack"));
+        assert!(!code.contains("pub use Combat :: Attack"));
+
+        // Only the snake_case module aliases are re-exported
+        assert!(code.contains("pub use Combat as combat"));
+        assert!(code.contains("pub use Movement as movement"));
     }
 
     /// Test deeply nested same names don't conflict.
@@ -745,7 +2302,7 @@ mod tests {
         ];
 
         let ns_crate = quote!(::bevy_tag);
-        let output = generate_tags_recursive(&nodes, "", 0, &ns_crate);
+        let output = generate_tags_recursive(&nodes, "", 0, false, &ns_crate, &MacroOptions::default());
 
         let code = quote! { #(#output)* }.to_string();
 
@@ -755,4 +2312,257 @@ mod tests {
         assert!(code.contains("\"A.X\""));
         assert!(code.contains("\"B.X\""));
     }
+
+    #[test]
+    fn test_snake_alias_skips_clashes_and_keywords() {
+        let leaf = |name: &str| Node {
+            name: Ident::new(name, Span::call_site()),
+            data_type: None,
+            attrs: NodeAttrs::default(),
+            children: vec![],
+        };
+        let nodes = vec![leaf("HitPoints"), leaf("Type"), leaf("Mana"), leaf("mana"), leaf("idle")];
+
+        let alias = |i: usize| generate_snake_alias(&nodes[i], &nodes).to_string();
+        assert!(alias(0).contains("pub use HitPoints as hit_points"));
+        assert!(alias(1).is_empty(), "`type` is a keyword");
+        assert!(alias(2).is_empty(), "`mana` is already a sibling");
+        assert!(alias(4).is_empty(), "already snake_case");
+    }
+
+    fn parse_input(tokens: TokenStream2) -> Result<NamespaceInput> {
+        syn::parse2(tokens)
+    }
+
+    #[test]
+    fn test_redirect_chain_is_flattened() {
+        let input = parse_input(quote! {
+            pub mod Tags {
+                #[redirect = "B"] A;
+                #[redirect = "C"] B;
+                C;
+            }
+        })
+        .unwrap();
+
+        match input.nodes[0].attrs.redirect_to {
+            Some(RedirectTarget::Path(ref lit)) => assert_eq!(lit.value(), "C"),
+            _ => panic!("expected path redirect"),
+        }
+    }
+
+    #[test]
+    fn test_redirect_to_missing_target_is_error() {
+        let err = parse_input(quote! {
+            pub mod Tags {
+                #[redirect = "Nope.Missing"] A;
+            }
+        })
+        .err()
+        .expect("missing target must fail");
+        assert!(err.to_string().contains("'Nope.Missing' (from 'A') does not exist"));
+    }
+
+    #[test]
+    fn test_alias_resolves_like_redirect_but_not_both() {
+        let input = parse_input(quote! {
+            pub mod Tags {
+                #[alias = "B"] A;
+                #[redirect = "C"] B;
+                C;
+            }
+        })
+        .unwrap();
+        assert!(input.nodes[0].attrs.is_alias);
+        assert!(!input.nodes[1].attrs.is_alias);
+        match input.nodes[0].attrs.redirect_to {
+            Some(RedirectTarget::Path(ref lit)) => assert_eq!(lit.value(), "C"),
+            _ => panic!("expected path alias"),
+        }
+
+        let err = parse_input(quote! {
+            pub mod Tags { #[alias = "B"] #[redirect = "B"] A; B; }
+        })
+        .err()
+        .expect("alias plus redirect must fail");
+        assert!(err.to_string().contains("only one #[redirect] or #[alias]"));
+    }
+
+    #[test]
+    fn test_redirect_cycles_are_errors() {
+        let self_cycle = parse_input(quote! {
+            pub mod Tags { #[redirect = "A"] A; }
+        })
+        .err()
+        .expect("self redirect must fail");
+        assert!(self_cycle.to_string().contains("redirect cycle: A -> A"));
+
+        let cycle = parse_input(quote! {
+            pub mod Tags {
+                Group {
+                    #[redirect = "Group.B"] A;
+                    #[redirect = "Group.A"] B;
+                }
+            }
+        })
+        .err()
+        .expect("cycle must fail");
+        assert!(cycle
+            .to_string()
+            .contains("redirect cycle: Group.A -> Group.B -> Group.A"));
+    }
+
+    #[test]
+    fn test_deprecated_attr_includes_since_and_redirect() {
+        let input = parse_input(quote! {
+            pub mod Tags {
+                New;
+                #[deprecated(since = "0.4.0", note = "renamed")]
+                #[redirect = "New"]
+                Old;
+                #[redirect = "New"]
+                Older;
+            }
+        })
+        .unwrap();
+
+        let attr = |i: usize| {
+            let node = &input.nodes[i];
+            node.attrs
+                .deprecation
+                .to_attr(node.attrs.redirect_to.as_ref())
+                .to_string()
+        };
+        assert_eq!(
+            attr(1),
+            "# [deprecated (since = \"0.4.0\" , note = \"renamed (redirected to New)\")]"
+        );
+        assert_eq!(attr(2), "# [deprecated (note = \"redirected to New\")]");
+        assert_eq!(attr(0), "");
+    }
+
+    #[test]
+    fn test_level_stats() {
+        let flat = |path: &str| FlatNode {
+            segments: path.split('.').map(String::from).collect(),
+            depth: path.matches('.').count() as u8,
+            cfg: TokenStream2::new(),
+        };
+        let nodes = vec![
+            flat("A"),
+            flat("A.X"),
+            flat("A.Y"),
+            flat("A.Z"),
+            flat("B"),
+            flat("B.X"),
+        ];
+        assert_eq!(level_stats(&nodes, 2), vec![(2, 2), (4, 3)]);
+        assert!(occupancy_percent(1, 3) < OCCUPANCY_WARN_PERCENT);
+        assert_eq!(occupancy_percent(7, 2048), 25.0);
+
+        let code = generate_level_stats(&nodes, 2).to_string();
+        assert!(!code.contains("OCCUPANCY_WARNING"));
+    }
+
+    #[test]
+    fn test_duplicate_siblings_are_errors() {
+        let err = parse_input(quote! {
+            pub mod Tags {
+                Combat { Attack; Block; Attack; }
+            }
+        })
+        .err()
+        .expect("duplicate sibling must fail");
+        assert!(err.to_string().contains("duplicate node 'Attack'"));
+
+        // Same name under different parents is fine
+        assert!(parse_input(quote! {
+            pub mod Tags { A { X; } B { X; } }
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn test_include_defs_mounts_under_a_node() {
+        let input = parse_input(quote! {
+            pub mod Tags {
+                Game { Engine { include_defs!(engine::Tags::DEFINITIONS); Local; } }
+            }
+        })
+        .unwrap();
+        let engine = &input.nodes[0].children[0];
+        assert_eq!(engine.attrs.mounts.len(), 1);
+        assert_eq!(engine.children.len(), 1);
+
+        let err = parse_input(quote! {
+            pub mod Tags { include_defs!(engine::Tags::DEFINITIONS); Game; }
+        })
+        .err()
+        .expect("root-level include_defs! must fail");
+        assert!(err.to_string().contains("must be inside a node"));
+
+        let err = parse_input(quote! {
+            pub mod Tags {
+                Game { Engine; #[redirect = "Game.Engine"] Old { include_defs!(engine::DEFS); } }
+            }
+        })
+        .err()
+        .expect("redirect with include_defs! must fail");
+        assert!(err.to_string().contains("is a redirect"));
+    }
+
+    #[test]
+    fn test_reserved_names_are_errors() {
+        let tag = parse_input(quote! {
+            pub mod Tags { Combat { Tag; } }
+        })
+        .err()
+        .expect("`Tag` must be rejected");
+        assert!(tag.to_string().contains("'Tag' is reserved"));
+
+        let prelude = parse_input(quote! {
+            pub mod Tags { prelude; }
+        })
+        .err()
+        .expect("top-level `prelude` must be rejected");
+        assert!(prelude.to_string().contains("reserved at the top level"));
+
+        // Value-namespace names don't clash with node modules
+        assert!(parse_input(quote! {
+            pub mod Tags { GID; Combat { PATH; prelude; } }
+        })
+        .is_ok());
+    }
+
+    #[test]
+    fn test_redirects_generate_tests() {
+        let input = parse_input(quote! {
+            pub mod Tags {
+                New;
+                #[redirect = "New"]
+                Old;
+                #[deprecated]
+                Legacy {
+                    #[redirect = "New"]
+                    Old;
+                }
+            }
+        })
+        .unwrap();
+        let ns_crate = quote!(::bevy_tag);
+
+        // Next to the redirect, except inside the deprecated node
+        let output = generate_tags_recursive(&input.nodes, "", 0, false, &ns_crate, &input.options);
+        let code = quote! { #(#output)* }.to_string();
+        assert_eq!(code.matches("# [test]").count(), 1);
+        assert!(code.contains("mod __redirect_test_Old"));
+        assert!(code.contains("fn redirect_resolves_to_canonical_target"));
+
+        let mut tests = Vec::new();
+        generate_deprecated_redirect_tests(&input.nodes, "", false, &ns_crate, &mut tests);
+        let code = quote! { #(#tests)* }.to_string();
+        assert_eq!(code.matches("# [test]").count(), 1);
+        assert!(code.contains("mod __redirect_test_Legacy_Old"));
+        assert!(code.contains("Legacy :: Old :: GID"));
+    }
 }
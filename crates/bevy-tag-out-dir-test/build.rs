@@ -0,0 +1,3 @@
+fn main() {
+    bevy_tag_build::generate_to_out_dir("tags.toml").expect("Failed to generate tags");
+}
@@ -0,0 +1,4 @@
+//! The `OUT_DIR` workflow end to end: `build.rs` runs
+//! `bevy_tag_build::generate_to_out_dir` and this crate includes the result.
+
+bevy_tag::include_tags!();
@@ -0,0 +1,9 @@
+use bevy_tag::hierarchical_gid;
+use bevy_tag_out_dir_test::Tags;
+
+#[test]
+fn included_tags_match_their_paths() {
+    assert_eq!(Tags::Item::GID, hierarchical_gid(&[b"Item"]));
+    assert_eq!(Tags::Item::Weapon::GID, hierarchical_gid(&[b"Item", b"Weapon"]));
+    assert_eq!(Tags::Item::CHILD_GIDS.len(), 2);
+}
@@ -0,0 +1,85 @@
+//! Alias system demonstration.
+//!
+//! This example shows how to:
+//! - Use `#[alias = "Target.Path"]` to give a tag a second, permanent name
+//! - The aliased type becomes `Alias<TargetType, _>` automatically
+//! - GID (and `Tag::STABLE_GID`) match the target, PATH stays the alias's own
+//!
+//! ## Alias vs Redirect
+//!
+//! `#[redirect]` marks a path as moved: it is deprecated and reports the
+//! canonical path. `#[alias]` is for names that are meant to stay, such as a
+//! designer-facing synonym, and reports the path it was declared at.
+
+use bevy_tag::bevy::TagContainer;
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Status {
+            Stunned;
+            Burning;
+        }
+
+        // Synonyms used by the quest scripts
+        Quest {
+            #[alias = "Status.Stunned"]
+            Dazed;
+
+            #[alias = "Status.Burning"]
+            OnFire;
+        }
+    }
+}
+
+fn main() {
+    println!("=== Alias System Example ===\n");
+
+    // -------------------------------------------------------------------------
+    // 1. Aliases share the target's GID
+    // -------------------------------------------------------------------------
+    println!("1. Alias GID equality:");
+
+    let stunned_gid = Tags::Status::Stunned::GID;
+    let dazed_gid = Tags::Quest::Dazed::Tag::STABLE_GID;
+
+    println!("   Status.Stunned GID: {:#034x}", stunned_gid);
+    println!("   Quest.Dazed GID:    {:#034x}", dazed_gid);
+    println!("   Are they equal? {}", stunned_gid == dazed_gid);
+    println!();
+
+    // -------------------------------------------------------------------------
+    // 2. PATH stays the alias's own path
+    // -------------------------------------------------------------------------
+    println!("2. PATH keeps the declared name:");
+
+    println!("   Quest.Dazed::PATH:      {}", Tags::Quest::Dazed::PATH);
+    println!(
+        "   canonical_path():       {}",
+        Tags::Quest::Dazed::Tag::canonical_path()
+    );
+    println!();
+
+    // -------------------------------------------------------------------------
+    // 3. Containers only see the GID
+    // -------------------------------------------------------------------------
+    println!("3. Runtime interchangeability:");
+
+    let mut tags = TagContainer::new();
+    tags.insert(Tags::Quest::OnFire::Tag::new().into_gid());
+    println!(
+        "   Inserted Quest.OnFire, has Status.Burning? {}",
+        tags.has(Tags::Status::Burning::GID)
+    );
+    println!(
+        "   lookup(\"Quest.OnFire\") == Status.Burning? {}",
+        Tags::lookup("Quest.OnFire") == Some(Tags::Status::Burning::GID)
+    );
+    println!();
+
+    println!("=== Summary ===");
+    println!();
+    println!("Use `#[alias = \"Target.Path\"]` for permanent synonyms;");
+    println!("use `#[redirect = \"Target.Path\"]` for paths that have moved.");
+}
@@ -71,6 +71,12 @@ fn main() {
     }
     println!();
 
+    println!("Item subtree:");
+    for node in registry.iter_subtree(StaticTags::Item::GID) {
+        println!("  {}{}", "  ".repeat(node.depth as usize), node.path);
+    }
+    println!();
+
     // 5. Descendant checks work across static/dynamic (no registry needed!)
     println!("Descendant checks:");
     let item = StaticTags::Item::GID;
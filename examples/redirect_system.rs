@@ -2,6 +2,8 @@
 //!
 //! This example shows how to:
 //! - Use `#[redirect = "Target.Path"]` to redirect old paths to new canonical paths
+//! - Use `#[redirect(type = other_crate::Tags::Target::Tag)]` when the target lives
+//!   in another namespace
 //! - The redirected type becomes `Redirect<TargetType>` automatically
 //! - GID matches the target, PATH returns canonical path
 //!
@@ -3,6 +3,13 @@
 //! Provides:
 //! - `NamespacePlugin` — builder-pattern plugin to initialize the registry as a Resource
 //! - `TagContainer` — multi-tag component with O(1) membership checks
+//! - `TagDelta` — tags added and removed between two containers, for logs
+//! - `TagValues` — an `f32` per tag, for threat, resistances and the like
+//! - `TagContainer` text form — `{Combat.Attack, Status.Burning}` via
+//!   `display`, `parse` and `parse_registering`
+//! - `TagTimestamps` — when each tag was last added and removed
+//! - `TagGrant` / `GrantedTags` — tags held only while a guard or effect lives,
+//!   with overlapping effects counted in `TagGrantCounts`
 //!
 //! # Example
 //!
@@ -34,11 +41,26 @@
 //! }
 //! ```
 
-use bevy::prelude::*;
-use std::collections::HashSet;
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+    time::Duration,
+};
+
+use bevy::{
+    ecs::{entity::EntityHashMap, lifecycle::HookContext, system::SystemParam, world::DeferredWorld},
+    prelude::*,
+};
 
 use crate::{
-    gid_is_descendant_of,
+    deprecation::{DeprecatedUse, DeprecationPolicy},
+    depth_of, gid_is_descendant_of,
+    migrate::RedirectMap,
+    hasher::{table_heap_bytes, GidMap, GidSet},
+    layout::{parent_of, root_of, SubtreeMask},
+    pattern::TagPattern,
+    traits::{IntoGid, IntoGidWithRegistry},
     registry::{NamespaceDef, NamespaceRegistry},
     GID,
 };
@@ -60,6 +82,26 @@ use crate::{
 #[derive(Default)]
 pub struct NamespacePlugin {
     definitions: Option<&'static [NamespaceDef]>,
+    sealed: Vec<GID>,
+    paths: Vec<String>,
+    #[cfg(feature = "toml")]
+    files: Vec<std::path::PathBuf>,
+    on_error: StartupErrorPolicy,
+    deprecation_policy: DeprecationPolicy,
+}
+
+/// What [`NamespacePlugin`] does when a startup path fails to register or a
+/// tag file can't be loaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StartupErrorPolicy {
+    /// Panic with the error (the default).
+    #[default]
+    Panic,
+    /// Record the error in the [`StartupErrors`] resource, log it through
+    /// `tracing` with that feature, and carry on.
+    Warn,
+    /// Carry on silently.
+    Ignore,
 }
 
 impl NamespacePlugin {
@@ -80,22 +122,212 @@ impl NamespacePlugin {
     pub fn from_definitions(definitions: &'static [NamespaceDef]) -> Self {
         Self {
             definitions: Some(definitions),
+            ..Self::default()
+        }
+    }
+
+    /// Builder: forbid runtime registration under `gid`; other subtrees stay
+    /// open. See [`NamespaceRegistry::seal_subtree`].
+    ///
+    /// ```ignore
+    /// NamespacePlugin::from_definitions(Tags::DEFINITIONS)
+    ///     .with_sealed_subtree(Tags::Engine::GID)
+    ///     .with_sealed_subtree(Tags::Network::GID)
+    /// ```
+    pub fn with_sealed_subtree(mut self, gid: impl IntoGid) -> Self {
+        self.sealed.push(gid.into_gid());
+        self
+    }
+
+    /// Builder: register these paths at startup, after the definitions and
+    /// before any subtree is sealed.
+    ///
+    /// ```ignore
+    /// NamespacePlugin::from_definitions(Tags::DEFINITIONS)
+    ///     .register_paths(["Runtime.Extra", "Runtime.Debug"])
+    /// ```
+    pub fn register_paths<S: Into<String>>(mut self, paths: impl IntoIterator<Item = S>) -> Self {
+        self.paths.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Builder: register the paths listed in a TOML file at startup, after
+    /// [`register_paths`](Self::register_paths). Tags are registered with
+    /// the file path as their source.
+    ///
+    /// The file holds a single top-level `paths` array. This is not
+    /// `bevy-tag-build`'s `tags.toml` format: any other key is an error, so
+    /// a `tags.toml` with dev-only groups or nested children fails to load
+    /// instead of registering part of the file.
+    ///
+    /// ```toml
+    /// paths = ["Mod.Items.Rapier", "Mod.Items.Buckler"]
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn load_from_file(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.files.push(path.into());
+        self
+    }
+
+    /// Builder: how to handle paths and files that fail to load.
+    pub fn on_startup_error(mut self, policy: StartupErrorPolicy) -> Self {
+        self.on_error = policy;
+        self
+    }
+
+    /// Builder: the registry's [`DeprecationPolicy`] for [`TagResolver`]
+    /// lookups, e.g. `Off` in release builds and `Warn` or `Strict` in
+    /// development and CI.
+    ///
+    /// ```ignore
+    /// NamespacePlugin::from_definitions(Tags::DEFINITIONS).deprecation_policy(
+    ///     if cfg!(debug_assertions) { DeprecationPolicy::Warn } else { DeprecationPolicy::Off },
+    /// )
+    /// ```
+    pub fn deprecation_policy(mut self, policy: DeprecationPolicy) -> Self {
+        self.deprecation_policy = policy;
+        self
+    }
+
+    fn startup_error(&self, error: String, errors: &mut Vec<String>) {
+        match self.on_error {
+            StartupErrorPolicy::Panic => panic!("NamespacePlugin: {}", error),
+            StartupErrorPolicy::Warn => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%error, "NamespacePlugin startup registration failed");
+                errors.push(error);
+            }
+            StartupErrorPolicy::Ignore => {}
         }
     }
 }
 
 impl Plugin for NamespacePlugin {
     fn build(&self, app: &mut App) {
-        let registry = if let Some(defs) = self.definitions {
+        let mut registry = if let Some(defs) = self.definitions {
             NamespaceRegistry::build(defs).expect("Failed to build NamespaceRegistry from definitions")
         } else {
             NamespaceRegistry::new()
         };
+        let mut errors = Vec::new();
+        for path in &self.paths {
+            if let Err(error) = registry.register(path) {
+                self.startup_error(error, &mut errors);
+            }
+        }
+        #[cfg(feature = "toml")]
+        for file in &self.files {
+            let source = file.display().to_string();
+            let paths = std::fs::read_to_string(file)
+                .map_err(|e| e.to_string())
+                .and_then(|text| paths_in_toml(&text))
+                .map_err(|e| format!("failed to load '{}': {}", source, e));
+            let paths = match paths {
+                Ok(paths) => paths,
+                Err(error) => {
+                    self.startup_error(error, &mut errors);
+                    continue;
+                }
+            };
+            for path in paths {
+                if let Err(error) = registry.register_with_source(&path, &source) {
+                    self.startup_error(format!("{} (from '{}')", error, source), &mut errors);
+                }
+            }
+        }
+        for &gid in &self.sealed {
+            registry.seal_subtree(gid);
+        }
+        registry.set_deprecation_policy(self.deprecation_policy);
 
-        app.insert_resource(registry);
+        #[cfg(feature = "tracing")]
+        tracing::info!(tags = registry.len(), "NamespacePlugin inserted registry");
+        app.insert_resource(registry)
+            .add_message::<DeprecatedTagUsed>();
+        if self.on_error == StartupErrorPolicy::Warn {
+            app.insert_resource(StartupErrors(errors));
+        }
     }
 }
 
+/// Startup paths and tag files that [`NamespacePlugin`] failed to register
+/// under [`StartupErrorPolicy::Warn`], in the order they were tried.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StartupErrors(pub Vec<String>);
+
+// =============================================================================
+// Checked lookups
+// =============================================================================
+
+/// Sent by [`TagResolver`] when it resolves a deprecated or redirected tag
+/// under [`DeprecationPolicy::Warn`].
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub struct DeprecatedTagUsed(pub DeprecatedUse);
+
+/// Path and GID lookups for data-driven content, with the registry's
+/// [`DeprecationPolicy`] applied and a [`RedirectMap`] resource, if present,
+/// followed.
+///
+/// ```ignore
+/// fn load_abilities(mut resolver: TagResolver, config: Res<AbilityConfig>) -> Result {
+///     for path in &config.tags {
+///         let gid = resolver.resolve_path(path)?;
+///         // ...
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct TagResolver<'w> {
+    registry: Res<'w, NamespaceRegistry>,
+    redirects: Option<Res<'w, RedirectMap>>,
+    used: MessageWriter<'w, DeprecatedTagUsed>,
+}
+
+impl TagResolver<'_> {
+    /// See [`NamespaceRegistry::resolve_path`].
+    pub fn resolve_path(&mut self, path: &str) -> Result<GID, String> {
+        let resolved = self.registry.resolve_path(path, self.redirects.as_deref())?;
+        if let Some(used) = resolved.deprecated {
+            self.used.write(DeprecatedTagUsed(used));
+        }
+        Ok(resolved.gid)
+    }
+
+    /// See [`NamespaceRegistry::resolve_gid`].
+    pub fn resolve_gid(&mut self, gid: GID) -> Result<GID, String> {
+        let resolved = self.registry.resolve_gid(gid, self.redirects.as_deref())?;
+        if let Some(used) = resolved.deprecated {
+            self.used.write(DeprecatedTagUsed(used));
+        }
+        Ok(resolved.gid)
+    }
+
+    /// The underlying registry.
+    pub fn registry(&self) -> &NamespaceRegistry {
+        &self.registry
+    }
+}
+
+/// The top-level `paths` array of a [`NamespacePlugin::load_from_file`] file.
+#[cfg(feature = "toml")]
+fn paths_in_toml(text: &str) -> Result<Vec<String>, String> {
+    let table: toml::Table = text.parse().map_err(|e: toml::de::Error| e.message().to_string())?;
+    if let Some(key) = table.keys().find(|key| *key != "paths") {
+        return Err(format!("unexpected key `{}`; only a top-level `paths` array is read", key));
+    }
+
+    let mut paths = Vec::new();
+    if let Some(list) = table.get("paths") {
+        let list = list.as_array().ok_or("`paths` must be an array of strings")?;
+        for path in list {
+            let path = path.as_str().ok_or("`paths` must be an array of strings")?;
+            paths.push(path.to_string());
+        }
+    }
+    Ok(paths)
+}
+
 // =============================================================================
 // TagContainer Component
 // =============================================================================
@@ -103,7 +335,7 @@ impl Plugin for NamespacePlugin {
 /// A container for multiple namespace tags.
 ///
 /// Use this when an entity can have multiple tags simultaneously.
-/// Provides O(1) membership checks via `HashSet`.
+/// Provides O(1) membership checks via a [`GidSet`].
 ///
 /// # Example
 ///
@@ -129,7 +361,7 @@ impl Plugin for NamespacePlugin {
 /// ```
 #[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
 pub struct TagContainer {
-    tags: HashSet<GID>,
+    tags: GidSet,
 }
 
 impl TagContainer {
@@ -139,10 +371,19 @@ impl TagContainer {
         Self::default()
     }
 
+    /// Create an empty container with room for `capacity` tags, e.g. for
+    /// short-lived entities that always get the same few tags.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            tags: GidSet::with_capacity_and_hasher(capacity, Default::default()),
+        }
+    }
+
     /// Create a container with a single tag.
     #[inline]
     pub fn single(gid: GID) -> Self {
-        let mut tags = HashSet::new();
+        let mut tags = GidSet::default();
         tags.insert(gid);
         Self { tags }
     }
@@ -150,7 +391,7 @@ impl TagContainer {
     /// Builder method: add a tag and return self.
     #[inline]
     pub fn with(mut self, gid: GID) -> Self {
-        self.tags.insert(gid);
+        self.insert(gid);
         self
     }
 
@@ -159,7 +400,12 @@ impl TagContainer {
     /// Returns `true` if the tag was newly inserted.
     #[inline]
     pub fn insert(&mut self, gid: GID) -> bool {
-        self.tags.insert(gid)
+        let added = self.tags.insert(gid);
+        #[cfg(feature = "tracing")]
+        if added {
+            tracing::trace!(gid = %format_args!("{:#034x}", gid), "tag added");
+        }
+        added
     }
 
     /// Remove a tag from the container.
@@ -167,7 +413,59 @@ impl TagContainer {
     /// Returns `true` if the tag was present.
     #[inline]
     pub fn remove(&mut self, gid: GID) -> bool {
-        self.tags.remove(&gid)
+        let removed = self.tags.remove(&gid);
+        #[cfg(feature = "tracing")]
+        if removed {
+            tracing::trace!(gid = %format_args!("{:#034x}", gid), "tag removed");
+        }
+        removed
+    }
+
+    /// Add a tag named by path (`&str`, `&String`, `&TagPath`), by
+    /// `&TagPattern` or by typed tag, resolved through `registry`.
+    ///
+    /// Returns whether the tag was newly inserted, or an error if `item`
+    /// names no registered tag.
+    pub fn insert_by(
+        &mut self,
+        registry: &NamespaceRegistry,
+        item: impl IntoGidWithRegistry,
+    ) -> Result<bool, String> {
+        Ok(self.insert(item.try_into_gid_with(registry)?))
+    }
+
+    /// Remove a tag named as for [`insert_by`](Self::insert_by).
+    ///
+    /// Returns whether the tag was present, or an error if `item` names no
+    /// registered tag.
+    pub fn remove_by(
+        &mut self,
+        registry: &NamespaceRegistry,
+        item: impl IntoGidWithRegistry,
+    ) -> Result<bool, String> {
+        Ok(self.remove(item.try_into_gid_with(registry)?))
+    }
+
+    /// Keep only the tags for which `keep` returns `true`.
+    pub fn retain(&mut self, mut keep: impl FnMut(GID) -> bool) {
+        self.tags.retain(|&gid| {
+            let kept = keep(gid);
+            #[cfg(feature = "tracing")]
+            if !kept {
+                tracing::trace!(gid = %format_args!("{:#034x}", gid), "tag removed");
+            }
+            kept
+        });
+    }
+
+    /// Remove `ancestor` and every tag under it, e.g. all `Status.*`
+    /// effects on cleanse.
+    ///
+    /// Returns the number of tags removed.
+    pub fn remove_descendants_of(&mut self, ancestor: GID) -> usize {
+        let before = self.tags.len();
+        self.retain(|gid| !gid_is_descendant_of(gid, ancestor));
+        before - self.tags.len()
     }
 
     /// Check if the container has a specific tag (O(1)).
@@ -179,12 +477,82 @@ impl TagContainer {
     /// Check if any tag in the container is a descendant of the given ancestor.
     ///
     /// This is O(n) where n is the number of tags in the container.
-    /// For frequent checks, consider caching results or using a different data structure.
+    /// For frequent checks against one ancestor, see [`has_under`](Self::has_under).
     #[inline]
     pub fn has_descendant_of(&self, ancestor: GID) -> bool {
         self.tags.iter().any(|&gid| gid_is_descendant_of(gid, ancestor))
     }
 
+    /// [`has_descendant_of`](Self::has_descendant_of) with a prebuilt mask,
+    /// for systems that test many containers against the same ancestor.
+    #[inline]
+    pub fn has_under(&self, mask: &SubtreeMask) -> bool {
+        self.tags.iter().any(|&gid| mask.contains(gid))
+    }
+
+    /// Check if the container has every tag in `other` (O(len of `other`)).
+    pub fn contains_all(&self, other: &TagContainer) -> bool {
+        other.tags.is_subset(&self.tags)
+    }
+
+    /// Check if the container shares at least one tag with `other`.
+    ///
+    /// Iterates the smaller of the two containers.
+    pub fn contains_any(&self, other: &TagContainer) -> bool {
+        let (small, large) = self.smaller_first(other);
+        small.tags.iter().any(|gid| large.tags.contains(gid))
+    }
+
+    /// Number of tags shared with `other`, without collecting them.
+    ///
+    /// Iterates the smaller of the two containers.
+    pub fn overlap_count(&self, other: &TagContainer) -> usize {
+        let (small, large) = self.smaller_first(other);
+        small.tags.iter().filter(|gid| large.tags.contains(gid)).count()
+    }
+
+    fn smaller_first<'a>(&'a self, other: &'a TagContainer) -> (&'a Self, &'a Self) {
+        if self.tags.len() <= other.tags.len() {
+            (self, other)
+        } else {
+            (other, self)
+        }
+    }
+
+    /// Check if any tag matches `pattern`.
+    pub fn has_match(&self, pattern: &TagPattern, registry: &NamespaceRegistry) -> bool {
+        self.tags.iter().any(|&gid| pattern.matches(gid, registry))
+    }
+
+    /// Tags matching `pattern`.
+    pub fn matching<'a>(
+        &'a self,
+        pattern: &'a TagPattern,
+        registry: &'a NamespaceRegistry,
+    ) -> impl Iterator<Item = GID> + 'a {
+        self.iter().filter(move |&gid| pattern.matches(gid, registry))
+    }
+
+    /// The deepest contained tag that is `query` or one of its ancestors:
+    /// with `Physical` and `Physical.Slash` contained, a `Physical.Slash.Heavy`
+    /// query gives `Physical.Slash`.
+    ///
+    /// O(depth of `query`).
+    pub fn best_match_for(&self, query: GID) -> Option<GID> {
+        std::iter::successors(Some(query), |&gid| parent_of(gid))
+            .find(|gid| self.tags.contains(gid))
+    }
+
+    /// Bucket the tags by top-level ancestor in one pass, e.g. for HUD
+    /// sections. Tags within a bucket are in no particular order.
+    pub fn group_by_root(&self) -> GidMap<Vec<GID>> {
+        let mut groups = GidMap::<Vec<GID>>::default();
+        for &gid in &self.tags {
+            groups.entry(root_of(gid)).or_default().push(gid);
+        }
+        groups
+    }
+
     /// Get all tags that are descendants of the given ancestor.
     pub fn descendants_of(&self, ancestor: GID) -> impl Iterator<Item = GID> + '_ {
         self.tags
@@ -193,12 +561,82 @@ impl TagContainer {
             .filter(move |&gid| gid_is_descendant_of(gid, ancestor))
     }
 
+    /// [`descendants_of`](Self::descendants_of), limited to at most
+    /// `max_extra_depth` levels below `ancestor`.
+    pub fn descendants_of_within(
+        &self,
+        ancestor: GID,
+        max_extra_depth: u8,
+    ) -> impl Iterator<Item = GID> + '_ {
+        let max_depth = depth_of(ancestor).saturating_add(max_extra_depth);
+        self.descendants_of(ancestor)
+            .filter(move |&gid| depth_of(gid) <= max_depth)
+    }
+
     /// Iterate over all tags in the container.
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = GID> + '_ {
         self.tags.iter().copied()
     }
 
+    /// All tags in ascending GID order.
+    pub fn to_sorted_vec(&self) -> Vec<GID> {
+        let mut gids: Vec<GID> = self.tags.iter().copied().collect();
+        gids.sort_unstable();
+        gids
+    }
+
+    /// Order-independent hash of the contained tags, for rollback and replay
+    /// state hashes.
+    ///
+    /// Deterministic across runs, platforms and the `siphash` feature: equal
+    /// tag sets always give equal checksums, whatever the insertion order.
+    pub fn checksum(&self) -> u64 {
+        let sum = self.tags.iter().fold(0u64, |sum, &gid| {
+            sum.wrapping_add(mix64(mix64(gid as u64) ^ (gid >> 64) as u64))
+        });
+        mix64(sum ^ self.tags.len() as u64)
+    }
+
+    /// Tags `other` has and `self` lacks (`added`), and the reverse
+    /// (`removed`): what changed going from `self` to `other`.
+    pub fn diff(&self, other: &TagContainer) -> TagDelta {
+        let mut added: Vec<GID> = other.tags.difference(&self.tags).copied().collect();
+        let mut removed: Vec<GID> = self.tags.difference(&other.tags).copied().collect();
+        added.sort_unstable();
+        removed.sort_unstable();
+        TagDelta { added, removed }
+    }
+
+    /// Format as `{Combat.Attack, Status.Burning}`: paths in alphabetical
+    /// order, then hex GIDs of tags `registry` doesn't know.
+    pub fn display<'a>(&'a self, registry: &'a NamespaceRegistry) -> DisplayTagContainer<'a> {
+        DisplayTagContainer {
+            container: self,
+            registry: Some(registry),
+        }
+    }
+
+    /// Parse the [`display`](Self::display) form. Every path must be
+    /// registered in `registry`; hex GIDs are taken as they are.
+    pub fn parse(s: &str, registry: &NamespaceRegistry) -> Result<Self, String> {
+        parse_items(s)?
+            .map(|item| {
+                parse_hex_gid(item)
+                    .or_else(|| registry.gid_of(item))
+                    .ok_or_else(|| format!("unknown tag '{}'", item))
+            })
+            .collect()
+    }
+
+    /// [`parse`](Self::parse), registering unknown paths in `registry`
+    /// instead of failing.
+    pub fn parse_registering(s: &str, registry: &mut NamespaceRegistry) -> Result<Self, String> {
+        parse_items(s)?
+            .map(|item| parse_hex_gid(item).map_or_else(|| registry.register(item), Ok))
+            .collect()
+    }
+
     /// Get the number of tags in the container.
     #[inline]
     pub fn len(&self) -> usize {
@@ -212,10 +650,176 @@ impl TagContainer {
     }
 
     /// Clear all tags from the container.
+    ///
+    /// Keeps the allocation; see [`shrink_to_fit`](Self::shrink_to_fit).
     #[inline]
     pub fn clear(&mut self) {
         self.tags.clear();
     }
+
+    /// Number of tags the container holds without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.tags.capacity()
+    }
+
+    /// Reserve room for `additional` more tags.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.tags.reserve(additional);
+    }
+
+    /// Shrink the allocation to fit the current tags.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.tags.shrink_to_fit();
+    }
+
+    /// Estimated heap memory used by the container, in bytes.
+    #[inline]
+    pub fn heap_bytes(&self) -> usize {
+        table_heap_bytes::<GID>(self.tags.capacity())
+    }
+}
+
+/// Tags added and removed between two [`TagContainer`]s, from
+/// [`TagContainer::diff`]. Both lists are in ascending GID order.
+///
+/// `Display` writes hex GIDs; [`display`](Self::display) writes paths:
+/// `+Status.Burning, -Movement.Idle`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagDelta {
+    /// Tags only in the newer container.
+    pub added: Vec<GID>,
+    /// Tags only in the older container.
+    pub removed: Vec<GID>,
+}
+
+impl TagDelta {
+    /// No tags were added or removed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+
+    /// Format with tag paths from `registry`; unknown GIDs stay hex.
+    pub fn display<'a>(&'a self, registry: &'a NamespaceRegistry) -> DisplayTagDelta<'a> {
+        DisplayTagDelta {
+            delta: self,
+            registry: Some(registry),
+        }
+    }
+}
+
+impl fmt::Display for TagDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayTagDelta {
+            delta: self,
+            registry: None,
+        }
+        .fmt(f)
+    }
+}
+
+/// [`TagDelta`] formatted with tag paths; see [`TagDelta::display`].
+pub struct DisplayTagDelta<'a> {
+    delta: &'a TagDelta,
+    registry: Option<&'a NamespaceRegistry>,
+}
+
+impl fmt::Display for DisplayTagDelta<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let added = self.delta.added.iter().map(|&gid| ('+', gid));
+        let removed = self.delta.removed.iter().map(|&gid| ('-', gid));
+        for (i, (sign, gid)) in added.chain(removed).enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match self.registry.and_then(|r| r.path_of(gid)) {
+                Some(path) => write!(f, "{}{}", sign, path)?,
+                None => write!(f, "{}{:#034x}", sign, gid)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TagContainer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        DisplayTagContainer {
+            container: self,
+            registry: None,
+        }
+        .fmt(f)
+    }
+}
+
+/// Parses the hex form `Display` writes: `{0x…, 0x…}`. Use
+/// [`TagContainer::parse`] for paths.
+impl FromStr for TagContainer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        parse_items(s)?
+            .map(|item| {
+                parse_hex_gid(item)
+                    .ok_or_else(|| format!("'{}' is not a hex GID (paths need a registry)", item))
+            })
+            .collect()
+    }
+}
+
+/// [`TagContainer`] formatted with tag paths; see [`TagContainer::display`].
+pub struct DisplayTagContainer<'a> {
+    container: &'a TagContainer,
+    registry: Option<&'a NamespaceRegistry>,
+}
+
+impl fmt::Display for DisplayTagContainer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut paths = Vec::new();
+        let mut unknown = Vec::new();
+        for gid in self.container.iter() {
+            match self.registry.and_then(|r| r.path_of(gid)) {
+                Some(path) => paths.push(path),
+                None => unknown.push(gid),
+            }
+        }
+        paths.sort_unstable();
+        unknown.sort_unstable();
+
+        f.write_str("{")?;
+        let unknown = unknown.iter().map(|gid| format!("{:#034x}", gid));
+        for (i, item) in paths.into_iter().map(str::to_string).chain(unknown).enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(&item)?;
+        }
+        f.write_str("}")
+    }
+}
+
+/// Items of `{a, b, c}`, trimmed.
+fn parse_items(s: &str) -> Result<impl Iterator<Item = &str>, String> {
+    let inner = s
+        .trim()
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or_else(|| format!("expected '{{...}}', found '{}'", s))?;
+    Ok(inner.split(',').map(str::trim).filter(|item| !item.is_empty()))
+}
+
+fn parse_hex_gid(item: &str) -> Option<GID> {
+    let hex = item.strip_prefix("0x")?;
+    GID::from_str_radix(hex, 16).ok()
+}
+
+/// SplitMix64 finalizer.
+fn mix64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+    x ^ (x >> 31)
 }
 
 impl FromIterator<GID> for TagContainer {
@@ -232,12 +836,466 @@ impl Extend<GID> for TagContainer {
     }
 }
 
+// =============================================================================
+// TagValues Component
+// =============================================================================
+
+/// Component holding one `f32` per tag.
+///
+/// For threat, resistances, reputation and other values where a tag alone
+/// isn't enough but a metadata struct is overkill. Aggregates over a subtree
+/// take the ancestor itself into account too.
+///
+/// ```ignore
+/// let resist = TagValues::new()
+///     .with(Tags::Damage::Fire::GID, 0.5)
+///     .with(Tags::Damage::Fire::Burn::GID, 0.25);
+/// assert_eq!(resist.sum_under(Tags::Damage::GID), 0.75);
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct TagValues {
+    values: GidMap<f32>,
+}
+
+impl TagValues {
+    /// Create an empty component.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty component with room for `capacity` values.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: GidMap::with_capacity_and_hasher(capacity, Default::default()),
+        }
+    }
+
+    /// Builder method: set a value and return self.
+    #[inline]
+    pub fn with(mut self, gid: GID, value: f32) -> Self {
+        self.set(gid, value);
+        self
+    }
+
+    /// Set the value of `gid`, returning the previous one.
+    #[inline]
+    pub fn set(&mut self, gid: GID, value: f32) -> Option<f32> {
+        self.values.insert(gid, value)
+    }
+
+    /// Remove `gid`, returning its value.
+    #[inline]
+    pub fn remove(&mut self, gid: GID) -> Option<f32> {
+        self.values.remove(&gid)
+    }
+
+    /// Value of `gid`, if set.
+    #[inline]
+    pub fn value_of(&self, gid: GID) -> Option<f32> {
+        self.values.get(&gid).copied()
+    }
+
+    /// Value of the deepest of `query` and its ancestors that has one, e.g.
+    /// the resistance that applies to a damage type. See
+    /// [`TagContainer::best_match_for`].
+    pub fn best_match_for(&self, query: GID) -> Option<(GID, f32)> {
+        std::iter::successors(Some(query), |&gid| parent_of(gid))
+            .find_map(|gid| Some((gid, self.value_of(gid)?)))
+    }
+
+    /// Sum of the values of `ancestor` and its descendants (`0.0` if none).
+    pub fn sum_under(&self, ancestor: GID) -> f32 {
+        self.under(ancestor).map(|(_, value)| value).sum()
+    }
+
+    /// Largest value of `ancestor` and its descendants.
+    pub fn max_under(&self, ancestor: GID) -> Option<f32> {
+        self.under(ancestor)
+            .map(|(_, value)| value)
+            .reduce(f32::max)
+    }
+
+    /// `(tag, value)` pairs of `ancestor` and its descendants.
+    pub fn under(&self, ancestor: GID) -> impl Iterator<Item = (GID, f32)> + '_ {
+        self.iter()
+            .filter(move |&(gid, _)| gid_is_descendant_of(gid, ancestor))
+    }
+
+    /// Iterate over all `(tag, value)` pairs.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (GID, f32)> + '_ {
+        self.values.iter().map(|(&gid, &value)| (gid, value))
+    }
+
+    /// Get the number of tags with a value.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check if no tag has a value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Clear all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Number of values the component holds without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Reserve room for `additional` more values.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional);
+    }
+
+    /// Shrink the allocation to fit the current values.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.values.shrink_to_fit();
+    }
+
+    /// Estimated heap memory used by the component, in bytes.
+    #[inline]
+    pub fn heap_bytes(&self) -> usize {
+        table_heap_bytes::<(GID, f32)>(self.values.capacity())
+    }
+}
+
+impl FromIterator<(GID, f32)> for TagValues {
+    fn from_iter<T: IntoIterator<Item = (GID, f32)>>(iter: T) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<(GID, f32)> for TagValues {
+    fn extend<T: IntoIterator<Item = (GID, f32)>>(&mut self, iter: T) {
+        self.values.extend(iter);
+    }
+}
+
+// =============================================================================
+// TagTimestamps Component
+// =============================================================================
+
+/// Component recording when each tag of the entity's [`TagContainer`] was
+/// last added and removed, for "recently damaged" or "airborne for more
+/// than 0.2 s" checks.
+///
+/// Times are [`Time::elapsed`] values. Add [`update_tag_timestamps`] to the
+/// schedule, or call [`update`](Self::update) yourself. Changes are seen
+/// when the update runs, so a tag added and removed again in between is
+/// missed.
+///
+/// ```ignore
+/// fn can_double_jump(time: Res<Time>, query: Query<&TagTimestamps>) {
+///     for stamps in &query {
+///         let airborne = stamps.time_with(Tags::State::Airborne::GID, time.elapsed());
+///         if airborne.is_some_and(|t| t > Duration::from_millis(200)) {
+///             // ...
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct TagTimestamps {
+    added: GidMap<Duration>,
+    removed: GidMap<Duration>,
+    /// Tags in the container at the last update.
+    held: GidSet,
+}
+
+impl TagTimestamps {
+    /// Create a component with no history.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record tags added to or removed from `container` since the last
+    /// update as happening at `now`.
+    pub fn update(&mut self, container: &TagContainer, now: Duration) {
+        for &gid in container.tags.difference(&self.held) {
+            self.added.insert(gid, now);
+        }
+        for &gid in self.held.difference(&container.tags) {
+            self.removed.insert(gid, now);
+        }
+        self.held.clone_from(&container.tags);
+    }
+
+    /// When `gid` was last added.
+    #[inline]
+    pub fn added_at(&self, gid: GID) -> Option<Duration> {
+        self.added.get(&gid).copied()
+    }
+
+    /// When `gid` was last removed.
+    #[inline]
+    pub fn removed_at(&self, gid: GID) -> Option<Duration> {
+        self.removed.get(&gid).copied()
+    }
+
+    /// How long `gid` has been held, if it is held.
+    pub fn time_with(&self, gid: GID, now: Duration) -> Option<Duration> {
+        if !self.held.contains(&gid) {
+            return None;
+        }
+        Some(now.saturating_sub(self.added_at(gid)?))
+    }
+
+    /// How long since `gid` was removed, if it was held before and isn't now.
+    pub fn time_without(&self, gid: GID, now: Duration) -> Option<Duration> {
+        if self.held.contains(&gid) {
+            return None;
+        }
+        Some(now.saturating_sub(self.removed_at(gid)?))
+    }
+
+    /// Check if `gid` is held, or was removed at most `window` ago.
+    pub fn has_had_since(&self, gid: GID, window: Duration, now: Duration) -> bool {
+        self.held.contains(&gid)
+            || self
+                .time_without(gid, now)
+                .is_some_and(|since| since <= window)
+    }
+
+    /// Forget all history.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.added.clear();
+        self.removed.clear();
+        self.held.clear();
+    }
+}
+
+/// Entities whose [`TagTimestamps`] may be out of date.
+type StaleTimestamps = Or<(Changed<TagContainer>, Added<TagTimestamps>)>;
+
+/// Update every [`TagTimestamps`] whose [`TagContainer`] changed, using
+/// [`Time::elapsed`].
+pub fn update_tag_timestamps(
+    time: Res<Time>,
+    mut query: Query<(&TagContainer, &mut TagTimestamps), StaleTimestamps>,
+) {
+    let now = time.elapsed();
+    for (container, mut stamps) in &mut query {
+        stamps.update(container, now);
+    }
+}
+
+// =============================================================================
+// Tag grants
+// =============================================================================
+
+/// Guard that adds tags to a [`TagContainer`] and removes them again when
+/// dropped, so a temporary state can't outlive the scope that granted it.
+///
+/// Only tags the container didn't already have are removed on drop. The
+/// container stays reachable through `Deref`/`DerefMut` while granted.
+///
+/// ```ignore
+/// {
+///     let tags = TagGrant::new(&mut container, [Tags::Status::Immobile::GID]);
+///     channel(&tags)?; // an early return still removes Status.Immobile
+/// }
+/// assert!(!container.has(Tags::Status::Immobile::GID));
+/// ```
+pub struct TagGrant<'a> {
+    container: &'a mut TagContainer,
+    granted: Vec<GID>,
+}
+
+impl<'a> TagGrant<'a> {
+    /// Insert `tags` into `container` until the guard is dropped.
+    pub fn new(container: &'a mut TagContainer, tags: impl IntoIterator<Item = GID>) -> Self {
+        let granted = tags.into_iter().filter(|&gid| container.insert(gid)).collect();
+        Self { container, granted }
+    }
+
+    /// Tags this guard inserted and will remove.
+    #[inline]
+    pub fn granted(&self) -> &[GID] {
+        &self.granted
+    }
+}
+
+impl Deref for TagGrant<'_> {
+    type Target = TagContainer;
+
+    fn deref(&self) -> &TagContainer {
+        self.container
+    }
+}
+
+impl DerefMut for TagGrant<'_> {
+    fn deref_mut(&mut self) -> &mut TagContainer {
+        self.container
+    }
+}
+
+impl Drop for TagGrant<'_> {
+    fn drop(&mut self) {
+        for &gid in &self.granted {
+            self.container.remove(gid);
+        }
+    }
+}
+
+/// Component granting tags to another entity's [`TagContainer`] for as long
+/// as it exists — the ECS counterpart of [`TagGrant`].
+///
+/// Put it on the effect entity (a channel, an aura, a buff). The tags are
+/// added when the component is inserted and dropped when it is removed,
+/// replaced or despawned, however the effect ends. Grants are counted per
+/// target in [`TagGrantCounts`]: a tag stays until the last grant holding it
+/// ends, and tags the target had before any grant are left alone. Both
+/// updates run as commands, flushed with the command that inserted or
+/// removed the component.
+///
+/// ```ignore
+/// commands.spawn((
+///     Channeling,
+///     GrantedTags::new(caster, [Tags::Status::Immobile::GID]),
+/// ));
+/// ```
+#[derive(Component, Debug)]
+#[component(on_insert = GrantedTags::on_insert, on_replace = GrantedTags::on_replace)]
+pub struct GrantedTags {
+    target: Entity,
+    tags: Vec<GID>,
+}
+
+impl GrantedTags {
+    /// Grant `tags` to `target` while this component exists.
+    pub fn new(target: Entity, tags: impl IntoIterator<Item = GID>) -> Self {
+        Self {
+            target,
+            tags: tags.into_iter().collect(),
+        }
+    }
+
+    /// The entity receiving the tags.
+    #[inline]
+    pub fn target(&self) -> Entity {
+        self.target
+    }
+
+    /// Tags this component grants.
+    #[inline]
+    pub fn tags(&self) -> &[GID] {
+        &self.tags
+    }
+
+    fn on_insert(mut world: DeferredWorld, ctx: HookContext) {
+        let Some(grant) = world.get::<GrantedTags>(ctx.entity) else {
+            return;
+        };
+        let (target, tags) = (grant.target, grant.tags.clone());
+        world.commands().queue(move |world: &mut World| {
+            world.init_resource::<TagGrantCounts>();
+            world.resource_scope(|world, mut counts: Mut<TagGrantCounts>| {
+                let mut container = world.get_mut::<TagContainer>(target);
+                counts.acquire(target, &tags, container.as_deref_mut());
+            });
+        });
+    }
+
+    fn on_replace(mut world: DeferredWorld, ctx: HookContext) {
+        let Some(grant) = world.get::<GrantedTags>(ctx.entity) else {
+            return;
+        };
+        let (target, tags) = (grant.target, grant.tags.clone());
+        world.commands().queue(move |world: &mut World| {
+            world.init_resource::<TagGrantCounts>();
+            world.resource_scope(|world, mut counts: Mut<TagGrantCounts>| {
+                let mut container = world.get_mut::<TagContainer>(target);
+                counts.release(target, &tags, container.as_deref_mut());
+            });
+        });
+    }
+}
+
+/// Active [`GrantedTags`] per target and tag, so overlapping effects that
+/// grant the same tag keep it until the last of them ends.
+///
+/// Initialized on first use.
+#[derive(Resource, Default, Debug)]
+pub struct TagGrantCounts {
+    targets: EntityHashMap<GidMap<GrantCount>>,
+}
+
+#[derive(Default, Debug)]
+struct GrantCount {
+    active: u32,
+    /// A grant inserted the tag, so the last grant to end removes it.
+    inserted: bool,
+}
+
+impl TagGrantCounts {
+    /// Number of live grants of `gid` on `target`.
+    pub fn count(&self, target: Entity, gid: GID) -> u32 {
+        self.targets
+            .get(&target)
+            .and_then(|counts| counts.get(&gid))
+            .map_or(0, |count| count.active)
+    }
+
+    fn acquire(&mut self, target: Entity, tags: &[GID], mut container: Option<&mut TagContainer>) {
+        let counts = self.targets.entry(target).or_default();
+        for &gid in tags {
+            let count = counts.entry(gid).or_default();
+            count.active += 1;
+            if let Some(container) = container.as_deref_mut() {
+                count.inserted |= container.insert(gid);
+            }
+        }
+    }
+
+    fn release(&mut self, target: Entity, tags: &[GID], mut container: Option<&mut TagContainer>) {
+        let Some(counts) = self.targets.get_mut(&target) else {
+            return;
+        };
+        for gid in tags {
+            let Some(count) = counts.get_mut(gid) else {
+                continue;
+            };
+            count.active -= 1;
+            if count.active == 0 {
+                if count.inserted
+                    && let Some(container) = container.as_deref_mut()
+                {
+                    container.remove(*gid);
+                }
+                counts.remove(gid);
+            }
+        }
+        if counts.is_empty() {
+            self.targets.remove(&target);
+        }
+    }
+}
+
 // =============================================================================
 // Resource impl for NamespaceRegistry
 // =============================================================================
 
 impl Resource for NamespaceRegistry {}
 
+impl Resource for RedirectMap {}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -273,6 +1331,207 @@ mod tests {
         assert!(container.is_empty());
     }
 
+    #[test]
+    fn tag_container_set_comparisons() {
+        let party: TagContainer = [1, 2, 3].into_iter().collect();
+        let wanted: TagContainer = [2, 3].into_iter().collect();
+        let other: TagContainer = [3, 4, 5, 6].into_iter().collect();
+
+        assert!(party.contains_all(&wanted));
+        assert!(!wanted.contains_all(&party));
+        assert!(party.contains_all(&TagContainer::new()));
+        assert!(party.contains_any(&other) && other.contains_any(&party));
+        assert!(!wanted.contains_any(&TagContainer::single(1)));
+        assert_eq!(party.overlap_count(&other), 1);
+        assert_eq!(other.overlap_count(&wanted), 1);
+        assert_eq!(party.overlap_count(&party), 3);
+    }
+
+    #[test]
+    fn tag_container_bulk_removal() {
+        let status = crate::hierarchical_gid(&[b"Status"]);
+        let burning = crate::hierarchical_gid(&[b"Status", b"Burning"]);
+        let stunned = crate::hierarchical_gid(&[b"Status", b"Stunned"]);
+        let block = crate::hierarchical_gid(&[b"Combat", b"Block"]);
+        let mut container: TagContainer = [status, burning, stunned, block].into_iter().collect();
+
+        assert_eq!(container.remove_descendants_of(status), 3);
+        assert_eq!(container.to_sorted_vec(), vec![block]);
+        assert_eq!(container.remove_descendants_of(status), 0);
+
+        container.extend([burning, stunned]);
+        container.retain(|gid| gid != stunned);
+        let mut expected = vec![burning, block];
+        expected.sort_unstable();
+        assert_eq!(container.to_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn tag_container_insert_by_path_and_pattern() {
+        let registry = NamespaceRegistry::build(&[
+            NamespaceDef::new("Status", None),
+            NamespaceDef::new("Status.Stunned", Some("Status")),
+        ])
+        .unwrap();
+        let status = registry.gid_of("Status").unwrap();
+        let stunned = registry.gid_of("Status.Stunned").unwrap();
+        let mut container = TagContainer::new();
+
+        assert_eq!(container.insert_by(&registry, "Status.Stunned"), Ok(true));
+        assert_eq!(container.insert_by(&registry, &String::from("Status.Stunned")), Ok(false));
+        let path: crate::TagPath = "Status".parse().unwrap();
+        assert_eq!(container.insert_by(&registry, &path), Ok(true));
+        assert_eq!(container.to_sorted_vec(), {
+            let mut expected = vec![status, stunned];
+            expected.sort_unstable();
+            expected
+        });
+
+        assert_eq!(container.remove_by(&registry, &TagPattern::glob("Status")), Ok(true));
+        assert!(container.remove_by(&registry, &TagPattern::subtree(stunned)).is_err());
+        assert_eq!(container.remove_by(&registry, &TagPattern::exact(stunned)), Ok(true));
+        assert!(container.insert_by(&registry, "Status.Missing").is_err());
+        assert!(container.insert_by(&registry, &TagPattern::glob("Status.*")).is_err());
+        assert!(container.is_empty());
+    }
+
+    #[test]
+    fn tag_grant_removes_only_what_it_added() {
+        let mut container = TagContainer::new().with(1);
+        {
+            let mut grant = TagGrant::new(&mut container, [1, 2, 3]);
+            assert_eq!(grant.granted(), [2, 3]);
+            assert!(grant.has(1) && grant.has(2) && grant.has(3));
+            grant.insert(4);
+        }
+        assert_eq!(container.to_sorted_vec(), vec![1, 4]);
+    }
+
+    #[test]
+    fn granted_tags_follow_the_effect_entity() {
+        let mut world = World::new();
+        let target = world.spawn(TagContainer::single(1)).id();
+
+        let effect = world.spawn(GrantedTags::new(target, [1, 2])).id();
+        assert_eq!(world.get::<TagContainer>(target).unwrap().to_sorted_vec(), vec![1, 2]);
+        world.despawn(effect);
+        assert_eq!(world.get::<TagContainer>(target).unwrap().to_sorted_vec(), vec![1]);
+
+        let effect = world.spawn(GrantedTags::new(target, [3])).id();
+        assert!(world.get::<TagContainer>(target).unwrap().has(3));
+        world.entity_mut(effect).remove::<GrantedTags>();
+        assert!(!world.get::<TagContainer>(target).unwrap().has(3));
+    }
+
+    #[test]
+    fn overlapping_grants_keep_the_tag_until_the_last_ends() {
+        let mut world = World::new();
+        let target = world.spawn(TagContainer::single(1)).id();
+
+        let first = world.spawn(GrantedTags::new(target, [1, 2])).id();
+        let second = world.spawn(GrantedTags::new(target, [1, 2])).id();
+        assert_eq!(world.resource::<TagGrantCounts>().count(target, 2), 2);
+
+        world.despawn(first);
+        assert_eq!(world.get::<TagContainer>(target).unwrap().to_sorted_vec(), vec![1, 2]);
+        world.despawn(second);
+        // Tag 1 predates both grants
+        assert_eq!(world.get::<TagContainer>(target).unwrap().to_sorted_vec(), vec![1]);
+        assert_eq!(world.resource::<TagGrantCounts>().count(target, 2), 0);
+    }
+
+    #[test]
+    fn best_match_is_the_deepest_ancestor() {
+        let physical = crate::hierarchical_gid(&[b"Physical"]);
+        let slash = crate::hierarchical_gid(&[b"Physical", b"Slash"]);
+        let heavy = crate::hierarchical_gid(&[b"Physical", b"Slash", b"Heavy"]);
+        let pierce = crate::hierarchical_gid(&[b"Physical", b"Pierce"]);
+        let fire = crate::hierarchical_gid(&[b"Fire"]);
+
+        let container = TagContainer::new().with(physical).with(slash);
+        assert_eq!(container.best_match_for(heavy), Some(slash));
+        assert_eq!(container.best_match_for(slash), Some(slash));
+        assert_eq!(container.best_match_for(pierce), Some(physical));
+        assert_eq!(container.best_match_for(fire), None);
+
+        let resist = TagValues::new().with(physical, 0.1).with(slash, 0.5);
+        assert_eq!(resist.best_match_for(heavy), Some((slash, 0.5)));
+        assert_eq!(resist.best_match_for(pierce), Some((physical, 0.1)));
+        assert_eq!(resist.best_match_for(fire), None);
+    }
+
+    #[test]
+    fn tag_container_text_roundtrip() {
+        let defs = &[
+            NamespaceDef::new("Combat", None),
+            NamespaceDef::new("Combat.Attack", Some("Combat")),
+            NamespaceDef::new("Status", None),
+            NamespaceDef::new("Status.Burning", Some("Status")),
+        ];
+        let mut registry = NamespaceRegistry::build(defs).unwrap();
+        let attack = registry.gid_of("Combat.Attack").unwrap();
+        let burning = registry.gid_of("Status.Burning").unwrap();
+
+        let container = TagContainer::new().with(burning).with(attack);
+        let text = container.display(&registry).to_string();
+        assert_eq!(text, "{Combat.Attack, Status.Burning}");
+        assert_eq!(TagContainer::parse(&text, &registry), Ok(container.clone()));
+        assert_eq!(container.to_string().parse::<TagContainer>(), Ok(container));
+        assert_eq!(TagContainer::parse(" { } ", &registry), Ok(TagContainer::new()));
+
+        let with_unknown = TagContainer::single(1).with(attack);
+        assert_eq!(
+            with_unknown.display(&registry).to_string(),
+            format!("{{Combat.Attack, {:#034x}}}", 1)
+        );
+
+        assert!(TagContainer::parse("{Combat.Block}", &registry).is_err());
+        assert!(TagContainer::parse("Combat.Attack", &registry).is_err());
+        assert!("{Combat.Attack}".parse::<TagContainer>().is_err());
+
+        let text = "{Combat.Block, Status.Burning}";
+        let parsed = TagContainer::parse_registering(text, &mut registry);
+        let block = registry.gid_of("Combat.Block").unwrap();
+        assert_eq!(parsed, Ok(TagContainer::new().with(block).with(burning)));
+    }
+
+    #[test]
+    fn tag_timestamps_track_additions_and_removals() {
+        let secs = Duration::from_secs;
+        let mut container = TagContainer::single(1);
+        let mut stamps = TagTimestamps::new();
+        stamps.update(&container, secs(1));
+
+        container.insert(2);
+        container.remove(1);
+        stamps.update(&container, secs(3));
+        assert_eq!(stamps.added_at(1), Some(secs(1)));
+        assert_eq!(stamps.removed_at(1), Some(secs(3)));
+        assert_eq!(stamps.time_with(2, secs(5)), Some(secs(2)));
+        assert_eq!(stamps.time_with(1, secs(5)), None);
+        assert_eq!(stamps.time_without(1, secs(5)), Some(secs(2)));
+        assert_eq!(stamps.time_without(3, secs(5)), None);
+
+        assert!(stamps.has_had_since(2, secs(0), secs(5)));
+        assert!(stamps.has_had_since(1, secs(2), secs(5)));
+        assert!(!stamps.has_had_since(1, secs(1), secs(5)));
+        assert!(!stamps.has_had_since(3, secs(10), secs(5)));
+    }
+
+    #[test]
+    fn update_tag_timestamps_uses_elapsed_time() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        let entity = world.spawn((TagContainer::single(1), TagTimestamps::new())).id();
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(2));
+        world.run_system_once(update_tag_timestamps).unwrap();
+
+        let stamps = world.get::<TagTimestamps>(entity).unwrap();
+        assert_eq!(stamps.added_at(1), Some(Duration::from_secs(2)));
+    }
+
     #[test]
     fn tag_container_from_iter() {
         let container: TagContainer = [1, 2, 3].into_iter().collect();
@@ -286,6 +1545,263 @@ mod tests {
         assert_eq!(container.len(), 3);
     }
 
+    #[test]
+    fn tag_container_masks_and_groups() {
+        let movement = crate::hierarchical_gid(&[b"Movement"]);
+        let idle = crate::hierarchical_gid(&[b"Movement", b"Idle"]);
+        let block = crate::hierarchical_gid(&[b"Combat", b"Block"]);
+        let container = TagContainer::new().with(idle).with(block).with(movement);
+
+        assert!(container.has_under(&SubtreeMask::new(movement)));
+        assert!(!container.has_under(&SubtreeMask::new(crate::hierarchical_gid(&[b"Ui"]))));
+
+        let groups = container.group_by_root();
+        assert_eq!(groups.len(), 2);
+        let mut moves = groups[&movement].clone();
+        moves.sort_unstable();
+        let mut expected = vec![movement, idle];
+        expected.sort_unstable();
+        assert_eq!(moves, expected);
+        assert_eq!(groups[&crate::hierarchical_gid(&[b"Combat"])], [block]);
+    }
+
+    #[test]
+    fn tag_container_descendants_within() {
+        let ui = crate::hierarchical_gid(&[b"Ui"]);
+        let menu = crate::hierarchical_gid(&[b"Ui", b"Menu"]);
+        let options = crate::hierarchical_gid(&[b"Ui", b"Menu", b"Options"]);
+        let container = TagContainer::new().with(ui).with(menu).with(options);
+
+        let mut within: Vec<GID> = container.descendants_of_within(ui, 1).collect();
+        within.sort_unstable();
+        let mut expected = vec![ui, menu];
+        expected.sort_unstable();
+        assert_eq!(within, expected);
+        assert_eq!(container.descendants_of_within(menu, 0).collect::<Vec<_>>(), [menu]);
+    }
+
+    #[test]
+    fn tag_container_sorted_and_checksum() {
+        let a: TagContainer = [3, 1 << 100, 2].into_iter().collect();
+        let b: TagContainer = [2, 3, 1 << 100].into_iter().collect();
+        assert_eq!(a.to_sorted_vec(), [2, 3, 1 << 100]);
+        assert_eq!(a.checksum(), b.checksum());
+        assert_ne!(a.checksum(), TagContainer::single(2).checksum());
+        assert_ne!(TagContainer::new().checksum(), TagContainer::single(0).checksum());
+        // Pinned: replays recorded by older builds must still verify
+        assert_eq!(b.checksum(), 0xc4db_0243_a6b4_23e4);
+    }
+
+    #[test]
+    fn tag_container_diff() {
+        let mut registry = NamespaceRegistry::new();
+        let idle = registry.register("Movement.Idle").unwrap();
+        let burning = registry.register("Status.Burning").unwrap();
+        let block = registry.register("Combat.Block").unwrap();
+        let before = TagContainer::new().with(idle).with(block);
+        let after = TagContainer::new().with(burning).with(block).with(7);
+
+        let delta = before.diff(&after);
+        let mut added = vec![burning, 7];
+        added.sort_unstable();
+        assert_eq!(delta, TagDelta { added, removed: vec![idle] });
+        assert!(before.diff(&before).is_empty());
+        assert_eq!(after.diff(&before).removed, delta.added);
+
+        let delta = TagDelta {
+            added: vec![burning, 7],
+            removed: vec![idle],
+        };
+        assert_eq!(
+            delta.display(&registry).to_string(),
+            "+Status.Burning, +0x00000000000000000000000000000007, -Movement.Idle"
+        );
+        assert!(delta.to_string().starts_with(&format!("+{:#034x}, ", burning)));
+        assert_eq!(TagDelta::default().to_string(), "");
+    }
+
+    #[test]
+    fn tag_values_aggregate_subtrees() {
+        let damage = crate::hierarchical_gid(&[b"Damage"]);
+        let fire = crate::hierarchical_gid(&[b"Damage", b"Fire"]);
+        let burn = crate::hierarchical_gid(&[b"Damage", b"Fire", b"Burn"]);
+        let frost = crate::hierarchical_gid(&[b"Damage", b"Frost"]);
+        let stealth = crate::hierarchical_gid(&[b"Stealth"]);
+        let mut values = TagValues::new()
+            .with(fire, 0.5)
+            .with(burn, 0.25)
+            .with(frost, -1.0)
+            .with(stealth, 3.0);
+
+        assert_eq!(values.value_of(fire), Some(0.5));
+        assert_eq!(values.value_of(damage), None);
+        assert_eq!(values.sum_under(damage), -0.25);
+        assert_eq!(values.sum_under(fire), 0.75);
+        assert_eq!(values.max_under(damage), Some(0.5));
+        assert_eq!(values.max_under(crate::hierarchical_gid(&[b"Ui"])), None);
+        assert_eq!(values.sum_under(crate::hierarchical_gid(&[b"Ui"])), 0.0);
+
+        assert_eq!(values.set(fire, 2.0), Some(0.5));
+        assert_eq!(values.max_under(damage), Some(2.0));
+        assert_eq!(values.remove(stealth), Some(3.0));
+        assert_eq!(values.len(), 3);
+        let collected: TagValues = values.iter().collect();
+        assert_eq!(collected, values);
+    }
+
+    #[test]
+    fn tag_container_patterns() {
+        let mut registry = NamespaceRegistry::new();
+        let idle = registry.register("Movement.Idle").unwrap();
+        let block = registry.register("Combat.Block").unwrap();
+        let container = TagContainer::new().with(idle).with(block);
+
+        let movement = TagPattern::subtree(registry.gid_of("Movement").unwrap());
+        assert!(container.has_match(&movement, &registry));
+        assert!(!container.has_match(&TagPattern::glob("*.Run"), &registry));
+        let blocks: Vec<GID> = container
+            .matching(&TagPattern::glob("*.Block"), &registry)
+            .collect();
+        assert_eq!(blocks, [block]);
+    }
+
+    #[test]
+    fn plugin_seals_subtrees() {
+        let engine = crate::hierarchical_gid(&[b"Engine"]);
+        let mut app = App::new();
+        NamespacePlugin::new().with_sealed_subtree(engine).build(&mut app);
+
+        let mut registry = app.world_mut().resource_mut::<NamespaceRegistry>();
+        assert!(registry.register("Engine.Render").is_err());
+        assert!(registry.register("Mods.Sword").is_ok());
+    }
+
+    #[test]
+    fn plugin_registers_startup_paths() {
+        let mut app = App::new();
+        NamespacePlugin::new()
+            .register_paths(["Runtime.Extra", ""])
+            .on_startup_error(StartupErrorPolicy::Ignore)
+            .with_sealed_subtree(crate::hierarchical_gid(&[b"Runtime"]))
+            .build(&mut app);
+
+        let registry = app.world().resource::<NamespaceRegistry>();
+        assert!(registry.gid_of("Runtime.Extra").is_some());
+        assert!(registry.is_sealed(registry.gid_of("Runtime").unwrap()));
+    }
+
+    #[test]
+    fn tag_resolver_reports_deprecated_uses() {
+        use bevy::ecs::system::RunSystemOnce;
+
+        static DEFS: &[NamespaceDef] = &[
+            NamespaceDef::new("Combat", None),
+            NamespaceDef::new("Combat.Slash", Some("Combat")).with_deprecation(
+                crate::Deprecation {
+                    since: None,
+                    note: None,
+                },
+            ),
+        ];
+        let mut app = App::new();
+        NamespacePlugin::from_definitions(DEFS)
+            .deprecation_policy(DeprecationPolicy::Warn)
+            .build(&mut app);
+        let mut redirects = RedirectMap::new();
+        redirects.insert_path("Combat.Cut", "Combat.Slash");
+        app.insert_resource(redirects);
+
+        let world = app.world_mut();
+        let slash = world.resource::<NamespaceRegistry>().gid_of("Combat.Slash").unwrap();
+        let resolved = world
+            .run_system_once(move |mut resolver: TagResolver| {
+                (
+                    resolver.resolve_path("Combat.Cut"),
+                    resolver.resolve_gid(slash),
+                    resolver.resolve_path("Combat"),
+                )
+            })
+            .unwrap();
+        let combat = crate::hierarchical_gid(&[b"Combat"]);
+        assert_eq!(resolved, (Ok(slash), Ok(slash), Ok(combat)));
+        assert_eq!(world.resource::<Messages<DeprecatedTagUsed>>().len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty path")]
+    fn plugin_panics_on_startup_errors_by_default() {
+        NamespacePlugin::new()
+            .register_paths([""])
+            .build(&mut App::new());
+    }
+
+    #[test]
+    fn plugin_records_startup_errors_under_warn() {
+        let mut app = App::new();
+        NamespacePlugin::new()
+            .register_paths(["", "Combat"])
+            .on_startup_error(StartupErrorPolicy::Warn)
+            .build(&mut app);
+
+        let errors = app.world().resource::<StartupErrors>();
+        assert_eq!(errors.0.len(), 1);
+        assert!(errors.0[0].contains("empty path"), "{:?}", errors);
+        assert!(app.world().resource::<NamespaceRegistry>().gid_of("Combat").is_some());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn plugin_loads_tag_files() {
+        let name = format!("bevy-tag-{}-mod_tags.toml", std::process::id());
+        let file = std::env::temp_dir().join(name);
+        std::fs::write(&file, "paths = [\"Mod.Root\", \"Mod.Items.Rapier\"]\n").unwrap();
+
+        let mut app = App::new();
+        NamespacePlugin::new()
+            .load_from_file(&file)
+            .load_from_file(file.with_extension("missing"))
+            .on_startup_error(StartupErrorPolicy::Ignore)
+            .build(&mut app);
+        std::fs::remove_file(&file).unwrap();
+
+        let registry = app.world().resource::<NamespaceRegistry>();
+        for path in ["Mod.Root", "Mod.Items.Rapier"] {
+            assert!(registry.gid_of(path).is_some(), "{path}");
+        }
+        let source = file.display().to_string();
+        let rapier = registry.gid_of("Mod.Items.Rapier").unwrap();
+        assert_eq!(registry.source_of(rapier), Some(source.as_str()));
+
+        assert!(paths_in_toml("paths = 3").is_err());
+        assert!(paths_in_toml("paths = [").is_err());
+        // bevy-tag-build's format is rejected rather than read in part
+        assert!(paths_in_toml("[tags]\npaths = [\"A\"]\n").is_err());
+        assert!(paths_in_toml("paths = [\"A\"]\n[tags.dev-only]\npaths = [\"B\"]\n").is_err());
+    }
+
+    #[test]
+    fn capacity_and_heap_bytes() {
+        let mut container = TagContainer::with_capacity(32);
+        assert!(container.capacity() >= 32);
+        assert_eq!(TagContainer::new().heap_bytes(), 0);
+        let reserved = container.heap_bytes();
+        assert!(reserved >= 32 * std::mem::size_of::<GID>());
+
+        container.insert(1);
+        container.shrink_to_fit();
+        assert!(container.heap_bytes() < reserved);
+        container.clear();
+        container.shrink_to_fit();
+        assert_eq!(container.heap_bytes(), 0);
+
+        let mut values = TagValues::with_capacity(8);
+        assert!(values.capacity() >= 8);
+        values.set(1, 1.0);
+        assert!(values.heap_bytes() > 8 * std::mem::size_of::<(GID, f32)>());
+        values.shrink_to_fit();
+        assert!(values.capacity() >= 1);
+    }
+
     #[test]
     fn tag_container_clear() {
         let mut container = TagContainer::new().with(1).with(2);
@@ -0,0 +1,225 @@
+//! Runtime enforcement of deprecated and redirected tags.
+//!
+//! `#[deprecated]` and `#[redirect]` only warn when Rust code names a tag.
+//! Paths and GIDs that arrive as data (configs, saves, mods) bypass the
+//! compiler, so [`NamespaceRegistry::resolve_path`] and
+//! [`NamespaceRegistry::resolve_gid`] apply the registry's
+//! [`DeprecationPolicy`] instead: silent in shipping builds, reported in
+//! development, an error in CI.
+//!
+//! ```
+//! use bevy_tag::{Deprecation, DeprecationPolicy, NamespaceDef, NamespaceRegistry};
+//!
+//! let mut registry = NamespaceRegistry::build(&[
+//!     NamespaceDef::new("Combat", None),
+//!     NamespaceDef::new("Combat.Slash", Some("Combat")).with_deprecation(Deprecation {
+//!         since: None,
+//!         note: Some("use Combat.Strike"),
+//!     }),
+//! ])
+//! .unwrap();
+//!
+//! assert!(registry.resolve_path("Combat.Slash", None).is_ok());
+//! registry.set_deprecation_policy(DeprecationPolicy::Strict);
+//! assert!(registry.resolve_path("Combat.Slash", None).is_err());
+//! ```
+
+use std::fmt;
+
+use crate::migrate::RedirectMap;
+use crate::registry::{Deprecation, NamespaceRegistry};
+use crate::GID;
+
+/// What checked lookups do when they resolve a deprecated or redirected tag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DeprecationPolicy {
+    /// Resolve silently (the default).
+    #[default]
+    Off,
+    /// Resolve and report the use in [`Resolved::deprecated`] (and as a
+    /// `DeprecatedTagUsed` message through `TagResolver`), logged through
+    /// `tracing` with that feature.
+    Warn,
+    /// Fail the lookup.
+    Strict,
+}
+
+/// A deprecated or redirected tag that was resolved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeprecatedUse {
+    /// A tag declared `#[deprecated]`.
+    Deprecated {
+        gid: GID,
+        path: String,
+        deprecation: Deprecation,
+    },
+    /// An old path or GID followed through a [`RedirectMap`].
+    Redirected { from: String, to: GID },
+}
+
+impl fmt::Display for DeprecatedUse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deprecated {
+                path, deprecation, ..
+            } => {
+                write!(f, "tag '{}' is deprecated", path)?;
+                if let Some(since) = deprecation.since {
+                    write!(f, " since {}", since)?;
+                }
+                if let Some(note) = deprecation.note {
+                    write!(f, ": {}", note)?;
+                }
+                Ok(())
+            }
+            Self::Redirected { from, to } => {
+                write!(f, "'{}' is redirected to {:#034x}", from, to)
+            }
+        }
+    }
+}
+
+/// Result of a checked lookup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Resolved {
+    pub gid: GID,
+    /// The deprecated use, under [`DeprecationPolicy::Warn`].
+    pub deprecated: Option<DeprecatedUse>,
+}
+
+impl NamespaceRegistry {
+    /// GID of `path`, following a path redirect in `redirects` first, with
+    /// the [`deprecation_policy`](Self::deprecation_policy) applied.
+    ///
+    /// Errors if the path is not registered, or if it is deprecated or
+    /// redirected under [`DeprecationPolicy::Strict`].
+    pub fn resolve_path(
+        &self,
+        path: &str,
+        redirects: Option<&RedirectMap>,
+    ) -> Result<Resolved, String> {
+        let target = redirects.map_or(path, |r| r.resolve_path(path));
+        let gid = self
+            .gid_of(target)
+            .ok_or_else(|| format!("tag path '{}' is not registered", target))?;
+        let used = if target != path {
+            Some(DeprecatedUse::Redirected {
+                from: path.to_string(),
+                to: gid,
+            })
+        } else {
+            self.deprecated_use_of(gid)
+        };
+        self.enforce(gid, used)
+    }
+
+    /// `gid`, following a GID redirect in `redirects` first, with the
+    /// [`deprecation_policy`](Self::deprecation_policy) applied.
+    ///
+    /// Errors as [`resolve_path`](Self::resolve_path) does.
+    pub fn resolve_gid(
+        &self,
+        gid: GID,
+        redirects: Option<&RedirectMap>,
+    ) -> Result<Resolved, String> {
+        let target = redirects.map_or(gid, |r| r.resolve(gid));
+        if !self.contains_gid(target) {
+            return Err(format!("GID {:#034x} is not registered", target));
+        }
+        let used = if target != gid {
+            Some(DeprecatedUse::Redirected {
+                from: format!("{:#034x}", gid),
+                to: target,
+            })
+        } else {
+            self.deprecated_use_of(target)
+        };
+        self.enforce(target, used)
+    }
+
+    fn deprecated_use_of(&self, gid: GID) -> Option<DeprecatedUse> {
+        let deprecation = *self.deprecation_of(gid)?;
+        Some(DeprecatedUse::Deprecated {
+            gid,
+            path: self.path_of(gid).unwrap_or_default().to_string(),
+            deprecation,
+        })
+    }
+
+    fn enforce(&self, gid: GID, used: Option<DeprecatedUse>) -> Result<Resolved, String> {
+        let deprecated = match (self.deprecation_policy(), used) {
+            (DeprecationPolicy::Off, _) | (_, None) => None,
+            (DeprecationPolicy::Warn, Some(used)) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(%used, "deprecated tag resolved");
+                Some(used)
+            }
+            (DeprecationPolicy::Strict, Some(used)) => return Err(used.to_string()),
+        };
+        Ok(Resolved { gid, deprecated })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::NamespaceDef;
+
+    fn registry() -> NamespaceRegistry {
+        NamespaceRegistry::build(&[
+            NamespaceDef::new("Combat", None),
+            NamespaceDef::new("Combat.Strike", Some("Combat")),
+            NamespaceDef::new("Combat.Slash", Some("Combat")).with_deprecation(Deprecation {
+                since: Some("0.3"),
+                note: Some("use Combat.Strike"),
+            }),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn policy_controls_deprecated_and_redirected_lookups() {
+        let mut registry = registry();
+        let strike = registry.gid_of("Combat.Strike").unwrap();
+        let slash = registry.gid_of("Combat.Slash").unwrap();
+        let mut redirects = RedirectMap::new();
+        redirects.insert_path("Combat.Hit", "Combat.Strike");
+        redirects.insert(0x1234, strike);
+
+        let quiet = registry.resolve_path("Combat.Slash", None).unwrap();
+        assert_eq!(
+            quiet,
+            Resolved {
+                gid: slash,
+                deprecated: None
+            }
+        );
+        assert!(registry.resolve_path("Combat.Missing", None).is_err());
+
+        registry.set_deprecation_policy(DeprecationPolicy::Warn);
+        let warned = registry.resolve_path("Combat.Slash", None).unwrap();
+        assert_eq!(
+            warned.deprecated.unwrap().to_string(),
+            "tag 'Combat.Slash' is deprecated since 0.3: use Combat.Strike"
+        );
+        let redirected = registry
+            .resolve_path("Combat.Hit", Some(&redirects))
+            .unwrap();
+        assert_eq!(redirected.gid, strike);
+        assert_eq!(
+            redirected.deprecated,
+            Some(DeprecatedUse::Redirected {
+                from: "Combat.Hit".into(),
+                to: strike
+            })
+        );
+        assert_eq!(registry.resolve_gid(strike, None).unwrap().deprecated, None);
+
+        registry.set_deprecation_policy(DeprecationPolicy::Strict);
+        assert!(registry.resolve_gid(slash, None).is_err());
+        assert!(registry.resolve_gid(0x1234, Some(&redirects)).is_err());
+        assert!(registry
+            .resolve_path("Combat.Strike", Some(&redirects))
+            .is_ok());
+    }
+}
@@ -0,0 +1,120 @@
+//! C ABI for embedding scripting layers (Lua, C#, C++).
+//!
+//! Enabled by the `ffi` feature. C has no portable 128-bit integer, so GIDs
+//! cross the boundary as a [`BevyTagGid`] pair of `u64` halves. The zero GID
+//! ([`ROOT_GID`](crate::ROOT_GID)) never names a tag and is returned for
+//! "no result".
+//!
+//! Build a shared library with
+//! `cargo rustc --release --features ffi --crate-type cdylib` and declare:
+//!
+//! ```c
+//! typedef struct { uint64_t hi; uint64_t lo; } BevyTagGid;
+//!
+//! BevyTagGid bevy_tag_gid_for_path(const char *path);
+//! bool       bevy_tag_is_descendant(BevyTagGid candidate, BevyTagGid ancestor);
+//! uint8_t    bevy_tag_depth(BevyTagGid gid);
+//! BevyTagGid bevy_tag_parent(BevyTagGid gid);
+//! ```
+
+use std::ffi::{c_char, CStr};
+
+use crate::hash::path_gid;
+use crate::layout::{depth_of, gid_is_descendant_of, parent_of};
+use crate::GID;
+
+/// A GID split into high and low 64-bit halves.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BevyTagGid {
+    pub hi: u64,
+    pub lo: u64,
+}
+
+impl From<GID> for BevyTagGid {
+    fn from(gid: GID) -> Self {
+        Self {
+            hi: (gid >> 64) as u64,
+            lo: gid as u64,
+        }
+    }
+}
+
+impl From<BevyTagGid> for GID {
+    fn from(gid: BevyTagGid) -> Self {
+        (u128::from(gid.hi) << 64) | u128::from(gid.lo)
+    }
+}
+
+/// GID of a dot-separated path, hashed exactly as `namespace!` does.
+///
+/// Returns zero if `path` is null, not UTF-8, has an empty segment, or is
+/// deeper than [`MAX_DEPTH`](crate::MAX_DEPTH). Pinned GIDs (`#[gid = ...]`)
+/// and redirects are not visible here; resolve those through the generated
+/// tables.
+///
+/// # Safety
+///
+/// `path` must be null or point to a NUL-terminated string valid for reads.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bevy_tag_gid_for_path(path: *const c_char) -> BevyTagGid {
+    if path.is_null() {
+        return BevyTagGid::default();
+    }
+    // SAFETY: non-null and NUL-terminated per the caller contract
+    let path = unsafe { CStr::from_ptr(path) };
+    let gid = path.to_str().ok().and_then(path_gid);
+    gid.unwrap_or(0).into()
+}
+
+/// Is `candidate` equal to or a descendant of `ancestor`?
+#[unsafe(no_mangle)]
+pub extern "C" fn bevy_tag_is_descendant(candidate: BevyTagGid, ancestor: BevyTagGid) -> bool {
+    gid_is_descendant_of(candidate.into(), ancestor.into())
+}
+
+/// Depth of `gid` (0 for top-level tags).
+#[unsafe(no_mangle)]
+pub extern "C" fn bevy_tag_depth(gid: BevyTagGid) -> u8 {
+    depth_of(gid.into())
+}
+
+/// Parent of `gid`, or zero for top-level tags.
+#[unsafe(no_mangle)]
+pub extern "C" fn bevy_tag_parent(gid: BevyTagGid) -> BevyTagGid {
+    parent_of(gid.into()).unwrap_or(0).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hierarchical_gid;
+
+    fn gid_for(path: &CStr) -> BevyTagGid {
+        unsafe { bevy_tag_gid_for_path(path.as_ptr()) }
+    }
+
+    #[test]
+    fn matches_the_rust_api() {
+        let idle = gid_for(c"Movement.Idle");
+        let movement = gid_for(c"Movement");
+        assert_eq!(GID::from(idle), hierarchical_gid(&[b"Movement", b"Idle"]));
+        assert_eq!(BevyTagGid::from(GID::from(idle)), idle);
+
+        assert!(bevy_tag_is_descendant(idle, movement));
+        assert!(!bevy_tag_is_descendant(movement, idle));
+        assert_eq!(bevy_tag_depth(idle), 1);
+        assert_eq!(bevy_tag_parent(idle), movement);
+        assert_eq!(bevy_tag_parent(movement), BevyTagGid::default());
+    }
+
+    #[test]
+    fn invalid_paths_yield_zero() {
+        let zero = BevyTagGid::default();
+        assert_eq!(unsafe { bevy_tag_gid_for_path(std::ptr::null()) }, zero);
+        assert_eq!(gid_for(c"Movement..Idle"), zero);
+        assert_eq!(gid_for(c""), zero);
+        assert_eq!(gid_for(c"A.B.C.D.E.F.G.H.I"), zero);
+        assert_eq!(gid_for(c"\xff"), zero);
+    }
+}
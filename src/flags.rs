@@ -0,0 +1,177 @@
+//! Category flags attached to tags (`#[flags(Cosmetic | Replicated)]`).
+
+/// Bitmask of category flags on a tag.
+///
+/// The low 16 bits are reserved for the built-in flags below; the high 16
+/// bits are free for project-specific flags via [`TagFlags::user`].
+///
+/// ```ignore
+/// pub const LOOT: TagFlags = TagFlags::user(0);
+///
+/// namespace! {
+///     pub mod Tags {
+///         #[flags(Cosmetic | DesignerVisible)]
+///         Sparkle;
+///         #[flags(Replicated | crate::LOOT)]
+///         Drop;
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TagFlags(u32);
+
+impl TagFlags {
+    /// No flags.
+    pub const EMPTY: Self = Self(0);
+    /// Purely visual; no gameplay effect.
+    pub const COSMETIC: Self = Self(1 << 0);
+    /// Should be synchronized over the network.
+    pub const REPLICATED: Self = Self(1 << 1);
+    /// Shown in designer-facing tools and pickers.
+    pub const DESIGNER_VISIBLE: Self = Self(1 << 2);
+    /// Only meaningful in editor builds.
+    pub const EDITOR_ONLY: Self = Self(1 << 3);
+    /// Debug/diagnostic tag.
+    pub const DEBUG: Self = Self(1 << 4);
+
+    /// Names of the built-in flags, for `Debug` output.
+    const NAMED: [(Self, &'static str); 5] = [
+        (Self::COSMETIC, "Cosmetic"),
+        (Self::REPLICATED, "Replicated"),
+        (Self::DESIGNER_VISIBLE, "DesignerVisible"),
+        (Self::EDITOR_ONLY, "EditorOnly"),
+        (Self::DEBUG, "Debug"),
+    ];
+
+    /// Project-specific flag `n` (0..16), stored in the high 16 bits.
+    pub const fn user(n: u32) -> Self {
+        assert!(n < 16, "user flag index must be < 16");
+        Self(1 << (16 + n))
+    }
+
+    /// Flags from raw bits.
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Raw bits.
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// True if no flags are set.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// True if every flag in `other` is set.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// True if any flag in `other` is set.
+    #[inline]
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// Union of both flag sets (usable in const context).
+    #[inline]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Set the flags in `other`.
+    #[inline]
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    /// Clear the flags in `other`.
+    #[inline]
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl core::ops::BitOr for TagFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl core::ops::BitOrAssign for TagFlags {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
+
+impl core::ops::BitAnd for TagFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl core::fmt::Debug for TagFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut set = f.debug_set();
+        let mut rest = self.0;
+        for (flag, name) in Self::NAMED {
+            if self.contains(flag) {
+                set.entry(&format_args!("{}", name));
+                rest &= !flag.0;
+            }
+        }
+        for bit in 0..32 {
+            if rest & (1 << bit) != 0 {
+                if bit >= 16 {
+                    set.entry(&format_args!("User({})", bit - 16));
+                } else {
+                    set.entry(&format_args!("Bit({})", bit));
+                }
+            }
+        }
+        set.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_operations() {
+        let flags = TagFlags::COSMETIC | TagFlags::REPLICATED;
+        assert!(flags.contains(TagFlags::COSMETIC));
+        assert!(!flags.contains(TagFlags::COSMETIC | TagFlags::DEBUG));
+        assert!(flags.intersects(TagFlags::COSMETIC | TagFlags::DEBUG));
+
+        let mut flags = flags;
+        flags.remove(TagFlags::COSMETIC);
+        assert_eq!(flags, TagFlags::REPLICATED);
+        assert!(TagFlags::EMPTY.is_empty());
+    }
+
+    #[test]
+    fn user_flags_use_high_bits() {
+        assert_eq!(TagFlags::user(0).bits(), 1 << 16);
+        assert!(!TagFlags::user(3).intersects(TagFlags::from_bits(0xFFFF)));
+    }
+
+    #[test]
+    fn debug_lists_names() {
+        let flags = TagFlags::DESIGNER_VISIBLE | TagFlags::user(2);
+        assert_eq!(format!("{:?}", flags), "{DesignerVisible, User(2)}");
+    }
+}
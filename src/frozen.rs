@@ -0,0 +1,504 @@
+//! Dense tag indices and compact binary encoding of GID collections.
+//!
+//! A GID is 16 bytes, which adds up quickly in network snapshots and replay
+//! files. [`FrozenRegistry`] snapshots a [`NamespaceRegistry`] and numbers its
+//! tags `0..len` in GID order, so peers built from the same tag set agree on
+//! the numbering without exchanging it (compare [`fingerprint`]s to check).
+//!
+//! [`TagOrdinals`] is a second numbering in depth-first path order, where
+//! every subtree is a contiguous range of ordinals — the layout bitset
+//! containers and array-indexed per-tag tables want. The same layout lets
+//! [`descendants_of`] and [`children_of`] return borrowed slices without
+//! allocating.
+//!
+//! [`encode_gids`] writes a LEB128 count followed by one varint token per
+//! GID. A known tag's token holds the zigzag delta from the previous known
+//! ordinal, so collections sorted by ordinal (and tags from one subtree,
+//! which sit next to each other) cost one byte per tag in practice; a GID
+//! the registry doesn't know (e.g. registered dynamically on one peer only)
+//! is written as a marker token followed by its 16 raw little-endian bytes.
+//!
+//! [`fingerprint`]: FrozenRegistry::fingerprint
+//! [`descendants_of`]: FrozenRegistry::descendants_of
+//! [`children_of`]: FrozenRegistry::children_of
+//! [`encode_gids`]: FrozenRegistry::encode_gids
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::hash::fnv1a_64;
+use crate::hasher::GidMap;
+use crate::registry::NamespaceRegistry;
+use crate::GID;
+
+/// Immutable snapshot of a registry's tag set with a dense `u32` index per tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrozenRegistry {
+    /// Tags sorted by GID; a tag's index is its position here.
+    gids: Vec<GID>,
+    gid_to_index: GidMap<u32>,
+    fingerprint: u64,
+    ordinals: TagOrdinals,
+}
+
+/// Dense tag numbering in depth-first order, siblings sorted by name, so
+/// each subtree occupies one range of ordinals.
+///
+/// Depends only on the registered paths, so registries with the same tag set
+/// agree on it regardless of declaration or registration order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagOrdinals {
+    /// Tags in ordinal order.
+    gids: Vec<GID>,
+    ordinal: GidMap<u32>,
+    /// Exclusive end of each tag's subtree range.
+    subtree_end: Vec<u32>,
+    /// Children of every tag, grouped by parent ordinal.
+    children: Vec<GID>,
+    /// `children[child_start[i]..child_start[i + 1]]` are the children of
+    /// ordinal `i`.
+    child_start: Vec<u32>,
+}
+
+impl TagOrdinals {
+    /// Number the tags currently in `registry`.
+    pub fn new(registry: &NamespaceRegistry) -> Self {
+        let mut entries: Vec<(&str, GID)> = registry
+            .entries()
+            .iter()
+            .map(|e| (e.path.as_str(), e.gid))
+            .collect();
+        // Segment-wise order puts parents first and keeps subtrees together
+        entries.sort_by(|a, b| a.0.split('.').cmp(b.0.split('.')));
+
+        let gids: Vec<GID> = entries.iter().map(|&(_, gid)| gid).collect();
+        let mut subtree_end = vec![gids.len() as u32; gids.len()];
+        let mut parents: Vec<(usize, GID)> = Vec::new();
+        let mut open: Vec<usize> = Vec::new();
+        for (i, &(path, gid)) in entries.iter().enumerate() {
+            while let Some(&top) = open.last() {
+                let ancestor = entries[top].0;
+                let inside = path.len() > ancestor.len()
+                    && path.starts_with(ancestor)
+                    && path.as_bytes()[ancestor.len()] == b'.';
+                if inside {
+                    break;
+                }
+                subtree_end[top] = i as u32;
+                open.pop();
+            }
+            if let Some(&parent) = open.last() {
+                parents.push((parent, gid));
+            }
+            open.push(i);
+        }
+        // Already in DFS order, so a stable sort keeps siblings by name
+        parents.sort_by_key(|&(parent, _)| parent);
+        let mut child_start = vec![0u32; gids.len() + 1];
+        for &(parent, _) in &parents {
+            child_start[parent + 1] += 1;
+        }
+        for i in 0..gids.len() {
+            child_start[i + 1] += child_start[i];
+        }
+        let children = parents.into_iter().map(|(_, gid)| gid).collect();
+        let ordinal = gids
+            .iter()
+            .enumerate()
+            .map(|(i, &gid)| (gid, i as u32))
+            .collect();
+        Self {
+            gids,
+            ordinal,
+            subtree_end,
+            children,
+            child_start,
+        }
+    }
+
+    /// Number of numbered tags.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.gids.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.gids.is_empty()
+    }
+
+    /// Ordinal of `gid`.
+    #[inline]
+    pub fn ordinal_of(&self, gid: GID) -> Option<u32> {
+        self.ordinal.get(&gid).copied()
+    }
+
+    /// GID with ordinal `ordinal`.
+    #[inline]
+    pub fn gid_of_ordinal(&self, ordinal: u32) -> Option<GID> {
+        self.gids.get(ordinal as usize).copied()
+    }
+
+    /// Ordinals of `gid` and all its descendants.
+    pub fn subtree_range(&self, gid: GID) -> Option<Range<u32>> {
+        let start = self.ordinal_of(gid)?;
+        Some(start..self.subtree_end[start as usize])
+    }
+
+    /// All tags in ordinal order; index with a
+    /// [`subtree_range`](Self::subtree_range) to get a subtree.
+    #[inline]
+    pub fn gids(&self) -> &[GID] {
+        &self.gids
+    }
+
+    /// `gid` and all its descendants in depth-first order; empty if `gid`
+    /// isn't numbered.
+    pub fn descendants_of(&self, gid: GID) -> &[GID] {
+        self.subtree_range(gid)
+            .map_or(&[], |range| &self.gids[range.start as usize..range.end as usize])
+    }
+
+    /// Direct children of `gid`, sorted by name; empty if `gid` is a leaf or
+    /// isn't numbered.
+    pub fn children_of(&self, gid: GID) -> &[GID] {
+        let Some(ordinal) = self.ordinal_of(gid) else {
+            return &[];
+        };
+        let start = self.child_start[ordinal as usize] as usize;
+        let end = self.child_start[ordinal as usize + 1] as usize;
+        &self.children[start..end]
+    }
+}
+
+/// Error decoding bytes produced by [`FrozenRegistry::encode_gids`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GidDecodeError {
+    /// Input ended in the middle of a value.
+    Truncated,
+    /// A varint is longer than 64 bits.
+    VarintOverflow,
+    /// An ordinal outside this registry; the encoder used a different tag set.
+    UnknownIndex(i64),
+    /// Bytes left over after the last GID.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for GidDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "unexpected end of GID data"),
+            Self::VarintOverflow => write!(f, "varint exceeds 64 bits"),
+            Self::UnknownIndex(index) => write!(
+                f,
+                "tag ordinal {} is not in this registry (was it encoded with a different tag set?)",
+                index
+            ),
+            Self::TrailingBytes(n) => write!(f, "{} trailing bytes after GID data", n),
+        }
+    }
+}
+
+impl std::error::Error for GidDecodeError {}
+
+/// Token marking a raw GID; known tags have an even token.
+const RAW_TOKEN: u64 = 1;
+
+impl FrozenRegistry {
+    /// Snapshot the tags currently in `registry`.
+    pub fn new(registry: &NamespaceRegistry) -> Self {
+        let mut gids: Vec<GID> = registry.entries().iter().map(|e| e.gid).collect();
+        gids.sort_unstable();
+        gids.dedup();
+        let gid_to_index = gids
+            .iter()
+            .enumerate()
+            .map(|(index, &gid)| (gid, index as u32))
+            .collect();
+        let ordinals = TagOrdinals::new(registry);
+        // Ordinal order pins down both numberings
+        let bytes: Vec<u8> = crate::GID_SCHEME
+            .bytes()
+            .chain(ordinals.gids().iter().flat_map(|gid| gid.to_le_bytes()))
+            .collect();
+        Self {
+            fingerprint: fnv1a_64(&bytes),
+            gids,
+            gid_to_index,
+            ordinals,
+        }
+    }
+
+    /// Number of indexed tags.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.gids.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.gids.is_empty()
+    }
+
+    /// Hash of the indexed tag set and [`GID_SCHEME`](crate::GID_SCHEME);
+    /// equal fingerprints mean equal numbering.
+    #[inline]
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Dense index of `gid`, if it was in the registry when frozen.
+    #[inline]
+    pub fn index_of(&self, gid: GID) -> Option<u32> {
+        self.gid_to_index.get(&gid).copied()
+    }
+
+    /// GID at a dense index.
+    #[inline]
+    pub fn gid_at(&self, index: u32) -> Option<GID> {
+        self.gids.get(index as usize).copied()
+    }
+
+    /// Depth-first numbering of the same tags.
+    #[inline]
+    pub fn ordinals(&self) -> &TagOrdinals {
+        &self.ordinals
+    }
+
+    /// Ordinal of `gid`; see [`TagOrdinals`].
+    #[inline]
+    pub fn ordinal_of(&self, gid: GID) -> Option<u32> {
+        self.ordinals.ordinal_of(gid)
+    }
+
+    /// GID with ordinal `ordinal`; see [`TagOrdinals`].
+    #[inline]
+    pub fn gid_of_ordinal(&self, ordinal: u32) -> Option<GID> {
+        self.ordinals.gid_of_ordinal(ordinal)
+    }
+
+    /// `gid` and all its descendants in depth-first order, without
+    /// allocating; see [`TagOrdinals::descendants_of`].
+    ///
+    /// The slice borrows the snapshot. To keep a subtree across frames,
+    /// keep the snapshot (e.g. as a resource) and either re-slice each frame
+    /// or hold the [`subtree_range`](TagOrdinals::subtree_range), which
+    /// indexes [`TagOrdinals::gids`].
+    #[inline]
+    pub fn descendants_of(&self, gid: GID) -> &[GID] {
+        self.ordinals.descendants_of(gid)
+    }
+
+    /// Direct children of `gid`, without allocating; see
+    /// [`TagOrdinals::children_of`].
+    #[inline]
+    pub fn children_of(&self, gid: GID) -> &[GID] {
+        self.ordinals.children_of(gid)
+    }
+
+    /// Encode `gids` by [ordinal](TagOrdinals), preserving order and
+    /// duplicates.
+    pub fn encode_gids(&self, gids: &[GID]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(gids.len() + 2);
+        write_varint(&mut out, gids.len() as u64);
+        let mut previous = 0i64;
+        for &gid in gids {
+            match self.ordinal_of(gid) {
+                Some(ordinal) => {
+                    let ordinal = i64::from(ordinal);
+                    write_varint(&mut out, zigzag(ordinal - previous) << 1);
+                    previous = ordinal;
+                }
+                None => {
+                    write_varint(&mut out, RAW_TOKEN);
+                    out.extend_from_slice(&gid.to_le_bytes());
+                }
+            }
+        }
+        out
+    }
+
+    /// Decode bytes produced by [`encode_gids`](Self::encode_gids) on a
+    /// registry with the same [`fingerprint`](Self::fingerprint).
+    pub fn decode_gids(&self, bytes: &[u8]) -> Result<Vec<GID>, GidDecodeError> {
+        let mut reader = bytes;
+        let count = read_varint(&mut reader)?;
+        // Every GID takes at least one byte, so a larger count is truncated
+        if count > reader.len() as u64 {
+            return Err(GidDecodeError::Truncated);
+        }
+        let mut gids = Vec::with_capacity(count as usize);
+        let mut previous = 0i64;
+        for _ in 0..count {
+            let token = read_varint(&mut reader)?;
+            if token == RAW_TOKEN {
+                let (raw, rest) = reader
+                    .split_first_chunk::<16>()
+                    .ok_or(GidDecodeError::Truncated)?;
+                gids.push(GID::from_le_bytes(*raw));
+                reader = rest;
+                continue;
+            }
+            let ordinal = previous.wrapping_add(unzigzag(token >> 1));
+            let gid = u32::try_from(ordinal)
+                .ok()
+                .and_then(|i| self.gid_of_ordinal(i))
+                .ok_or(GidDecodeError::UnknownIndex(ordinal))?;
+            gids.push(gid);
+            previous = ordinal;
+        }
+        if !reader.is_empty() {
+            return Err(GidDecodeError::TrailingBytes(reader.len()));
+        }
+        Ok(gids)
+    }
+}
+
+impl NamespaceRegistry {
+    /// Snapshot the current tag set for dense indexing and compact encoding.
+    pub fn freeze(&self) -> FrozenRegistry {
+        FrozenRegistry::new(self)
+    }
+}
+
+fn zigzag(n: i64) -> u64 {
+    ((n << 1) ^ (n >> 63)) as u64
+}
+
+fn unzigzag(n: u64) -> i64 {
+    ((n >> 1) as i64) ^ -((n & 1) as i64)
+}
+
+/// Unsigned LEB128.
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+pub(crate) fn read_varint(reader: &mut &[u8]) -> Result<u64, GidDecodeError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = reader.split_first().ok_or(GidDecodeError::Truncated)?;
+        *reader = rest;
+        if shift == 63 && byte > 1 {
+            return Err(GidDecodeError::VarintOverflow);
+        }
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(GidDecodeError::VarintOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hierarchical_gid;
+    use crate::registry::NamespaceDef;
+
+    fn registry() -> NamespaceRegistry {
+        const DEFS: &[NamespaceDef] = &[
+            NamespaceDef::new("Item", None),
+            NamespaceDef::new("Item.Sword", Some("Item")),
+            NamespaceDef::new("Item.Shield", Some("Item")),
+            NamespaceDef::new("Skill", None),
+            NamespaceDef::new("Skill.Fire", Some("Skill")),
+        ];
+        NamespaceRegistry::build(DEFS).unwrap()
+    }
+
+    #[test]
+    fn dense_indices_follow_gid_order() {
+        let frozen = registry().freeze();
+        assert_eq!(frozen.len(), 5);
+        for index in 0..frozen.len() as u32 {
+            let gid = frozen.gid_at(index).unwrap();
+            assert_eq!(frozen.index_of(gid), Some(index));
+        }
+        assert_eq!(frozen.gid_at(5), None);
+        assert_eq!(frozen.fingerprint(), registry().freeze().fingerprint());
+    }
+
+    #[test]
+    fn ordinals_keep_subtrees_contiguous() {
+        let registry = registry();
+        let frozen = registry.freeze();
+        let ordinals = frozen.ordinals();
+        let paths: Vec<&str> = (0..ordinals.len() as u32)
+            .map(|i| registry.path_of(frozen.gid_of_ordinal(i).unwrap()).unwrap())
+            .collect();
+        assert_eq!(paths, ["Item", "Item.Shield", "Item.Sword", "Skill", "Skill.Fire"]);
+
+        let item = hierarchical_gid(&[b"Item"]);
+        let shield = hierarchical_gid(&[b"Item", b"Shield"]);
+        assert_eq!(ordinals.subtree_range(item), Some(0..3));
+        assert_eq!(ordinals.subtree_range(shield), Some(1..2));
+        assert_eq!(frozen.ordinal_of(shield), Some(1));
+        assert_eq!(ordinals.subtree_range(7), None);
+
+        let sword = hierarchical_gid(&[b"Item", b"Sword"]);
+        let skill = hierarchical_gid(&[b"Skill"]);
+        assert_eq!(frozen.descendants_of(item), [item, shield, sword]);
+        assert_eq!(frozen.children_of(item), [shield, sword]);
+        assert_eq!(frozen.children_of(skill), [hierarchical_gid(&[b"Skill", b"Fire"])]);
+        assert!(frozen.children_of(shield).is_empty());
+        assert!(frozen.descendants_of(7).is_empty());
+
+        // Registration order doesn't matter
+        let mut dynamic = NamespaceRegistry::new();
+        for path in ["Skill.Fire", "Item.Sword", "Item.Shield"] {
+            dynamic.register(path).unwrap();
+        }
+        assert_eq!(&TagOrdinals::new(&dynamic), ordinals);
+    }
+
+    #[test]
+    fn round_trips_known_and_unknown_gids() {
+        let frozen = registry().freeze();
+        let sword = hierarchical_gid(&[b"Item", b"Sword"]);
+        let fire = hierarchical_gid(&[b"Skill", b"Fire"]);
+        let unknown = hierarchical_gid(&[b"Quest", b"Main"]);
+
+        let gids = [fire, sword, unknown, sword, fire];
+        let bytes = frozen.encode_gids(&gids);
+        // count + four one-byte tokens + marker and 16 raw bytes
+        assert_eq!(bytes.len(), 1 + 4 + 1 + 16);
+        assert_eq!(frozen.decode_gids(&bytes).unwrap(), gids);
+        assert_eq!(frozen.decode_gids(&frozen.encode_gids(&[])).unwrap(), []);
+
+        // Tokens are ordinal deltas, so a subtree encodes as small steps
+        let item = hierarchical_gid(&[b"Item"]);
+        let shield = hierarchical_gid(&[b"Item", b"Shield"]);
+        let subtree = frozen.descendants_of(item);
+        assert_eq!(subtree, [item, shield, sword]);
+        assert_eq!(frozen.encode_gids(subtree), [3, 0, zigzag(1) as u8 * 2, zigzag(1) as u8 * 2]);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        let frozen = registry().freeze();
+        let sword = hierarchical_gid(&[b"Item", b"Sword"]);
+        let bytes = frozen.encode_gids(&[sword, 7]);
+
+        assert_eq!(
+            frozen.decode_gids(&bytes[..bytes.len() - 1]),
+            Err(GidDecodeError::Truncated)
+        );
+        assert_eq!(
+            frozen.decode_gids(&[bytes.as_slice(), &[0]].concat()),
+            Err(GidDecodeError::TrailingBytes(1))
+        );
+        assert_eq!(
+            frozen.decode_gids(&[1, zigzag(9) as u8 * 2]),
+            Err(GidDecodeError::UnknownIndex(9))
+        );
+        assert_eq!(
+            frozen.decode_gids(&[1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]),
+            Err(GidDecodeError::VarintOverflow)
+        );
+    }
+}
@@ -3,7 +3,14 @@
 //! Uses FNV-1a for fast, const-compatible hashing with good distribution.
 //! The depth is automatically encoded into the GID (bits 127:125).
 
-use crate::layout::{encode_gid, DEPTH_MASK, LEVEL_OFFSETS, LEVEL_WIDTHS, MAX_DEPTH};
+use crate::layout::{encode_gid, segment_of, DEPTH_MASK, LEVEL_OFFSETS, LEVEL_WIDTHS, MAX_DEPTH};
+
+/// Version of the segment hash (FNV-1a 64 plus the fold in
+/// `segment_hash`).
+///
+/// Bump it with any change that alters a GID; stored GIDs and lock files
+/// from the previous version then need migrating.
+pub const HASH_VERSION: u32 = 1;
 
 /// FNV-1a 64-bit hash — simple, fast, const-compatible.
 pub const fn fnv1a_64(bytes: &[u8]) -> u64 {
@@ -70,6 +77,52 @@ pub const fn hierarchical_gid(segments: &[&[u8]]) -> u128 {
     encode_gid(payload, depth)
 }
 
+/// Runtime [`hierarchical_gid`] of a dot-separated path, or `None` if it has
+/// an empty segment or is deeper than [`MAX_DEPTH`].
+#[cfg(any(feature = "serde", feature = "ffi", feature = "wasm"))]
+pub(crate) fn path_gid(path: &str) -> Option<u128> {
+    let segments: Vec<&[u8]> = path.split('.').map(str::as_bytes).collect();
+    if segments.len() > MAX_DEPTH || segments.iter().any(|s| s.is_empty()) {
+        return None;
+    }
+    Some(hierarchical_gid(&segments))
+}
+
+/// Append an explicit segment value at `depth` under `parent`.
+///
+/// `parent` is ignored for `depth == 0` (pass `ROOT_GID`). This is how pinned
+/// segments (`#[segment = ...]`) are placed into the layout.
+///
+/// # Panics
+///
+/// Panics at compile time if `value` is zero or does not fit the level width.
+pub const fn gid_with_segment(parent: u128, depth: u8, value: u128) -> u128 {
+    let level = depth as usize;
+    assert!(level < MAX_DEPTH, "tree depth exceeds MAX_DEPTH (8)");
+    assert!(value != 0, "segment value 0 is reserved for empty levels");
+    assert!(
+        value < (1u128 << LEVEL_WIDTHS[level]),
+        "segment value does not fit the level width"
+    );
+    assert!(
+        level == 0 || segment_of(parent, level - 1) != 0,
+        "parent GID does not reach the level above this segment"
+    );
+
+    let parent_payload = if level == 0 { 0 } else { parent & !DEPTH_MASK };
+    encode_gid(parent_payload | (value << LEVEL_OFFSETS[level]), depth)
+}
+
+/// Compute the GID of a child by hashing `segment` at `depth` under `parent`.
+///
+/// Equivalent to [`hierarchical_gid`] when `parent` was itself hashed from a
+/// path, but also follows pinned ancestors.
+pub const fn child_gid(parent: u128, depth: u8, segment: &[u8]) -> u128 {
+    let level = depth as usize;
+    assert!(level < MAX_DEPTH, "tree depth exceeds MAX_DEPTH (8)");
+    gid_with_segment(parent, depth, segment_hash(segment, LEVEL_WIDTHS[level]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -81,6 +134,14 @@ mod tests {
         assert_eq!(fnv1a_64(b"hello"), fnv1a_64(b"hello"));
     }
 
+    #[test]
+    fn fnv_reference_vectors() {
+        // From the FNV reference test suite
+        assert_eq!(fnv1a_64(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(fnv1a_64(b"foobar"), 0x85944171f73967e8);
+    }
+
     #[test]
     fn segment_hash_never_zero() {
         // Test a bunch of inputs — none should produce 0
@@ -137,6 +198,24 @@ mod tests {
         assert_ne!(gid1, gid3);
     }
 
+    #[test]
+    fn child_gid_matches_hierarchical() {
+        let root = hierarchical_gid(&[b"A"]);
+        assert_eq!(child_gid(0, 0, b"A"), root);
+        assert_eq!(child_gid(root, 1, b"B"), hierarchical_gid(&[b"A", b"B"]));
+    }
+
+    #[test]
+    fn gid_with_segment_places_value() {
+        let pinned = gid_with_segment(0, 0, 0x42);
+        assert_eq!(depth_of(pinned), 0);
+        assert_eq!(segment_of(pinned, 0), 0x42);
+
+        let child = child_gid(pinned, 1, b"Child");
+        assert!(gid_is_descendant_of(child, pinned));
+        assert_eq!(depth_of(child), 1);
+    }
+
     #[test]
     fn deep_hierarchy_works() {
         // Test with many levels (up to MAX_DEPTH = 8)
@@ -0,0 +1,178 @@
+//! Cheap hashing for GID-keyed maps and sets.
+//!
+//! GIDs are already FNV-1a hashes, so running them through SipHash again is
+//! wasted work. [`GidHasher`] folds the two halves of the `u128` together and
+//! mixes the result with one widening multiply — needed because a shallow
+//! GID only fills its lowest few dozen bits, while `HashMap` also takes bits
+//! from the top of the hash.
+//!
+//! The `siphash` feature switches [`GidBuildHasher`] (and so every internal
+//! GID map, such as the registry index and `TagContainer` sets) to std's
+//! randomly keyed SipHash, for servers that hash GIDs chosen by untrusted
+//! clients. The types stay the same either way. [`SipGidMap`] and
+//! [`SipGidSet`] always use SipHash, for single collections of your own.
+
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+
+use crate::GID;
+
+/// Odd constant with well-spread bits (the 64-bit golden ratio).
+const MULTIPLIER: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Hasher for [`GID`] keys: fold to 64 bits, then a folded multiply.
+///
+/// Not HashDoS resistant; see the module docs.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GidHasher {
+    state: u64,
+}
+
+impl GidHasher {
+    #[inline]
+    fn mix(&mut self, value: u64) {
+        let product = u128::from(self.state ^ value) * u128::from(MULTIPLIER);
+        self.state = (product as u64) ^ ((product >> 64) as u64);
+    }
+}
+
+impl Hasher for GidHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    #[inline]
+    fn write_u128(&mut self, value: u128) {
+        self.mix((value as u64) ^ ((value >> 64) as u64));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, value: u64) {
+        self.mix(value);
+    }
+
+    /// Fallback for non-GID keys.
+    fn write(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(8) {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+            self.mix(u64::from_le_bytes(word));
+        }
+    }
+}
+
+/// Build hasher for GID-keyed collections.
+///
+/// Builds a [`GidHasher`], or with the `siphash` feature a SipHash hasher
+/// keyed randomly per instance.
+#[derive(Clone, Debug, Default)]
+pub struct GidBuildHasher {
+    #[cfg(feature = "siphash")]
+    keys: RandomState,
+}
+
+impl BuildHasher for GidBuildHasher {
+    #[cfg(not(feature = "siphash"))]
+    type Hasher = GidHasher;
+    #[cfg(feature = "siphash")]
+    type Hasher = std::hash::DefaultHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> Self::Hasher {
+        #[cfg(not(feature = "siphash"))]
+        return GidHasher::default();
+        #[cfg(feature = "siphash")]
+        return self.keys.build_hasher();
+    }
+}
+
+/// `HashMap` keyed by GID. Create with `GidMap::default()`.
+pub type GidMap<V> = HashMap<GID, V, GidBuildHasher>;
+
+/// `HashSet` of GIDs. Create with `GidSet::default()`.
+pub type GidSet = HashSet<GID, GidBuildHasher>;
+
+/// `HashMap` keyed by GID with std's randomly keyed SipHash, for GIDs from
+/// untrusted sources.
+pub type SipGidMap<V> = HashMap<GID, V, RandomState>;
+
+/// `HashSet` of GIDs with std's randomly keyed SipHash, for GIDs from
+/// untrusted sources.
+pub type SipGidSet = HashSet<GID, RandomState>;
+
+/// Estimated heap size of a std hash table holding `capacity` entries of
+/// type `T`: a power-of-two bucket array kept at most 7/8 full, plus one
+/// control byte per bucket and a 16-byte group tail.
+pub(crate) fn table_heap_bytes<T>(capacity: usize) -> usize {
+    let buckets = match capacity {
+        0 => return 0,
+        1..8 => (capacity + 1).next_power_of_two(),
+        _ => (capacity * 8 / 7).next_power_of_two(),
+    };
+    buckets * (std::mem::size_of::<T>() + 1) + 16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hierarchical_gid;
+    use std::hash::BuildHasherDefault;
+
+    #[test]
+    fn spreads_shallow_gids_across_all_bits() {
+        let build = BuildHasherDefault::<GidHasher>::default();
+        let buckets: HashSet<u64> = (0..1024u32)
+            .map(|i| {
+                let name = format!("Tag{}", i);
+                build.hash_one(hierarchical_gid(&[name.as_bytes()])) & 0x3ff
+            })
+            .collect();
+        // Random placement of 1024 keys fills about 63% of 1024 buckets
+        assert!(buckets.len() > 550, "only {} buckets used", buckets.len());
+
+        let top_bits: HashSet<u64> = (0..1024u32)
+            .map(|i| {
+                let name = format!("Tag{}", i);
+                build.hash_one(hierarchical_gid(&[name.as_bytes()])) >> 57
+            })
+            .collect();
+        assert!(top_bits.len() > 100, "only {} top-bit patterns", top_bits.len());
+    }
+
+    #[test]
+    fn collections_work_with_either_hasher() {
+        let mut map = GidMap::default();
+        map.insert(hierarchical_gid(&[b"A"]), 1);
+        map.insert(hierarchical_gid(&[b"A", b"B"]), 2);
+        assert_eq!(map[&hierarchical_gid(&[b"A", b"B"])], 2);
+
+        let set: GidSet = map.keys().copied().collect();
+        assert!(set.contains(&hierarchical_gid(&[b"A"])));
+
+        let sip: SipGidMap<i32> = map.iter().map(|(&gid, &v)| (gid, v)).collect();
+        assert_eq!(sip[&hierarchical_gid(&[b"A"])], 1);
+        let sip_set: SipGidSet = set.iter().copied().collect();
+        assert_eq!(sip_set.len(), 2);
+    }
+
+    #[cfg(feature = "siphash")]
+    #[test]
+    fn siphash_feature_keys_internal_maps() {
+        let gid = hierarchical_gid(&[b"A", b"B"]);
+        // Each build hasher draws its own keys
+        let hashes: HashSet<u64> = (0..8)
+            .map(|_| GidBuildHasher::default().hash_one(gid))
+            .collect();
+        assert!(hashes.len() > 1);
+        assert_ne!(
+            GidBuildHasher::default().hash_one(gid),
+            BuildHasherDefault::<GidHasher>::default().hash_one(gid)
+        );
+
+        let mut map = GidMap::default();
+        map.insert(gid, 1);
+        assert_eq!(map[&gid], 1);
+    }
+}
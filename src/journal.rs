@@ -0,0 +1,266 @@
+//! Opt-in record of registry mutations.
+//!
+//! After [`NamespaceRegistry::enable_journal`], every dynamic registration,
+//! unregistration, metadata change, (un)sealing and redirect added through
+//! [`NamespaceRegistry::insert_redirect`] is appended to the registry's
+//! journal with a timestamp and, for registrations and unregistrations,
+//! the tag source. Entries carry the values they replaced, so an in-game
+//! editor can undo them by replaying them backwards; [`export_journal`]
+//! writes one line per entry for audit logs:
+//!
+//! ```text
+//! 1760700000.125 mod:swords registered Item.Sword.Rapier 0x20000000000000000000000001c4a0d9
+//! 1760700001.500 - set meta 'damage' on 0x20000000000000000000000001c4a0d9 (4 bytes)
+//! ```
+//!
+//! [`export_journal`]: NamespaceRegistry::export_journal
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::registry::NamespaceRegistry;
+use crate::GID;
+
+/// One recorded registry mutation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JournalEntry {
+    /// When the mutation happened.
+    pub timestamp: SystemTime,
+    /// Source passed to `register_with_source` or `unregister_source`.
+    pub source: Option<String>,
+    pub change: JournalChange,
+}
+
+/// What a [`JournalEntry`] changed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JournalChange {
+    /// A tag was registered dynamically (including auto-created parents).
+    Registered { gid: GID, path: String },
+    /// A tag was removed by `unregister_source`.
+    Unregistered { gid: GID, path: String },
+    /// A metadata value was set; `previous` is the value it replaced.
+    MetaSet {
+        gid: GID,
+        key: String,
+        value: Vec<u8>,
+        previous: Option<Vec<u8>>,
+    },
+    /// A metadata value was removed, directly or with its tag.
+    MetaRemoved {
+        gid: GID,
+        key: String,
+        previous: Vec<u8>,
+    },
+    /// A subtree was sealed.
+    Sealed { gid: GID },
+    /// A subtree was unsealed.
+    Unsealed { gid: GID },
+    /// `gid` was redirected to `to`; `previous` is the target it replaced.
+    RedirectAdded {
+        gid: GID,
+        to: GID,
+        previous: Option<GID>,
+    },
+}
+
+impl JournalChange {
+    /// The tag the change applies to.
+    pub fn gid(&self) -> GID {
+        match *self {
+            Self::Registered { gid, .. }
+            | Self::Unregistered { gid, .. }
+            | Self::MetaSet { gid, .. }
+            | Self::MetaRemoved { gid, .. }
+            | Self::Sealed { gid }
+            | Self::Unsealed { gid }
+            | Self::RedirectAdded { gid, .. } => gid,
+        }
+    }
+}
+
+impl JournalEntry {
+    pub(crate) fn now(source: Option<&str>, change: JournalChange) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            source: source.map(str::to_string),
+            change,
+        }
+    }
+}
+
+impl fmt::Display for JournalEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let since_epoch = self
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        write!(
+            f,
+            "{}.{:03} {} ",
+            since_epoch.as_secs(),
+            since_epoch.subsec_millis(),
+            self.source.as_deref().unwrap_or("-")
+        )?;
+        match &self.change {
+            JournalChange::Registered { gid, path } => {
+                write!(f, "registered {} {:#034x}", path, gid)
+            }
+            JournalChange::Unregistered { gid, path } => {
+                write!(f, "unregistered {} {:#034x}", path, gid)
+            }
+            JournalChange::MetaSet {
+                gid, key, value, ..
+            } => {
+                write!(
+                    f,
+                    "set meta '{}' on {:#034x} ({} bytes)",
+                    key,
+                    gid,
+                    value.len()
+                )
+            }
+            JournalChange::MetaRemoved { gid, key, .. } => {
+                write!(f, "removed meta '{}' from {:#034x}", key, gid)
+            }
+            JournalChange::Sealed { gid } => write!(f, "sealed {:#034x}", gid),
+            JournalChange::Unsealed { gid } => write!(f, "unsealed {:#034x}", gid),
+            JournalChange::RedirectAdded { gid, to, .. } => {
+                write!(f, "redirected {:#034x} to {:#034x}", gid, to)
+            }
+        }
+    }
+}
+
+impl NamespaceRegistry {
+    /// Journal entries concerning `gid`, oldest first.
+    pub fn journal_of(&self, gid: GID) -> impl Iterator<Item = &JournalEntry> {
+        self.journal()
+            .iter()
+            .filter(move |entry| entry.change.gid() == gid)
+    }
+
+    /// Journal entries recorded with `source`, oldest first.
+    pub fn journal_from_source<'a>(
+        &'a self,
+        source: &'a str,
+    ) -> impl Iterator<Item = &'a JournalEntry> {
+        self.journal()
+            .iter()
+            .filter(move |entry| entry.source.as_deref() == Some(source))
+    }
+
+    /// The journal as text, one line per entry.
+    pub fn export_journal(&self) -> String {
+        self.journal()
+            .iter()
+            .map(|entry| format!("{}\n", entry))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::NamespaceDef;
+
+    #[test]
+    fn records_mutations_only_while_enabled() {
+        let mut registry = NamespaceRegistry::build(&[NamespaceDef::new("Item", None)]).unwrap();
+        registry.register("Item.Untracked").unwrap();
+        assert!(!registry.is_journaling());
+
+        registry.enable_journal();
+        let rapier = registry
+            .register_with_source("Item.Sword.Rapier", "mod:swords")
+            .unwrap();
+        let sword = registry.gid_of("Item.Sword").unwrap();
+        registry.set_meta(rapier, "damage", &7u32);
+        registry.set_meta(rapier, "damage", &9u32);
+        registry.seal_subtree(sword);
+        registry.unregister_source("mod:swords");
+
+        let changes: Vec<&JournalChange> = registry.journal().iter().map(|e| &e.change).collect();
+        assert_eq!(changes.len(), 8);
+        assert_eq!(
+            changes[0],
+            &JournalChange::Registered {
+                gid: sword,
+                path: "Item.Sword".into()
+            }
+        );
+        assert_eq!(
+            changes[3],
+            &JournalChange::MetaSet {
+                gid: rapier,
+                key: "damage".into(),
+                value: 9u32.to_ne_bytes().to_vec(),
+                previous: Some(7u32.to_ne_bytes().to_vec()),
+            }
+        );
+        assert_eq!(changes[4], &JournalChange::Sealed { gid: sword });
+        assert!(matches!(changes[5], JournalChange::MetaRemoved { gid, .. } if *gid == rapier));
+        assert!(matches!(changes[6], JournalChange::Unregistered { gid, .. } if *gid == rapier));
+        assert!(matches!(changes[7], JournalChange::Unregistered { gid, .. } if *gid == sword));
+
+        assert_eq!(registry.journal_of(rapier).count(), 5);
+        assert_eq!(registry.journal_from_source("mod:swords").count(), 5);
+        let export = registry.export_journal();
+        assert_eq!(export.lines().count(), 8);
+        assert!(export
+            .lines()
+            .next()
+            .unwrap()
+            .contains(" mod:swords registered Item.Sword 0x"));
+
+        let mut redirects = crate::migrate::RedirectMap::new();
+        registry.insert_redirect(&mut redirects, 1, 2);
+        registry.insert_redirect(&mut redirects, 1, 3);
+        assert_eq!(redirects.get(1), Some(3));
+        assert_eq!(
+            registry.journal().last().map(|e| &e.change),
+            Some(&JournalChange::RedirectAdded {
+                gid: 1,
+                to: 3,
+                previous: Some(2)
+            })
+        );
+
+        assert_eq!(registry.take_journal().len(), 10);
+        assert!(registry.journal().is_empty());
+        registry.disable_journal();
+        registry.register("Item.Shield").unwrap();
+        assert!(registry.journal().is_empty());
+    }
+
+    #[test]
+    fn unregistered_subtree_undoes_parents_first() {
+        let mut registry = NamespaceRegistry::build(&[NamespaceDef::new("Item", None)]).unwrap();
+        registry.register_with_source("Item.Sword.Rapier.Long", "mod:swords").unwrap();
+        registry.register_with_source("Item.Sword.Saber", "mod:swords").unwrap();
+        let snapshot = |registry: &NamespaceRegistry| {
+            let mut tags: Vec<(GID, String, Option<String>)> = registry
+                .entries()
+                .iter()
+                .map(|e| (e.gid, e.path.clone(), registry.source_of(e.gid).map(String::from)))
+                .collect();
+            tags.sort();
+            tags
+        };
+        let before = snapshot(&registry);
+
+        registry.enable_journal();
+        assert_eq!(registry.unregister_source("mod:swords").len(), 4);
+
+        // Undo: replay backwards, re-registering each removed tag
+        for entry in registry.take_journal().into_iter().rev() {
+            let JournalChange::Unregistered { gid, path } = entry.change else {
+                continue;
+            };
+            let (parent, _) = path.rsplit_once('.').unwrap();
+            assert!(registry.gid_of(parent).is_some(), "'{}' before its parent", path);
+            let source = entry.source.as_deref().unwrap();
+            assert_eq!(registry.register_with_source(&path, source), Ok(gid));
+        }
+        assert_eq!(snapshot(&registry), before);
+    }
+}
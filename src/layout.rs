@@ -1,5 +1,10 @@
 use crate::GID;
 
+/// Version of the bit layout (depth field and [`LEVEL_WIDTHS`]).
+///
+/// Bump it with any change to where segments or the depth are stored.
+pub const LAYOUT_VERSION: u32 = 1;
+
 /// Maximum supported tree depth (0-7, encoded in 3 bits).
 pub const MAX_DEPTH: usize = 8;
 
@@ -130,7 +135,7 @@ pub const fn encode_gid(payload: u128, depth: u8) -> GID {
 /// is_descendant_of(Combat.Attack.GID, Movement.GID) → false
 /// ```
 #[inline]
-pub fn gid_is_descendant_of(candidate: GID, ancestor: GID) -> bool {
+pub const fn gid_is_descendant_of(candidate: GID, ancestor: GID) -> bool {
     let ancestor_depth = depth_of(ancestor) as usize;
     if ancestor_depth >= MAX_DEPTH {
         return false;
@@ -141,6 +146,67 @@ pub fn gid_is_descendant_of(candidate: GID, ancestor: GID) -> bool {
     (candidate & payload_mask) == (ancestor & payload_mask)
 }
 
+/// Precomputed subtree test for one ancestor.
+///
+/// [`gid_is_descendant_of`] looks up the ancestor's mask on every call; build
+/// this once when testing many GIDs against the same ancestor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SubtreeMask {
+    mask: u128,
+    bits: u128,
+}
+
+impl SubtreeMask {
+    /// Mask matching `ancestor` and everything below it.
+    #[inline]
+    pub const fn new(ancestor: GID) -> Self {
+        let mask = LEVEL_MASKS[depth_of(ancestor) as usize] & !DEPTH_MASK;
+        Self {
+            mask,
+            bits: ancestor & mask,
+        }
+    }
+
+    /// Same as `gid_is_descendant_of(gid, ancestor)`.
+    #[inline]
+    pub const fn contains(&self, gid: GID) -> bool {
+        gid & self.mask == self.bits
+    }
+}
+
+impl From<GID> for SubtreeMask {
+    #[inline]
+    fn from(ancestor: GID) -> Self {
+        Self::new(ancestor)
+    }
+}
+
+/// Extract the raw segment value stored at `level` (0 if the level is empty).
+#[inline]
+pub const fn segment_of(gid: GID, level: usize) -> u128 {
+    if level >= MAX_DEPTH {
+        return 0;
+    }
+    (gid >> LEVEL_OFFSETS[level]) & ((1u128 << LEVEL_WIDTHS[level]) - 1)
+}
+
+/// Check that a GID has the shape produced by hashing a path:
+/// every level up to its depth is non-zero and every deeper level is zero.
+///
+/// Used to validate externally assigned (pinned) GIDs.
+pub const fn is_well_formed(gid: GID) -> bool {
+    let depth = depth_of(gid) as usize;
+    let mut level = 0;
+    while level < MAX_DEPTH {
+        let occupied = segment_of(gid, level) != 0;
+        if occupied != (level <= depth) {
+            return false;
+        }
+        level += 1;
+    }
+    true
+}
+
 /// Check if two GIDs share the same parent at a given depth.
 #[inline]
 pub const fn is_sibling(a: GID, b: GID) -> bool {
@@ -155,6 +221,13 @@ pub const fn is_sibling(a: GID, b: GID) -> bool {
     (a & parent_mask) == (b & parent_mask)
 }
 
+/// Get the top-level (depth 0) ancestor of a GID, or the GID itself if it
+/// is top-level.
+#[inline]
+pub const fn root_of(gid: GID) -> GID {
+    gid & LEVEL_MASKS[0] & !DEPTH_MASK
+}
+
 /// Get the parent GID of a given GID.
 ///
 /// Returns `None` if the GID is at root level (depth 0).
@@ -176,6 +249,22 @@ pub const fn parent_of(gid: GID) -> Option<GID> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn subtree_mask_matches_descendant_check() {
+        let a = 0x5 << LEVEL_OFFSETS[0];
+        let ab = encode_gid(a | 0x7 << LEVEL_OFFSETS[1], 1);
+        let abc = encode_gid(ab & !DEPTH_MASK | 0x9 << LEVEL_OFFSETS[2], 2);
+        let other = 0x6 << LEVEL_OFFSETS[0];
+        for ancestor in [a, ab, abc, other] {
+            let mask = SubtreeMask::new(ancestor);
+            for gid in [a, ab, abc, other] {
+                assert_eq!(mask.contains(gid), gid_is_descendant_of(gid, ancestor));
+            }
+        }
+        assert_eq!(root_of(abc), a);
+        assert_eq!(root_of(a), a);
+    }
+
     #[test]
     fn layout_constants_are_valid() {
         // Total bits should be 125 (128 - 3 for depth)
@@ -222,9 +311,9 @@ mod tests {
     #[test]
     fn masks_include_depth() {
         // All masks should include the depth bits
-        for i in 0..MAX_DEPTH {
+        for (i, mask) in LEVEL_MASKS.iter().enumerate() {
             assert_eq!(
-                LEVEL_MASKS[i] & DEPTH_MASK,
+                mask & DEPTH_MASK,
                 DEPTH_MASK,
                 "mask {} should include depth bits",
                 i
@@ -246,6 +335,22 @@ mod tests {
         assert!(gid_is_descendant_of(level1, parent));
     }
 
+    #[test]
+    fn well_formed_checks_levels() {
+        let root = encode_gid(0x100000u128 << LEVEL_OFFSETS[0], 0);
+        assert!(is_well_formed(root));
+        assert_eq!(segment_of(root, 0), 0x100000);
+        assert_eq!(segment_of(root, 1), 0);
+
+        // Depth says 1 but level 1 is empty
+        let bad_depth = encode_gid(0x100000u128 << LEVEL_OFFSETS[0], 1);
+        assert!(!is_well_formed(bad_depth));
+
+        // Level 2 populated but depth is 0
+        let stray = root | (1u128 << LEVEL_OFFSETS[2]);
+        assert!(!is_well_formed(stray));
+    }
+
     #[test]
     fn deep_hierarchy_fits() {
         // Build a GID with all 8 levels populated
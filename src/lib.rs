@@ -55,12 +55,40 @@
 //! assert_eq!(registry.path_of(gid), Some("Movement.Idle"));
 //! ```
 
+mod deprecation;
+mod flags;
+mod frozen;
 pub(crate) mod hash;
+mod hasher;
+pub mod journal;
 pub(crate) mod layout;
+mod lookup;
+mod meta_store;
+mod mount;
+pub mod migrate;
+mod occupancy;
+mod pattern;
 mod registry;
+#[cfg(feature = "rand")]
+mod sample;
+mod static_registry;
+mod tag_path;
 mod traits;
 
 pub mod bevy;
+pub mod stats;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "serde")]
+pub mod serde_support;
+
+#[cfg(feature = "sync")]
+pub mod sync;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 // =============================================================================
 // Core Types
@@ -81,9 +109,42 @@ pub const ROOT_GID: GID = 0;
 /// Maximum supported tree depth (0-7, 8 levels total).
 pub use layout::MAX_DEPTH;
 
-pub use traits::{HasData, IntoGid, IntoGids, IntoGidWithRegistry, NamespaceTag, Redirect};
-pub use layout::{depth_of, gid_is_descendant_of, is_sibling, parent_of};
-pub use registry::{NamespaceDef, NamespaceEntry, NamespaceRegistry};
+/// Segment bit width of each level.
+pub use layout::LEVEL_WIDTHS;
+
+pub use hash::HASH_VERSION;
+pub use layout::LAYOUT_VERSION;
+
+/// Hash function and bit layout GIDs are computed with, as recorded in the
+/// `gid_scheme` of `bevy-tag-build` lock files.
+///
+/// Two builds agree on every GID exactly when their schemes are equal.
+pub const GID_SCHEME: &str = "fnv1a64-mix-v1/21.18.16.16.14.14.13.13";
+
+pub use traits::{
+    Alias, AliasPath, HasData, IntoGid, IntoGids, IntoGidWithRegistry, NamespaceTag, Redirect,
+    TagDescriptor,
+};
+
+/// Namespace definition macros (`namespace!` and the `#[namespace_mod]` attribute form).
+pub use bevy_tag_macro::{namespace, namespace_mod};
+pub use deprecation::{DeprecatedUse, DeprecationPolicy, Resolved};
+pub use flags::TagFlags;
+pub use hasher::{GidBuildHasher, GidHasher, GidMap, GidSet, SipGidMap, SipGidSet};
+pub use frozen::{FrozenRegistry, GidDecodeError, TagOrdinals};
+pub use layout::{
+    depth_of, gid_is_descendant_of, is_sibling, is_well_formed, parent_of, root_of, segment_of,
+    SubtreeMask,
+};
+pub use lookup::{is_sorted_by_path, lookup_path};
+pub use occupancy::LevelLoad;
+pub use pattern::TagPattern;
+pub use registry::{
+    default_loc_key, Deprecation, NamespaceDef, NamespaceEntry, NamespaceRegistry, SubtreeItem,
+    TagStr,
+};
+pub use static_registry::{StaticEntry, StaticRegistry};
+pub use tag_path::TagPath;
 
 /// Compute a full hierarchical GID from path segments.
 ///
@@ -92,3 +153,143 @@ pub use registry::{NamespaceDef, NamespaceEntry, NamespaceRegistry};
 #[doc(hidden)]
 pub use hash::hierarchical_gid;
 
+/// Child GID helpers used by the `namespace!` macro for pinned GIDs/segments.
+#[doc(hidden)]
+pub use hash::{child_gid, gid_with_segment};
+
+/// Re-exports used by macro-generated code. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use ::bevy;
+    pub use ::serde;
+
+    pub use crate::lookup::{sort_by_gid, tables_agree};
+    pub use crate::mount::{
+        concat_defs, concat_entries, merge_path_table, mount, mounted_len, mounted_path_bytes,
+        mounted_paths,
+    };
+
+    /// `handled` lists every GID of `children` exactly once.
+    pub const fn covers_children(children: &[crate::GID], handled: &[crate::GID]) -> bool {
+        if children.len() != handled.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < children.len() {
+            let mut found = false;
+            let mut j = 0;
+            while j < handled.len() {
+                found |= handled[j] == children[i];
+                j += 1;
+            }
+            if !found {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+}
+
+/// Include the tags generated into `OUT_DIR` by
+/// `bevy_tag_build::generate_to_out_dir` (from the `bevy-tag-build` crate).
+///
+/// ```ignore
+/// // build.rs
+/// bevy_tag_build::generate_to_out_dir("tags.toml").unwrap();
+///
+/// // src/lib.rs
+/// bevy_tag::include_tags!();
+/// use Tags::Item;
+/// ```
+///
+/// Pass a file name to include another file from `OUT_DIR`.
+#[macro_export]
+macro_rules! include_tags {
+    () => {
+        $crate::include_tags!("bevy_tags.rs");
+    };
+    ($file:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/", $file));
+    };
+}
+
+/// Fail to compile unless the listed children are exactly the direct
+/// children of a tag, so per-tag dispatch tables get `match`-like
+/// exhaustiveness: adding a child to the subtree breaks the build until it
+/// is handled.
+///
+/// ```ignore
+/// bevy_tag::assert_covers_children!(Tags::Damage::Physical, [Blunt, Slash, Pierce]);
+/// ```
+///
+/// Order doesn't matter. Deprecated children count; redirects don't. A tag
+/// with `include_defs!` tables mounted under it is rejected, since the
+/// mounted roots have no `GID` constants to list.
+#[macro_export]
+macro_rules! assert_covers_children {
+    ($($node:ident)::+, [$($child:ident),* $(,)?]) => {
+        // Deprecated children still have to be listed
+        #[allow(deprecated)]
+        const _: () = {
+            use $($node)::+ as node;
+            assert!(
+                !node::HAS_MOUNTS,
+                concat!(
+                    "assert_covers_children!(",
+                    stringify!($($node)::+),
+                    "): the tag mounts include_defs! tables, whose roots CHILD_GIDS does not list",
+                ),
+            );
+            assert!(
+                $crate::__private::covers_children(&node::CHILD_GIDS, &[$(node::$child::GID),*]),
+                concat!(
+                    "assert_covers_children!(",
+                    stringify!($($node)::+, [$($child),*]),
+                    "): the list is not exactly the children of the tag",
+                ),
+            );
+        };
+    };
+}
+
+/// Build one [`NamespaceRegistry`] from `namespace!` modules defined in
+/// different crates, checking at compile time that they don't collide.
+///
+/// The compile-time check compares the modules' `PATH_TABLE`s: a path both
+/// define must get the same GID, and no GID may belong to two different
+/// paths unless one namespace redirects one path to the other. The result
+/// is [`NamespaceRegistry::build_many`], which names the offending paths if
+/// the definitions still don't merge.
+///
+/// ```ignore
+/// let registry = bevy_tag::link_namespaces!(combat_tags::Tags, ui_tags::Tags)?;
+/// ```
+#[macro_export]
+macro_rules! link_namespaces {
+    ($($($ns:ident)::+),+ $(,)?) => {{
+        const _: () = assert!(
+            $crate::__private::tables_agree(&[$((
+                $($ns::)+PATH_TABLE,
+                &$crate::__private::sort_by_gid::<{ $($ns::)+PATH_TABLE.len() }>(
+                    $($ns::)+PATH_TABLE,
+                ),
+            )),+]),
+            concat!(
+                "link_namespaces!(",
+                stringify!($($($ns)::+),+),
+                "): namespaces disagree on a GID; NamespaceRegistry::build_many reports which",
+            ),
+        );
+        $crate::NamespaceRegistry::build_many(&[$($($ns::)+DEFINITIONS),+])
+    }};
+}
+
+/// No-op when the `serde` feature is disabled (see `bevy_tag::serde_support`).
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_tag_serde {
+    ($tag:ident) => {};
+}
+
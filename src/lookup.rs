@@ -0,0 +1,262 @@
+//! Const lookup over static, path-sorted `(path, GID)` tables.
+//!
+//! `namespace!` emits such a table as `Tags::PATH_TABLE` and wraps
+//! [`lookup_path`] as `Tags::lookup`, so read-only code can resolve paths
+//! without building a [`NamespaceRegistry`](crate::NamespaceRegistry).
+
+use core::cmp::Ordering;
+
+use crate::GID;
+
+/// A `(path, GID)` table, as emitted by `namespace!`.
+pub type PathTable<'a> = &'a [(&'a str, GID)];
+
+/// Byte-wise string comparison usable in const context.
+pub(crate) const fn cmp_str(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] < b[i] {
+            return Ordering::Less;
+        }
+        if a[i] > b[i] {
+            return Ordering::Greater;
+        }
+        i += 1;
+    }
+    if a.len() < b.len() {
+        Ordering::Less
+    } else if a.len() > b.len() {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Binary-search a table sorted by path (byte order).
+pub const fn lookup_path(table: &[(&str, GID)], path: &str) -> Option<GID> {
+    let (mut lo, mut hi) = (0, table.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match cmp_str(table[mid].0, path) {
+            Ordering::Less => lo = mid + 1,
+            Ordering::Greater => hi = mid,
+            Ordering::Equal => return Some(table[mid].1),
+        }
+    }
+    None
+}
+
+/// Check that a table is strictly sorted by path (no duplicates).
+pub const fn is_sorted_by_path(table: &[(&str, GID)]) -> bool {
+    let mut i = 1;
+    while i < table.len() {
+        if !matches!(cmp_str(table[i - 1].0, table[i].0), Ordering::Less) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Copy of a path table sorted by GID (heapsort), for [`tables_agree`].
+///
+/// Redirects end up next to their targets, since they share the GID.
+pub const fn sort_by_gid<const N: usize>(table: &[(&'static str, GID)]) -> [(&'static str, GID); N] {
+    assert!(table.len() == N, "table length does not match N");
+    let mut out = [("", 0); N];
+    let mut i = 0;
+    while i < N {
+        out[i] = table[i];
+        i += 1;
+    }
+    let mut start = N / 2;
+    while start > 0 {
+        start -= 1;
+        sift_down_by_gid(&mut out, start, N);
+    }
+    let mut end = N;
+    while end > 1 {
+        end -= 1;
+        let first = out[0];
+        out[0] = out[end];
+        out[end] = first;
+        sift_down_by_gid(&mut out, 0, end);
+    }
+    out
+}
+
+const fn sift_down_by_gid(table: &mut [(&str, GID)], mut root: usize, end: usize) {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            return;
+        }
+        if child + 1 < end && table[child + 1].1 > table[child].1 {
+            child += 1;
+        }
+        if table[root].1 >= table[child].1 {
+            return;
+        }
+        let tmp = table[root];
+        table[root] = table[child];
+        table[child] = tmp;
+        root = child;
+    }
+}
+
+/// Check that tables from different namespaces agree: a path present in two
+/// tables has the same GID in both, and two different paths share a GID only
+/// if one table maps both to it (a redirect).
+///
+/// Each namespace is given as its path table and the same table sorted by
+/// GID ([`sort_by_gid`]). Pairs of tables are merge-walked in both orders,
+/// so the check stays linear in the table sizes per pair.
+pub const fn tables_agree(tables: &[(PathTable<'_>, PathTable<'_>)]) -> bool {
+    let mut a = 0;
+    while a < tables.len() {
+        let mut b = a + 1;
+        while b < tables.len() {
+            if !paths_agree(tables[a].0, tables[b].0) || !gids_agree(tables[a].1, tables[b].1) {
+                return false;
+            }
+            b += 1;
+        }
+        a += 1;
+    }
+    true
+}
+
+/// Merge-walk two path-sorted tables: shared paths must share the GID.
+const fn paths_agree(x: &[(&str, GID)], y: &[(&str, GID)]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    while i < x.len() && j < y.len() {
+        match cmp_str(x[i].0, y[j].0) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                if x[i].1 != y[j].1 {
+                    return false;
+                }
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    true
+}
+
+/// Merge-walk two GID-sorted tables: for a GID both use, every pair of
+/// different paths must be known to one of the tables.
+const fn gids_agree(x: &[(&str, GID)], y: &[(&str, GID)]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    while i < x.len() && j < y.len() {
+        let gid = x[i].1;
+        if gid < y[j].1 {
+            i += 1;
+            continue;
+        }
+        if gid > y[j].1 {
+            j += 1;
+            continue;
+        }
+        // Runs of entries with this GID (a path plus its redirects)
+        let x_end = run_end(x, i);
+        let y_end = run_end(y, j);
+        let mut p = i;
+        while p < x_end {
+            let mut q = j;
+            while q < y_end {
+                if !matches!(cmp_str(x[p].0, y[q].0), Ordering::Equal)
+                    && !run_contains(x, i, x_end, y[q].0)
+                    && !run_contains(y, j, y_end, x[p].0)
+                {
+                    return false;
+                }
+                q += 1;
+            }
+            p += 1;
+        }
+        i = x_end;
+        j = y_end;
+    }
+    true
+}
+
+const fn run_end(table: &[(&str, GID)], start: usize) -> usize {
+    let mut end = start + 1;
+    while end < table.len() && table[end].1 == table[start].1 {
+        end += 1;
+    }
+    end
+}
+
+const fn run_contains(table: &[(&str, GID)], start: usize, end: usize, path: &str) -> bool {
+    let mut k = start;
+    while k < end {
+        if matches!(cmp_str(table[k].0, path), Ordering::Equal) {
+            return true;
+        }
+        k += 1;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TABLE: &[(&str, GID)] = &[("A", 1), ("A.B", 2), ("AB", 3), ("B", 4)];
+
+    #[test]
+    fn finds_every_entry() {
+        assert!(is_sorted_by_path(TABLE));
+        for &(path, gid) in TABLE {
+            assert_eq!(lookup_path(TABLE, path), Some(gid));
+        }
+    }
+
+    #[test]
+    fn misses() {
+        assert_eq!(lookup_path(TABLE, ""), None);
+        assert_eq!(lookup_path(TABLE, "A."), None);
+        assert_eq!(lookup_path(TABLE, "C"), None);
+        assert_eq!(lookup_path(&[], "A"), None);
+    }
+
+    #[test]
+    fn works_in_const() {
+        const B: Option<GID> = lookup_path(TABLE, "A.B");
+        assert_eq!(B, Some(2));
+        assert!(!is_sorted_by_path(&[("B", 1), ("A", 2)]));
+    }
+
+    /// Pair each table with its GID-sorted copy, as `link_namespaces!` does.
+    macro_rules! agree {
+        ($($table:expr),+) => {
+            tables_agree(&[$(($table, &sort_by_gid::<{ $table.len() }>($table))),+])
+        };
+    }
+
+    #[test]
+    fn sorts_by_gid() {
+        const SORTED: [(&str, GID); 3] = sort_by_gid(&[("B", 3), ("A", 1), ("C", 2)]);
+        assert_eq!(SORTED, [("A", 1), ("C", 2), ("B", 3)]);
+    }
+
+    #[test]
+    fn tables_agree_across_namespaces() {
+        const SHARED: &[(&str, GID)] = &[("A", 1), ("C", 5)];
+        const REDIRECTS: &[(&str, GID)] = &[("A", 1), ("Old", 5)];
+        const ALIASED: &[(&str, GID)] = &[("A.B", 2), ("A.Old", 2)];
+        const NEW_AND_OLD: &[(&str, GID)] = &[("C", 5), ("Old", 5)];
+        assert!(agree!(TABLE, SHARED));
+        assert!(agree!(TABLE, ALIASED));
+        // Same path, different GID
+        assert!(!agree!(TABLE, &[("A", 9)]));
+        // Different paths, same GID, no redirect
+        assert!(!agree!(TABLE, &[("Z", 4)]));
+        assert!(!agree!(TABLE, SHARED, REDIRECTS));
+        assert!(agree!(SHARED, NEW_AND_OLD));
+    }
+}
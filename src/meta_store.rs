@@ -0,0 +1,229 @@
+//! Arena storage for registry metadata.
+//!
+//! Every value lives in one shared buffer, indexed by `(GID, key id)`, so
+//! thousands of tags with small stats blobs cost a few allocations instead of
+//! one per value. Key names are interned once.
+
+use std::collections::{BTreeMap, HashMap};
+
+use zerocopy::IntoBytes;
+
+use crate::GID;
+
+/// Values start on 16-byte boundaries so `zerocopy` can hand out `&T` for any
+/// primitive-aligned `T`.
+type Word = u128;
+const WORD: usize = size_of::<Word>();
+
+/// Location of one value in the arena.
+#[derive(Clone, Copy, Debug)]
+struct Span {
+    /// Byte offset; always a multiple of `WORD`.
+    start: u32,
+    len: u32,
+}
+
+impl Span {
+    fn capacity(self) -> usize {
+        (self.len as usize).div_ceil(WORD) * WORD
+    }
+
+    fn range(self) -> std::ops::Range<usize> {
+        self.start as usize..(self.start + self.len) as usize
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MetaStore {
+    key_names: Vec<String>,
+    key_ids: HashMap<String, u32>,
+    /// Ordered by GID first, so one tag's values are a contiguous range.
+    index: BTreeMap<(GID, u32), Span>,
+    arena: Vec<Word>,
+    /// Bytes in `arena` no longer referenced by `index`.
+    garbage: usize,
+}
+
+impl MetaStore {
+    pub(crate) fn get(&self, gid: GID, key: &str) -> Option<&[u8]> {
+        let id = *self.key_ids.get(key)?;
+        let span = *self.index.get(&(gid, id))?;
+        Some(&self.arena.as_bytes()[span.range()])
+    }
+
+    pub(crate) fn contains(&self, gid: GID, key: &str) -> bool {
+        self.key_ids
+            .get(key)
+            .is_some_and(|&id| self.index.contains_key(&(gid, id)))
+    }
+
+    /// Store `value`, returning the bytes it replaced.
+    pub(crate) fn insert(&mut self, gid: GID, key: String, value: &[u8]) -> Option<Vec<u8>> {
+        let id = self.key_id(key);
+        let len = u32::try_from(value.len()).expect("metadata value exceeds 4 GiB");
+        match self.index.get(&(gid, id)).copied() {
+            Some(old) => {
+                let previous = self.arena.as_bytes()[old.range()].to_vec();
+                if value.len() <= old.capacity() {
+                    let span = Span {
+                        start: old.start,
+                        len,
+                    };
+                    self.arena.as_mut_bytes()[span.range()].copy_from_slice(value);
+                    self.index.insert((gid, id), span);
+                } else {
+                    let span = self.append(value);
+                    self.index.insert((gid, id), span);
+                    self.release(old);
+                }
+                Some(previous)
+            }
+            None => {
+                let span = self.append(value);
+                self.index.insert((gid, id), span);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, gid: GID, key: &str) -> Option<Vec<u8>> {
+        let id = *self.key_ids.get(key)?;
+        let span = self.index.remove(&(gid, id))?;
+        let previous = self.arena.as_bytes()[span.range()].to_vec();
+        self.release(span);
+        Some(previous)
+    }
+
+    /// Drop every value stored for `gid`.
+    pub(crate) fn remove_all(&mut self, gid: GID) {
+        let keys: Vec<_> = self
+            .index
+            .range((gid, 0)..=(gid, u32::MAX))
+            .map(|(&key, _)| key)
+            .collect();
+        for key in keys {
+            if let Some(span) = self.index.remove(&key) {
+                self.release(span);
+            }
+        }
+    }
+
+    /// `(key, bytes)` pairs stored for `gid`, in key-interning order.
+    pub(crate) fn iter(&self, gid: GID) -> impl Iterator<Item = (&str, &[u8])> {
+        let bytes = self.arena.as_bytes();
+        self.index
+            .range((gid, 0)..=(gid, u32::MAX))
+            .map(move |(&(_, id), &span)| {
+                (self.key_names[id as usize].as_str(), &bytes[span.range()])
+            })
+    }
+
+    pub(crate) fn has_any(&self, gid: GID) -> bool {
+        self.iter(gid).next().is_some()
+    }
+
+    fn key_id(&mut self, key: String) -> u32 {
+        if let Some(&id) = self.key_ids.get(&key) {
+            return id;
+        }
+        let id = self.key_names.len() as u32;
+        self.key_names.push(key.clone());
+        self.key_ids.insert(key, id);
+        id
+    }
+
+    fn append(&mut self, value: &[u8]) -> Span {
+        let start = self.arena.len() * WORD;
+        self.arena
+            .resize(self.arena.len() + value.len().div_ceil(WORD), 0);
+        let span = Span {
+            start: u32::try_from(start).expect("metadata arena exceeds 4 GiB"),
+            len: value.len() as u32,
+        };
+        self.arena.as_mut_bytes()[span.range()].copy_from_slice(value);
+        span
+    }
+
+    /// Forget `span`'s slot, compacting once half the arena is garbage.
+    fn release(&mut self, span: Span) {
+        self.garbage += span.capacity();
+        if self.garbage * 2 > self.arena.len() * WORD {
+            self.compact();
+        }
+    }
+
+    fn compact(&mut self) {
+        let old = std::mem::take(&mut self.arena);
+        let bytes = old.as_bytes();
+        let spans: Vec<_> = self.index.values().copied().collect();
+        let mut moved = Vec::with_capacity(spans.len());
+        for span in spans {
+            moved.push(self.append(&bytes[span.range()]));
+        }
+        for (slot, span) in self.index.values_mut().zip(moved) {
+            *slot = span;
+        }
+        self.garbage = 0;
+    }
+}
+
+/// Equal when the same GIDs hold the same keys and bytes, whatever the arena
+/// layout or key interning order.
+impl PartialEq for MetaStore {
+    fn eq(&self, other: &Self) -> bool {
+        self.index.len() == other.index.len()
+            && self.index.keys().all(|&(gid, id)| {
+                let key = &self.key_names[id as usize];
+                self.get(gid, key) == other.get(gid, key)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_slots_and_compacts() {
+        let mut store = MetaStore::default();
+        for gid in 0..8 {
+            store.insert(gid, "stats".into(), &[gid as u8; 20]);
+        }
+        let words = store.arena.len();
+
+        // Fits the old 32-byte slot: rewritten in place
+        assert_eq!(store.insert(3, "stats".into(), &[9; 30]), Some(vec![3; 20]));
+        assert_eq!(store.arena.len(), words);
+        assert_eq!(store.get(3, "stats"), Some(&[9; 30][..]));
+
+        for gid in 0..6 {
+            store.remove(gid, "stats");
+        }
+        assert!(store.arena.len() < words);
+        assert_eq!(store.get(7, "stats"), Some(&[7; 20][..]));
+        assert!(!store.has_any(0));
+    }
+
+    #[test]
+    fn values_are_aligned() {
+        let mut store = MetaStore::default();
+        store.insert(1, "a".into(), &[1; 3]);
+        store.insert(1, "b".into(), 7u64.as_bytes());
+        let bytes = store.get(1, "b").unwrap();
+        assert_eq!(bytes.as_ptr() as usize % WORD, 0);
+    }
+
+    #[test]
+    fn equality_ignores_layout() {
+        let mut a = MetaStore::default();
+        a.insert(1, "x".into(), &[1]);
+        a.insert(1, "y".into(), &[2]);
+        let mut b = MetaStore::default();
+        b.insert(1, "y".into(), &[5; 17]);
+        b.insert(1, "x".into(), &[1]);
+        b.insert(1, "y".into(), &[2]);
+        assert_eq!(a, b);
+        b.remove(1, "x");
+        assert_ne!(a, b);
+    }
+}
@@ -0,0 +1,478 @@
+//! Offline upgrade of stored GIDs after renames or hash/layout changes.
+//!
+//! A [`RedirectMap`] maps old GIDs and paths to the ones the game uses now.
+//! `bevy-tag-build` generates one from `[redirects]` and the lock file
+//! (`[codegen] redirects = true`), and the registry and
+//! [`gid_path`](crate::serde_support::gid_path) resolve through it. The GID
+//! half can also be read from the text `cargo bevy-tag migrations` prints,
+//! one `old new` pair of hex GIDs per line:
+//!
+//! ```text
+//! # old                                new
+//! 0x00000000000000000000000000001234 0x00000000000000000000000000005678  # Item.Blade -> Item.Sword
+//! ```
+//!
+//! Opaque files (binary saves, replays) are patched with [`rewrite_gids`],
+//! which replaces every little-endian occurrence of an old GID. Data that is
+//! already deserialized is patched through [`VisitGidsMut`]:
+//!
+//! ```
+//! use bevy_tag::migrate::{RedirectMap, VisitGidsMut};
+//!
+//! struct Save { equipped: Vec<u128>, quest: Option<u128> }
+//!
+//! impl VisitGidsMut for Save {
+//!     fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut u128)) {
+//!         self.equipped.visit_gids_mut(f);
+//!         self.quest.visit_gids_mut(f);
+//!     }
+//! }
+//!
+//! let map: RedirectMap = "0x1 0x2".parse().unwrap();
+//! let mut save = Save { equipped: vec![1, 3], quest: Some(1) };
+//! assert_eq!(map.apply(&mut save), 2);
+//! assert_eq!(save.equipped, [2, 3]);
+//! ```
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+
+use crate::hasher::GidMap;
+use crate::journal::JournalChange;
+use crate::registry::NamespaceRegistry;
+use crate::GID;
+
+/// Old GID → current GID and old path → current path.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RedirectMap {
+    map: GidMap<GID>,
+    paths: HashMap<String, String>,
+}
+
+impl RedirectMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Map `old` to `new`, replacing any earlier mapping of `old`.
+    pub fn insert(&mut self, old: GID, new: GID) {
+        self.map.insert(old, new);
+    }
+
+    /// Current GID for `old`, if it was migrated.
+    #[inline]
+    pub fn get(&self, old: GID) -> Option<GID> {
+        self.map.get(&old).copied()
+    }
+
+    /// Current GID for `gid`: its mapping, or itself.
+    #[inline]
+    pub fn resolve(&self, gid: GID) -> GID {
+        self.get(gid).unwrap_or(gid)
+    }
+
+    /// Map path `old` to `new`, replacing any earlier mapping of `old`.
+    pub fn insert_path(&mut self, old: impl Into<String>, new: impl Into<String>) {
+        self.paths.insert(old.into(), new.into());
+    }
+
+    /// Current path for `old`, if it was redirected.
+    #[inline]
+    pub fn get_path(&self, old: &str) -> Option<&str> {
+        self.paths.get(old).map(String::as_str)
+    }
+
+    /// Current path for `path`: its mapping, or itself.
+    #[inline]
+    pub fn resolve_path<'a>(&'a self, path: &'a str) -> &'a str {
+        self.get_path(path).unwrap_or(path)
+    }
+
+    /// `(old, new)` GID pairs, in no particular order.
+    pub fn gids(&self) -> impl Iterator<Item = (GID, GID)> + '_ {
+        self.map.iter().map(|(&old, &new)| (old, new))
+    }
+
+    /// `(old, new)` path pairs, in no particular order.
+    pub fn paths(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.paths.iter().map(|(old, new)| (old.as_str(), new.as_str()))
+    }
+
+    /// Number of GID and path redirects.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len() + self.paths.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty() && self.paths.is_empty()
+    }
+
+    /// Rewrite every GID `value` exposes; returns how many changed.
+    pub fn apply<T: VisitGidsMut + ?Sized>(&self, value: &mut T) -> usize {
+        let mut changed = 0;
+        value.visit_gids_mut(&mut |gid| {
+            if let Some(new) = self.get(*gid) {
+                *gid = new;
+                changed += 1;
+            }
+        });
+        changed
+    }
+}
+
+impl FromIterator<(GID, GID)> for RedirectMap {
+    fn from_iter<I: IntoIterator<Item = (GID, GID)>>(iter: I) -> Self {
+        Self {
+            map: iter.into_iter().collect(),
+            paths: HashMap::new(),
+        }
+    }
+}
+
+impl FromStr for RedirectMap {
+    type Err = String;
+
+    /// Parse `old new` hex pairs, one per line; `#` starts a comment.
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut map = Self::new();
+        for (number, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut fields = line.split_whitespace();
+            let (Some(old), Some(new), None) = (fields.next(), fields.next(), fields.next()) else {
+                return Err(format!(
+                    "line {}: expected '<old GID> <new GID>', found '{}'",
+                    number + 1,
+                    line
+                ));
+            };
+            let parse = |raw: &str| {
+                raw.strip_prefix("0x")
+                    .or_else(|| raw.strip_prefix("0X"))
+                    .and_then(|hex| GID::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("line {}: '{}' is not a hex GID", number + 1, raw))
+            };
+            map.insert(parse(old)?, parse(new)?);
+        }
+        Ok(map)
+    }
+}
+
+impl NamespaceRegistry {
+    /// [`RedirectMap::insert`], recorded in the registry's journal while
+    /// journaling is enabled.
+    pub fn insert_redirect(&mut self, redirects: &mut RedirectMap, old: GID, new: GID) {
+        let previous = redirects.get(old);
+        redirects.insert(old, new);
+        self.record(None, || JournalChange::RedirectAdded {
+            gid: old,
+            to: new,
+            previous,
+        });
+    }
+
+    /// GID of `path`, following a path redirect in `redirects` first.
+    pub fn gid_of_redirected(&self, path: &str, redirects: &RedirectMap) -> Option<GID> {
+        self.gid_of(redirects.resolve_path(path))
+    }
+
+    /// Path of `gid`, following a GID redirect in `redirects` first.
+    pub fn path_of_redirected(&self, gid: GID, redirects: &RedirectMap) -> Option<&str> {
+        self.path_of(redirects.resolve(gid))
+    }
+
+    /// Check that `redirects` keeps path ↔ GID lookup unambiguous through
+    /// [`gid_of_redirected`](Self::gid_of_redirected) and
+    /// [`path_of_redirected`](Self::path_of_redirected): every target is
+    /// registered and final, and no registered tag is redirected away, which
+    /// would give two paths the same GID.
+    ///
+    /// Reports the first problem, by sorted old path or GID.
+    pub fn verify_redirects(&self, redirects: &RedirectMap) -> Result<(), String> {
+        let mut paths: Vec<(&str, &str)> = redirects.paths().collect();
+        paths.sort_unstable();
+        for (old, new) in paths {
+            if let Some(next) = redirects.get_path(new) {
+                return Err(format!(
+                    "redirect chain '{}' -> '{}' -> '{}': redirect straight to the final tag",
+                    old, new, next
+                ));
+            }
+            let Some(new_gid) = self.gid_of(new) else {
+                return Err(format!(
+                    "redirect target '{}' (from '{}') is not registered",
+                    new, old
+                ));
+            };
+            if let Some(old_gid) = self.gid_of(old) {
+                return Err(format!(
+                    "'{}' is registered as {:#034x} and also redirected to '{}' ({:#034x})",
+                    old, old_gid, new, new_gid
+                ));
+            }
+        }
+
+        let mut gids: Vec<(GID, GID)> = redirects.gids().collect();
+        gids.sort_unstable();
+        for (old, new) in gids {
+            if let Some(next) = redirects.get(new) {
+                return Err(format!(
+                    "redirect chain {:#034x} -> {:#034x} -> {:#034x}: redirect straight to the final tag",
+                    old, new, next
+                ));
+            }
+            let Some(new_path) = self.path_of(new) else {
+                return Err(format!(
+                    "redirect target {:#034x} (from {:#034x}) is not registered",
+                    new, old
+                ));
+            };
+            if let Some(old_path) = self.path_of(old) {
+                return Err(format!(
+                    "'{}' and '{}' map to the same GID {:#034x} through a redirect from {:#034x}",
+                    old_path, new_path, new, old
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Copy `reader` to `writer`, replacing every 16-byte little-endian
+/// occurrence of an old GID in `map`; returns the number replaced.
+///
+/// GIDs are found at any byte offset, so this works on formats it knows
+/// nothing about. A match consumes its 16 bytes, so replacements never
+/// overlap. Random data matches a GID by chance with negligible
+/// probability, but compressed or encrypted files must be unpacked first.
+pub fn rewrite_gids<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    map: &RedirectMap,
+) -> io::Result<usize> {
+    const GID_BYTES: usize = size_of::<GID>();
+    const CHUNK: usize = 64 * 1024;
+
+    let mut buffer = Vec::with_capacity(CHUNK + GID_BYTES);
+    let mut chunk = vec![0; CHUNK];
+    let mut replaced = 0;
+    let mut eof = false;
+    while !eof {
+        let read = reader.read(&mut chunk)?;
+        eof = read == 0;
+        buffer.extend_from_slice(&chunk[..read]);
+
+        // Scan every offset that has a full GID after it; until EOF, keep
+        // the last partial window for the next chunk.
+        let mut offset = 0;
+        while offset + GID_BYTES <= buffer.len() {
+            let window: [u8; GID_BYTES] = buffer[offset..offset + GID_BYTES].try_into().unwrap();
+            match map.get(GID::from_le_bytes(window)) {
+                Some(new) => {
+                    buffer[offset..offset + GID_BYTES].copy_from_slice(&new.to_le_bytes());
+                    replaced += 1;
+                    offset += GID_BYTES;
+                }
+                None => offset += 1,
+            }
+        }
+        let keep = if eof { 0 } else { buffer.len() - offset };
+        writer.write_all(&buffer[..buffer.len() - keep])?;
+        buffer.drain(..buffer.len() - keep);
+    }
+    writer.flush()?;
+    Ok(replaced)
+}
+
+/// Structured data holding GIDs, for [`RedirectMap::apply`].
+///
+/// Implement it for save-game types by forwarding to each field; GIDs
+/// (`u128`) and common containers of them are covered here.
+pub trait VisitGidsMut {
+    /// Call `f` with every GID in `self`.
+    fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut GID));
+}
+
+impl VisitGidsMut for GID {
+    fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut GID)) {
+        f(self);
+    }
+}
+
+impl<T: VisitGidsMut> VisitGidsMut for [T] {
+    fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut GID)) {
+        self.iter_mut().for_each(|item| item.visit_gids_mut(f));
+    }
+}
+
+impl<T: VisitGidsMut, const N: usize> VisitGidsMut for [T; N] {
+    fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut GID)) {
+        self.as_mut_slice().visit_gids_mut(f);
+    }
+}
+
+impl<T: VisitGidsMut> VisitGidsMut for Vec<T> {
+    fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut GID)) {
+        self.as_mut_slice().visit_gids_mut(f);
+    }
+}
+
+impl<T: VisitGidsMut> VisitGidsMut for Option<T> {
+    fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut GID)) {
+        if let Some(value) = self {
+            value.visit_gids_mut(f);
+        }
+    }
+}
+
+impl<T: VisitGidsMut + ?Sized> VisitGidsMut for Box<T> {
+    fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut GID)) {
+        (**self).visit_gids_mut(f);
+    }
+}
+
+impl<K, V: VisitGidsMut, S> VisitGidsMut for HashMap<K, V, S> {
+    /// Visits values only; keys can't be changed in place.
+    fn visit_gids_mut(&mut self, f: &mut dyn FnMut(&mut GID)) {
+        self.values_mut().for_each(|value| value.visit_gids_mut(f));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: GID = 0x2000_0000_0000_0000_0000_0000_0000_1234;
+    const NEW: GID = 0x2000_0000_0000_0000_0000_0000_0000_5678;
+
+    fn map() -> RedirectMap {
+        RedirectMap::from_iter([(OLD, NEW)])
+    }
+
+    #[test]
+    fn parses_the_cli_format() {
+        let text = format!(
+            "# old new\n\n0x{:032x} 0X{:032x}  # Item.Blade -> Item.Sword\n",
+            OLD, NEW
+        );
+        let parsed: RedirectMap = text.parse().unwrap();
+        assert_eq!(parsed, map());
+        assert_eq!(parsed.resolve(OLD), NEW);
+        assert_eq!(parsed.resolve(NEW), NEW);
+
+        assert_eq!(
+            "0x1".parse::<RedirectMap>().unwrap_err(),
+            "line 1: expected '<old GID> <new GID>', found '0x1'"
+        );
+        assert_eq!(
+            "\n0x1 12".parse::<RedirectMap>().unwrap_err(),
+            "line 2: '12' is not a hex GID"
+        );
+    }
+
+    #[test]
+    fn redirects_paths_alongside_gids() {
+        let mut map = map();
+        map.insert_path("Item.Blade", "Item.Sword");
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get_path("Item.Blade"), Some("Item.Sword"));
+        assert_eq!(map.resolve_path("Item.Blade"), "Item.Sword");
+        assert_eq!(map.resolve_path("Item.Sword"), "Item.Sword");
+        assert_eq!(map.gids().collect::<Vec<_>>(), [(OLD, NEW)]);
+        assert_eq!(map.paths().collect::<Vec<_>>(), [("Item.Blade", "Item.Sword")]);
+        assert_ne!(map, self::map());
+
+        let mut registry = NamespaceRegistry::new();
+        let sword = registry.register("Item.Sword").unwrap();
+        assert_eq!(registry.gid_of_redirected("Item.Blade", &map), Some(sword));
+        assert_eq!(registry.gid_of_redirected("Item.Sword", &map), Some(sword));
+        assert_eq!(registry.gid_of("Item.Blade"), None);
+
+        map.insert(OLD, sword);
+        assert_eq!(registry.path_of_redirected(OLD, &map), Some("Item.Sword"));
+    }
+
+    #[test]
+    fn verifies_redirects_against_registry() {
+        let mut registry = NamespaceRegistry::new();
+        let sword = registry.register("Item.Sword").unwrap();
+        let shield = registry.register("Item.Shield").unwrap();
+
+        let mut redirects = RedirectMap::new();
+        redirects.insert_path("Item.Blade", "Item.Sword");
+        redirects.insert(OLD, sword);
+        assert_eq!(registry.verify_redirects(&redirects), Ok(()));
+
+        // A live tag redirected elsewhere: two paths resolve to one GID
+        let mut live = redirects.clone();
+        live.insert(shield, sword);
+        assert_eq!(
+            registry.verify_redirects(&live),
+            Err(format!(
+                "'Item.Shield' and 'Item.Sword' map to the same GID {:#034x} through a redirect from {:#034x}",
+                sword, shield
+            ))
+        );
+
+        let mut missing = redirects.clone();
+        missing.insert_path("Item.Axe", "Item.Hatchet");
+        assert_eq!(
+            registry.verify_redirects(&missing),
+            Err("redirect target 'Item.Hatchet' (from 'Item.Axe') is not registered".into())
+        );
+
+        let mut chained = redirects.clone();
+        chained.insert_path("Item.Knife", "Item.Blade");
+        assert_eq!(
+            registry.verify_redirects(&chained),
+            Err("redirect chain 'Item.Knife' -> 'Item.Blade' -> 'Item.Sword': redirect straight to the final tag".into())
+        );
+
+        let mut shadowed = redirects;
+        shadowed.insert_path("Item.Shield", "Item.Sword");
+        assert!(registry
+            .verify_redirects(&shadowed)
+            .unwrap_err()
+            .starts_with("'Item.Shield' is registered as"));
+    }
+
+    #[test]
+    fn rewrites_unaligned_gids_across_chunks() {
+        let mut input = vec![0xAA; 3];
+        input.extend_from_slice(&OLD.to_le_bytes());
+        // Straddle the 64 KiB chunk boundary
+        input.resize(64 * 1024 - 5, 0xBB);
+        input.extend_from_slice(&OLD.to_le_bytes());
+        input.extend_from_slice(&NEW.to_le_bytes());
+        input.push(0xCC);
+
+        let mut output = Vec::new();
+        let replaced = rewrite_gids(input.as_slice(), &mut output, &map()).unwrap();
+        assert_eq!(replaced, 2);
+        assert_eq!(output.len(), input.len());
+        assert_eq!(output[3..19], NEW.to_le_bytes());
+        assert_eq!(output[64 * 1024 - 5..64 * 1024 + 11], NEW.to_le_bytes());
+        assert_eq!(output[..3], input[..3]);
+        assert_eq!(output.last(), Some(&0xCC));
+
+        let mut short = Vec::new();
+        assert_eq!(rewrite_gids(&[1u8, 2][..], &mut short, &map()).unwrap(), 0);
+        assert_eq!(short, [1, 2]);
+    }
+
+    #[test]
+    fn visits_nested_containers() {
+        let mut data: Vec<Option<[GID; 2]>> = vec![Some([OLD, 1]), None, Some([2, OLD])];
+        assert_eq!(map().apply(&mut data), 2);
+        assert_eq!(data, [Some([NEW, 1]), None, Some([2, NEW])]);
+
+        let mut by_slot: HashMap<&str, GID> = HashMap::from([("head", OLD)]);
+        assert_eq!(map().apply(&mut by_slot), 1);
+        assert_eq!(by_slot["head"], NEW);
+    }
+}
@@ -0,0 +1,309 @@
+//! Const re-rooting of another namespace's `DEFINITIONS`.
+//!
+//! `include_defs!(other::Tags::DEFINITIONS);` inside a `namespace!` node
+//! mounts that table under the node. The macro can't see the table's
+//! contents, so the new paths and GIDs are computed here during const
+//! evaluation: the paths are concatenated into one byte buffer whose length
+//! is itself a const, and each mounted entry borrows its path from it.
+
+use core::cmp::Ordering;
+
+use crate::hash::child_gid;
+use crate::layout::depth_of;
+use crate::lookup::cmp_str;
+use crate::registry::NamespaceDef;
+use crate::static_registry::StaticEntry;
+use crate::GID;
+
+const EMPTY_ENTRY: StaticEntry = StaticEntry {
+    gid: 0,
+    def: NamespaceDef::new("", None),
+};
+
+/// Length of every path in `defs` re-rooted under `prefix`, concatenated.
+pub const fn mounted_path_bytes(prefix: &str, defs: &[NamespaceDef]) -> usize {
+    let mut total = 0;
+    let mut i = 0;
+    while i < defs.len() {
+        total += prefix.len() + 1 + defs[i].path.len();
+        i += 1;
+    }
+    total
+}
+
+/// Every path in `defs` re-rooted under `prefix` (`prefix.path`), concatenated.
+///
+/// `N` must be [`mounted_path_bytes`].
+pub const fn mounted_paths<const N: usize>(prefix: &str, defs: &[NamespaceDef]) -> [u8; N] {
+    let mut out = [0u8; N];
+    let mut at = 0;
+    let mut i = 0;
+    while i < defs.len() {
+        at = copy_into(&mut out, at, prefix.as_bytes());
+        out[at] = b'.';
+        at = copy_into(&mut out, at + 1, defs[i].path.as_bytes());
+        i += 1;
+    }
+    assert!(at == N, "mounted path buffer has the wrong length");
+    out
+}
+
+const fn copy_into(out: &mut [u8], mut at: usize, bytes: &[u8]) -> usize {
+    let mut i = 0;
+    while i < bytes.len() {
+        out[at] = bytes[i];
+        at += 1;
+        i += 1;
+    }
+    at
+}
+
+/// `defs` re-rooted under the node `prefix` with GID `prefix_gid`, paths
+/// borrowed from `paths` (built by [`mounted_paths`]).
+///
+/// Mounted nodes keep their metadata; their GIDs are rehashed under
+/// `prefix_gid`. `N` must be `defs.len()`.
+///
+/// # Panics
+///
+/// At compile time if a def pins its GID (pinned GIDs can't move), names a
+/// parent outside `defs`, or the mount goes deeper than
+/// [`MAX_DEPTH`](crate::MAX_DEPTH) levels.
+pub const fn mount<const N: usize>(
+    prefix: &'static str,
+    prefix_gid: GID,
+    defs: &[NamespaceDef],
+    paths: &'static [u8],
+) -> [StaticEntry; N] {
+    assert!(N == defs.len(), "mount length does not match the table");
+    let first_depth = depth_of(prefix_gid) + 1;
+    let mut out = [EMPTY_ENTRY; N];
+    let mut i = 0;
+    while i < N {
+        let def = defs[i];
+        assert!(def.gid.is_none(), "cannot mount a table with pinned GIDs");
+
+        let mut gid = prefix_gid;
+        let mut depth = first_depth;
+        let mut rest = def.path.as_bytes();
+        loop {
+            let end = segment_end(rest);
+            let (segment, tail) = rest.split_at(end);
+            gid = child_gid(gid, depth, segment);
+            if tail.is_empty() {
+                break;
+            }
+            rest = tail.split_at(1).1;
+            depth += 1;
+        }
+
+        let parent = match def.parent {
+            None => prefix,
+            Some(parent) => mounted_path(prefix, defs, paths, index_of(defs, parent)),
+        };
+        out[i] = StaticEntry {
+            gid,
+            def: NamespaceDef {
+                path: mounted_path(prefix, defs, paths, i),
+                parent: Some(parent),
+                gid: None,
+                ..def
+            },
+        };
+        i += 1;
+    }
+    out
+}
+
+const fn segment_end(bytes: &[u8]) -> usize {
+    let mut i = 0;
+    while i < bytes.len() && bytes[i] != b'.' {
+        i += 1;
+    }
+    i
+}
+
+const fn index_of(defs: &[NamespaceDef], path: &str) -> usize {
+    let mut i = 0;
+    while i < defs.len() {
+        if matches!(cmp_str(defs[i].path, path), Ordering::Equal) {
+            return i;
+        }
+        i += 1;
+    }
+    panic!("mounted table names a parent that is not in the table")
+}
+
+/// The re-rooted path of `defs[index]` within `paths`.
+const fn mounted_path(
+    prefix: &str,
+    defs: &[NamespaceDef],
+    paths: &'static [u8],
+    index: usize,
+) -> &'static str {
+    let mut start = 0;
+    let mut i = 0;
+    while i < index {
+        start += prefix.len() + 1 + defs[i].path.len();
+        i += 1;
+    }
+    let len = prefix.len() + 1 + defs[index].path.len();
+    let bytes = paths.split_at(start).1.split_at(len).0;
+    match core::str::from_utf8(bytes) {
+        Ok(path) => path,
+        Err(_) => panic!("mounted path is not UTF-8"),
+    }
+}
+
+/// Total number of mounted entries.
+pub const fn mounted_len(mounted: &[&[StaticEntry]]) -> usize {
+    let mut total = 0;
+    let mut i = 0;
+    while i < mounted.len() {
+        total += mounted[i].len();
+        i += 1;
+    }
+    total
+}
+
+/// `local` followed by every mounted definition. `N` must be the total.
+pub const fn concat_defs<const N: usize>(
+    local: &[NamespaceDef],
+    mounted: &[&[StaticEntry]],
+) -> [NamespaceDef; N] {
+    let mut out = [EMPTY_ENTRY.def; N];
+    let mut at = 0;
+    while at < local.len() {
+        out[at] = local[at];
+        at += 1;
+    }
+    let mut m = 0;
+    while m < mounted.len() {
+        let mut i = 0;
+        while i < mounted[m].len() {
+            out[at] = mounted[m][i].def;
+            at += 1;
+            i += 1;
+        }
+        m += 1;
+    }
+    assert!(at == N, "definition count does not match");
+    out
+}
+
+/// `local` followed by every mounted entry. `N` must be the total.
+pub const fn concat_entries<const N: usize>(
+    local: &[StaticEntry],
+    mounted: &[&[StaticEntry]],
+) -> [StaticEntry; N] {
+    let mut out = [EMPTY_ENTRY; N];
+    let mut at = 0;
+    while at < local.len() {
+        out[at] = local[at];
+        at += 1;
+    }
+    let mut m = 0;
+    while m < mounted.len() {
+        let mut i = 0;
+        while i < mounted[m].len() {
+            out[at] = mounted[m][i];
+            at += 1;
+            i += 1;
+        }
+        m += 1;
+    }
+    assert!(at == N, "entry count does not match");
+    out
+}
+
+/// `local` (sorted by path) merged with every mounted `(path, GID)`, sorted
+/// by path. `N` must be the total.
+///
+/// # Panics
+///
+/// At compile time if a mounted path is also in `local` or in another mount.
+pub const fn merge_path_table<const N: usize>(
+    local: &[(&'static str, GID)],
+    mounted: &[&[StaticEntry]],
+) -> [(&'static str, GID); N] {
+    let mut out = [("", 0); N];
+    let mut len = 0;
+    while len < local.len() {
+        out[len] = local[len];
+        len += 1;
+    }
+    let mut m = 0;
+    while m < mounted.len() {
+        let mut i = 0;
+        while i < mounted[m].len() {
+            // Insertion sort: shift larger paths up one slot
+            let entry = (mounted[m][i].def.path, mounted[m][i].gid);
+            let mut at = len;
+            while at > 0 && matches!(cmp_str(out[at - 1].0, entry.0), Ordering::Greater) {
+                out[at] = out[at - 1];
+                at -= 1;
+            }
+            assert!(
+                at == 0 || !matches!(cmp_str(out[at - 1].0, entry.0), Ordering::Equal),
+                "include_defs!: a mounted path is already declared in the namespace or mounted twice"
+            );
+            out[at] = entry;
+            len += 1;
+            i += 1;
+        }
+        m += 1;
+    }
+    assert!(len == N, "path table size does not match");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hierarchical_gid;
+
+    const ENGINE: &[NamespaceDef] = &[
+        NamespaceDef::new("Weapon", None).with_display_name("Weapon"),
+        NamespaceDef::new("Weapon.Blade", Some("Weapon")),
+    ];
+    const PREFIX: &str = "Game.Engine";
+    const BYTES: usize = mounted_path_bytes(PREFIX, ENGINE);
+    const PATHS: [u8; BYTES] = mounted_paths(PREFIX, ENGINE);
+    const MOUNTED: [StaticEntry; 2] = mount(
+        PREFIX,
+        hierarchical_gid(&[b"Game", b"Engine"]),
+        ENGINE,
+        &PATHS,
+    );
+
+    #[test]
+    fn rehashes_paths_under_the_prefix() {
+        assert_eq!(MOUNTED[0].def.path, "Game.Engine.Weapon");
+        assert_eq!(MOUNTED[0].def.parent, Some("Game.Engine"));
+        assert_eq!(MOUNTED[0].def.display_name, Some("Weapon"));
+        assert_eq!(MOUNTED[1].def.path, "Game.Engine.Weapon.Blade");
+        assert_eq!(MOUNTED[1].def.parent, Some("Game.Engine.Weapon"));
+        assert_eq!(
+            MOUNTED[1].gid,
+            hierarchical_gid(&[b"Game", b"Engine", b"Weapon", b"Blade"])
+        );
+
+        let table = merge_path_table::<3>(&[("Game.Local", 7)], &[&MOUNTED]);
+        let paths: Vec<&str> = table.iter().map(|&(path, _)| path).collect();
+        assert_eq!(
+            paths,
+            [
+                "Game.Engine.Weapon",
+                "Game.Engine.Weapon.Blade",
+                "Game.Local"
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "already declared")]
+    fn rejects_paths_declared_locally() {
+        let local = [("Game.Engine.Weapon", 7)];
+        merge_path_table::<3>(&local, &[&MOUNTED]);
+    }
+}
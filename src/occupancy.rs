@@ -0,0 +1,166 @@
+//! Segment-space load per tree level.
+//!
+//! Siblings collide when their segments hash to the same bits, so the risk
+//! grows with the widest sibling group at each level relative to that level's
+//! `2^width` slots. Like the `namespace!` macro and `bevy-tag-build`, the
+//! checks here warn well before the first collision error.
+
+use std::fmt;
+
+use crate::layout::{LEVEL_WIDTHS, MAX_DEPTH};
+use crate::registry::{parent_path, NamespaceDef, NamespaceRegistry};
+use crate::GID;
+
+/// How full one level's segment space is.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LevelLoad {
+    /// Level (0 = roots).
+    pub level: usize,
+    /// Size of the widest sibling group at this level.
+    pub used: usize,
+    /// Number of distinct segment values at this level.
+    pub slots: u64,
+}
+
+impl LevelLoad {
+    /// `used` as a fraction of `slots`.
+    pub fn fraction(&self) -> f64 {
+        self.used as f64 / self.slots as f64
+    }
+}
+
+impl fmt::Display for LevelLoad {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "level {}: {}/{} slots used ({:.2}%)",
+            self.level,
+            self.used,
+            self.slots,
+            self.fraction() * 100.0
+        )
+    }
+}
+
+fn load(level: usize, used: usize) -> LevelLoad {
+    LevelLoad {
+        level,
+        used,
+        slots: 1u64 << LEVEL_WIDTHS[level],
+    }
+}
+
+impl NamespaceRegistry {
+    /// Load of every level the registry reaches.
+    pub fn level_loads(&self) -> Vec<LevelLoad> {
+        let depth = self.tree_depth().min(MAX_DEPTH);
+        let mut used = vec![0; depth];
+        for (parent, &count) in self.sibling_counts() {
+            // Children of a root sit at level 1
+            let level = if parent.is_empty() { 0 } else { parent.matches('.').count() + 1 };
+            if let Some(widest) = used.get_mut(level) {
+                *widest = count.max(*widest);
+            }
+        }
+        used.into_iter()
+            .enumerate()
+            .map(|(level, used)| load(level, used))
+            .collect()
+    }
+
+    /// [`build`](Self::build), then call `warn` for each level whose load
+    /// exceeds `threshold` (a fraction, e.g. `0.25`).
+    pub fn build_with_load_warnings(
+        defs: &[NamespaceDef],
+        threshold: f64,
+        mut warn: impl FnMut(LevelLoad),
+    ) -> Result<Self, String> {
+        let registry = Self::build(defs)?;
+        for load in registry.level_loads() {
+            if load.fraction() > threshold {
+                warn(load);
+            }
+        }
+        Ok(registry)
+    }
+
+    /// [`register`](Self::register), then call `warn` for each sibling group
+    /// along `path` whose load exceeds `threshold` (a fraction, e.g. `0.25`).
+    pub fn register_with_load_warnings(
+        &mut self,
+        path: &str,
+        threshold: f64,
+        mut warn: impl FnMut(LevelLoad),
+    ) -> Result<GID, String> {
+        let gid = self.register(path)?;
+        let mut prefix = path;
+        let mut prefixes = vec![path];
+        while let Some((parent, _)) = prefix.rsplit_once('.') {
+            prefixes.push(parent);
+            prefix = parent;
+        }
+        for node in prefixes.into_iter().rev() {
+            let level = node.matches('.').count();
+            let used = self.sibling_counts().get(parent_path(node)).copied().unwrap_or(0);
+            let load = load(level, used);
+            if load.fraction() > threshold {
+                warn(load);
+            }
+        }
+        Ok(gid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_widest_sibling_group() {
+        let mut registry = NamespaceRegistry::new();
+        for path in ["A.X", "A.Y", "A.Z", "B.X"] {
+            registry.register(path).unwrap();
+        }
+        let loads = registry.level_loads();
+        assert_eq!(loads, [load(0, 2), load(1, 3)]);
+        assert_eq!(loads[1].slots, 1 << 18);
+
+        // Counts follow removals
+        registry.register_with_source("A.W", "mod").unwrap();
+        assert_eq!(registry.level_loads()[1], load(1, 4));
+        registry.unregister_source("mod");
+        assert_eq!(registry.level_loads()[1], load(1, 3));
+        assert_eq!(
+            loads[0].to_string(),
+            "level 0: 2/2097152 slots used (0.00%)"
+        );
+    }
+
+    #[test]
+    fn warns_above_threshold() {
+        let mut registry = NamespaceRegistry::new();
+        let mut warnings = Vec::new();
+        // Level 7 has 8192 slots: a tenth of a percent is 8 tags
+        for i in 0..10 {
+            let path = format!("A.B.C.D.E.F.G.N{}", i);
+            registry
+                .register_with_load_warnings(&path, 0.001, |load| warnings.push(load))
+                .unwrap();
+        }
+        assert_eq!(warnings.len(), 2);
+        assert_eq!((warnings[0].level, warnings[0].used), (7, 9));
+        assert_eq!(warnings[1].used, 10);
+
+        let defs: Vec<_> = registry
+            .entries()
+            .iter()
+            .map(|e| {
+                let path: &'static str = Box::leak(e.path.clone().into_boxed_str());
+                NamespaceDef::new(path, path.rsplit_once('.').map(|(p, _)| p))
+            })
+            .collect();
+        let mut built = Vec::new();
+        NamespaceRegistry::build_with_load_warnings(&defs, 0.001, |load| built.push(load)).unwrap();
+        assert_eq!(built, [load(7, 10)]);
+    }
+}
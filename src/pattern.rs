@@ -0,0 +1,197 @@
+//! Explicit match semantics for tag queries.
+//!
+//! Whether a query means "this tag", "this tag or anything under it" or "any
+//! path of this shape" is otherwise implied by which method is called
+//! (`has` vs `has_descendant_of`). A [`TagPattern`] names it, and
+//! [`TagContainer`](crate::bevy::TagContainer) and [`NamespaceRegistry`]
+//! accept one wherever a query can go either way.
+
+use crate::layout::gid_is_descendant_of;
+use crate::registry::NamespaceRegistry;
+use crate::traits::{IntoGid, IntoGidWithRegistry};
+use crate::GID;
+
+/// What a query matches.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum TagPattern {
+    /// Exactly this tag.
+    Exact(GID),
+    /// This tag or any descendant.
+    Subtree(GID),
+    /// Paths matching a dot-separated glob: `*` in a segment matches any
+    /// run of characters within it, and a `**` segment matches any number of
+    /// segments (including none). `Combat.*.Heavy` matches
+    /// `Combat.Melee.Heavy`; `Item.**` matches `Item` and everything below.
+    ///
+    /// Needs the registry to know paths; unknown GIDs never match.
+    Glob(String),
+}
+
+impl TagPattern {
+    /// Exactly `tag`.
+    pub fn exact(tag: impl IntoGid) -> Self {
+        Self::Exact(tag.into_gid())
+    }
+
+    /// `tag` or any descendant.
+    pub fn subtree(tag: impl IntoGid) -> Self {
+        Self::Subtree(tag.into_gid())
+    }
+
+    /// Paths matching `pattern`; see [`TagPattern::Glob`].
+    pub fn glob(pattern: impl Into<String>) -> Self {
+        Self::Glob(pattern.into())
+    }
+
+    /// Does `gid` match?
+    ///
+    /// Only [`Glob`](Self::Glob) consults `registry`.
+    pub fn matches(&self, gid: GID, registry: &NamespaceRegistry) -> bool {
+        match self {
+            Self::Exact(tag) => gid == *tag,
+            Self::Subtree(ancestor) => gid_is_descendant_of(gid, *ancestor),
+            Self::Glob(pattern) => registry
+                .path_of(gid)
+                .is_some_and(|path| glob_matches(pattern, path)),
+        }
+    }
+}
+
+/// The one tag a pattern names: the tag of [`Exact`](TagPattern::Exact) or
+/// the path of a [`Glob`](TagPattern::Glob) without wildcards.
+/// [`Subtree`](TagPattern::Subtree) and globs with wildcards match more than
+/// one tag and fail; use e.g. `TagContainer::remove_descendants_of` for those.
+impl IntoGidWithRegistry for &TagPattern {
+    fn into_gid_with(self, registry: &NamespaceRegistry) -> GID {
+        self.try_into_gid_with(registry)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_into_gid_with(self, registry: &NamespaceRegistry) -> Result<GID, String> {
+        match self {
+            TagPattern::Exact(gid) => Ok(*gid),
+            TagPattern::Subtree(gid) => Err(format!(
+                "subtree pattern of {:#034x} does not name a single tag",
+                gid
+            )),
+            TagPattern::Glob(pattern) if pattern.contains('*') => Err(format!(
+                "glob '{}' does not name a single tag",
+                pattern
+            )),
+            TagPattern::Glob(path) => path.as_str().try_into_gid_with(registry),
+        }
+    }
+}
+
+/// Does dot-separated `path` match `pattern`?
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('.').collect();
+    let path: Vec<&str> = path.split('.').collect();
+    segments_match(&pattern, &path)
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| segments_match(rest, &path[skip..])),
+        Some((first, rest)) => path.split_first().is_some_and(|(segment, tail)| {
+            wildcard_match(first, segment) && segments_match(rest, tail)
+        }),
+    }
+}
+
+/// Match one segment, where `*` stands for any run of characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*`: the whole segment must match literally
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+impl NamespaceRegistry {
+    /// Registered tags matching `pattern`, in DFS order.
+    pub fn query(&self, pattern: &TagPattern) -> Vec<GID> {
+        match pattern {
+            TagPattern::Exact(gid) if self.contains_gid(*gid) => vec![*gid],
+            TagPattern::Exact(_) => Vec::new(),
+            TagPattern::Subtree(ancestor) => self.descendants_of(*ancestor),
+            TagPattern::Glob(_) => self
+                .dfs_order()
+                .iter()
+                .copied()
+                .filter(|&gid| pattern.matches(gid, self))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards_within_a_segment() {
+        assert!(wildcard_match("Fire*", "Fireball"));
+        assert!(wildcard_match("*ball", "Fireball"));
+        assert!(wildcard_match("F*b*l", "Fireball"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("Fire", "Fireball"));
+        assert!(!wildcard_match("*bal", "Fireball"));
+        assert!(!wildcard_match("ab*ba", "aba"));
+    }
+
+    #[test]
+    fn globs_over_segments() {
+        assert!(glob_matches("Combat.*.Heavy", "Combat.Melee.Heavy"));
+        assert!(!glob_matches("Combat.*.Heavy", "Combat.Heavy"));
+        assert!(glob_matches("Item.**", "Item"));
+        assert!(glob_matches("Item.**", "Item.Weapon.Sword"));
+        assert!(glob_matches("**.Sword", "Item.Weapon.Sword"));
+        assert!(!glob_matches("Item.**", "Items.Sword"));
+        assert!(!glob_matches("Item", "Item.Sword"));
+    }
+
+    #[test]
+    fn patterns_state_their_semantics() {
+        let mut registry = NamespaceRegistry::new();
+        let item = registry.register("Item").unwrap();
+        let sword = registry.register("Item.Weapon.Sword").unwrap();
+        let shield = registry.register("Item.Shield").unwrap();
+        let weapon = registry.gid_of("Item.Weapon").unwrap();
+
+        assert!(TagPattern::exact(item).matches(item, &registry));
+        assert!(!TagPattern::exact(item).matches(sword, &registry));
+        assert!(TagPattern::subtree(item).matches(sword, &registry));
+        assert!(TagPattern::glob("Item.*").matches(shield, &registry));
+        assert!(!TagPattern::glob("Item.*").matches(sword, &registry));
+        assert!(!TagPattern::glob("**").matches(12345, &registry));
+
+        assert_eq!((&TagPattern::exact(item)).try_into_gid_with(&registry), Ok(item));
+        assert!((&TagPattern::subtree(item)).try_into_gid_with(&registry).is_err());
+
+        assert_eq!(registry.query(&TagPattern::exact(sword)), [sword]);
+        assert_eq!(registry.query(&TagPattern::exact(12345)), []);
+        assert_eq!(
+            registry.query(&TagPattern::subtree(item)),
+            registry.descendants_of(item)
+        );
+        let mut globbed = registry.query(&TagPattern::glob("Item.*"));
+        globbed.sort_unstable();
+        let mut expected = vec![weapon, shield];
+        expected.sort_unstable();
+        assert_eq!(globbed, expected);
+    }
+}
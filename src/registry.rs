@@ -1,24 +1,147 @@
 //! Namespace registry — runtime lookup and validation for hierarchical GIDs.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
-use crate::hash::hierarchical_gid;
-use crate::layout::{gid_is_descendant_of as gid_is_descendant_of, LEVEL_MASKS, MAX_DEPTH};
-use crate::traits::IntoGid;
+use crate::deprecation::DeprecationPolicy;
+use crate::flags::TagFlags;
+use crate::hash::child_gid;
+use crate::hasher::{GidMap, GidSet};
+use crate::journal::{JournalChange, JournalEntry};
+use crate::layout::{
+    depth_of, gid_is_descendant_of as gid_is_descendant_of, is_well_formed, LEVEL_MASKS, MAX_DEPTH,
+};
+use crate::meta_store::MetaStore;
+use crate::traits::{IntoGid, IntoGidWithRegistry};
 use crate::GID;
 
+/// Default localization key for a path: `Combat.HeavyAttack` -> `tag-combat-heavy-attack`.
+///
+/// Each segment is converted from CamelCase to kebab-case, with acronyms
+/// kept whole and `_` treated as a word break (`HUDIcon` -> `hud-icon`,
+/// `Fire_Ball` -> `fire-ball`); this is the same key `namespace!` emits when
+/// no `#[loc_key]` is given.
+pub fn default_loc_key(path: &str) -> String {
+    let mut key = String::from("tag");
+    for segment in path.split('.') {
+        key.push('-');
+        let start = key.len();
+        let chars: Vec<char> = segment.chars().collect();
+        for (i, &c) in chars.iter().enumerate() {
+            if c == '_' || c == '-' {
+                if key.len() > start && !key.ends_with('-') {
+                    key.push('-');
+                }
+                continue;
+            }
+            // A capital starts a word after a lowercase letter or digit, and
+            // ends an acronym when a lowercase letter follows: `HUDIcon`
+            let prev = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1);
+            let boundary = c.is_uppercase()
+                && prev.is_some_and(|p| {
+                    p.is_lowercase()
+                        || p.is_ascii_digit()
+                        || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+                });
+            if boundary && !key.ends_with('-') {
+                key.push('-');
+            }
+            key.extend(c.to_lowercase());
+        }
+    }
+    key
+}
+
+/// Structured deprecation info (`#[deprecated(since = "...", note = "...")]`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Version the node was deprecated in.
+    pub since: Option<&'static str>,
+    /// Why it was deprecated / what to use instead.
+    pub note: Option<&'static str>,
+}
+
 /// Definition of a namespace node (used for registry building from macro).
 #[derive(Clone, Copy, Debug)]
 pub struct NamespaceDef {
     pub path: &'static str,
     pub parent: Option<&'static str>,
+    /// Human-readable name for UI (`#[display_name = "..."]`).
+    pub display_name: Option<&'static str>,
+    /// Free-form description (`#[description = "..."]`).
+    pub description: Option<&'static str>,
+    /// Localization key (`#[loc_key = "..."]`, or derived from the path by the macro).
+    pub loc_key: Option<&'static str>,
+    /// Category flags (`#[flags(...)]`).
+    pub flags: TagFlags,
+    /// Explicitly pinned GID (`#[gid = ...]` / `#[segment = ...]`).
+    ///
+    /// When `None`, the GID is derived from the path (and any pinned ancestor).
+    pub gid: Option<GID>,
+    /// Deprecation info (`#[deprecated(...)]`), for runtime tooling.
+    pub deprecated: Option<Deprecation>,
+    /// Name of the associated data type (`Node<Type>` / `#[data(Type)]`),
+    /// as written in the macro.
+    pub data_type: Option<&'static str>,
 }
 
 impl NamespaceDef {
     pub const fn new(path: &'static str, parent: Option<&'static str>) -> Self {
-        Self { path, parent }
+        Self {
+            path,
+            parent,
+            display_name: None,
+            description: None,
+            loc_key: None,
+            flags: TagFlags::EMPTY,
+            gid: None,
+            deprecated: None,
+            data_type: None,
+        }
+    }
+
+    /// Builder: set the display name.
+    pub const fn with_display_name(mut self, display_name: &'static str) -> Self {
+        self.display_name = Some(display_name);
+        self
+    }
+
+    /// Builder: set the description.
+    pub const fn with_description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Builder: set the localization key.
+    pub const fn with_loc_key(mut self, loc_key: &'static str) -> Self {
+        self.loc_key = Some(loc_key);
+        self
+    }
+
+    /// Builder: set the category flags.
+    pub const fn with_flags(mut self, flags: TagFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Builder: pin the GID instead of hashing the path.
+    pub const fn with_gid(mut self, gid: GID) -> Self {
+        self.gid = Some(gid);
+        self
+    }
+
+    /// Builder: mark the node as deprecated.
+    pub const fn with_deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecated = Some(deprecation);
+        self
+    }
+
+    /// Builder: set the associated data type name.
+    pub const fn with_data_type(mut self, data_type: &'static str) -> Self {
+        self.data_type = Some(data_type);
+        self
     }
 }
 
@@ -29,6 +152,78 @@ pub struct NamespaceEntry {
     pub path: String,
     /// True if this tag was registered at runtime (not from macro).
     pub is_dynamic: bool,
+    /// Human-readable name, if one was declared.
+    pub display_name: Option<String>,
+    /// Description, if one was declared.
+    pub description: Option<String>,
+    /// Localization key, if one was declared.
+    pub loc_key: Option<String>,
+    /// Category flags.
+    pub flags: TagFlags,
+    /// Deprecation info, if the node was declared `#[deprecated]`.
+    pub deprecation: Option<Deprecation>,
+    /// Origin label from [`NamespaceRegistry::register_with_source`]
+    /// (e.g. `"mod:cool_guns"`).
+    pub source: Option<String>,
+    /// Name of the associated data type, if one was declared.
+    pub data_type: Option<String>,
+}
+
+/// One node of [`NamespaceRegistry::iter_subtree`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubtreeItem<'a> {
+    /// Levels below the walked subtree's root (0 for the root itself).
+    pub depth: u8,
+    /// Full dot-separated path.
+    pub path: &'a str,
+    pub gid: GID,
+}
+
+/// Interned tag path: a 4-byte handle to an entry of the
+/// [`NamespaceRegistry`] that issued it.
+///
+/// Cheap to copy, hash and compare, and not tied to the registry's lifetime,
+/// so systems can keep and log paths without cloning `String`s. A handle
+/// stays valid for its registry (and clones of it) as more tags are
+/// registered, until [`NamespaceRegistry::unregister_source`] removes tags.
+/// Two handles from the same registry are equal exactly when their paths are.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TagStr(u32);
+
+impl TagStr {
+    /// Position of the tag in [`NamespaceRegistry::entries`].
+    #[inline]
+    pub fn index(self) -> u32 {
+        self.0
+    }
+
+    /// The tag's path.
+    ///
+    /// # Panics
+    ///
+    /// If `registry` is not the registry that issued this handle (or a clone
+    /// of it) and has fewer entries.
+    #[inline]
+    pub fn as_str(self, registry: &NamespaceRegistry) -> &str {
+        &registry.entries[self.0 as usize].path
+    }
+
+    /// The tag's GID.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`as_str`](Self::as_str).
+    #[inline]
+    pub fn gid(self, registry: &NamespaceRegistry) -> GID {
+        registry.entries[self.0 as usize].gid
+    }
+}
+
+impl IntoGidWithRegistry for TagStr {
+    #[inline]
+    fn into_gid_with(self, registry: &NamespaceRegistry) -> GID {
+        self.gid(registry)
+    }
 }
 
 /// Registry for namespace tags.
@@ -44,11 +239,23 @@ pub struct NamespaceRegistry {
     max_depth: usize,
     entries: Vec<NamespaceEntry>,
     path_to_idx: HashMap<String, usize>,
-    gid_to_idx: HashMap<GID, usize>,
+    gid_to_idx: GidMap<usize>,
     dfs_order: Vec<GID>,
-    /// Dynamic metadata storage: GID → (key → bytes)
+    /// Dynamic metadata storage: (GID, key) → bytes in one arena.
     /// User is responsible for serialization/deserialization.
-    metadata: HashMap<GID, HashMap<String, Vec<u8>>>,
+    metadata: MetaStore,
+    /// Subtrees closed to dynamic registration.
+    sealed: Vec<GID>,
+    /// Tags of unregistered sources, kept only as parents of other sources'
+    /// tags and pruned once they have no children left.
+    retained: GidSet,
+    /// Number of children per parent path (`""` for roots), for
+    /// [`level_loads`](Self::level_loads).
+    sibling_counts: HashMap<String, usize>,
+    /// Recorded mutations, while journaling is enabled.
+    journal: Option<Vec<JournalEntry>>,
+    /// What checked lookups do with deprecated and redirected tags.
+    deprecation_policy: DeprecationPolicy,
 }
 
 impl Default for NamespaceRegistry {
@@ -63,15 +270,24 @@ impl NamespaceRegistry {
             max_depth: 0,
             entries: Vec::new(),
             path_to_idx: HashMap::new(),
-            gid_to_idx: HashMap::new(),
+            gid_to_idx: GidMap::default(),
             dfs_order: Vec::new(),
-            metadata: HashMap::new(),
+            metadata: MetaStore::default(),
+            sealed: Vec::new(),
+            retained: GidSet::default(),
+            sibling_counts: HashMap::new(),
+            journal: None,
+            deprecation_policy: DeprecationPolicy::Off,
         }
     }
 
     /// Build a registry from namespace definitions (from macro).
     ///
     /// Uses the fixed static layout for GID computation.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "registry_build", skip_all, fields(defs = defs.len()))
+    )]
     pub fn build(defs: &[NamespaceDef]) -> Result<Self, String> {
         if defs.is_empty() {
             return Ok(Self::new());
@@ -86,57 +302,156 @@ impl NamespaceRegistry {
         // 3. Record max depth
         let max_depth = tree.max_depth as usize + 1;
 
-        // 4. Assign hierarchical GIDs
-        let mut entries = Vec::with_capacity(defs.len());
-        let mut gid_set: HashMap<GID, &'static str> = HashMap::new();
-
-        for node in &tree.nodes {
-            let segments = Self::path_segments(node.path);
-            let seg_bytes: Vec<&[u8]> = segments.iter().map(|s| s.as_bytes()).collect();
-
-            let gid = hierarchical_gid(&seg_bytes);
-
-            // 5. Collision detection
-            if let Some(&existing) = gid_set.get(&gid) {
-                return Err(format!(
-                    "GID collision: '{}' and '{}' produce the same hierarchical hash {:#034x}. \
-                     Consider renaming one of them.",
-                    node.path, existing, gid
-                ));
-            }
-            gid_set.insert(gid, node.path);
-
-            entries.push(NamespaceEntry {
-                gid,
-                path: node.path.to_string(),
-                is_dynamic: false,
-            });
-        }
-
-        // 6. Build indices
-        let path_to_idx: HashMap<String, usize> = entries
+        // 4. Assign hierarchical GIDs. Each node walks its own ancestor
+        // chain (at most MAX_DEPTH links), so nodes are independent and can
+        // be hashed in parallel; results stay in DFS order.
+        let idx_of: HashMap<&'static str, usize> = tree
+            .nodes
             .iter()
             .enumerate()
-            .map(|(i, e)| (e.path.clone(), i))
+            .map(|(i, node)| (node.def.path, i))
             .collect();
+        let gids = par_map(&tree.nodes, |_, node| {
+            Self::node_gid(node, &tree.nodes, &idx_of)
+        });
+        let gids: Vec<GID> = gids.into_iter().collect::<Result<_, _>>()?;
 
-        let gid_to_idx: HashMap<GID, usize> = entries
-            .iter()
-            .enumerate()
-            .map(|(i, e)| (e.gid, i))
-            .collect();
+        let entries = par_map(&tree.nodes, |idx, node| {
+            let def = &node.def;
+            NamespaceEntry {
+                gid: gids[idx],
+                path: def.path.to_string(),
+                is_dynamic: false,
+                display_name: def.display_name.map(str::to_string),
+                description: def.description.map(str::to_string),
+                loc_key: def.loc_key.map(str::to_string),
+                flags: def.flags,
+                deprecation: def.deprecated,
+                source: None,
+                data_type: def.data_type.map(str::to_string),
+            }
+        });
+
+        // 5-6. GID index with collision detection, and the path index
+        let (gid_to_idx, path_to_idx) = join(
+            || {
+                let mut gid_to_idx =
+                    GidMap::with_capacity_and_hasher(gids.len(), Default::default());
+                for (idx, &gid) in gids.iter().enumerate() {
+                    if let Some(&existing) = gid_to_idx.get(&gid) {
+                        return Err((idx, existing));
+                    }
+                    gid_to_idx.insert(gid, idx);
+                }
+                Ok(gid_to_idx)
+            },
+            || {
+                let mut path_to_idx = HashMap::with_capacity(entries.len());
+                for (idx, entry) in entries.iter().enumerate() {
+                    path_to_idx.insert(entry.path.clone(), idx);
+                }
+                path_to_idx
+            },
+        );
+        let gid_to_idx: GidMap<usize> = gid_to_idx.map_err(|(idx, existing)| {
+            let (path, existing) = (&entries[idx].path, &entries[existing].path);
+            let gid = gids[idx];
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                path = %path,
+                existing = %existing,
+                gid = %format_args!("{:#034x}", gid),
+                "GID collision"
+            );
+            format!(
+                "GID collision: '{}' and '{}' produce the same hierarchical hash {:#034x}. \
+                 Consider renaming one of them.",
+                path, existing, gid
+            )
+        })?;
 
         // 7. DFS order (entries are already in DFS order from TreeBuilder)
         let dfs_order: Vec<GID> = entries.iter().map(|e| e.gid).collect();
+        let sibling_counts = count_siblings(&entries);
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(tags = entries.len(), max_depth, "built namespace registry");
 
-        Ok(Self {
+        let registry = Self {
             max_depth,
             entries,
             path_to_idx,
             gid_to_idx,
             dfs_order,
-            metadata: HashMap::new(),
-        })
+            metadata: MetaStore::default(),
+            sealed: Vec::new(),
+            retained: GidSet::default(),
+            sibling_counts,
+            journal: None,
+            deprecation_policy: DeprecationPolicy::Off,
+        };
+        debug_assert_eq!(registry.verify_roundtrips(), Ok(()));
+        Ok(registry)
+    }
+
+    /// Build one registry from the definitions of several namespaces, e.g.
+    /// from different crates; see [`link_namespaces!`](crate::link_namespaces).
+    ///
+    /// A path defined in more than one set (typically a shared root such as
+    /// `Item`) is kept once if every set defines it identically; any
+    /// difference (parent, pinned GID, display name, flags, ...) is an error.
+    /// GID collisions between sets are reported like those within one.
+    pub fn build_many(sets: &[&[NamespaceDef]]) -> Result<Self, String> {
+        let mut merged: Vec<NamespaceDef> = Vec::new();
+        let mut first_set: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (set, defs) in sets.iter().enumerate() {
+            for def in defs.iter() {
+                let Some(&(other_set, idx)) = first_set.get(def.path) else {
+                    first_set.insert(def.path, (set, merged.len()));
+                    merged.push(*def);
+                    continue;
+                };
+                let other = &merged[idx];
+                if other_set == set {
+                    return Err(format!("duplicate namespace path: {}", def.path));
+                }
+                if let Some(field) = differing_field(other, def) {
+                    return Err(format!(
+                        "'{}' is defined differently in namespaces {} and {} ({} differs)",
+                        def.path, other_set, set, field
+                    ));
+                }
+            }
+        }
+        Self::build(&merged)
+    }
+
+    /// Check that path ↔ GID lookup is a bijection: `gid_of(path_of(gid))`
+    /// gives back `gid` and `path_of(gid_of(path))` gives back `path` for
+    /// every entry.
+    ///
+    /// Checked under debug assertions after [`build`](Self::build) and
+    /// [`unregister_source`](Self::unregister_source). Redirects are checked
+    /// separately by [`verify_redirects`](Self::verify_redirects).
+    pub fn verify_roundtrips(&self) -> Result<(), String> {
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if self.path_to_idx.get(&entry.path) != Some(&idx) {
+                return Err(format!("path '{}' does not map back to its entry", entry.path));
+            }
+            if let Some(&other) = self.gid_to_idx.get(&entry.gid).filter(|&&i| i != idx) {
+                return Err(format!(
+                    "'{}' and '{}' map to the same GID {:#034x}",
+                    entry.path, self.entries[other].path, entry.gid
+                ));
+            }
+            if !self.gid_to_idx.contains_key(&entry.gid) {
+                return Err(format!("GID {:#034x} of '{}' is not indexed", entry.gid, entry.path));
+            }
+        }
+        if self.path_to_idx.len() != self.entries.len() || self.gid_to_idx.len() != self.entries.len() {
+            return Err("lookup tables hold entries that are no longer registered".into());
+        }
+        Ok(())
     }
 
     /// Path → GID
@@ -155,6 +470,90 @@ impl NamespaceRegistry {
             .map(|&i| self.entries[i].path.as_str())
     }
 
+    /// GID → display name.
+    ///
+    /// Falls back to the last path segment when no display name was declared.
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn display_name_of(&self, gid: impl IntoGid) -> Option<&str> {
+        let entry = &self.entries[*self.gid_to_idx.get(&gid.into_gid())?];
+        Some(match entry.display_name {
+            Some(ref name) => name.as_str(),
+            None => entry.path.rsplit('.').next().unwrap_or(&entry.path),
+        })
+    }
+
+    /// GID → description, if one was declared.
+    ///
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn description_of(&self, gid: impl IntoGid) -> Option<&str> {
+        let entry = &self.entries[*self.gid_to_idx.get(&gid.into_gid())?];
+        entry.description.as_deref()
+    }
+
+    /// GID → localization key.
+    ///
+    /// Falls back to [`default_loc_key`] when no key was declared (e.g. for
+    /// dynamically registered tags). Accepts both raw `GID` and `Tag` types.
+    pub fn loc_key_of(&self, gid: impl IntoGid) -> Option<String> {
+        let entry = &self.entries[*self.gid_to_idx.get(&gid.into_gid())?];
+        Some(match entry.loc_key {
+            Some(ref key) => key.clone(),
+            None => default_loc_key(&entry.path),
+        })
+    }
+
+    /// GID → category flags.
+    ///
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn flags_of(&self, gid: impl IntoGid) -> Option<TagFlags> {
+        let entry = &self.entries[*self.gid_to_idx.get(&gid.into_gid())?];
+        Some(entry.flags)
+    }
+
+    /// All GIDs whose flags contain every flag in `flag`, in DFS order.
+    pub fn gids_with_flag(&self, flag: TagFlags) -> Vec<GID> {
+        self.dfs_order
+            .iter()
+            .copied()
+            .filter(|&gid| self.entries[self.gid_to_idx[&gid]].flags.contains(flag))
+            .collect()
+    }
+
+    /// GID → deprecation info, if the node was declared deprecated.
+    ///
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn deprecation_of(&self, gid: impl IntoGid) -> Option<&Deprecation> {
+        let entry = &self.entries[*self.gid_to_idx.get(&gid.into_gid())?];
+        entry.deprecation.as_ref()
+    }
+
+    /// Check whether a node was declared deprecated.
+    #[inline]
+    pub fn is_deprecated(&self, gid: impl IntoGid) -> bool {
+        self.deprecation_of(gid).is_some()
+    }
+
+    /// Set what [`resolve_path`](Self::resolve_path) and
+    /// [`resolve_gid`](Self::resolve_gid) do with deprecated and redirected
+    /// tags.
+    pub fn set_deprecation_policy(&mut self, policy: DeprecationPolicy) {
+        self.deprecation_policy = policy;
+    }
+
+    /// Current deprecation policy ([`DeprecationPolicy::Off`] by default).
+    #[inline]
+    pub fn deprecation_policy(&self) -> DeprecationPolicy {
+        self.deprecation_policy
+    }
+
+    /// GID → associated data type name, if one was declared.
+    ///
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn data_type_of(&self, gid: impl IntoGid) -> Option<&str> {
+        let entry = &self.entries[*self.gid_to_idx.get(&gid.into_gid())?];
+        entry.data_type.as_deref()
+    }
+
     /// Get the current maximum tree depth (0 = empty, 1 = only root nodes, etc.).
     ///
     /// This value grows dynamically as deeper tags are registered.
@@ -163,6 +562,12 @@ impl NamespaceRegistry {
         self.max_depth
     }
 
+    /// Children per parent path (`""` for roots), kept up to date as tags
+    /// are added and removed.
+    pub(crate) fn sibling_counts(&self) -> &HashMap<String, usize> {
+        &self.sibling_counts
+    }
+
     /// Total number of registered nodes.
     #[inline]
     pub fn len(&self) -> usize {
@@ -185,6 +590,38 @@ impl NamespaceRegistry {
         &self.entries
     }
 
+    /// Walk `ancestor` and its registered descendants in DFS order, with
+    /// depths relative to `ancestor`: ready for printing an indented tree
+    /// or building a UI model. Empty if `ancestor` isn't registered.
+    ///
+    /// ```
+    /// # use bevy_tag::NamespaceRegistry;
+    /// let mut registry = NamespaceRegistry::new();
+    /// registry.register("Item.Weapon.Sword").unwrap();
+    /// let weapon = registry.gid_of("Item.Weapon").unwrap();
+    /// for node in registry.iter_subtree(weapon) {
+    ///     println!("{}{}", "  ".repeat(node.depth as usize), node.path);
+    /// }
+    /// ```
+    pub fn iter_subtree(&self, ancestor: impl IntoGid) -> impl Iterator<Item = SubtreeItem<'_>> {
+        let ancestor = ancestor.into_gid();
+        let base = depth_of(ancestor);
+        // Subtrees are contiguous in DFS order
+        let start = self
+            .dfs_order
+            .iter()
+            .position(|&gid| gid == ancestor)
+            .unwrap_or(self.dfs_order.len());
+        self.dfs_order[start..]
+            .iter()
+            .take_while(move |&&gid| gid_is_descendant_of(gid, ancestor))
+            .map(move |&gid| SubtreeItem {
+                depth: depth_of(gid) - base,
+                path: &self.entries[self.gid_to_idx[&gid]].path,
+                gid,
+            })
+    }
+
     /// Register a new tag at runtime.
     ///
     /// The path must be a valid dot-separated path (e.g., "Combat.Special.Fireball").
@@ -197,7 +634,24 @@ impl NamespaceRegistry {
     /// - Returns error if path is empty
     /// - Returns error if path depth exceeds MAX_DEPTH (8)
     /// - Returns error if path already exists (no-op, returns existing GID via Ok)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "registry_register", skip(self))
+    )]
     pub fn register(&mut self, path: &str) -> Result<GID, String> {
+        self.register_from(path, None)
+    }
+
+    /// [`register`](Self::register), labelling every tag it creates with
+    /// `source` so [`unregister_source`](Self::unregister_source) can remove
+    /// them again (e.g. when a mod unloads).
+    ///
+    /// Tags that already exist keep their original source.
+    pub fn register_with_source(&mut self, path: &str, source: &str) -> Result<GID, String> {
+        self.register_from(path, Some(source))
+    }
+
+    fn register_from(&mut self, path: &str, source: Option<&str>) -> Result<GID, String> {
         if path.is_empty() {
             return Err("empty path is not allowed".into());
         }
@@ -217,50 +671,94 @@ impl NamespaceRegistry {
             ));
         }
 
-        // Ensure all parent nodes exist (auto-create)
-        // Note: DFS order will be rebuilt after the final node is added
-        for i in 0..segments.len() - 1 {
-            let parent_path: String = segments[..=i].join(".");
-            if self.path_to_idx.contains_key(&parent_path) {
+        // Refuse before creating anything if a new node would land in a
+        // sealed subtree
+        if !self.sealed.is_empty() {
+            let mut gid = crate::ROOT_GID;
+            for i in 0..segments.len() {
+                let prefix = segments[..=i].join(".");
+                if let Some(&idx) = self.path_to_idx.get(&prefix) {
+                    gid = self.entries[idx].gid;
+                    continue;
+                }
+                gid = child_gid(gid, i as u8, segments[i].as_bytes());
+                let sealed = self.sealed.iter().find(|&&s| gid_is_descendant_of(gid, s));
+                if let Some(&sealed) = sealed {
+                    return Err(format!(
+                        "cannot register '{}': subtree '{}' is sealed",
+                        path,
+                        self.path_of(sealed).unwrap_or("<unregistered>")
+                    ));
+                }
+            }
+        }
+
+        // Work out every node to create (missing parents, then the tag
+        // itself) and check them all for collisions before touching the
+        // registry, so a failed registration leaves nothing behind.
+        // GIDs follow pinned ancestors, if any.
+        let mut created: Vec<(String, GID)> = Vec::new();
+        let mut gid = crate::ROOT_GID;
+        for i in 0..segments.len() {
+            let prefix = segments[..=i].join(".");
+            if let Some(&idx) = self.path_to_idx.get(&prefix) {
+                gid = self.entries[idx].gid;
                 continue;
             }
-            // Auto-create parent
-            let parent_segs: Vec<&[u8]> = segments[..=i].iter().map(|s| s.as_bytes()).collect();
-            let gid = hierarchical_gid(&parent_segs);
+            gid = child_gid(gid, i as u8, segments[i].as_bytes());
+
+            // Check for GID collision
+            if let Some(&existing_idx) = self.gid_to_idx.get(&gid) {
+                let existing_path = &self.entries[existing_idx].path;
+                #[cfg(feature = "tracing")]
+                tracing::debug!(
+                    path = %prefix,
+                    existing = %existing_path,
+                    gid = %format_args!("{:#034x}", gid),
+                    "GID collision"
+                );
+                return Err(format!(
+                    "GID collision: '{}' and '{}' produce the same hash {:#034x}",
+                    prefix, existing_path, gid
+                ));
+            }
+            created.push((prefix, gid));
+        }
 
+        for (node_path, node_gid) in created {
+            #[cfg(feature = "tracing")]
+            if node_path != path {
+                tracing::debug!(
+                    path = %node_path,
+                    gid = %format_args!("{:#034x}", node_gid),
+                    "auto-registered parent tag"
+                );
+            }
             let idx = self.entries.len();
             self.entries.push(NamespaceEntry {
-                gid,
-                path: parent_path.clone(),
+                gid: node_gid,
+                path: node_path.clone(),
                 is_dynamic: true,
+                display_name: None,
+                description: None,
+                loc_key: None,
+                flags: TagFlags::EMPTY,
+                deprecation: None,
+                source: source.map(str::to_string),
+                data_type: None,
             });
-            self.path_to_idx.insert(parent_path, idx);
-            self.gid_to_idx.insert(gid, idx);
-            // Don't push to dfs_order here - will be rebuilt at the end
-        }
-
-        // Register the actual node
-        let seg_bytes: Vec<&[u8]> = segments.iter().map(|s| s.as_bytes()).collect();
-        let gid = hierarchical_gid(&seg_bytes);
-
-        // Check for GID collision
-        if let Some(&existing_idx) = self.gid_to_idx.get(&gid) {
-            let existing_path = &self.entries[existing_idx].path;
-            return Err(format!(
-                "GID collision: '{}' and '{}' produce the same hash {:#034x}",
-                path, existing_path, gid
-            ));
+            self.record(source, || JournalChange::Registered {
+                gid: node_gid,
+                path: node_path.clone(),
+            });
+            *self
+                .sibling_counts
+                .entry(parent_path(&node_path).to_string())
+                .or_default() += 1;
+            self.path_to_idx.insert(node_path, idx);
+            self.gid_to_idx.insert(node_gid, idx);
         }
 
-        let idx = self.entries.len();
-        self.entries.push(NamespaceEntry {
-            gid,
-            path: path.to_string(),
-            is_dynamic: true,
-        });
-        self.path_to_idx.insert(path.to_string(), idx);
-        self.gid_to_idx.insert(gid, idx);
-
         // Rebuild DFS order to maintain correct ordering
         self.rebuild_dfs_order();
 
@@ -269,6 +767,12 @@ impl NamespaceRegistry {
             self.max_depth = depth + 1;
         }
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            path,
+            gid = %format_args!("{:#034x}", gid),
+            "registered dynamic tag"
+        );
         Ok(gid)
     }
 
@@ -280,11 +784,7 @@ impl NamespaceRegistry {
         let mut children: HashMap<Option<String>, Vec<(String, GID)>> = HashMap::new();
 
         for entry in &self.entries {
-            let parent = if let Some(pos) = entry.path.rfind('.') {
-                Some(entry.path[..pos].to_string())
-            } else {
-                None
-            };
+            let parent = entry.path.rfind('.').map(|pos| entry.path[..pos].to_string());
             children
                 .entry(parent)
                 .or_default()
@@ -315,6 +815,170 @@ impl NamespaceRegistry {
         }
     }
 
+    /// Origin label of a dynamically registered tag.
+    ///
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn source_of(&self, gid: impl IntoGid) -> Option<&str> {
+        self.gid_to_idx
+            .get(&gid.into_gid())
+            .and_then(|&i| self.entries[i].source.as_deref())
+    }
+
+    /// Remove every tag registered with `source`, along with its metadata,
+    /// and return their GIDs.
+    ///
+    /// A tag that another source (or a static definition) registered
+    /// children under is kept, so the tree stays connected, and removed by
+    /// the call that unregisters its last child. Invalidates [`TagStr`]
+    /// handles issued before the call.
+    pub fn unregister_source(&mut self, source: &str) -> Vec<GID> {
+        let retained = &self.retained;
+        let live = |e: &NamespaceEntry| {
+            e.source.as_deref() != Some(source) && !retained.contains(&e.gid)
+        };
+        // Every ancestor of a tag that stays
+        let mut needed: HashSet<&str> = HashSet::new();
+        for entry in self.entries.iter().filter(|e| live(e)) {
+            let mut path = entry.path.as_str();
+            while let Some((parent, _)) = path.rsplit_once('.') {
+                if !needed.insert(parent) {
+                    break;
+                }
+                path = parent;
+            }
+        }
+        let mut doomed = GidSet::default();
+        let mut kept = Vec::new();
+        for entry in self.entries.iter().filter(|e| !live(e)) {
+            if !needed.contains(entry.path.as_str()) {
+                doomed.insert(entry.gid);
+            } else if entry.source.as_deref() == Some(source) {
+                kept.push(entry.gid);
+            }
+        }
+        self.retained.extend(kept);
+        if doomed.is_empty() {
+            return Vec::new();
+        }
+        self.retained.retain(|gid| !doomed.contains(gid));
+
+        let mut removed = Vec::with_capacity(doomed.len());
+        let mut removed_paths = Vec::new();
+        let journaling = self.is_journaling();
+        self.entries.retain_mut(|e| {
+            let keep = !doomed.contains(&e.gid);
+            if !keep {
+                removed.push(e.gid);
+                if journaling {
+                    removed_paths.push(std::mem::take(&mut e.path));
+                }
+            }
+            keep
+        });
+        // Deepest first, so undoing the journal backwards re-registers
+        // parents before their children
+        let mut order: Vec<usize> = (0..removed.len()).collect();
+        if journaling {
+            order.sort_by_key(|&i| std::cmp::Reverse(removed_paths[i].matches('.').count()));
+        }
+        for i in order {
+            let gid = removed[i];
+            if journaling {
+                let meta: Vec<(String, Vec<u8>)> = self
+                    .metadata
+                    .iter(gid)
+                    .map(|(key, bytes)| (key.to_string(), bytes.to_vec()))
+                    .collect();
+                for (key, previous) in meta {
+                    self.record(Some(source), || JournalChange::MetaRemoved {
+                        gid,
+                        key,
+                        previous,
+                    });
+                }
+                let path = std::mem::take(&mut removed_paths[i]);
+                self.record(Some(source), || JournalChange::Unregistered { gid, path });
+            }
+            self.metadata.remove_all(gid);
+        }
+        self.path_to_idx = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.path.clone(), i))
+            .collect();
+        self.gid_to_idx = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.gid, i))
+            .collect();
+        self.sibling_counts = count_siblings(&self.entries);
+        self.max_depth = self
+            .entries
+            .iter()
+            .map(|e| e.path.matches('.').count() + 1)
+            .max()
+            .unwrap_or(0);
+        self.rebuild_dfs_order();
+        debug_assert_eq!(self.verify_roundtrips(), Ok(()));
+        removed
+    }
+
+    /// Forbid dynamic registration of new tags under `gid` (inclusive).
+    ///
+    /// Tags already in the subtree, and static definitions, are unaffected.
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn seal_subtree(&mut self, gid: impl IntoGid) {
+        let gid = gid.into_gid();
+        if !self.sealed.contains(&gid) {
+            self.sealed.push(gid);
+            self.record(None, || JournalChange::Sealed { gid });
+        }
+    }
+
+    /// Reopen a subtree sealed with [`seal_subtree`](Self::seal_subtree).
+    ///
+    /// Returns `true` if it was sealed.
+    pub fn unseal_subtree(&mut self, gid: impl IntoGid) -> bool {
+        let gid = gid.into_gid();
+        let before = self.sealed.len();
+        self.sealed.retain(|&s| s != gid);
+        let unsealed = self.sealed.len() != before;
+        if unsealed {
+            self.record(None, || JournalChange::Unsealed { gid });
+        }
+        unsealed
+    }
+
+    /// Check if `gid` lies in a sealed subtree.
+    ///
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn is_sealed(&self, gid: impl IntoGid) -> bool {
+        let gid = gid.into_gid();
+        self.sealed.iter().any(|&s| gid_is_descendant_of(gid, s))
+    }
+
+    /// Interned handle for a registered path.
+    #[inline]
+    pub fn intern(&self, path: &str) -> Option<TagStr> {
+        self.path_to_idx.get(path).map(|&i| TagStr(i as u32))
+    }
+
+    /// Interned handle for a registered GID.
+    ///
+    /// Accepts both raw `GID` and `Tag` types.
+    #[inline]
+    pub fn intern_gid(&self, gid: impl IntoGid) -> Option<TagStr> {
+        self.gid_to_idx.get(&gid.into_gid()).map(|&i| TagStr(i as u32))
+    }
+
+    /// Interned handle for `path`, registering it dynamically if needed.
+    pub fn intern_or_register(&mut self, path: &str) -> Result<TagStr, String> {
+        let gid = self.register(path)?;
+        Ok(TagStr(self.gid_to_idx[&gid] as u32))
+    }
+
     /// Check if a path is registered.
     #[inline]
     pub fn contains(&self, path: &str) -> bool {
@@ -343,10 +1007,7 @@ impl NamespaceRegistry {
         key: impl Into<String>,
         value: &T,
     ) -> Option<Vec<u8>> {
-        self.metadata
-            .entry(gid.into_gid())
-            .or_default()
-            .insert(key.into(), value.as_bytes().to_vec())
+        self.insert_meta(gid.into_gid(), key.into(), value.as_bytes())
     }
 
     /// Get typed metadata for a GID.
@@ -363,7 +1024,7 @@ impl NamespaceRegistry {
         gid: impl IntoGid,
         key: &str,
     ) -> Option<&T> {
-        let bytes = self.metadata.get(&gid.into_gid())?.get(key)?;
+        let bytes = self.metadata.get(gid.into_gid(), key)?;
         T::ref_from_bytes(bytes).ok()
     }
 
@@ -378,10 +1039,22 @@ impl NamespaceRegistry {
         key: impl Into<String>,
         value: Vec<u8>,
     ) -> Option<Vec<u8>> {
-        self.metadata
-            .entry(gid.into_gid())
-            .or_default()
-            .insert(key.into(), value)
+        self.insert_meta(gid.into_gid(), key.into(), &value)
+    }
+
+    /// Store metadata, copying it into the journal only while journaling.
+    fn insert_meta(&mut self, gid: GID, key: String, value: &[u8]) -> Option<Vec<u8>> {
+        if !self.is_journaling() {
+            return self.metadata.insert(gid, key, value);
+        }
+        let previous = self.metadata.insert(gid, key.clone(), value);
+        self.record(None, || JournalChange::MetaSet {
+            gid,
+            key,
+            value: value.to_vec(),
+            previous: previous.clone(),
+        });
+        previous
     }
 
     /// Get raw bytes metadata for a GID.
@@ -389,10 +1062,7 @@ impl NamespaceRegistry {
     /// Accepts both raw `GID` and `Tag` types.
     #[inline]
     pub fn get_meta_raw(&self, gid: impl IntoGid, key: &str) -> Option<&[u8]> {
-        self.metadata
-            .get(&gid.into_gid())?
-            .get(key)
-            .map(|v| v.as_slice())
+        self.metadata.get(gid.into_gid(), key)
     }
 
     /// Check if a GID has a specific metadata key.
@@ -400,9 +1070,7 @@ impl NamespaceRegistry {
     /// Accepts both raw `GID` and `Tag` types.
     #[inline]
     pub fn has_meta(&self, gid: impl IntoGid, key: &str) -> bool {
-        self.metadata
-            .get(&gid.into_gid())
-            .is_some_and(|m| m.contains_key(key))
+        self.metadata.contains(gid.into_gid(), key)
     }
 
     /// Remove metadata for a GID.
@@ -411,25 +1079,68 @@ impl NamespaceRegistry {
     ///
     /// Returns the removed raw bytes if any.
     pub fn remove_meta(&mut self, gid: impl IntoGid, key: &str) -> Option<Vec<u8>> {
-        self.metadata.get_mut(&gid.into_gid())?.remove(key)
+        let gid = gid.into_gid();
+        let previous = self.metadata.remove(gid, key)?;
+        self.record(None, || JournalChange::MetaRemoved {
+            gid,
+            key: key.to_string(),
+            previous: previous.clone(),
+        });
+        Some(previous)
+    }
+
+    /// Start recording mutations; see [`journal`](crate::journal).
+    ///
+    /// Keeps entries already recorded.
+    pub fn enable_journal(&mut self) {
+        self.journal.get_or_insert_with(Vec::new);
+    }
+
+    /// Stop recording mutations and return the recorded entries.
+    pub fn disable_journal(&mut self) -> Vec<JournalEntry> {
+        self.journal.take().unwrap_or_default()
+    }
+
+    /// Check if mutations are being recorded.
+    #[inline]
+    pub fn is_journaling(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    /// Recorded mutations, oldest first (empty unless journaling).
+    pub fn journal(&self) -> &[JournalEntry] {
+        self.journal.as_deref().unwrap_or_default()
+    }
+
+    /// Return the recorded entries and start an empty journal, e.g. after
+    /// saving them. Journaling stays enabled.
+    pub fn take_journal(&mut self) -> Vec<JournalEntry> {
+        self.journal.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    pub(crate) fn record(&mut self, source: Option<&str>, change: impl FnOnce() -> JournalChange) {
+        if let Some(journal) = &mut self.journal {
+            journal.push(JournalEntry::now(source, change()));
+        }
     }
 
     /// Get all metadata keys for a GID.
     ///
     /// Accepts both raw `GID` and `Tag` types.
+    ///
+    /// Returns `None` if the GID has no metadata.
     pub fn meta_keys(&self, gid: impl IntoGid) -> Option<impl Iterator<Item = &str>> {
-        self.metadata
-            .get(&gid.into_gid())
-            .map(|m| m.keys().map(|s| s.as_str()))
+        self.meta_iter(gid).map(|pairs| pairs.map(|(key, _)| key))
     }
 
     /// Get all metadata for a GID as (key, bytes) pairs.
     ///
     /// Accepts both raw `GID` and `Tag` types.
+    ///
+    /// Returns `None` if the GID has no metadata.
     pub fn meta_iter(&self, gid: impl IntoGid) -> Option<impl Iterator<Item = (&str, &[u8])>> {
-        self.metadata
-            .get(&gid.into_gid())
-            .map(|m| m.iter().map(|(k, v)| (k.as_str(), v.as_slice())))
+        let gid = gid.into_gid();
+        self.metadata.has_any(gid).then(|| self.metadata.iter(gid))
     }
 
     /// Check if `candidate` path is a descendant of (or equal to) `ancestor` path.
@@ -483,6 +1194,24 @@ impl NamespaceRegistry {
             .collect()
     }
 
+    /// [`descendants_of`](Self::descendants_of), limited to at most
+    /// `max_extra_depth` levels below `ancestor`: `0` is just the ancestor,
+    /// `1` adds its children, `2` its grandchildren.
+    ///
+    /// For UI trees that only show the expanded levels; still iterates all
+    /// entries, but never collects the deeper parts of the subtree.
+    pub fn descendants_of_within(&self, ancestor: impl IntoGid, max_extra_depth: u8) -> Vec<GID> {
+        let ancestor_gid = ancestor.into_gid();
+        let ancestor_depth = depth_of(ancestor_gid);
+        let max_depth = ancestor_depth.saturating_add(max_extra_depth);
+
+        self.entries
+            .iter()
+            .filter(|e| depth_of(e.gid) <= max_depth && gid_is_descendant_of(e.gid, ancestor_gid))
+            .map(|e| e.gid)
+            .collect()
+    }
+
     fn validate_defs(defs: &[NamespaceDef]) -> Result<(), String> {
         let mut paths = std::collections::HashSet::new();
         for def in defs {
@@ -499,23 +1228,165 @@ impl NamespaceRegistry {
             {
                 return Err(format!("missing parent for '{}': '{}'", def.path, parent));
             }
+            // The parent must be the path minus its last segment, otherwise
+            // the GID chain would not match the path.
+            match (def.path.rsplit_once('.'), def.parent) {
+                (None, None) => {}
+                (Some((prefix, _)), Some(parent)) if prefix == parent => {}
+                (Some(_), None) => {
+                    return Err(format!("'{}' has more than one segment but no parent", def.path));
+                }
+                (_, Some(parent)) => {
+                    return Err(format!(
+                        "parent '{}' of '{}' is not its path prefix",
+                        parent, def.path
+                    ));
+                }
+            }
         }
         Ok(())
     }
 
+    /// Check that a pinned GID fits the node's position in the tree.
+    fn validate_pinned(def: &NamespaceDef, gid: GID, depth: u8, parent_gid: GID) -> Result<(), String> {
+        if depth_of(gid) != depth {
+            return Err(format!(
+                "pinned GID {:#034x} for '{}' encodes depth {}, expected {}",
+                gid,
+                def.path,
+                depth_of(gid),
+                depth
+            ));
+        }
+        if !is_well_formed(gid) {
+            return Err(format!(
+                "pinned GID {:#034x} for '{}' has empty or stray level bits",
+                gid, def.path
+            ));
+        }
+        if def.parent.is_some() && !gid_is_descendant_of(gid, parent_gid) {
+            return Err(format!(
+                "pinned GID {:#034x} for '{}' is not under its parent's GID {:#034x}",
+                gid, def.path, parent_gid
+            ));
+        }
+        Ok(())
+    }
+
+    /// GID of `node`: pinned, or its last segment hashed under its parent's
+    /// GID, recursively up to the root.
+    fn node_gid(
+        node: &TreeNode,
+        nodes: &[TreeNode],
+        idx_of: &HashMap<&'static str, usize>,
+    ) -> Result<GID, String> {
+        // Ancestors first; the tree builder already bounded the depth
+        let mut chain = vec![&node.def];
+        while let Some(parent) = chain[chain.len() - 1].parent {
+            chain.push(&nodes[idx_of[parent]].def);
+        }
+        let mut gid = crate::ROOT_GID;
+        for def in chain.into_iter().rev() {
+            let segments = Self::path_segments(def.path);
+            let depth = (segments.len() - 1) as u8;
+            gid = match def.gid {
+                Some(pinned) => {
+                    Self::validate_pinned(def, pinned, depth, gid)?;
+                    pinned
+                }
+                None => child_gid(gid, depth, segments[depth as usize].as_bytes()),
+            };
+        }
+        Ok(gid)
+    }
+
     /// Split "A.B.C" into ["A", "B", "C"].
     fn path_segments(path: &str) -> Vec<&str> {
         path.split('.').collect()
     }
 }
 
+/// Path of the parent of `path` (`""` for a root).
+pub(crate) fn parent_path(path: &str) -> &str {
+    path.rsplit_once('.').map_or("", |(parent, _)| parent)
+}
+
+/// Number of children per parent path.
+fn count_siblings(entries: &[NamespaceEntry]) -> HashMap<String, usize> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for entry in entries {
+        *counts.entry(parent_path(&entry.path).to_string()).or_default() += 1;
+    }
+    counts
+}
+
+/// First field (other than the path) in which two definitions of the same
+/// path differ, for [`NamespaceRegistry::build_many`].
+fn differing_field(a: &NamespaceDef, b: &NamespaceDef) -> Option<&'static str> {
+    if a.parent != b.parent {
+        Some("parent")
+    } else if a.gid != b.gid {
+        Some("gid")
+    } else if a.display_name != b.display_name {
+        Some("display_name")
+    } else if a.description != b.description {
+        Some("description")
+    } else if a.loc_key != b.loc_key {
+        Some("loc_key")
+    } else if a.flags != b.flags {
+        Some("flags")
+    } else if a.deprecated != b.deprecated {
+        Some("deprecated")
+    } else if a.data_type != b.data_type {
+        Some("data_type")
+    } else {
+        None
+    }
+}
+
+// =============================================================================
+// Parallel helpers (`rayon` feature)
+// =============================================================================
+
+/// `f(index, item)` for every item, in order; on the rayon pool with the
+/// `rayon` feature.
+fn par_map<T: Sync, U: Send>(
+    items: &[T],
+    f: impl Fn(usize, &T) -> U + Sync + Send,
+) -> Vec<U> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().enumerate().map(|(i, item)| f(i, item)).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        items.iter().enumerate().map(|(i, item)| f(i, item)).collect()
+    }
+}
+
+/// Run `a` and `b`, concurrently with the `rayon` feature.
+fn join<A: Send, B: Send>(
+    a: impl FnOnce() -> A + Send,
+    b: impl FnOnce() -> B + Send,
+) -> (A, B) {
+    #[cfg(feature = "rayon")]
+    {
+        rayon::join(a, b)
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        (a(), b())
+    }
+}
+
 // =============================================================================
 // Tree builder — reconstructs tree from flat NamespaceDef slice
 // =============================================================================
 
 #[derive(Debug)]
 struct TreeNode {
-    path: &'static str,
+    def: NamespaceDef,
 }
 
 #[derive(Debug)]
@@ -588,7 +1459,7 @@ impl TreeBuilder {
         let key = parent;
         if let Some(kids) = children.get(&key) {
             for kid in kids {
-                out.push(TreeNode { path: kid.path });
+                out.push(TreeNode { def: **kid });
                 Self::dfs_collect(Some(kid.path), children, out);
             }
         }
@@ -725,6 +1596,71 @@ mod tests {
         assert!(!desc_paths.contains(&"Combat.Attack"));
     }
 
+    #[test]
+    fn verify_roundtrips_reports_broken_indices() {
+        let mut reg = NamespaceRegistry::build(sample_defs()).unwrap();
+        reg.register("Ui.Hud").unwrap();
+        assert_eq!(reg.verify_roundtrips(), Ok(()));
+
+        let idle = reg.gid_of("Movement.Idle").unwrap();
+        let idx = reg.path_to_idx["Movement.Running"];
+        reg.gid_to_idx.insert(idle, idx);
+        assert_eq!(
+            reg.verify_roundtrips(),
+            Err(format!(
+                "'Movement.Idle' and 'Movement.Running' map to the same GID {:#034x}",
+                idle
+            ))
+        );
+
+        let mut reg = NamespaceRegistry::build(sample_defs()).unwrap();
+        reg.path_to_idx.insert("Stale".into(), 0);
+        assert!(reg.verify_roundtrips().unwrap_err().contains("no longer registered"));
+    }
+
+    #[test]
+    fn iter_subtree_walks_in_dfs_order() {
+        let mut reg = NamespaceRegistry::build(sample_defs()).unwrap();
+        reg.register("Movement.Idle.Breathing").unwrap();
+        let movement = reg.gid_of("Movement").unwrap();
+
+        let items: Vec<_> = reg.iter_subtree(movement).collect();
+        assert_eq!(items.len(), reg.descendants_of(movement).len());
+        assert_eq!(
+            (items[0].depth, items[0].path, items[0].gid),
+            (0, "Movement", movement)
+        );
+        let breathing = items.iter().position(|i| i.path == "Movement.Idle.Breathing").unwrap();
+        assert_eq!(items[breathing].depth, 2);
+        assert_eq!(items[breathing - 1].path, "Movement.Idle");
+        assert!(items[1..].iter().all(|i| i.depth > 0 && i.path.starts_with("Movement.")));
+
+        let idle = reg.gid_of("Movement.Idle").unwrap();
+        assert_eq!(reg.iter_subtree(idle).map(|i| i.depth).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(reg.iter_subtree(12345).count(), 0);
+    }
+
+    #[test]
+    fn descendants_of_within_stops_at_depth() {
+        let mut reg = NamespaceRegistry::new();
+        reg.register("Ui.Menu.Options.Audio").unwrap();
+        reg.register("Ui.Hud").unwrap();
+        let ui = reg.gid_of("Ui").unwrap();
+        let paths = |gids: Vec<GID>| {
+            let mut paths: Vec<&str> = gids.iter().filter_map(|&gid| reg.path_of(gid)).collect();
+            paths.sort_unstable();
+            paths
+        };
+
+        assert_eq!(paths(reg.descendants_of_within(ui, 0)), ["Ui"]);
+        assert_eq!(paths(reg.descendants_of_within(ui, 1)), ["Ui", "Ui.Hud", "Ui.Menu"]);
+        assert_eq!(
+            paths(reg.descendants_of_within(ui, 2)),
+            ["Ui", "Ui.Hud", "Ui.Menu", "Ui.Menu.Options"]
+        );
+        assert_eq!(reg.descendants_of_within(ui, u8::MAX).len(), reg.descendants_of(ui).len());
+    }
+
     #[test]
     fn depth_tracking() {
         use crate::layout::depth_of;
@@ -747,6 +1683,165 @@ mod tests {
         assert_eq!(parent_of(idle), Some(movement));
     }
 
+    #[test]
+    fn display_name_and_description() {
+        let defs = &[
+            NamespaceDef::new("Combat", None),
+            NamespaceDef::new("Combat.HeavyAttack", Some("Combat"))
+                .with_display_name("Heavy Attack")
+                .with_description("A slow, powerful swing"),
+        ];
+        let reg = NamespaceRegistry::build(defs).unwrap();
+
+        let heavy = reg.gid_of("Combat.HeavyAttack").unwrap();
+        assert_eq!(reg.display_name_of(heavy), Some("Heavy Attack"));
+        assert_eq!(reg.description_of(heavy), Some("A slow, powerful swing"));
+
+        // Falls back to the last segment
+        let combat = reg.gid_of("Combat").unwrap();
+        assert_eq!(reg.display_name_of(combat), Some("Combat"));
+        assert_eq!(reg.description_of(combat), None);
+
+        // Unknown GID
+        assert_eq!(reg.display_name_of(0u128), None);
+    }
+
+    #[test]
+    fn loc_keys() {
+        assert_eq!(default_loc_key("Combat.HeavyAttack"), "tag-combat-heavy-attack");
+
+        let defs = &[
+            NamespaceDef::new("Combat", None),
+            NamespaceDef::new("Combat.Attack", Some("Combat")).with_loc_key("combat-attack"),
+        ];
+        let mut reg = NamespaceRegistry::build(defs).unwrap();
+        let dynamic = reg.register("Combat.HeavyAttack").unwrap();
+
+        let attack = reg.gid_of("Combat.Attack").unwrap();
+        assert_eq!(reg.loc_key_of(attack).as_deref(), Some("combat-attack"));
+        assert_eq!(reg.loc_key_of(dynamic).as_deref(), Some("tag-combat-heavy-attack"));
+        assert_eq!(reg.loc_key_of(0u128), None);
+    }
+
+    #[test]
+    fn flag_queries() {
+        let defs = &[
+            NamespaceDef::new("Fx", None),
+            NamespaceDef::new("Fx.Sparkle", Some("Fx"))
+                .with_flags(TagFlags::COSMETIC.union(TagFlags::DESIGNER_VISIBLE)),
+            NamespaceDef::new("Fx.Trail", Some("Fx")).with_flags(TagFlags::COSMETIC),
+        ];
+        let reg = NamespaceRegistry::build(defs).unwrap();
+        let sparkle = reg.gid_of("Fx.Sparkle").unwrap();
+        let trail = reg.gid_of("Fx.Trail").unwrap();
+
+        assert_eq!(reg.gids_with_flag(TagFlags::COSMETIC), vec![sparkle, trail]);
+        assert_eq!(
+            reg.gids_with_flag(TagFlags::COSMETIC | TagFlags::DESIGNER_VISIBLE),
+            vec![sparkle]
+        );
+        assert_eq!(reg.flags_of(reg.gid_of("Fx").unwrap()), Some(TagFlags::EMPTY));
+    }
+
+    #[test]
+    fn deprecation_is_recorded() {
+        let defs = &[
+            NamespaceDef::new("Combat", None),
+            NamespaceDef::new("Combat.Old", Some("Combat")).with_deprecation(Deprecation {
+                since: Some("0.3.0"),
+                note: Some("use Combat.New"),
+            }),
+        ];
+        let reg = NamespaceRegistry::build(defs).unwrap();
+
+        let old = reg.gid_of("Combat.Old").unwrap();
+        assert!(reg.is_deprecated(old));
+        let info = reg.deprecation_of(old).unwrap();
+        assert_eq!(info.since, Some("0.3.0"));
+        assert_eq!(info.note, Some("use Combat.New"));
+
+        assert!(!reg.is_deprecated(reg.gid_of("Combat").unwrap()));
+    }
+
+    #[test]
+    fn pinned_gid_is_used_and_inherited() {
+        use crate::hash::gid_with_segment;
+
+        const PINNED: GID = gid_with_segment(crate::ROOT_GID, 0, 0x1234);
+        let defs = &[
+            NamespaceDef::new("Legacy", None).with_gid(PINNED),
+            NamespaceDef::new("Legacy.Item", Some("Legacy")),
+        ];
+        let mut reg = NamespaceRegistry::build(defs).unwrap();
+
+        assert_eq!(reg.gid_of("Legacy"), Some(PINNED));
+        let item = reg.gid_of("Legacy.Item").unwrap();
+        assert!(gid_is_descendant_of(item, PINNED));
+
+        // Dynamic registration follows the pinned prefix too
+        let extra = reg.register("Legacy.Item.Extra").unwrap();
+        assert!(gid_is_descendant_of(extra, PINNED));
+        assert_eq!(crate::layout::parent_of(extra), Some(item));
+    }
+
+    #[test]
+    fn large_build_matches_hierarchical_hash_in_dfs_order() {
+        use crate::hash::hierarchical_gid;
+
+        let leak = |s: String| -> &'static str { Box::leak(s.into_boxed_str()) };
+        let mut defs = Vec::new();
+        for a in 0..16 {
+            let root = leak(format!("R{a}"));
+            defs.push(NamespaceDef::new(root, None));
+            for b in 0..16 {
+                let child = leak(format!("{root}.C{b}"));
+                defs.push(NamespaceDef::new(child, Some(root)));
+                for c in 0..4 {
+                    defs.push(NamespaceDef::new(leak(format!("{child}.L{c}")), Some(child)));
+                }
+            }
+        }
+        // Definition order must not matter
+        defs.reverse();
+
+        let reg = NamespaceRegistry::build(&defs).unwrap();
+        let again = NamespaceRegistry::build(&defs).unwrap();
+        assert_eq!(reg.len(), 16 * 16 * 5 + 16);
+        assert_eq!(reg.dfs_order, again.dfs_order);
+
+        for (idx, entry) in reg.entries.iter().enumerate() {
+            let segments: Vec<&[u8]> = entry.path.split('.').map(str::as_bytes).collect();
+            assert_eq!(entry.gid, hierarchical_gid(&segments), "{}", entry.path);
+            assert_eq!(reg.dfs_order[idx], entry.gid);
+            assert_eq!(reg.gid_of(&entry.path), Some(entry.gid));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_pinned_gid() {
+        use crate::hash::{gid_with_segment, hierarchical_gid};
+
+        // Wrong depth
+        let defs = &[NamespaceDef::new("A", None).with_gid(hierarchical_gid(&[b"X", b"Y"]))];
+        assert!(NamespaceRegistry::build(defs).is_err());
+
+        // Not under the parent
+        let defs = &[
+            NamespaceDef::new("A", None),
+            NamespaceDef::new("A.B", Some("A")).with_gid(hierarchical_gid(&[b"X", b"Y"])),
+        ];
+        let err = NamespaceRegistry::build(defs).unwrap_err();
+        assert!(err.contains("not under its parent"));
+
+        // Well-formed and under the parent
+        let a = hierarchical_gid(&[b"A"]);
+        let defs = &[
+            NamespaceDef::new("A", None),
+            NamespaceDef::new("A.B", Some("A")).with_gid(gid_with_segment(a, 1, 7)),
+        ];
+        assert!(NamespaceRegistry::build(defs).is_ok());
+    }
+
     #[test]
     fn empty_build_returns_empty_registry() {
         let reg = NamespaceRegistry::build(&[]).unwrap();
@@ -765,6 +1860,27 @@ mod tests {
         assert!(NamespaceRegistry::build(defs).is_err());
     }
 
+    #[test]
+    fn rejects_multi_segment_root() {
+        let defs = &[NamespaceDef::new("A.B", None)];
+        let err = NamespaceRegistry::build(defs).unwrap_err();
+        assert!(err.contains("no parent"));
+    }
+
+    #[test]
+    fn rejects_parent_skipping_a_level() {
+        let defs = &[NamespaceDef::new("A", None), NamespaceDef::new("A.B.C", Some("A"))];
+        let err = NamespaceRegistry::build(defs).unwrap_err();
+        assert!(err.contains("not its path prefix"));
+    }
+
+    #[test]
+    fn rejects_parent_not_matching_path() {
+        let defs = &[NamespaceDef::new("A", None), NamespaceDef::new("X.Y", Some("A"))];
+        let err = NamespaceRegistry::build(defs).unwrap_err();
+        assert!(err.contains("not its path prefix"));
+    }
+
     #[test]
     fn deep_tree_works() {
         // 4 levels deep - should work fine with static layout
@@ -839,9 +1955,8 @@ mod tests {
 
         // Verify depths (use standalone function)
         use crate::layout::depth_of;
-        for i in 0..8 {
-            let path = defs[i].path;
-            assert_eq!(depth_of(reg.gid_of(path).unwrap()), i as u8);
+        for (i, def) in defs.iter().enumerate() {
+            assert_eq!(depth_of(reg.gid_of(def.path).unwrap()), i as u8);
         }
 
         // Verify descendant relationships
@@ -1038,6 +2153,95 @@ mod tests {
         assert_eq!(reg.tree_depth(), 5);
     }
 
+    // =========================================================================
+    // Provenance tests
+    // =========================================================================
+
+    #[test]
+    fn unregister_source_removes_only_its_tags() {
+        let mut reg = NamespaceRegistry::build(&[NamespaceDef::new("Item", None)]).unwrap();
+        let gun = reg.register_with_source("Item.Gun.Laser", "mod:cool_guns").unwrap();
+        let gun_parent = reg.gid_of("Item.Gun").unwrap();
+        let hat = reg.register_with_source("Cosmetic.Hat", "mod:hats").unwrap();
+        // Another mod nests under the first one's tag
+        let scope = reg.register_with_source("Item.Gun.Scope", "mod:scopes").unwrap();
+        reg.set_meta(gun, "damage", &30i32);
+
+        assert_eq!(reg.source_of(gun), Some("mod:cool_guns"));
+        assert_eq!(reg.source_of(reg.gid_of("Item").unwrap()), None);
+
+        let removed = reg.unregister_source("mod:cool_guns");
+        assert_eq!(removed, [gun]);
+        assert!(!reg.contains_gid(gun));
+        assert!(!reg.has_meta(gun, "damage"));
+        // Still the parent of mod:scopes' tag
+        assert!(reg.contains_gid(gun_parent));
+        assert_eq!(reg.path_of(scope), Some("Item.Gun.Scope"));
+        assert_eq!(reg.path_of(hat), Some("Cosmetic.Hat"));
+        assert_eq!(reg.dfs_order().len(), reg.len());
+
+        // The last child takes the unloaded source's parent with it
+        let removed = reg.unregister_source("mod:scopes");
+        assert_eq!(removed, [gun_parent, scope]);
+        assert_eq!(reg.unregister_source("mod:cool_guns"), []);
+        assert_eq!(reg.len(), 3);
+
+        // A loaded source's tag stays when its children go
+        let feather = reg.register_with_source("Cosmetic.Hat.Feather", "mod:feathers").unwrap();
+        assert_eq!(reg.unregister_source("mod:feathers"), [feather]);
+        assert_eq!(reg.path_of(hat), Some("Cosmetic.Hat"));
+    }
+
+    // =========================================================================
+    // Sealing tests
+    // =========================================================================
+
+    #[test]
+    fn sealed_subtrees_reject_registration() {
+        let mut reg = NamespaceRegistry::new();
+        let engine = reg.register("Engine.Render").unwrap();
+        let engine_root = reg.gid_of("Engine").unwrap();
+        reg.seal_subtree(engine_root);
+
+        assert!(reg.is_sealed(engine));
+        assert_eq!(
+            reg.register("Engine.Physics.Step"),
+            Err("cannot register 'Engine.Physics.Step': subtree 'Engine' is sealed".into())
+        );
+        assert!(!reg.contains("Engine.Physics"));
+        // Existing tags still resolve
+        assert_eq!(reg.register("Engine.Render"), Ok(engine));
+        // Other branches stay open
+        assert!(reg.register("Mods.Sword").is_ok());
+
+        assert!(reg.unseal_subtree(engine_root));
+        assert!(!reg.unseal_subtree(engine_root));
+        assert!(reg.register("Engine.Physics").is_ok());
+    }
+
+    // =========================================================================
+    // Interning tests
+    // =========================================================================
+
+    #[test]
+    fn interned_handles_resolve_and_survive_registration() {
+        let mut reg = NamespaceRegistry::new();
+        let combat = reg.intern_or_register("Combat.Attack").unwrap();
+        assert_eq!(combat.as_str(&reg), "Combat.Attack");
+
+        let parent = reg.intern("Combat").unwrap();
+        assert_eq!(reg.intern_gid(parent.gid(&reg)), Some(parent));
+        assert_eq!(reg.intern_or_register("Combat.Attack"), Ok(combat));
+        assert_eq!(reg.intern("Missing"), None);
+
+        for i in 0..16 {
+            reg.register(&format!("Other.Tag{}", i)).unwrap();
+        }
+        assert_eq!(combat.as_str(&reg), "Combat.Attack");
+        assert_eq!(combat.into_gid_with(&reg), reg.gid_of("Combat.Attack").unwrap());
+        assert_eq!(reg.entries()[combat.index() as usize].path, "Combat.Attack");
+    }
+
     // =========================================================================
     // Metadata tests
     // =========================================================================
@@ -1199,6 +2403,19 @@ mod tests {
         assert_eq!(paths_after, vec!["Combat", "Combat.Ability", "Combat.Attack"]);
     }
 
+    #[test]
+    fn register_rejects_colliding_parent_without_side_effects() {
+        // Both hash to the same depth-0 segment
+        let mut reg = NamespaceRegistry::new();
+        let existing = reg.register("B5bWGqX").unwrap();
+
+        let err = reg.register("Tt__G_r.A").unwrap_err();
+        assert!(err.contains("GID collision"), "{err}");
+        assert_eq!(reg.path_of(existing), Some("B5bWGqX"));
+        assert_eq!(reg.gid_of("Tt__G_r"), None);
+        assert_eq!(reg.len(), 1);
+    }
+
     #[test]
     fn standalone_is_descendant_of_works_with_dynamic_gids() {
         use crate::layout::{depth_of, gid_is_descendant_of};
@@ -0,0 +1,196 @@
+//! Random tag sampling (`rand` feature).
+//!
+//! Loot tables and procedural generation pick "a random tag under
+//! `Item.Weapon`" every roll. These walk the registry once with reservoir
+//! sampling instead of collecting the subtree into a `Vec` first.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use rand::Rng;
+
+use crate::layout::gid_is_descendant_of;
+use crate::registry::NamespaceRegistry;
+use crate::traits::IntoGid;
+use crate::GID;
+
+impl NamespaceRegistry {
+    /// A uniformly random registered tag under `ancestor` (including
+    /// `ancestor` itself, as in [`descendants_of`](Self::descendants_of)).
+    pub fn sample_descendant<R: Rng + ?Sized>(
+        &self,
+        ancestor: impl IntoGid,
+        rng: &mut R,
+    ) -> Option<GID> {
+        let ancestor = ancestor.into_gid();
+        let mut picked = None;
+        let mut seen = 0u64;
+        for entry in self.entries() {
+            if gid_is_descendant_of(entry.gid, ancestor) {
+                seen += 1;
+                if rng.random_range(0..seen) == 0 {
+                    picked = Some(entry.gid);
+                }
+            }
+        }
+        picked
+    }
+
+    /// A random tag under `ancestor`, chosen with probability proportional
+    /// to `weight(gid)`. Tags weighing zero, less, or NaN are never chosen.
+    pub fn sample_descendant_by<R: Rng + ?Sized>(
+        &self,
+        ancestor: impl IntoGid,
+        mut weight: impl FnMut(GID) -> f64,
+        rng: &mut R,
+    ) -> Option<GID> {
+        let ancestor = ancestor.into_gid();
+        let mut picked = None;
+        let mut total = 0.0;
+        for entry in self.entries() {
+            if !gid_is_descendant_of(entry.gid, ancestor) {
+                continue;
+            }
+            let weight = weight(entry.gid);
+            if weight > 0.0 {
+                total += weight;
+                if rng.random::<f64>() * total < weight {
+                    picked = Some(entry.gid);
+                }
+            }
+        }
+        picked
+    }
+
+    /// [`sample_descendant_by`](Self::sample_descendant_by) with weights
+    /// from `weights`; tags missing from it are never chosen.
+    pub fn sample_descendant_weighted<R: Rng + ?Sized, S: BuildHasher>(
+        &self,
+        ancestor: impl IntoGid,
+        weights: &HashMap<GID, f64, S>,
+        rng: &mut R,
+    ) -> Option<GID> {
+        self.sample_descendant_by(
+            ancestor,
+            |gid| weights.get(&gid).copied().unwrap_or(0.0),
+            rng,
+        )
+    }
+
+    /// [`sample_descendant_by`](Self::sample_descendant_by) with weights
+    /// from metadata `key`, stored as `f64` or `i32` (the types
+    /// `bevy-tag-build` uses for float and integer `[tags.meta]`). Tags
+    /// without it are never chosen.
+    pub fn sample_descendant_by_meta<R: Rng + ?Sized>(
+        &self,
+        ancestor: impl IntoGid,
+        key: &str,
+        rng: &mut R,
+    ) -> Option<GID> {
+        self.sample_descendant_by(
+            ancestor,
+            |gid| {
+                self.get_meta::<f64>(gid, key)
+                    .copied()
+                    .or_else(|| self.get_meta::<i32>(gid, key).map(|&w| f64::from(w)))
+                    .unwrap_or(0.0)
+            },
+            rng,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    /// SplitMix64, so the tests don't need an RNG crate with `std`.
+    struct TestRng(u64);
+
+    impl RngCore for TestRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            rand::rand_core::impls::fill_bytes_via_next(self, dest);
+        }
+    }
+
+    fn registry() -> NamespaceRegistry {
+        let mut registry = NamespaceRegistry::new();
+        for path in [
+            "Item.Weapon.Sword",
+            "Item.Weapon.Bow",
+            "Item.Weapon.Axe",
+            "Item.Potion",
+        ] {
+            registry.register(path).unwrap();
+        }
+        registry
+    }
+
+    #[test]
+    fn samples_stay_in_the_subtree() {
+        let registry = registry();
+        let weapon = registry.gid_of("Item.Weapon").unwrap();
+        let mut rng = TestRng(1);
+        let mut counts: HashMap<GID, u32> = HashMap::new();
+        for _ in 0..4000 {
+            let gid = registry.sample_descendant(weapon, &mut rng).unwrap();
+            *counts.entry(gid).or_default() += 1;
+        }
+        // Weapon itself and its three children, about 1000 each
+        assert_eq!(counts.len(), 4);
+        assert!(
+            counts.values().all(|&n| (850..1150).contains(&n)),
+            "{:?}",
+            counts
+        );
+        assert!(counts
+            .keys()
+            .all(|&gid| registry.is_descendant_of(gid, weapon)));
+
+        let unknown = crate::hash::hierarchical_gid(&[b"Ui"]);
+        assert_eq!(registry.sample_descendant(unknown, &mut rng), None);
+    }
+
+    #[test]
+    fn weights_from_map_and_metadata() {
+        let mut registry = registry();
+        let weapon = registry.gid_of("Item.Weapon").unwrap();
+        let sword = registry.gid_of("Item.Weapon.Sword").unwrap();
+        let bow = registry.gid_of("Item.Weapon.Bow").unwrap();
+        let potion = registry.gid_of("Item.Potion").unwrap();
+        let mut rng = TestRng(7);
+
+        let weights = HashMap::from([(sword, 3.0), (bow, 1.0), (potion, 100.0)]);
+        let swords = (0..4000)
+            .filter(|_| {
+                registry.sample_descendant_weighted(weapon, &weights, &mut rng) == Some(sword)
+            })
+            .count();
+        assert!((2850..3150).contains(&swords), "{}", swords);
+
+        registry.set_meta(sword, "drop_weight", &0.5f64);
+        registry.set_meta(bow, "drop_weight", &2i32);
+        let mut picked = std::collections::HashSet::new();
+        for _ in 0..200 {
+            picked.insert(registry.sample_descendant_by_meta(weapon, "drop_weight", &mut rng));
+        }
+        assert_eq!(picked, [Some(sword), Some(bow)].into());
+        assert_eq!(
+            registry.sample_descendant_by_meta(weapon, "missing", &mut rng),
+            None
+        );
+    }
+}
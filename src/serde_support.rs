@@ -0,0 +1,429 @@
+//! Serde support (enabled with the `serde` feature).
+//!
+//! Generated `Tag` types serialize as their `PATH` string, so typed tags can
+//! appear directly in config structs:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct WeaponConfig {
+//!     kind: Tags::Equipment::Weapon::Blade::Tag, // "Equipment.Weapon.Blade"
+//! }
+//! ```
+//!
+//! Deserialization only accepts the exact path of the type.
+//!
+//! Raw [`GID`] fields serialize as plain `u128`s, which JSON and some other
+//! formats can't represent. [`gid_hex`] writes them as `"0x..."` strings
+//! instead, and [`gid_path`] as tag paths using a registry seeded at startup:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct SaveSlot {
+//!     #[serde(with = "bevy_tag::serde_support::gid_hex")]
+//!     last_ability: GID, // "0x2000..."
+//!     #[serde(with = "bevy_tag::serde_support::gid_path")]
+//!     stance: GID, // "Movement.Crouch"
+//! }
+//! ```
+//!
+//! For save files and scenes, [`WithRegistry`] and [`RegistrySeed`] scope a
+//! borrowed registry over a whole (de)serialization instead, so every
+//! `gid_path` GID nested anywhere in the data is written and resolved by
+//! path. Such files survive hash or layout changes and read well in diffs:
+//!
+//! ```ignore
+//! let text = ron::to_string(&WithRegistry::new(&registry, &save))?;
+//! let save: Save = RegistrySeed::new(&registry)
+//!     .deserialize(&mut ron::Deserializer::from_str(&text)?)?;
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+use std::cell::RefCell;
+
+use ::serde::de::{self, DeserializeSeed, Visitor};
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::migrate::RedirectMap;
+use crate::registry::NamespaceRegistry;
+use crate::traits::{Alias, AliasPath, NamespaceTag, Redirect};
+use crate::GID;
+
+/// Serialize a tag type as its path.
+#[doc(hidden)]
+pub fn serialize_tag<T: NamespaceTag, S: Serializer>(serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(T::PATH)
+}
+
+/// Deserialize a tag type, checking the path matches `T::PATH`.
+#[doc(hidden)]
+pub fn deserialize_tag<'de, T: NamespaceTag + Default, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    deserializer.deserialize_str(TagPathVisitor(T::PATH))?;
+    Ok(T::default())
+}
+
+struct TagPathVisitor(&'static str);
+
+impl Visitor<'_> for TagPathVisitor {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the tag path \"{}\"", self.0)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<(), E> {
+        if value == self.0 {
+            Ok(())
+        } else {
+            Err(E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+}
+
+impl<T: NamespaceTag> Serialize for Redirect<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_tag::<T, S>(serializer)
+    }
+}
+
+impl<'de, T: NamespaceTag> Deserialize<'de> for Redirect<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(TagPathVisitor(T::PATH))?;
+        Ok(Self::new())
+    }
+}
+
+/// Aliases serialize as their own path and also accept the target's path.
+impl<T: NamespaceTag, P: AliasPath> Serialize for Alias<T, P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_tag::<Self, S>(serializer)
+    }
+}
+
+impl<'de, T: NamespaceTag, P: AliasPath> Deserialize<'de> for Alias<T, P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        if path != P::PATH {
+            TagPathVisitor(T::PATH).visit_str::<D::Error>(&path)?;
+        }
+        Ok(Self::new())
+    }
+}
+
+/// Serialize a [`GID`] as a `"0x..."` hex string.
+///
+/// Use with `#[serde(with = "bevy_tag::serde_support::gid_hex")]`.
+/// Deserialization also accepts plain integers, so data saved before
+/// switching to this representation still loads.
+pub mod gid_hex {
+    use super::*;
+
+    /// Serialize `gid` as `"0x"` followed by 32 hex digits.
+    pub fn serialize<S: Serializer>(gid: &GID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("0x{:032x}", gid))
+    }
+
+    /// Deserialize a hex string or an integer.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<GID, D::Error> {
+        deserializer.deserialize_any(GidVisitor { paths: false })
+    }
+}
+
+/// Serialize a [`GID`] as its tag path, looked up in the registry of the
+/// enclosing [`WithRegistry`]/[`RegistrySeed`], or else one passed to
+/// [`gid_path::seed`].
+///
+/// Use with `#[serde(with = "bevy_tag::serde_support::gid_path")]`. GIDs the
+/// registry doesn't know (or all of them, before seeding) are written as
+/// hex, like [`gid_hex`]. Deserialization accepts paths, hex strings and
+/// integers; without a seeded registry, paths are hashed, which is only
+/// correct for tags without pinned GIDs. Old paths and GIDs in a map passed
+/// to [`gid_path::seed_redirects`] load as their current tag.
+pub mod gid_path {
+    use super::*;
+    use std::sync::RwLock;
+
+    static REGISTRY: RwLock<Option<&'static NamespaceRegistry>> = RwLock::new(None);
+    static REDIRECTS: RwLock<Option<&'static RedirectMap>> = RwLock::new(None);
+
+    /// Use `registry` for path lookups from now on, replacing any earlier one.
+    pub fn seed(registry: &'static NamespaceRegistry) {
+        *REGISTRY.write().unwrap_or_else(|e| e.into_inner()) = Some(registry);
+    }
+
+    /// The seeded registry, if any.
+    pub(super) fn registry() -> Option<&'static NamespaceRegistry> {
+        *REGISTRY.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Resolve deserialized paths and GIDs through `redirects` from now on,
+    /// replacing any earlier map. Typically the generated `redirects()`.
+    pub fn seed_redirects(redirects: &'static RedirectMap) {
+        *REDIRECTS.write().unwrap_or_else(|e| e.into_inner()) = Some(redirects);
+    }
+
+    /// The seeded redirects, if any.
+    pub(super) fn redirects() -> Option<&'static RedirectMap> {
+        *REDIRECTS.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Serialize `gid` as its path, or as hex if the path is unknown.
+    pub fn serialize<S: Serializer>(gid: &GID, serializer: S) -> Result<S::Ok, S::Error> {
+        with_registry(|registry| match registry.and_then(|r| r.path_of(*gid)) {
+            Some(path) => serializer.serialize_str(path),
+            None => gid_hex::serialize(gid, serializer),
+        })
+    }
+
+    /// Deserialize a path, a hex string or an integer.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<GID, D::Error> {
+        deserializer.deserialize_any(GidVisitor { paths: true })
+    }
+}
+
+/// Serialize `value` with [`gid_path`] GIDs anywhere inside it looked up in
+/// `registry` (taking precedence over [`gid_path::seed`]).
+pub struct WithRegistry<'a, T: ?Sized> {
+    registry: &'a NamespaceRegistry,
+    value: &'a T,
+}
+
+impl<'a, T: ?Sized> WithRegistry<'a, T> {
+    /// Wrap `value` for serialization against `registry`.
+    pub fn new(registry: &'a NamespaceRegistry, value: &'a T) -> Self {
+        Self { registry, value }
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for WithRegistry<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let _scope = RegistryScope::enter(self.registry);
+        self.value.serialize(serializer)
+    }
+}
+
+/// Deserialize a `T` with [`gid_path`] GIDs anywhere inside it resolved in
+/// `registry` (taking precedence over [`gid_path::seed`]).
+pub struct RegistrySeed<'a, T> {
+    registry: &'a NamespaceRegistry,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> RegistrySeed<'a, T> {
+    /// Seed for deserializing a `T` against `registry`.
+    pub fn new(registry: &'a NamespaceRegistry) -> Self {
+        Self {
+            registry,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> DeserializeSeed<'de> for RegistrySeed<'_, T> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        let _scope = RegistryScope::enter(self.registry);
+        T::deserialize(deserializer)
+    }
+}
+
+thread_local! {
+    /// Registries of the active [`WithRegistry`]/[`RegistrySeed`] scopes,
+    /// innermost last.
+    static SCOPES: RefCell<Vec<*const NamespaceRegistry>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pushes a borrowed registry on creation and pops it on drop (also when
+/// unwinding), so [`SCOPES`] never holds a registry past its borrow.
+struct RegistryScope<'a>(PhantomData<&'a NamespaceRegistry>);
+
+impl<'a> RegistryScope<'a> {
+    fn enter(registry: &'a NamespaceRegistry) -> Self {
+        SCOPES.with(|scopes| scopes.borrow_mut().push(registry));
+        Self(PhantomData)
+    }
+}
+
+impl Drop for RegistryScope<'_> {
+    fn drop(&mut self) {
+        SCOPES.with(|scopes| scopes.borrow_mut().pop());
+    }
+}
+
+/// Call `f` with the registry [`gid_path`] looks up paths in: the innermost
+/// scope's, else the seeded one.
+fn with_registry<R>(f: impl FnOnce(Option<&NamespaceRegistry>) -> R) -> R {
+    let scoped = SCOPES.with(|scopes| scopes.borrow().last().copied());
+    match scoped {
+        // SAFETY: the pointer was pushed by a `RegistryScope` that borrows
+        // the registry and pops it on drop. Scopes nest, so that scope
+        // outlives this call.
+        Some(registry) => f(Some(unsafe { &*registry })),
+        None => f(gid_path::registry()),
+    }
+}
+
+/// Visitor for [`gid_hex`] and [`gid_path`].
+struct GidVisitor {
+    /// Accept tag paths as well as hex strings
+    paths: bool,
+}
+
+impl GidVisitor {
+    /// GID of `path`: from the scoped or seeded registry, or hashed without one.
+    fn gid_of_path<E: de::Error>(&self, path: &str) -> Result<GID, E> {
+        let redirects = gid_path::redirects();
+        let path = redirects.map_or(path, |r| r.resolve_path(path));
+        with_registry(|registry| match registry {
+            Some(registry) => registry
+                .gid_of(path)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(path), &"a registered tag path")),
+            None => crate::hash::path_gid(path)
+                .ok_or_else(|| E::invalid_value(de::Unexpected::Str(path), self)),
+        })
+    }
+
+    /// `gid` after the seeded redirects ([`gid_path`] only).
+    fn redirect(&self, gid: GID) -> GID {
+        match gid_path::redirects() {
+            Some(redirects) if self.paths => redirects.resolve(gid),
+            _ => gid,
+        }
+    }
+}
+
+impl Visitor<'_> for GidVisitor {
+    type Value = GID;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.paths {
+            f.write_str("a tag path, a \"0x...\" GID or an integer GID")
+        } else {
+            f.write_str("a \"0x...\" GID or an integer GID")
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<GID, E> {
+        match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+            Some(hex) => GID::from_str_radix(hex, 16)
+                .map(|gid| self.redirect(gid))
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self)),
+            None if self.paths => self.gid_of_path(value),
+            None => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<GID, E> {
+        Ok(self.redirect(GID::from(value)))
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<GID, E> {
+        Ok(self.redirect(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<GID, E> {
+        GID::try_from(value)
+            .map(|gid| self.redirect(gid))
+            .map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+}
+
+/// Implement `Serialize`/`Deserialize` for a generated tag type.
+///
+/// Emitted by the `namespace!` macro; expands to nothing without the `serde` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_tag_serde {
+    ($tag:ident) => {
+        impl $crate::__private::serde::Serialize for $tag {
+            fn serialize<S: $crate::__private::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                $crate::serde_support::serialize_tag::<$tag, S>(serializer)
+            }
+        }
+
+        impl<'de> $crate::__private::serde::Deserialize<'de> for $tag {
+            fn deserialize<D: $crate::__private::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                $crate::serde_support::deserialize_tag::<$tag, D>(deserializer)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hierarchical_gid;
+    use crate::GID;
+
+    #[derive(Clone, Copy, Default, Debug, PartialEq)]
+    struct Blade;
+
+    impl NamespaceTag for Blade {
+        const PATH: &'static str = "Equipment.Blade";
+        const DEPTH: u8 = 1;
+        const GID: GID = hierarchical_gid(&[b"Equipment", b"Blade"]);
+    }
+
+    crate::__impl_tag_serde!(Blade);
+
+    #[test]
+    fn tag_roundtrips_as_path() {
+        let json = serde_json::to_string(&Blade).unwrap();
+        assert_eq!(json, "\"Equipment.Blade\"");
+        assert_eq!(serde_json::from_str::<Blade>(&json).unwrap(), Blade);
+    }
+
+    #[test]
+    fn tag_rejects_other_path() {
+        assert!(serde_json::from_str::<Blade>("\"Equipment.Bow\"").is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Saved {
+        #[serde(with = "gid_hex")]
+        gid: GID,
+    }
+
+    #[test]
+    fn gid_hex_roundtrips_and_accepts_integers() {
+        let saved = Saved { gid: Blade::GID };
+        let json = serde_json::to_string(&saved).unwrap();
+        assert_eq!(json, format!("{{\"gid\":\"0x{:032x}\"}}", Blade::GID));
+        assert_eq!(serde_json::from_str::<Saved>(&json).unwrap(), saved);
+
+        assert_eq!(serde_json::from_str::<Saved>(r#"{"gid":"0X2A"}"#).unwrap().gid, 42);
+        assert_eq!(serde_json::from_str::<Saved>(r#"{"gid":42}"#).unwrap().gid, 42);
+        assert!(serde_json::from_str::<Saved>(r#"{"gid":"Equipment.Blade"}"#).is_err());
+        assert!(serde_json::from_str::<Saved>(r#"{"gid":-1}"#).is_err());
+    }
+
+    #[test]
+    fn redirect_serializes_canonical_path() {
+        let json = serde_json::to_string(&Redirect::<Blade>::new()).unwrap();
+        assert_eq!(json, "\"Equipment.Blade\"");
+        assert!(serde_json::from_str::<Redirect<Blade>>(&json).is_ok());
+    }
+
+    #[test]
+    fn alias_serializes_own_path_and_accepts_target() {
+        #[derive(Clone, Copy)]
+        struct Sword;
+        impl AliasPath for Sword {
+            const PATH: &'static str = "Item.Weapon.Sword";
+        }
+
+        let json = serde_json::to_string(&Alias::<Blade, Sword>::new()).unwrap();
+        assert_eq!(json, "\"Item.Weapon.Sword\"");
+        assert!(serde_json::from_str::<Alias<Blade, Sword>>(&json).is_ok());
+        assert!(serde_json::from_str::<Alias<Blade, Sword>>("\"Equipment.Blade\"").is_ok());
+        assert!(serde_json::from_str::<Alias<Blade, Sword>>("\"Equipment.Bow\"").is_err());
+    }
+}
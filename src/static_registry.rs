@@ -0,0 +1,341 @@
+//! Read-only registry over const tables, for fully static namespaces.
+//!
+//! `namespace!` emits one as `Tags::STATIC_REGISTRY`: its entries are sorted
+//! by GID and indexed by path during const evaluation, so lookups are binary
+//! searches over `'static` data with no startup cost and no heap. The read
+//! API mirrors [`NamespaceRegistry`](crate::NamespaceRegistry); building
+//! needs only `core`, so the tables are usable from `no_std` code linked
+//! against the generated module.
+//!
+//! ```
+//! bevy_tag::namespace! {
+//!     pub mod Tags {
+//!         Item { Sword; Shield; }
+//!     }
+//! }
+//!
+//! const SWORD: Option<bevy_tag::GID> = Tags::STATIC_REGISTRY.gid_of("Item.Sword");
+//! assert_eq!(SWORD, Some(Tags::Item::Sword::GID));
+//! assert_eq!(Tags::STATIC_REGISTRY.path_of(Tags::Item::Shield::GID), Some("Item.Shield"));
+//! ```
+
+use core::cmp::Ordering;
+
+use crate::flags::TagFlags;
+use crate::layout::{depth_of, gid_is_descendant_of};
+use crate::lookup::cmp_str;
+use crate::registry::{Deprecation, NamespaceDef};
+use crate::traits::IntoGid;
+use crate::GID;
+
+/// A namespace definition with its GID.
+#[derive(Clone, Copy, Debug)]
+pub struct StaticEntry {
+    pub gid: GID,
+    pub def: NamespaceDef,
+}
+
+/// Const-built, read-only counterpart of
+/// [`NamespaceRegistry`](crate::NamespaceRegistry).
+///
+/// Build with [`StaticRegistry::sort_by_gid`], [`StaticRegistry::path_index`]
+/// and [`StaticRegistry::new`] in const items, as `namespace!` does.
+#[derive(Clone, Copy, Debug)]
+pub struct StaticRegistry {
+    /// Sorted by GID.
+    entries: &'static [StaticEntry],
+    /// Indices into `entries`, sorted by path.
+    by_path: &'static [usize],
+    tree_depth: usize,
+}
+
+impl StaticRegistry {
+    /// Wrap tables produced by [`sort_by_gid`](Self::sort_by_gid) and
+    /// [`path_index`](Self::path_index).
+    ///
+    /// # Panics
+    ///
+    /// Panics (at compile time in a const) if the tables are not sorted, or
+    /// two entries share a GID or path.
+    pub const fn new(entries: &'static [StaticEntry], by_path: &'static [usize]) -> Self {
+        assert!(
+            entries.len() == by_path.len(),
+            "path index does not match entries"
+        );
+        let mut tree_depth = 0;
+        let mut i = 0;
+        while i < entries.len() {
+            assert!(
+                i == 0 || entries[i - 1].gid < entries[i].gid,
+                "entries are not strictly sorted by GID"
+            );
+            assert!(
+                i == 0 || path_less(&entries[by_path[i - 1]], &entries[by_path[i]]),
+                "path index is not strictly sorted by path"
+            );
+            let depth = depth_of(entries[i].gid) as usize + 1;
+            if depth > tree_depth {
+                tree_depth = depth;
+            }
+            i += 1;
+        }
+        Self {
+            entries,
+            by_path,
+            tree_depth,
+        }
+    }
+
+    /// Sort entries by GID (heapsort, so large namespaces stay cheap to
+    /// const-evaluate).
+    pub const fn sort_by_gid<const N: usize>(mut entries: [StaticEntry; N]) -> [StaticEntry; N] {
+        let mut start = N / 2;
+        while start > 0 {
+            start -= 1;
+            sift_down(&mut entries, start, N);
+        }
+        let mut end = N;
+        while end > 1 {
+            end -= 1;
+            let first = entries[0];
+            entries[0] = entries[end];
+            entries[end] = first;
+            sift_down(&mut entries, 0, end);
+        }
+        entries
+    }
+
+    /// Indices of `entries` sorted by path.
+    pub const fn path_index<const N: usize>(entries: &[StaticEntry; N]) -> [usize; N] {
+        let mut index = [0; N];
+        let mut i = 0;
+        while i < N {
+            index[i] = i;
+            i += 1;
+        }
+        let mut start = N / 2;
+        while start > 0 {
+            start -= 1;
+            sift_down_by_path(&mut index, entries, start, N);
+        }
+        let mut end = N;
+        while end > 1 {
+            end -= 1;
+            let first = index[0];
+            index[0] = index[end];
+            index[end] = first;
+            sift_down_by_path(&mut index, entries, 0, end);
+        }
+        index
+    }
+
+    /// Entry of a GID (const binary search).
+    pub const fn get(&self, gid: GID) -> Option<&'static StaticEntry> {
+        let entries = self.entries;
+        let (mut lo, mut hi) = (0, entries.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if entries[mid].gid < gid {
+                lo = mid + 1;
+            } else if entries[mid].gid > gid {
+                hi = mid;
+            } else {
+                return Some(&entries[mid]);
+            }
+        }
+        None
+    }
+
+    /// Entry of a path (const binary search).
+    pub const fn get_path(&self, path: &str) -> Option<&'static StaticEntry> {
+        let entries = self.entries;
+        let (mut lo, mut hi) = (0, self.by_path.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let entry = &entries[self.by_path[mid]];
+            match cmp_str(entry.def.path, path) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Greater => hi = mid,
+                Ordering::Equal => return Some(entry),
+            }
+        }
+        None
+    }
+
+    /// Path → GID
+    pub const fn gid_of(&self, path: &str) -> Option<GID> {
+        match self.get_path(path) {
+            Some(entry) => Some(entry.gid),
+            None => None,
+        }
+    }
+
+    /// GID → Path
+    ///
+    /// Accepts both raw `GID` and `Tag` types.
+    pub fn path_of(&self, gid: impl IntoGid) -> Option<&'static str> {
+        self.get(gid.into_gid()).map(|e| e.def.path)
+    }
+
+    /// GID → display name.
+    ///
+    /// Falls back to the last path segment when no display name was declared.
+    pub fn display_name_of(&self, gid: impl IntoGid) -> Option<&'static str> {
+        let def = &self.get(gid.into_gid())?.def;
+        Some(match def.display_name {
+            Some(name) => name,
+            None => def.path.rsplit('.').next().unwrap_or(def.path),
+        })
+    }
+
+    /// GID → description, if one was declared.
+    pub fn description_of(&self, gid: impl IntoGid) -> Option<&'static str> {
+        self.get(gid.into_gid())?.def.description
+    }
+
+    /// GID → localization key.
+    ///
+    /// `namespace!` always records one, so unlike
+    /// [`NamespaceRegistry::loc_key_of`](crate::NamespaceRegistry::loc_key_of)
+    /// this never allocates a default.
+    pub fn loc_key_of(&self, gid: impl IntoGid) -> Option<&'static str> {
+        self.get(gid.into_gid())?.def.loc_key
+    }
+
+    /// GID → category flags.
+    pub fn flags_of(&self, gid: impl IntoGid) -> Option<TagFlags> {
+        self.get(gid.into_gid()).map(|e| e.def.flags)
+    }
+
+    /// All GIDs whose flags contain every flag in `flag`, in GID order.
+    pub fn gids_with_flag(&self, flag: TagFlags) -> impl Iterator<Item = GID> + 'static {
+        self.entries
+            .iter()
+            .filter(move |e| e.def.flags.contains(flag))
+            .map(|e| e.gid)
+    }
+
+    /// GID → deprecation info, if the node was declared deprecated.
+    pub fn deprecation_of(&self, gid: impl IntoGid) -> Option<&'static Deprecation> {
+        self.get(gid.into_gid())?.def.deprecated.as_ref()
+    }
+
+    /// Check whether a node was declared deprecated.
+    #[inline]
+    pub fn is_deprecated(&self, gid: impl IntoGid) -> bool {
+        self.deprecation_of(gid).is_some()
+    }
+
+    /// GID → associated data type name, if one was declared.
+    pub fn data_type_of(&self, gid: impl IntoGid) -> Option<&'static str> {
+        self.get(gid.into_gid())?.def.data_type
+    }
+
+    /// Maximum tree depth (0 = empty, 1 = only root nodes, etc.).
+    #[inline]
+    pub const fn tree_depth(&self) -> usize {
+        self.tree_depth
+    }
+
+    /// Total number of nodes.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All entries in GID order.
+    #[inline]
+    pub const fn entries(&self) -> &'static [StaticEntry] {
+        self.entries
+    }
+
+    /// Check if a path is registered.
+    #[inline]
+    pub const fn contains(&self, path: &str) -> bool {
+        self.get_path(path).is_some()
+    }
+
+    /// Check if a GID is registered.
+    #[inline]
+    pub fn contains_gid(&self, gid: impl IntoGid) -> bool {
+        self.get(gid.into_gid()).is_some()
+    }
+
+    /// Check if `candidate` path is a descendant of (or equal to) `ancestor`;
+    /// `None` if either path is unknown.
+    pub fn is_descendant_of_path(&self, candidate: &str, ancestor: &str) -> Option<bool> {
+        let candidate = self.gid_of(candidate)?;
+        let ancestor = self.gid_of(ancestor)?;
+        Some(gid_is_descendant_of(candidate, ancestor))
+    }
+
+    /// Check if `candidate` is a descendant of (or equal to) `ancestor`.
+    pub fn is_descendant_of(&self, candidate: impl IntoGid, ancestor: impl IntoGid) -> bool {
+        gid_is_descendant_of(candidate.into_gid(), ancestor.into_gid())
+    }
+
+    /// All descendants of `ancestor` (including itself), in GID order.
+    ///
+    /// Not O(1) — iterates all entries. Use `is_descendant_of` for single checks.
+    pub fn descendants_of(&self, ancestor: impl IntoGid) -> impl Iterator<Item = GID> + 'static {
+        let ancestor = ancestor.into_gid();
+        self.entries
+            .iter()
+            .map(|e| e.gid)
+            .filter(move |&gid| gid_is_descendant_of(gid, ancestor))
+    }
+}
+
+/// Restore the max-heap (by GID) below `root`, within `entries[..end]`.
+const fn sift_down(entries: &mut [StaticEntry], mut root: usize, end: usize) {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            return;
+        }
+        if child + 1 < end && entries[child].gid < entries[child + 1].gid {
+            child += 1;
+        }
+        if entries[root].gid >= entries[child].gid {
+            return;
+        }
+        let parent = entries[root];
+        entries[root] = entries[child];
+        entries[child] = parent;
+        root = child;
+    }
+}
+
+const fn path_less(a: &StaticEntry, b: &StaticEntry) -> bool {
+    matches!(cmp_str(a.def.path, b.def.path), Ordering::Less)
+}
+
+/// Restore the max-heap (by path) of entry indices below `root`.
+const fn sift_down_by_path(
+    index: &mut [usize],
+    entries: &[StaticEntry],
+    mut root: usize,
+    end: usize,
+) {
+    loop {
+        let mut child = 2 * root + 1;
+        if child >= end {
+            return;
+        }
+        if child + 1 < end && path_less(&entries[index[child]], &entries[index[child + 1]]) {
+            child += 1;
+        }
+        if !path_less(&entries[index[root]], &entries[index[child]]) {
+            return;
+        }
+        let parent = index[root];
+        index[root] = index[child];
+        index[child] = parent;
+        root = child;
+    }
+}
@@ -0,0 +1,287 @@
+//! Tag usage statistics for analytics.
+//!
+//! [`TagStatsPlugin`] watches every [`TagContainer`] and keeps per-tag
+//! counters in the [`TagStats`] resource: how often a tag was added and
+//! removed this session and in the last frame, how many distinct entities
+//! ever had it, and how many have it now and at most at once. That answers
+//! questions like "is anyone ever Frozen?" from real play sessions.
+//!
+//! Export by reading the resource, e.g. [`TagStats::to_csv`] at the end of a
+//! session, or [`TagStats::iter`] from a system that forwards counters to an
+//! analytics backend:
+//!
+//! ```ignore
+//! app.add_plugins(TagStatsPlugin);
+//!
+//! fn upload(stats: Res<TagStats>, registry: Res<NamespaceRegistry>) {
+//!     for (gid, stat) in stats.iter() {
+//!         analytics::counter(registry.path_of(gid).unwrap_or("?"), stat.added);
+//!     }
+//! }
+//! ```
+
+use bevy::ecs::entity::{EntityHashMap, Entities};
+use bevy::prelude::*;
+
+use crate::bevy::TagContainer;
+use crate::hasher::{GidMap, GidSet};
+use crate::registry::NamespaceRegistry;
+use crate::GID;
+
+/// Adds [`TagStats`] and updates it in [`Last`] every frame.
+pub struct TagStatsPlugin;
+
+impl Plugin for TagStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TagStats>()
+            .add_systems(Last, collect_tag_stats);
+    }
+}
+
+/// Counters for one tag.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TagStat {
+    /// Times the tag was added this session.
+    pub added: u64,
+    /// Times the tag was removed this session, including by despawning.
+    pub removed: u64,
+    /// Times the tag was added in the last collected frame.
+    pub added_last_frame: u32,
+    /// Distinct entities that had the tag this session.
+    pub unique_entities: u64,
+    /// Entities that have the tag now.
+    pub current: u64,
+    /// Most entities that had the tag at once this session.
+    pub peak: u64,
+}
+
+/// Per-tag usage counters, collected by [`TagStatsPlugin`].
+#[derive(Resource, Debug, Default)]
+pub struct TagStats {
+    tags: GidMap<TagStat>,
+    frames: u64,
+    /// Last seen container of each entity, to diff against.
+    previous: EntityHashMap<TagContainer>,
+    /// Tags already counted in `unique_entities`, per live entity.
+    seen: EntityHashMap<GidSet>,
+    /// Tags added this frame, applied after all of the frame's removals.
+    pending: Vec<GID>,
+}
+
+impl TagStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counters for `gid`, if it was ever seen.
+    pub fn get(&self, gid: GID) -> Option<&TagStat> {
+        self.tags.get(&gid)
+    }
+
+    /// Counters of every tag seen, in ascending GID order.
+    pub fn iter(&self) -> impl Iterator<Item = (GID, &TagStat)> {
+        let mut tags: Vec<(GID, &TagStat)> =
+            self.tags.iter().map(|(&gid, stat)| (gid, stat)).collect();
+        tags.sort_unstable_by_key(|&(gid, _)| gid);
+        tags.into_iter()
+    }
+
+    /// Frames collected this session.
+    #[inline]
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Start a new session: zero every counter except `current`, and restart
+    /// `peak` from it.
+    pub fn reset(&mut self) {
+        self.frames = 0;
+        self.seen.clear();
+        for stat in self.tags.values_mut() {
+            *stat = TagStat {
+                current: stat.current,
+                peak: stat.current,
+                ..TagStat::default()
+            };
+        }
+    }
+
+    /// CSV with a header and one row per tag, sorted by path; tags the
+    /// registry doesn't know are written as hex GIDs.
+    pub fn to_csv(&self, registry: &NamespaceRegistry) -> String {
+        let mut rows: Vec<(String, &TagStat)> = self
+            .iter()
+            .map(|(gid, stat)| {
+                let name = registry
+                    .path_of(gid)
+                    .map_or_else(|| format!("{:#034x}", gid), str::to_string);
+                (name, stat)
+            })
+            .collect();
+        rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut out = String::from("tag,added,removed,unique_entities,current,peak\n");
+        for (name, stat) in rows {
+            out.push_str(&format!(
+                "{},{},{},{},{},{}\n",
+                name, stat.added, stat.removed, stat.unique_entities, stat.current, stat.peak
+            ));
+        }
+        out
+    }
+
+    fn begin_frame(&mut self) {
+        self.frames += 1;
+        for stat in self.tags.values_mut() {
+            stat.added_last_frame = 0;
+        }
+    }
+
+    /// Record the change from `entity`'s last seen container to `container`.
+    /// Removals apply now, additions in [`end_frame`](Self::end_frame).
+    fn observe(&mut self, entity: Entity, container: &TagContainer) {
+        let delta = match self.previous.get(&entity) {
+            Some(previous) => previous.diff(container),
+            None => TagContainer::new().diff(container),
+        };
+        let seen = self.seen.entry(entity).or_default();
+        for &gid in &delta.added {
+            let stat = self.tags.entry(gid).or_default();
+            stat.added += 1;
+            stat.added_last_frame += 1;
+            if seen.insert(gid) {
+                stat.unique_entities += 1;
+            }
+        }
+        self.pending.extend(delta.added);
+        for gid in delta.removed {
+            self.remove(gid);
+        }
+        self.previous.insert(entity, container.clone());
+    }
+
+    /// `entity` lost its container or was despawned.
+    fn forget(&mut self, entity: Entity, despawned: bool) {
+        if let Some(previous) = self.previous.remove(&entity) {
+            for gid in previous.iter() {
+                self.remove(gid);
+            }
+        }
+        if despawned {
+            self.seen.remove(&entity);
+        }
+    }
+
+    /// Apply the frame's additions once every removal is in, so a tag that
+    /// moves between entities within a frame doesn't raise `peak`.
+    fn end_frame(&mut self) {
+        for gid in self.pending.drain(..) {
+            let stat = self.tags.entry(gid).or_default();
+            stat.current += 1;
+            stat.peak = stat.peak.max(stat.current);
+        }
+    }
+
+    fn remove(&mut self, gid: GID) {
+        let stat = self.tags.entry(gid).or_default();
+        stat.removed += 1;
+        stat.current = stat.current.saturating_sub(1);
+    }
+}
+
+/// Update [`TagStats`] from containers changed or removed since the last run.
+pub fn collect_tag_stats(
+    mut stats: ResMut<TagStats>,
+    changed: Query<(Entity, &TagContainer), Changed<TagContainer>>,
+    mut removed: RemovedComponents<TagContainer>,
+    entities: &Entities,
+) {
+    stats.begin_frame();
+    for entity in removed.read() {
+        stats.forget(entity, !entities.contains(entity));
+    }
+    for (entity, container) in &changed {
+        stats.observe(entity, container);
+    }
+    stats.end_frame();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::NamespaceDef;
+
+    #[test]
+    fn counts_adds_removals_and_peaks() {
+        let registry = NamespaceRegistry::build(&[
+            NamespaceDef::new("Status", None),
+            NamespaceDef::new("Status.Frozen", Some("Status")),
+        ])
+        .unwrap();
+        let frozen = registry.gid_of("Status.Frozen").unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(TagStatsPlugin);
+        let a = app.world_mut().spawn(TagContainer::single(frozen)).id();
+        let b = app.world_mut().spawn(TagContainer::single(frozen)).id();
+        app.update();
+
+        app.world_mut()
+            .get_mut::<TagContainer>(a)
+            .unwrap()
+            .remove(frozen);
+        app.world_mut().despawn(b);
+        app.update();
+        app.world_mut()
+            .get_mut::<TagContainer>(a)
+            .unwrap()
+            .insert(frozen);
+        app.update();
+
+        let stats = app.world().resource::<TagStats>();
+        assert_eq!(stats.frames(), 3);
+        let stat = stats.get(frozen).unwrap();
+        assert_eq!(
+            *stat,
+            TagStat {
+                added: 3,
+                removed: 2,
+                added_last_frame: 1,
+                unique_entities: 2,
+                current: 1,
+                peak: 2,
+            }
+        );
+        assert_eq!(
+            stats.to_csv(&registry),
+            "tag,added,removed,unique_entities,current,peak\nStatus.Frozen,3,2,2,1,2\n"
+        );
+
+        let mut stats = app.world_mut().resource_mut::<TagStats>();
+        stats.reset();
+        assert_eq!(stats.get(frozen).unwrap().peak, 1);
+        assert_eq!(stats.get(frozen).unwrap().added, 0);
+    }
+
+    #[test]
+    fn handover_within_a_frame_does_not_raise_peak() {
+        let frozen = crate::hierarchical_gid(&[b"Frozen"]);
+
+        let mut app = App::new();
+        app.add_plugins(TagStatsPlugin);
+        let a = app.world_mut().spawn(TagContainer::single(frozen)).id();
+        app.update();
+
+        // B gains the tag as A loses it; B may be visited first
+        app.world_mut().spawn(TagContainer::single(frozen));
+        app.world_mut().get_mut::<TagContainer>(a).unwrap().remove(frozen);
+        app.update();
+        let stat = *app.world().resource::<TagStats>().get(frozen).unwrap();
+        assert_eq!((stat.current, stat.peak), (1, 1));
+
+        // Despawned entities are dropped from the unique-entity bookkeeping
+        app.world_mut().despawn(a);
+        app.update();
+        assert!(!app.world().resource::<TagStats>().seen.contains_key(&a));
+    }
+}
@@ -0,0 +1,385 @@
+//! Late-join sync of dynamically registered tags.
+//!
+//! Tags registered at runtime (mods, user content, server-side scripts) exist
+//! only on the peer that registered them. The server keeps a
+//! [`TagDictionary`] numbering its dynamic tags `0..` in registration order;
+//! ordinals are never reused, so a client that has applied the dictionary can
+//! read messages carrying a small varint ordinal instead of a 16-byte GID or a
+//! path.
+//!
+//! [`encode`](TagDictionary::encode) writes the whole dictionary for a joining
+//! client and [`encode_since`](TagDictionary::encode_since) only the entries
+//! after a known length, for clients already in sync. The client
+//! [`decode`](TagDictionary::decode)s the bytes and
+//! [`apply`](TagDictionary::apply)s the entries: each path is registered and
+//! its GID checked against the server's, which catches peers built from
+//! different tag definitions or hash schemes.
+//!
+//! Wire format: a LEB128 first ordinal and entry count, then per entry a
+//! LEB128 path length, the UTF-8 path and the 16 little-endian GID bytes.
+//!
+//! These ordinals are separate from [`TagOrdinals`]: those number the whole
+//! tag set in path order, so registering a tag shifts the ordinals of every
+//! tag after it, and clients already holding numbers would have to be
+//! renumbered. Dictionary ordinals are append-only, so an update is just the
+//! new entries.
+//!
+//! [`TagOrdinals`]: crate::frozen::TagOrdinals
+//!
+//! With Bevy, [`publish_tag_dictionary`] (server) and [`apply_tag_dictionary`]
+//! (client) move the bytes through [`TagDictionaryMessage`]s; the transport
+//! only has to forward them:
+//!
+//! ```ignore
+//! // Server
+//! app.add_message::<TagDictionaryMessage>()
+//!     .init_resource::<TagDictionary>()
+//!     .add_systems(Update, publish_tag_dictionary);
+//! // On connect, send the full dictionary
+//! send(client, dictionary.encode());
+//!
+//! // Client: write a TagDictionaryMessage for every received payload
+//! app.add_message::<TagDictionaryMessage>()
+//!     .add_message::<TagDictionaryError>()
+//!     .init_resource::<TagDictionary>()
+//!     .add_systems(PreUpdate, apply_tag_dictionary);
+//! ```
+
+use bevy::prelude::*;
+
+use crate::frozen::{read_varint, write_varint};
+use crate::hasher::GidMap;
+use crate::registry::NamespaceRegistry;
+use crate::GID;
+
+/// Ordinal numbering of dynamically registered tags, shared between a server
+/// and its clients.
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagDictionary {
+    /// Tags in ordinal order.
+    entries: Vec<(String, GID)>,
+    ordinals: GidMap<u32>,
+}
+
+/// One dictionary entry, as decoded from the wire.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DictionaryEntry {
+    pub ordinal: u32,
+    pub path: String,
+    pub gid: GID,
+}
+
+impl TagDictionary {
+    /// Create an empty dictionary.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Server side: number the dynamic tags of `registry` that have no
+    /// ordinal yet. Returns how many were added.
+    pub fn update(&mut self, registry: &NamespaceRegistry) -> usize {
+        let before = self.entries.len();
+        for entry in registry.entries().iter().filter(|e| e.is_dynamic) {
+            if !self.ordinals.contains_key(&entry.gid) {
+                self.push(entry.path.clone(), entry.gid);
+            }
+        }
+        self.entries.len() - before
+    }
+
+    /// Number of numbered tags; also the next ordinal.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Ordinal of `gid`, if numbered.
+    #[inline]
+    pub fn ordinal_of(&self, gid: GID) -> Option<u32> {
+        self.ordinals.get(&gid).copied()
+    }
+
+    /// GID with ordinal `ordinal`.
+    #[inline]
+    pub fn gid_of_ordinal(&self, ordinal: u32) -> Option<GID> {
+        self.entries.get(ordinal as usize).map(|&(_, gid)| gid)
+    }
+
+    /// Path with ordinal `ordinal`.
+    #[inline]
+    pub fn path_of_ordinal(&self, ordinal: u32) -> Option<&str> {
+        self.entries
+            .get(ordinal as usize)
+            .map(|(path, _)| path.as_str())
+    }
+
+    /// Encode the whole dictionary, for a joining client.
+    pub fn encode(&self) -> Vec<u8> {
+        self.encode_since(0)
+    }
+
+    /// Encode the entries from ordinal `first` on, for a client that already
+    /// has the ones before it.
+    pub fn encode_since(&self, first: u32) -> Vec<u8> {
+        let entries = self.entries.get(first as usize..).unwrap_or_default();
+        let mut out = Vec::new();
+        write_varint(&mut out, u64::from(first));
+        write_varint(&mut out, entries.len() as u64);
+        for (path, gid) in entries {
+            write_varint(&mut out, path.len() as u64);
+            out.extend_from_slice(path.as_bytes());
+            out.extend_from_slice(&gid.to_le_bytes());
+        }
+        out
+    }
+
+    /// Decode bytes produced by [`encode`](Self::encode) or
+    /// [`encode_since`](Self::encode_since).
+    pub fn decode(bytes: &[u8]) -> Result<Vec<DictionaryEntry>, String> {
+        let mut reader = bytes;
+        let first = read_varint(&mut reader).map_err(|e| e.to_string())?;
+        let count = read_varint(&mut reader).map_err(|e| e.to_string())?;
+        // Every entry takes at least 17 bytes
+        if count > (reader.len() / 17) as u64 {
+            return Err("unexpected end of tag dictionary".into());
+        }
+        let end = first
+            .checked_add(count)
+            .filter(|&end| end <= u64::from(u32::MAX))
+            .ok_or("tag dictionary ordinals are out of range")?;
+
+        let mut entries = Vec::with_capacity(count as usize);
+        for ordinal in first..end {
+            let len = read_varint(&mut reader).map_err(|e| e.to_string())?;
+            if len > reader.len() as u64 {
+                return Err("unexpected end of tag dictionary".into());
+            }
+            let (path, rest) = reader.split_at(len as usize);
+            let path = std::str::from_utf8(path)
+                .map_err(|_| format!("tag dictionary path {} is not valid UTF-8", ordinal))?;
+            let (gid, rest) = rest
+                .split_first_chunk::<16>()
+                .ok_or("unexpected end of tag dictionary")?;
+            entries.push(DictionaryEntry {
+                ordinal: ordinal as u32,
+                path: path.to_string(),
+                gid: GID::from_le_bytes(*gid),
+            });
+            reader = rest;
+        }
+        if !reader.is_empty() {
+            return Err(format!(
+                "{} trailing bytes after tag dictionary",
+                reader.len()
+            ));
+        }
+        Ok(entries)
+    }
+
+    /// Client side: register every entry in `registry` and number it.
+    ///
+    /// Entries this dictionary already has are checked and skipped, so
+    /// resending is harmless. Fails on a gap in the ordinals or when a path
+    /// hashes to a different GID than on the server; entries before the
+    /// failing one stay applied.
+    pub fn apply(
+        &mut self,
+        entries: &[DictionaryEntry],
+        registry: &mut NamespaceRegistry,
+    ) -> Result<(), String> {
+        for entry in entries {
+            let next = self.entries.len() as u32;
+            if entry.ordinal < next {
+                if self.entries[entry.ordinal as usize] != (entry.path.clone(), entry.gid) {
+                    return Err(format!(
+                        "tag dictionary ordinal {} is '{}', not '{}'",
+                        entry.ordinal, self.entries[entry.ordinal as usize].0, entry.path
+                    ));
+                }
+                continue;
+            }
+            if entry.ordinal > next {
+                return Err(format!(
+                    "tag dictionary is missing ordinals {}..{}",
+                    next, entry.ordinal
+                ));
+            }
+
+            let gid = registry.register(&entry.path)?;
+            if gid != entry.gid {
+                return Err(format!(
+                    "'{}' is {:#034x} here but {:#034x} on the server \
+                     (different tag definitions or GID scheme?)",
+                    entry.path, gid, entry.gid
+                ));
+            }
+            self.push(entry.path.clone(), gid);
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, path: String, gid: GID) {
+        self.ordinals.insert(gid, self.entries.len() as u32);
+        self.entries.push((path, gid));
+    }
+}
+
+// =============================================================================
+// Bevy systems
+// =============================================================================
+
+/// Encoded dictionary update, written by [`publish_tag_dictionary`] on the
+/// server and read by [`apply_tag_dictionary`] on the client.
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub struct TagDictionaryMessage(pub Vec<u8>);
+
+/// Server system: number newly registered dynamic tags and write their
+/// entries as one [`TagDictionaryMessage`] for connected clients.
+pub fn publish_tag_dictionary(
+    registry: Res<NamespaceRegistry>,
+    mut dictionary: ResMut<TagDictionary>,
+    mut messages: MessageWriter<TagDictionaryMessage>,
+) {
+    if !registry.is_changed() {
+        return;
+    }
+    let first = dictionary.len() as u32;
+    if dictionary.update(&registry) > 0 {
+        messages.write(TagDictionaryMessage(dictionary.encode_since(first)));
+    }
+}
+
+/// A [`TagDictionaryMessage`] the client could not decode or apply.
+#[derive(Message, Clone, Debug, PartialEq, Eq)]
+pub struct TagDictionaryError(pub String);
+
+/// Client system: decode and apply received [`TagDictionaryMessage`]s.
+///
+/// A malformed or mismatched payload is skipped and reported as a
+/// [`TagDictionaryError`] (and a `tracing` warning with that feature);
+/// later messages are still applied.
+pub fn apply_tag_dictionary(
+    mut messages: MessageReader<TagDictionaryMessage>,
+    mut errors: MessageWriter<TagDictionaryError>,
+    mut registry: ResMut<NamespaceRegistry>,
+    mut dictionary: ResMut<TagDictionary>,
+) {
+    for TagDictionaryMessage(bytes) in messages.read() {
+        let applied = TagDictionary::decode(bytes)
+            .and_then(|entries| dictionary.apply(&entries, &mut registry));
+        if let Err(error) = applied {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(%error, "skipped tag dictionary message");
+            errors.write(TagDictionaryError(error));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::NamespaceDef;
+
+    fn server() -> NamespaceRegistry {
+        let defs = &[NamespaceDef::new("Item", None)];
+        let mut registry = NamespaceRegistry::build(defs).unwrap();
+        registry.register("Item.Mod.Sword").unwrap();
+        registry
+    }
+
+    #[test]
+    fn late_join_and_incremental_updates() {
+        let mut registry = server();
+        let mut dictionary = TagDictionary::new();
+        assert_eq!(dictionary.update(&registry), 2);
+        assert_eq!(dictionary.path_of_ordinal(0), Some("Item.Mod"));
+
+        let mut client = NamespaceRegistry::build(&[NamespaceDef::new("Item", None)]).unwrap();
+        let mut client_dictionary = TagDictionary::new();
+        let entries = TagDictionary::decode(&dictionary.encode()).unwrap();
+        client_dictionary.apply(&entries, &mut client).unwrap();
+        assert_eq!(client_dictionary, dictionary);
+        assert!(client.gid_of("Item.Mod.Sword").is_some());
+
+        // Later registrations travel as a delta; resending is harmless
+        registry.register("Item.Mod.Shield").unwrap();
+        let first = dictionary.len() as u32;
+        assert_eq!(dictionary.update(&registry), 1);
+        let delta = TagDictionary::decode(&dictionary.encode_since(first)).unwrap();
+        assert_eq!(delta.len(), 1);
+        client_dictionary.apply(&delta, &mut client).unwrap();
+        client_dictionary.apply(&delta, &mut client).unwrap();
+
+        let shield = registry.gid_of("Item.Mod.Shield").unwrap();
+        assert_eq!(client_dictionary.ordinal_of(shield), Some(2));
+        assert_eq!(client_dictionary.gid_of_ordinal(2), Some(shield));
+    }
+
+    #[test]
+    fn rejects_gaps_mismatches_and_bad_bytes() {
+        let mut dictionary = TagDictionary::new();
+        dictionary.update(&server());
+        let bytes = dictionary.encode();
+
+        let mut client = NamespaceRegistry::new();
+        let mut gap = TagDictionary::decode(&dictionary.encode_since(1)).unwrap();
+        assert!(TagDictionary::new().apply(&gap, &mut client).is_err());
+
+        gap[0].ordinal = 0;
+        gap[0].gid ^= 1;
+        let err = TagDictionary::new().apply(&gap, &mut client).unwrap_err();
+        assert!(err.contains("on the server"), "{err}");
+
+        assert!(TagDictionary::decode(&bytes[..bytes.len() - 1]).is_err());
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(TagDictionary::decode(&trailing).is_err());
+    }
+
+    #[test]
+    fn systems_carry_updates_from_server_to_client() {
+        let mut server_app = App::new();
+        server_app
+            .add_message::<TagDictionaryMessage>()
+            .insert_resource(server())
+            .init_resource::<TagDictionary>()
+            .add_systems(Update, publish_tag_dictionary);
+        server_app.update();
+
+        let mut client_app = App::new();
+        client_app
+            .add_message::<TagDictionaryMessage>()
+            .add_message::<TagDictionaryError>()
+            .insert_resource(NamespaceRegistry::new())
+            .init_resource::<TagDictionary>()
+            .add_systems(Update, apply_tag_dictionary);
+
+        let sent: Vec<TagDictionaryMessage> = server_app
+            .world_mut()
+            .resource_mut::<Messages<TagDictionaryMessage>>()
+            .drain()
+            .collect();
+        assert_eq!(sent.len(), 1);
+        // A bad payload is reported and skipped, not fatal
+        client_app.world_mut().write_message(TagDictionaryMessage(vec![0xff]));
+        client_app.world_mut().write_message_batch(sent);
+        client_app.update();
+        assert_eq!(
+            client_app.world().resource::<Messages<TagDictionaryError>>().len(),
+            1
+        );
+
+        let client = client_app.world().resource::<NamespaceRegistry>();
+        assert!(client.gid_of("Item.Mod.Sword").is_some());
+        assert_eq!(
+            client_app.world().resource::<TagDictionary>(),
+            server_app.world().resource::<TagDictionary>()
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Building tag paths segment by segment.
+//!
+//! Dynamic registration code otherwise joins paths with `format!("{}.{}",
+//! a, b)`, which happily produces `"Combat..Attack"` or a ninth level.
+//! [`TagPath`] checks every segment as it is appended and keeps the hashed
+//! GID up to date along the way.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::hash::child_gid;
+use crate::layout::MAX_DEPTH;
+use crate::registry::NamespaceRegistry;
+use crate::traits::IntoGidWithRegistry;
+use crate::{GID, ROOT_GID};
+
+/// A validated dot-separated tag path.
+///
+/// ```
+/// use bevy_tag::{NamespaceRegistry, TagPath};
+///
+/// let attack = TagPath::new("Combat").child("Attack");
+/// assert_eq!(attack.as_str(), "Combat.Attack");
+///
+/// let mut registry = NamespaceRegistry::new();
+/// registry.register(attack.as_str()).unwrap();
+/// assert_eq!(attack.gid_in(&registry), Some(attack.gid()));
+/// assert!(TagPath::new("Combat").try_child("Bad.Segment").is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TagPath {
+    path: String,
+    depth: u8,
+    gid: GID,
+}
+
+impl TagPath {
+    /// A root path.
+    ///
+    /// # Panics
+    ///
+    /// If `root` is not a valid segment; see [`try_new`](Self::try_new).
+    pub fn new(root: &str) -> Self {
+        Self::try_new(root).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// A root path, or an error if `root` is empty or contains a `.`.
+    pub fn try_new(root: &str) -> Result<Self, String> {
+        check_segment(root)?;
+        Ok(Self {
+            path: root.to_string(),
+            depth: 0,
+            gid: child_gid(ROOT_GID, 0, root.as_bytes()),
+        })
+    }
+
+    /// This path with `segment` appended.
+    ///
+    /// # Panics
+    ///
+    /// If `segment` is not a valid segment or the path is already
+    /// [`MAX_DEPTH`] levels deep; see [`try_child`](Self::try_child).
+    pub fn child(self, segment: &str) -> Self {
+        self.try_child(segment).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// This path with `segment` appended, or an error if `segment` is empty,
+    /// contains a `.`, or would go deeper than [`MAX_DEPTH`] levels.
+    pub fn try_child(mut self, segment: &str) -> Result<Self, String> {
+        check_segment(segment)?;
+        let depth = self.depth + 1;
+        if depth as usize >= MAX_DEPTH {
+            return Err(format!(
+                "cannot append '{}' to '{}': paths are at most {} levels deep",
+                segment, self.path, MAX_DEPTH
+            ));
+        }
+        self.path.push('.');
+        self.path.push_str(segment);
+        self.gid = child_gid(self.gid, depth, segment.as_bytes());
+        self.depth = depth;
+        Ok(self)
+    }
+
+    /// The parent path, or `None` for a root.
+    pub fn parent(&self) -> Option<TagPath> {
+        let (parent, _) = self.path.rsplit_once('.')?;
+        parent.parse().ok()
+    }
+
+    /// Depth (0 = root).
+    #[inline]
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// The segments, root first.
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.path.split('.')
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /// GID from hashing the path. Differs from the registered GID for tags
+    /// with pinned segments; use [`gid_in`](Self::gid_in) for those.
+    #[inline]
+    pub fn gid(&self) -> GID {
+        self.gid
+    }
+
+    /// GID registered for this path in `registry`.
+    pub fn gid_in(&self, registry: &NamespaceRegistry) -> Option<GID> {
+        registry.gid_of(&self.path)
+    }
+}
+
+/// Resolves through the registry, so pinned tags get their registered GID.
+impl IntoGidWithRegistry for &TagPath {
+    fn into_gid_with(self, registry: &NamespaceRegistry) -> GID {
+        self.as_str().into_gid_with(registry)
+    }
+
+    fn try_into_gid_with(self, registry: &NamespaceRegistry) -> Result<GID, String> {
+        self.as_str().try_into_gid_with(registry)
+    }
+}
+
+fn check_segment(segment: &str) -> Result<(), String> {
+    if segment.is_empty() {
+        return Err("empty path segment is not allowed".into());
+    }
+    if segment.contains('.') {
+        return Err(format!("path segment '{}' contains '.'", segment));
+    }
+    Ok(())
+}
+
+impl FromStr for TagPath {
+    type Err = String;
+
+    /// Parse a whole dot-separated path, checking every segment.
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut segments = s.split('.');
+        let root = segments.next().unwrap_or("");
+        segments.try_fold(Self::try_new(root)?, |path, segment| {
+            path.try_child(segment)
+        })
+    }
+}
+
+impl fmt::Display for TagPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
+impl AsRef<str> for TagPath {
+    fn as_ref(&self) -> &str {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::hierarchical_gid;
+
+    #[test]
+    fn builds_and_hashes_incrementally() {
+        let path = TagPath::new("Combat").child("Melee").child("Heavy");
+        assert_eq!(path.as_str(), "Combat.Melee.Heavy");
+        assert_eq!(path.depth(), 2);
+        assert_eq!(
+            path.gid(),
+            hierarchical_gid(&[b"Combat", b"Melee", b"Heavy"])
+        );
+        assert_eq!(
+            path.segments().collect::<Vec<_>>(),
+            ["Combat", "Melee", "Heavy"]
+        );
+        assert_eq!(path.parent(), Some(TagPath::new("Combat").child("Melee")));
+        assert_eq!(TagPath::new("Combat").parent(), None);
+        assert_eq!("Combat.Melee.Heavy".parse(), Ok(path));
+    }
+
+    #[test]
+    fn rejects_bad_segments_and_depth() {
+        assert_eq!(
+            TagPath::try_new("").unwrap_err(),
+            "empty path segment is not allowed"
+        );
+        assert_eq!(
+            TagPath::new("A").try_child("B.C").unwrap_err(),
+            "path segment 'B.C' contains '.'"
+        );
+        assert!("A..B".parse::<TagPath>().is_err());
+
+        let deepest: TagPath = "L0.L1.L2.L3.L4.L5.L6.L7".parse().unwrap();
+        assert_eq!(
+            deepest.try_child("L8").unwrap_err(),
+            "cannot append 'L8' to 'L0.L1.L2.L3.L4.L5.L6.L7': paths are at most 8 levels deep"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "contains '.'")]
+    fn child_panics_on_invalid_segment() {
+        let _ = TagPath::new("A").child("B.C");
+    }
+}
@@ -78,6 +78,104 @@ impl<T: NamespaceTag + HasData> HasData for Redirect<T> {
     type Data = T::Data;
 }
 
+/// The declared path of an [`Alias`], supplied by a marker type.
+///
+/// Generated by `#[alias = "..."]` in the `namespace!` macro; implement it by
+/// hand only for aliases declared outside a namespace.
+pub trait AliasPath: Copy + 'static {
+    /// Full dot-separated path of the alias itself.
+    const PATH: &'static str;
+}
+
+/// A zero-cost wrapper giving tag `T` a second, permanent name.
+///
+/// Unlike [`Redirect<T>`], an alias is not a migration aid: it is never
+/// deprecated and keeps its own declared path.
+///
+/// | | `Redirect<T>` | `Alias<T, P>` |
+/// |---|---|---|
+/// | `GID` | `T::GID` | `T::GID` |
+/// | `PATH` | `T::PATH` (canonical) | `P::PATH` (the alias) |
+/// | `DEPTH` | `T::DEPTH` | depth of `P::PATH` |
+/// | Deprecated | always | only if marked |
+///
+/// Containers, queries and saved data only ever see the GID, so an alias and
+/// its target are interchangeable at runtime; only the path differs.
+///
+/// ```rust,ignore
+/// namespace! {
+///     pub mod Tags {
+///         Status { Stunned; }
+///         #[alias = "Status.Stunned"]
+///         Dazed;
+///     }
+/// }
+///
+/// assert_eq!(Tags::Dazed::Tag::STABLE_GID, Tags::Status::Stunned::GID);
+/// assert_eq!(Tags::Dazed::PATH, "Dazed");
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Alias<T: NamespaceTag, P: AliasPath>(PhantomData<(T, P)>);
+
+impl<T: NamespaceTag, P: AliasPath> Alias<T, P> {
+    /// The GID shared by the alias and its target.
+    pub const STABLE_GID: GID = T::GID;
+
+    /// Create a new alias instance.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+
+    /// Path of the tag this aliases.
+    pub const fn canonical_path() -> &'static str {
+        T::PATH
+    }
+}
+
+impl<T: NamespaceTag, P: AliasPath> Default for Alias<T, P> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: NamespaceTag, P: AliasPath> core::fmt::Debug for Alias<T, P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Alias<{} as {}>", T::PATH, P::PATH)
+    }
+}
+
+impl<T: NamespaceTag, P: AliasPath> NamespaceTag for Alias<T, P> {
+    /// The alias's own declared path.
+    const PATH: &'static str = P::PATH;
+
+    /// Depth of the alias path, which may differ from the target's.
+    const DEPTH: u8 = path_depth(P::PATH);
+
+    /// GID matches the target tag.
+    const GID: GID = T::GID;
+}
+
+impl<T: NamespaceTag + HasData, P: AliasPath> HasData for Alias<T, P> {
+    type Data = T::Data;
+}
+
+/// Number of `.` separators in `path`.
+const fn path_depth(path: &str) -> u8 {
+    let bytes = path.as_bytes();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'.' {
+            depth += 1;
+        }
+        i += 1;
+    }
+    depth
+}
+
 /// A compile-time namespace tag, generated by the `namespace!` macro.
 ///
 /// Each node in the namespace tree gets a zero-sized Tag type with
@@ -115,6 +213,62 @@ pub trait NamespaceTag: Copy + 'static {
     }
 }
 
+/// Static description of a tag, as yielded by the generated `iter()` functions.
+///
+/// ```rust,ignore
+/// for child in DamageTags::Magical::iter() {
+///     ui.option(child.display_name, child.gid);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TagDescriptor {
+    /// Full dot-separated path.
+    pub path: &'static str,
+    /// Stable hierarchical GID.
+    pub gid: GID,
+    /// Depth in the tree (0 = top-level).
+    pub depth: u8,
+    /// Human-readable name (defaults to the node name).
+    pub display_name: &'static str,
+}
+
+impl TagDescriptor {
+    /// Descriptor for a tag type, using its last path segment as display name.
+    pub const fn of<T: NamespaceTag>() -> Self {
+        Self {
+            path: T::PATH,
+            gid: T::GID,
+            depth: T::DEPTH,
+            display_name: last_segment(T::PATH),
+        }
+    }
+
+    /// Builder: override the display name.
+    pub const fn with_display_name(mut self, display_name: &'static str) -> Self {
+        self.display_name = display_name;
+        self
+    }
+
+    /// The node's own name (last path segment).
+    pub const fn name(&self) -> &'static str {
+        last_segment(self.path)
+    }
+}
+
+/// Last dot-separated segment of a path (const).
+const fn last_segment(path: &'static str) -> &'static str {
+    let bytes = path.as_bytes();
+    let mut i = bytes.len();
+    while i > 0 {
+        if bytes[i - 1] == b'.' {
+            let (_, tail) = path.split_at(i);
+            return tail;
+        }
+        i -= 1;
+    }
+    path
+}
+
 /// Convert to GID. Implemented for raw `GID` (passthrough) and all `NamespaceTag` types.
 pub trait IntoGid: Copy {
     fn into_gid(self) -> GID;
@@ -136,9 +290,23 @@ impl<T: NamespaceTag> IntoGid for T {
 
 /// Convert to GID using a specific registry instance.
 ///
-/// Useful for ECS integrations where the registry is a `Res<NamespaceRegistry>`.
+/// Useful for ECS integrations where the registry is a `Res<NamespaceRegistry>`,
+/// and for data-driven content that names tags by path.
 pub trait IntoGidWithRegistry: Copy {
+    /// # Panics
+    ///
+    /// If `self` is a path, [`TagPath`](crate::TagPath) or
+    /// [`TagPattern`](crate::TagPattern) that names no tag `registry` knows.
+    /// Use [`try_into_gid_with`](Self::try_into_gid_with) for paths read from
+    /// data files.
     fn into_gid_with(self, registry: &NamespaceRegistry) -> GID;
+
+    /// The GID, or an error if `self` names a tag `registry` doesn't know.
+    ///
+    /// Typed tags and raw GIDs always succeed.
+    fn try_into_gid_with(self, registry: &NamespaceRegistry) -> Result<GID, String> {
+        Ok(self.into_gid_with(registry))
+    }
 }
 
 impl IntoGidWithRegistry for GID {
@@ -156,6 +324,29 @@ impl<T: NamespaceTag> IntoGidWithRegistry for T {
     }
 }
 
+impl IntoGidWithRegistry for &str {
+    fn into_gid_with(self, registry: &NamespaceRegistry) -> GID {
+        self.try_into_gid_with(registry)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    fn try_into_gid_with(self, registry: &NamespaceRegistry) -> Result<GID, String> {
+        registry
+            .gid_of(self)
+            .ok_or_else(|| format!("tag path '{}' is not registered", self))
+    }
+}
+
+impl IntoGidWithRegistry for &String {
+    fn into_gid_with(self, registry: &NamespaceRegistry) -> GID {
+        self.as_str().into_gid_with(registry)
+    }
+
+    fn try_into_gid_with(self, registry: &NamespaceRegistry) -> Result<GID, String> {
+        self.as_str().try_into_gid_with(registry)
+    }
+}
+
 /// Convert a collection of items into a `Vec<GID>`.
 pub trait IntoGids {
     fn into_gids(self) -> Vec<GID>;
@@ -0,0 +1,224 @@
+//! JavaScript bindings for browser tools and level editors.
+//!
+//! Enabled by the `wasm` feature; build with `wasm-pack build --features wasm`.
+//! GIDs cross the boundary as `0x`-prefixed, 32-digit hex strings, the same
+//! format `tags_manifest.json` uses:
+//!
+//! ```js
+//! import { gidForPath, isDescendant, TagRegistry } from "bevy_tag";
+//!
+//! isDescendant(gidForPath("Item.Sword"), gidForPath("Item")); // true
+//!
+//! const tags = TagRegistry.fromManifest(await (await fetch("tags_manifest.json")).text());
+//! tags.contains("Item.Sword");  // validate a path typed into the editor
+//! tags.gidOf("Item.Blade");     // redirects resolve to their target's GID
+//! ```
+//!
+//! [`gid_for_path`] hashes like `namespace!`, so it can't see pinned GIDs or
+//! redirects; [`TagRegistry`] answers from the manifest and can.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use wasm_bindgen::prelude::*;
+
+use crate::hash::path_gid;
+use crate::layout::gid_is_descendant_of;
+use crate::GID;
+
+/// Hex GID of a dot-separated path, or `undefined` if the path is invalid.
+#[wasm_bindgen(js_name = gidForPath)]
+pub fn gid_for_path(path: &str) -> Option<String> {
+    path_gid(path).map(format_gid)
+}
+
+/// Is hex GID `candidate` equal to or a descendant of hex GID `ancestor`?
+#[wasm_bindgen(js_name = isDescendant)]
+pub fn is_descendant(candidate: &str, ancestor: &str) -> Result<bool, JsError> {
+    let candidate = parse_gid(candidate).map_err(|e| JsError::new(&e))?;
+    let ancestor = parse_gid(ancestor).map_err(|e| JsError::new(&e))?;
+    Ok(gid_is_descendant_of(candidate, ancestor))
+}
+
+/// Tags loaded from a `tags_manifest.json` written by `bevy-tag-build`.
+#[wasm_bindgen]
+#[derive(Clone, Debug, Default)]
+pub struct TagRegistry {
+    /// Every path, including deprecated ones and redirects
+    tags: HashMap<String, ManifestTag>,
+    /// Canonical path of each GID (redirects excluded)
+    paths: HashMap<GID, String>,
+}
+
+#[derive(Clone, Debug)]
+struct ManifestTag {
+    gid: Option<GID>,
+    deprecated: bool,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    tags: Vec<ManifestEntry>,
+}
+
+#[derive(Deserialize)]
+struct ManifestEntry {
+    path: String,
+    gid: Option<String>,
+    #[serde(default)]
+    deprecated: bool,
+    #[serde(default)]
+    redirect: Option<String>,
+}
+
+impl TagRegistry {
+    /// Parse manifest JSON.
+    pub fn from_manifest_str(json: &str) -> Result<Self, String> {
+        let manifest: Manifest =
+            serde_json::from_str(json).map_err(|e| format!("Invalid tag manifest: {}", e))?;
+        let mut registry = Self::default();
+        for entry in manifest.tags {
+            let gid = entry
+                .gid
+                .as_deref()
+                .map(parse_gid)
+                .transpose()
+                .map_err(|e| format!("Tag '{}': {}", entry.path, e))?;
+            if let (Some(gid), None) = (gid, &entry.redirect) {
+                registry.paths.insert(gid, entry.path.clone());
+            }
+            let deprecated = entry.deprecated;
+            registry
+                .tags
+                .insert(entry.path, ManifestTag { gid, deprecated });
+        }
+        Ok(registry)
+    }
+
+    fn gid(&self, path: &str) -> Option<GID> {
+        self.tags.get(path).and_then(|tag| tag.gid)
+    }
+}
+
+#[wasm_bindgen]
+impl TagRegistry {
+    /// Load the registry from the text of `tags_manifest.json`.
+    #[wasm_bindgen(js_name = fromManifest)]
+    pub fn from_manifest(json: &str) -> Result<TagRegistry, JsError> {
+        Self::from_manifest_str(json).map_err(|e| JsError::new(&e))
+    }
+
+    /// Number of paths, including deprecated ones and redirects.
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Is `path` a known tag or redirect?
+    pub fn contains(&self, path: &str) -> bool {
+        self.tags.contains_key(path)
+    }
+
+    /// Is `path` deprecated (explicitly, removed, or redirected)?
+    #[wasm_bindgen(js_name = isDeprecated)]
+    pub fn is_deprecated(&self, path: &str) -> bool {
+        self.tags.get(path).is_some_and(|tag| tag.deprecated)
+    }
+
+    /// Hex GID of `path`; a redirect has its target's GID.
+    #[wasm_bindgen(js_name = gidOf)]
+    pub fn gid_of(&self, path: &str) -> Option<String> {
+        self.gid(path).map(format_gid)
+    }
+
+    /// Canonical path of a hex GID.
+    #[wasm_bindgen(js_name = pathOf)]
+    pub fn path_of(&self, gid: &str) -> Option<String> {
+        let gid = parse_gid(gid).ok()?;
+        self.paths.get(&gid).cloned()
+    }
+
+    /// Is `path` equal to or under `ancestor`? False if either is unknown.
+    #[wasm_bindgen(js_name = isDescendant)]
+    pub fn is_descendant(&self, path: &str, ancestor: &str) -> bool {
+        match (self.gid(path), self.gid(ancestor)) {
+            (Some(path), Some(ancestor)) => gid_is_descendant_of(path, ancestor),
+            _ => false,
+        }
+    }
+
+    /// Every path, sorted.
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.tags.keys().cloned().collect();
+        paths.sort_unstable();
+        paths
+    }
+}
+
+fn format_gid(gid: GID) -> String {
+    format!("0x{:032x}", gid)
+}
+
+fn parse_gid(hex: &str) -> Result<GID, String> {
+    hex.strip_prefix("0x")
+        .or_else(|| hex.strip_prefix("0X"))
+        .and_then(|digits| GID::from_str_radix(digits, 16).ok())
+        .ok_or_else(|| format!("'{}' is not a hex GID", hex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "gid_scheme": "fnv1a64-mix-v1/21.18.16.16.14.14.13.13",
+        "module_name": "Tags",
+        "tags": [
+            { "path": "Item", "gid": "ITEM", "depth": 0, "parent": null,
+              "deprecated": false, "redirect": null },
+            { "path": "Item.Blade", "gid": "SWORD", "depth": 1, "parent": "Item",
+              "deprecated": true, "redirect": "Item.Sword" },
+            { "path": "Item.Sword", "gid": "SWORD", "depth": 1, "parent": "Item",
+              "deprecated": false, "redirect": null }
+        ]
+    }"#;
+
+    fn registry() -> TagRegistry {
+        let json = MANIFEST
+            .replace("ITEM", &gid_for_path("Item").unwrap())
+            .replace("SWORD", &gid_for_path("Item.Sword").unwrap());
+        TagRegistry::from_manifest_str(&json).unwrap()
+    }
+
+    #[test]
+    fn hashes_paths_like_the_macro() {
+        let sword = gid_for_path("Item.Sword").unwrap();
+        assert_eq!(sword.len(), 34);
+        assert_eq!(
+            parse_gid(&sword),
+            Ok(crate::hash::hierarchical_gid(&[b"Item", b"Sword"]))
+        );
+        assert_eq!(gid_for_path("Item..Sword"), None);
+        assert!(parse_gid("Item.Sword").is_err());
+    }
+
+    #[test]
+    fn loads_the_build_manifest() {
+        let tags = registry();
+        assert_eq!(tags.size(), 3);
+        assert!(tags.contains("Item.Blade"));
+        assert!(!tags.contains("Item.Axe"));
+        assert!(tags.is_deprecated("Item.Blade"));
+        assert_eq!(tags.gid_of("Item.Blade"), tags.gid_of("Item.Sword"));
+
+        let sword = tags.gid_of("Item.Sword").unwrap();
+        assert_eq!(tags.path_of(&sword).as_deref(), Some("Item.Sword"));
+        assert!(tags.is_descendant("Item.Blade", "Item"));
+        assert!(!tags.is_descendant("Item", "Item.Sword"));
+        assert!(!tags.is_descendant("Item.Axe", "Item"));
+        assert_eq!(tags.paths(), ["Item", "Item.Blade", "Item.Sword"]);
+
+        let error = TagRegistry::from_manifest_str(&MANIFEST.replace("\"ITEM\"", "\"Item\""));
+        assert_eq!(error.unwrap_err(), "Tag 'Item': 'Item' is not a hex GID");
+    }
+}
@@ -0,0 +1,49 @@
+//! Tests for `#[alias]` nodes: second names that keep their own path.
+
+use bevy_tag::bevy::TagContainer;
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Status {
+            Stunned;
+            #[alias = "Status.Stunned"]
+            Dazed;
+        }
+        Legacy {
+            #[alias = "Status.Stunned"]
+            Stun;
+        }
+    }
+}
+
+#[test]
+fn test_alias_shares_gid_but_keeps_path() {
+    assert_eq!(Tags::Legacy::Stun::GID, Tags::Status::Stunned::GID);
+    assert_eq!(
+        Tags::Legacy::Stun::Tag::STABLE_GID,
+        Tags::Status::Stunned::GID
+    );
+    assert_eq!(Tags::Legacy::Stun::PATH, "Legacy.Stun");
+    assert_eq!(Tags::Status::Dazed::PATH, "Status.Dazed");
+    assert_eq!(Tags::Legacy::Stun::DEPTH, 1);
+    assert_eq!(Tags::Legacy::Stun::Tag::canonical_path(), "Status.Stunned");
+}
+
+#[test]
+fn test_alias_is_interchangeable_at_runtime() {
+    let mut container = TagContainer::new();
+    container.insert(Tags::Legacy::Stun::Tag::new().into_gid());
+    assert!(container.has(Tags::Status::Stunned::GID));
+    assert_eq!(
+        Tags::lookup("Legacy.Stun"),
+        Some(Tags::Status::Stunned::GID)
+    );
+}
+
+#[test]
+fn test_alias_excluded_from_definitions() {
+    let paths: Vec<_> = Tags::DEFINITIONS.iter().map(|d| d.path).collect();
+    assert_eq!(paths, vec!["Status", "Status.Stunned", "Legacy"]);
+}
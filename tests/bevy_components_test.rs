@@ -0,0 +1,34 @@
+//! Tests for the `bevy_components` macro option.
+
+use bevy::prelude::*;
+use bevy_tag::NamespaceTag;
+use bevy_tag_macro::namespace;
+
+namespace!(bevy_components; pub mod Tags {
+    Status {
+        Stunned;
+        Burning;
+    }
+});
+
+#[test]
+fn test_tags_are_components() {
+    let mut world = World::new();
+    let stunned = world.spawn(Tags::Status::Stunned::Tag).id();
+    world.spawn(Tags::Status::Burning::Tag);
+
+    let mut query = world.query_filtered::<Entity, With<Tags::Status::Stunned::Tag>>();
+    let matched: Vec<Entity> = query.iter(&world).collect();
+    assert_eq!(matched, vec![stunned]);
+}
+
+#[test]
+fn test_tags_are_reflect() {
+    fn path_of_reflected<T: Reflect + TypePath + NamespaceTag>(value: &T) -> &'static str {
+        assert!(value.as_partial_reflect().represents::<T>());
+        T::PATH
+    }
+
+    assert_eq!(path_of_reflected(&Tags::Status::Burning::Tag), "Status.Burning");
+    assert!(Tags::Status::Tag::type_path().ends_with("Tag"));
+}
@@ -0,0 +1,65 @@
+//! Tests for `#[bundle]` marker + data bundles.
+
+use bevy::ecs::world::CommandQueue;
+use bevy::prelude::*;
+use bevy_tag_macro::namespace;
+use serde::{Deserialize, Serialize};
+
+#[derive(Component, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BurnDamage {
+    pub damage_per_second: f32,
+}
+
+#[derive(Component, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FreezeTimer {
+    pub seconds: f32,
+}
+
+impl Default for FreezeTimer {
+    fn default() -> Self {
+        Self { seconds: 3.0 }
+    }
+}
+
+namespace!(bevy_components; pub mod Tags {
+    Status {
+        #[bundle]
+        Burning<crate::BurnDamage>;
+        #[bundle]
+        Frozen<crate::FreezeTimer>;
+        Stunned;
+    }
+});
+
+#[test]
+fn test_bundle_contains_marker_and_default_data() {
+    let mut world = World::new();
+    let entity = world.spawn(Tags::Status::Frozen::TagBundle::default()).id();
+
+    assert!(world.entity(entity).contains::<Tags::Status::Frozen::Tag>());
+    assert_eq!(world.get::<FreezeTimer>(entity), Some(&FreezeTimer { seconds: 3.0 }));
+}
+
+#[test]
+fn test_bundle_with_custom_data() {
+    let mut world = World::new();
+    let bundle = Tags::Status::Burning::TagBundle::new(BurnDamage {
+        damage_per_second: 5.0,
+    });
+    let entity = world.spawn(bundle).id();
+    assert_eq!(world.get::<BurnDamage>(entity).unwrap().damage_per_second, 5.0);
+}
+
+#[test]
+fn test_spawn_helper() {
+    let mut world = World::new();
+    let mut queue = CommandQueue::default();
+    let entity = {
+        let mut commands = Commands::new(&mut queue, &world);
+        Tags::Status::Burning::spawn(&mut commands).id()
+    };
+    queue.apply(&mut world);
+
+    assert!(world.entity(entity).contains::<Tags::Status::Burning::Tag>());
+    assert_eq!(world.get::<BurnDamage>(entity), Some(&BurnDamage::default()));
+}
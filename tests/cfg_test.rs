@@ -0,0 +1,41 @@
+//! Tests for `#[cfg(...)]`-gated nodes.
+
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Debug {
+            #[cfg(any())]
+            GodMode {
+                Invulnerable;
+            }
+            #[cfg(all())]
+            NoClip;
+        }
+        Item;
+    }
+}
+
+#[test]
+fn test_disabled_nodes_are_absent() {
+    assert_eq!(Tags::Debug::CHILD_PATHS, ["Debug.NoClip"]);
+    assert_eq!(Tags::Debug::CHILD_GIDS, [Tags::Debug::NoClip::GID]);
+    assert_eq!(Tags::Debug::VARIANT_COUNT, 1);
+    assert_eq!(Tags::Debug::CHILDREN.len(), 1);
+}
+
+#[test]
+fn test_disabled_nodes_are_not_registered() {
+    assert_eq!(Tags::NODE_COUNT, 3);
+    assert!(Tags::lookup("Debug.NoClip").is_some());
+    assert!(Tags::lookup("Debug.GodMode").is_none());
+    assert!(Tags::lookup("Debug.GodMode.Invulnerable").is_none());
+}
+
+#[test]
+fn test_gid_unaffected_by_gating() {
+    assert_eq!(
+        Tags::Debug::NoClip::GID,
+        bevy_tag::hierarchical_gid(&[b"Debug", b"NoClip"])
+    );
+}
@@ -0,0 +1,55 @@
+//! Tests for the generated `CHILD_GIDS` / `CHILD_PATHS` arrays.
+
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Damage {
+            Fire;
+            Ice;
+            #[deprecated]
+            Poison;
+            #[redirect = "Damage.Fire"]
+            Burn;
+        }
+        Status;
+    }
+}
+
+type Handler = fn(u32) -> u32;
+
+bevy_tag::assert_covers_children!(Tags::Damage, [Ice, Fire, Poison]);
+
+const HANDLERS: [Handler; Tags::Damage::CHILD_GIDS.len()] = [|x| x * 2, |x| x / 2, |x| x + 1];
+
+#[test]
+fn test_child_arrays_in_declaration_order() {
+    assert_eq!(
+        Tags::Damage::CHILD_GIDS,
+        [Tags::Damage::Fire::GID, Tags::Damage::Ice::GID, {
+            #[allow(deprecated)]
+            Tags::Damage::Poison::GID
+        }]
+    );
+    assert_eq!(
+        Tags::Damage::CHILD_PATHS,
+        ["Damage.Fire", "Damage.Ice", "Damage.Poison"]
+    );
+}
+
+#[test]
+fn test_root_and_leaf_arrays() {
+    assert_eq!(Tags::CHILD_PATHS, ["Damage", "Status"]);
+    assert!(Tags::Status::CHILD_GIDS.is_empty());
+    assert!(Tags::Damage::Fire::CHILD_PATHS.is_empty());
+}
+
+#[test]
+fn test_dispatch_by_child_index() {
+    const ICE: Option<usize> = Tags::Damage::child_index(Tags::Damage::Ice::GID);
+    assert_eq!(ICE, Some(1));
+
+    let handler = HANDLERS[Tags::Damage::child_index(Tags::Damage::Fire::GID).unwrap()];
+    assert_eq!(handler(10), 20);
+    assert_eq!(Tags::Damage::child_index(Tags::Status::GID), None);
+}
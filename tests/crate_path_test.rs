@@ -0,0 +1,32 @@
+//! Tests for overriding the bevy-tag path with `crate = ...`.
+
+/// Stand-in for a facade crate re-exporting bevy-tag.
+mod engine {
+    pub mod tags {
+        pub use bevy_tag::*;
+    }
+}
+
+bevy_tag::namespace!(crate = crate::engine::tags, bevy_components; pub mod Tags {
+    Movement {
+        Idle;
+    }
+});
+
+#[bevy_tag::namespace_mod(crate = crate::engine::tags)]
+pub mod ModTags {
+    pub mod Movement {
+        pub mod Idle {}
+    }
+}
+
+#[test]
+fn test_generated_code_uses_facade_path() {
+    use engine::tags::NamespaceTag;
+
+    assert_eq!(<Tags::Movement::Idle::Tag as NamespaceTag>::PATH, "Movement.Idle");
+    assert_eq!(Tags::Movement::Idle::GID, ModTags::Movement::Idle::GID);
+
+    let reg = engine::tags::NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    assert!(reg.contains("Movement.Idle"));
+}
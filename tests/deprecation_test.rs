@@ -0,0 +1,63 @@
+//! Tests for structured `#[deprecated(since = "...", note = "...")]` nodes.
+
+#![allow(deprecated)]
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Combat {
+            Strike;
+            #[deprecated(since = "0.2.0", note = "use Combat.Strike")]
+            Attack;
+            #[deprecated(since = "0.3.0")]
+            Parry;
+            #[deprecated]
+            Dodge;
+            #[deprecated(note = "renamed", since = "0.4.0")]
+            #[redirect = "Combat.Strike"]
+            Hit;
+        }
+    }
+}
+
+fn def(path: &str) -> &'static NamespaceDef {
+    Tags::DEFINITIONS.iter().find(|d| d.path == path).unwrap()
+}
+
+#[test]
+fn test_deprecation_recorded_in_definitions() {
+    assert_eq!(
+        def("Combat.Attack").deprecated,
+        Some(Deprecation {
+            since: Some("0.2.0"),
+            note: Some("use Combat.Strike"),
+        })
+    );
+    assert_eq!(
+        def("Combat.Parry").deprecated,
+        Some(Deprecation {
+            since: Some("0.3.0"),
+            note: None,
+        })
+    );
+    assert_eq!(def("Combat.Dodge").deprecated, Some(Deprecation::default()));
+    assert_eq!(def("Combat.Strike").deprecated, None);
+}
+
+#[test]
+fn test_deprecation_available_at_runtime() {
+    let reg = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    assert!(reg.is_deprecated(Tags::Combat::Attack::GID));
+    assert_eq!(
+        reg.deprecation_of(Tags::Combat::Parry::GID).unwrap().since,
+        Some("0.3.0")
+    );
+    assert!(!reg.is_deprecated(Tags::Combat::Strike::GID));
+}
+
+#[test]
+fn test_deprecated_redirect_still_resolves() {
+    assert_eq!(Tags::Combat::Hit::GID, Tags::Combat::Strike::GID);
+}
@@ -0,0 +1,61 @@
+//! Tests for `#[display_name]` and `#[description]` attributes.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Combat {
+            #[display_name = "Heavy Attack"]
+            #[description = "A slow, powerful swing"]
+            HeavyAttack;
+
+            #[damage = 10]
+            Jab;
+        }
+    }
+}
+
+#[test]
+fn test_display_name_consts() {
+    assert_eq!(Tags::Combat::HeavyAttack::Tag::DISPLAY_NAME, "Heavy Attack");
+    assert_eq!(
+        Tags::Combat::HeavyAttack::Tag::DESCRIPTION,
+        "A slow, powerful swing"
+    );
+
+    // Defaults: node name and empty description
+    assert_eq!(Tags::Combat::Jab::Tag::DISPLAY_NAME, "Jab");
+    assert_eq!(Tags::Combat::Jab::Tag::DESCRIPTION, "");
+
+    // Regular metadata still works alongside
+    assert_eq!(Tags::Combat::Jab::Tag::DAMAGE, 10);
+}
+
+#[test]
+fn test_display_name_in_definitions() {
+    let def = Tags::DEFINITIONS
+        .iter()
+        .find(|d| d.path == "Combat.HeavyAttack")
+        .unwrap();
+    assert_eq!(def.display_name, Some("Heavy Attack"));
+    assert_eq!(def.description, Some("A slow, powerful swing"));
+
+    let jab = Tags::DEFINITIONS.iter().find(|d| d.path == "Combat.Jab").unwrap();
+    assert_eq!(jab.display_name, None);
+}
+
+#[test]
+fn test_display_name_in_registry() {
+    let registry = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+
+    assert_eq!(
+        registry.display_name_of(Tags::Combat::HeavyAttack::GID),
+        Some("Heavy Attack")
+    );
+    assert_eq!(
+        registry.description_of(Tags::Combat::HeavyAttack::GID),
+        Some("A slow, powerful swing")
+    );
+    assert_eq!(registry.display_name_of(Tags::Combat::Jab::GID), Some("Jab"));
+}
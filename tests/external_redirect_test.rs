@@ -0,0 +1,54 @@
+//! Tests for redirects whose target lives in another namespace.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+/// Stand-in for a namespace owned by another crate.
+mod inventory {
+    use bevy_tag_macro::namespace;
+
+    namespace! {
+        pub mod Tags {
+            Weapon {
+                Blade;
+            }
+        }
+    }
+}
+
+namespace! {
+    pub mod Tags {
+        Item {
+            #[redirect(type = crate::inventory::Tags::Weapon::Blade::Tag)]
+            Sword;
+
+            #[deprecated(note = "moved to the inventory crate")]
+            #[redirect(type = crate::inventory::Tags::Weapon::Tag)]
+            Weapon;
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_external_redirect_uses_target_identity() {
+    assert_eq!(Tags::Item::Sword::GID, inventory::Tags::Weapon::Blade::GID);
+    assert_eq!(Tags::Item::Sword::PATH, "Weapon.Blade");
+    assert_eq!(Tags::Item::Weapon::GID, inventory::Tags::Weapon::GID);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_external_redirect_type() {
+    let _: Redirect<inventory::Tags::Weapon::Blade::Tag> = Tags::Item::Sword::Tag::new();
+    assert_eq!(
+        <Tags::Item::Sword::Tag as NamespaceTag>::PATH,
+        inventory::Tags::Weapon::Blade::PATH
+    );
+}
+
+#[test]
+fn test_external_redirect_excluded_from_definitions() {
+    let paths: Vec<_> = Tags::DEFINITIONS.iter().map(|d| d.path).collect();
+    assert_eq!(paths, vec!["Item"]);
+}
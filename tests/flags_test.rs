@@ -0,0 +1,47 @@
+//! Tests for `#[flags(...)]` category flags.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+pub const LOOT: TagFlags = TagFlags::user(0);
+
+namespace! {
+    pub mod Tags {
+        Fx {
+            #[flags(Cosmetic | DesignerVisible)]
+            Sparkle;
+            #[flags(Cosmetic)]
+            Trail;
+        }
+        Item {
+            #[flags(Replicated | crate::LOOT)]
+            Drop;
+            Bag;
+        }
+    }
+}
+
+#[test]
+fn test_flags_const() {
+    assert_eq!(
+        Tags::Fx::Sparkle::Tag::FLAGS,
+        TagFlags::COSMETIC | TagFlags::DESIGNER_VISIBLE
+    );
+    assert!(Tags::Item::Drop::Tag::FLAGS.contains(LOOT));
+    assert!(Tags::Item::Bag::Tag::FLAGS.is_empty());
+}
+
+#[test]
+fn test_registry_filters_by_flag() {
+    let reg = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+
+    assert_eq!(
+        reg.gids_with_flag(TagFlags::COSMETIC),
+        vec![Tags::Fx::Sparkle::GID, Tags::Fx::Trail::GID]
+    );
+    assert_eq!(reg.gids_with_flag(LOOT), vec![Tags::Item::Drop::GID]);
+    assert_eq!(
+        reg.flags_of(Tags::Item::Drop::GID),
+        Some(TagFlags::REPLICATED | LOOT)
+    );
+}
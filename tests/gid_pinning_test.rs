@@ -0,0 +1,72 @@
+//! Tests for `#[gid = ...]` and `#[segment = ...]` GID pinning.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+/// Externally assigned ID for the `Legacy` root (depth 0, segment 0x1234).
+const LEGACY_GID: GID = bevy_tag::gid_with_segment(ROOT_GID, 0, 0x1234);
+
+namespace! {
+    pub mod Tags {
+        #[gid = crate::LEGACY_GID]
+        Legacy {
+            Sword;
+
+            #[segment = 0x2A]
+            Shield;
+        }
+
+        #[segment = 0x77]
+        Pinned;
+
+        Hashed {
+            Child;
+        }
+
+        #[deprecated(note = "gone")]
+        Old {
+            Child;
+        }
+    }
+}
+
+#[test]
+fn test_pinned_gid_is_used() {
+    assert_eq!(Tags::Legacy::GID, LEGACY_GID);
+    assert_eq!(segment_of(Tags::Pinned::GID, 0), 0x77);
+    assert_eq!(depth_of(Tags::Pinned::GID), 0);
+}
+
+#[test]
+fn test_children_inherit_pinned_prefix() {
+    assert!(gid_is_descendant_of(Tags::Legacy::Sword::GID, Tags::Legacy::GID));
+    assert!(gid_is_descendant_of(Tags::Legacy::Shield::GID, Tags::Legacy::GID));
+    assert_eq!(segment_of(Tags::Legacy::Shield::GID, 1), 0x2A);
+    assert_eq!(parent_of(Tags::Legacy::Sword::GID), Some(Tags::Legacy::GID));
+}
+
+#[test]
+fn test_unpinned_gids_unchanged() {
+    assert_eq!(Tags::Hashed::GID, hierarchical_gid(&[b"Hashed"]));
+    assert_eq!(
+        Tags::Hashed::Child::GID,
+        hierarchical_gid(&[b"Hashed", b"Child"])
+    );
+}
+
+#[test]
+fn test_registry_matches_pinned_consts() {
+    let registry = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+
+    assert_eq!(registry.gid_of("Legacy"), Some(Tags::Legacy::GID));
+    assert_eq!(registry.gid_of("Legacy.Sword"), Some(Tags::Legacy::Sword::GID));
+    assert_eq!(registry.gid_of("Legacy.Shield"), Some(Tags::Legacy::Shield::GID));
+    assert_eq!(registry.gid_of("Pinned"), Some(Tags::Pinned::GID));
+    assert_eq!(registry.path_of(LEGACY_GID), Some("Legacy"));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_deprecated_parent_children() {
+    assert!(gid_is_descendant_of(Tags::Old::Child::GID, Tags::Old::GID));
+}
@@ -0,0 +1,70 @@
+//! Known path → GID vectors.
+//!
+//! GIDs end up in save files, network packets and lock files, so they must be
+//! identical on every platform and release. If one of these tests fails, the
+//! hash or bit layout changed: bump `HASH_VERSION` or `LAYOUT_VERSION`, update
+//! `GID_SCHEME` here and in `bevy-tag-build`, and ship a migration.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+const VECTORS: &[(&str, GID)] = &[
+    ("A", 0x00000000000000000000000000079da1),
+    ("Item", 0x000000000000000000000000000005f4),
+    ("Item.Weapon.Sword", 0x40000000000000000044e2bbecc005f4),
+    ("Movement.Idle", 0x2000000000000000000000302d03639e),
+    ("Combat.Melee.Sword.Heavy", 0x60000000000000063544e2ca2ef079c7),
+    ("L0.L1.L2.L3.L4.L5.L6.L7", 0xe6843ebf2d5e5ef8ac7e75bf87d5e171),
+];
+
+namespace! {
+    pub mod Tags {
+        Item {
+            Weapon {
+                Sword;
+            }
+        }
+        Movement {
+            Idle;
+        }
+    }
+}
+
+fn hash_path(path: &str) -> GID {
+    let segments: Vec<&[u8]> = path.split('.').map(str::as_bytes).collect();
+    hierarchical_gid(&segments)
+}
+
+#[test]
+fn test_hash_matches_vectors() {
+    for &(path, gid) in VECTORS {
+        assert_eq!(hash_path(path), gid, "{}", path);
+    }
+}
+
+#[test]
+fn test_registration_matches_vectors() {
+    let mut registry = NamespaceRegistry::new();
+    for &(path, gid) in VECTORS {
+        assert_eq!(registry.register(path), Ok(gid), "{}", path);
+    }
+}
+
+#[test]
+fn test_macro_matches_vectors() {
+    assert_eq!(Tags::Item::GID, 0x000000000000000000000000000005f4);
+    assert_eq!(Tags::Item::Weapon::Sword::GID, 0x40000000000000000044e2bbecc005f4);
+    assert_eq!(Tags::Movement::Idle::GID, 0x2000000000000000000000302d03639e);
+}
+
+#[test]
+fn test_scheme_names_versions_and_widths() {
+    let widths: Vec<String> = LEVEL_WIDTHS.iter().map(u8::to_string).collect();
+    assert_eq!(
+        GID_SCHEME,
+        format!("fnv1a64-mix-v{}/{}", HASH_VERSION, widths.join("."))
+    );
+    // A layout change without a width change (e.g. moving the depth bits)
+    // must bump LAYOUT_VERSION and be reflected in the scheme by hand
+    assert_eq!(LAYOUT_VERSION, 1);
+}
@@ -0,0 +1,124 @@
+//! Tests for mounting another namespace's `DEFINITIONS` with `include_defs!`.
+
+use bevy_tag::*;
+use bevy_tag_macro::{namespace, namespace_mod};
+
+/// Stand-in for an engine crate's base tags.
+mod engine {
+    use bevy_tag_macro::namespace;
+
+    namespace! {
+        pub mod Tags {
+            #[display_name = "Weapon"]
+            Weapon {
+                Blade;
+                #[flags(Replicated)]
+                Bow;
+            }
+            Status;
+        }
+    }
+}
+
+namespace! {
+    pub mod Tags {
+        Game {
+            Engine {
+                include_defs!(crate::engine::Tags::DEFINITIONS);
+            }
+            Local;
+        }
+    }
+}
+
+#[namespace_mod]
+pub mod ModTags {
+    pub mod Base {
+        include_defs!(crate::engine::Tags::DEFINITIONS);
+    }
+}
+
+#[test]
+fn test_mounted_paths_are_re_rooted() {
+    let paths: Vec<_> = Tags::DEFINITIONS.iter().map(|d| d.path).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "Game",
+            "Game.Engine",
+            "Game.Local",
+            "Game.Engine.Weapon",
+            "Game.Engine.Weapon.Blade",
+            "Game.Engine.Weapon.Bow",
+            "Game.Engine.Status",
+        ]
+    );
+    assert_eq!(Tags::NODE_COUNT, 7);
+
+    let blade = Tags::DEFINITIONS
+        .iter()
+        .find(|d| d.path == "Game.Engine.Weapon.Blade")
+        .unwrap();
+    assert_eq!(blade.parent, Some("Game.Engine.Weapon"));
+    let weapon = Tags::DEFINITIONS
+        .iter()
+        .find(|d| d.path == "Game.Engine.Weapon")
+        .unwrap();
+    assert_eq!(weapon.parent, Some("Game.Engine"));
+    assert_eq!(weapon.display_name, Some("Weapon"));
+}
+
+#[test]
+fn test_mounted_gids_hash_under_the_mount_point() {
+    const BLADE: Option<GID> = Tags::lookup("Game.Engine.Weapon.Blade");
+    assert_eq!(
+        BLADE,
+        Some(hierarchical_gid(&[b"Game", b"Engine", b"Weapon", b"Blade"]))
+    );
+    assert_ne!(BLADE, Some(engine::Tags::Weapon::Blade::GID));
+    assert_eq!(Tags::lookup("Game.Local"), Some(Tags::Game::Local::GID));
+    assert!(is_sorted_by_path(Tags::PATH_TABLE));
+}
+
+#[test]
+fn test_static_registry_includes_mounts() {
+    let bow = Tags::STATIC_REGISTRY
+        .gid_of("Game.Engine.Weapon.Bow")
+        .unwrap();
+    assert!(Tags::STATIC_REGISTRY.is_descendant_of(bow, Tags::Game::Engine::GID));
+    assert_eq!(
+        Tags::STATIC_REGISTRY.flags_of(bow),
+        Some(TagFlags::REPLICATED)
+    );
+}
+
+#[test]
+fn test_registry_accepts_mounted_definitions() {
+    let registry = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    assert_eq!(
+        registry.gid_of("Game.Engine.Status"),
+        Tags::lookup("Game.Engine.Status")
+    );
+}
+
+#[test]
+fn test_namespace_mod_mounts() {
+    assert_eq!(
+        ModTags::lookup("Base.Weapon.Blade"),
+        Some(hierarchical_gid(&[b"Base", b"Weapon", b"Blade"]))
+    );
+    assert_eq!(ModTags::NODE_COUNT, 5);
+}
+
+// Mount points themselves are rejected, since their mounted roots are not
+// in CHILD_GIDS
+bevy_tag::assert_covers_children!(Tags::Game, [Engine, Local]);
+
+#[test]
+fn test_mount_points_are_flagged() {
+    assert_eq!(
+        (Tags::Game::Engine::HAS_MOUNTS, Tags::Game::HAS_MOUNTS),
+        (true, false)
+    );
+    assert_eq!(Tags::Game::Engine::VARIANT_COUNT, 0);
+}
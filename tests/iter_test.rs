@@ -0,0 +1,49 @@
+//! Tests for the strum-style `VARIANT_COUNT` / `iter()` / `name()` helpers.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod DamageTags {
+        Physical {
+            Slash;
+            Pierce;
+        }
+        Magical {
+            Fire;
+            #[display_name = "Frost"]
+            Ice;
+            Arcane;
+        }
+    }
+}
+
+#[test]
+fn test_variant_count() {
+    assert_eq!(DamageTags::VARIANT_COUNT, 2);
+    assert_eq!(DamageTags::Magical::VARIANT_COUNT, 3);
+    assert_eq!(DamageTags::Magical::Fire::VARIANT_COUNT, 0);
+}
+
+#[test]
+fn test_iter_children() {
+    let children: Vec<TagDescriptor> = DamageTags::Magical::iter().collect();
+    let paths: Vec<_> = children.iter().map(|c| c.path).collect();
+    assert_eq!(paths, ["Magical.Fire", "Magical.Ice", "Magical.Arcane"]);
+
+    let ice = children[1];
+    assert_eq!(ice.gid, DamageTags::Magical::Ice::GID);
+    assert_eq!(ice.depth, 1);
+    assert_eq!(ice.display_name, "Frost");
+    assert_eq!(ice.name(), "Ice");
+}
+
+#[test]
+fn test_tag_name() {
+    assert_eq!(DamageTags::Magical::Ice::Tag::name(), "Ice");
+    assert_eq!(DamageTags::Physical::Tag::NAME, "Physical");
+    assert_eq!(
+        TagDescriptor::of::<DamageTags::Physical::Slash::Tag>().display_name,
+        "Slash"
+    );
+}
@@ -0,0 +1,33 @@
+//! Tests for the generated per-level statistics.
+
+use bevy_tag::LEVEL_WIDTHS;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Movement {
+            Idle;
+            Running;
+            Jumping;
+        }
+        Combat {
+            Attack {
+                Light;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_nodes_and_widths_per_level() {
+    assert_eq!(Tags::NODES_PER_LEVEL, [2, 4, 1]);
+    assert_eq!(Tags::MAX_WIDTH_USED, [2, 3, 1]);
+    assert_eq!(Tags::NODES_PER_LEVEL.iter().sum::<usize>(), Tags::NODE_COUNT);
+}
+
+#[test]
+fn test_occupancy_percent() {
+    let expected = 3.0 / (1u64 << LEVEL_WIDTHS[1]) as f64 * 100.0;
+    assert!((Tags::LEVEL_OCCUPANCY_PERCENT[1] - expected).abs() < 1e-12);
+    assert!(Tags::LEVEL_OCCUPANCY_PERCENT.iter().all(|&p| p < 25.0));
+}
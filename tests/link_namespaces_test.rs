@@ -0,0 +1,73 @@
+//! Tests for `link_namespaces!` and `NamespaceRegistry::build_many`.
+
+use bevy_tag::{link_namespaces, NamespaceDef, NamespaceRegistry};
+use bevy_tag_macro::namespace;
+
+mod combat {
+    bevy_tag_macro::namespace! {
+        pub mod Tags {
+            Item { Sword; }
+            Status { Stunned; }
+        }
+    }
+}
+
+mod ui {
+    bevy_tag_macro::namespace! {
+        pub mod Tags {
+            Item { Shield; }
+            Menu;
+        }
+    }
+}
+
+namespace! {
+    pub mod Local {
+        Item { Sword; }
+    }
+}
+
+#[test]
+fn test_link_merges_shared_roots() {
+    let registry = link_namespaces!(combat::Tags, ui::Tags, Local).unwrap();
+    assert_eq!(registry.len(), 6);
+    assert_eq!(
+        registry.gid_of("Item.Sword"),
+        Some(combat::Tags::Item::Sword::GID)
+    );
+    assert_eq!(
+        registry.gid_of("Item.Shield"),
+        Some(ui::Tags::Item::Shield::GID)
+    );
+    assert_eq!(registry.gid_of("Menu"), Some(ui::Tags::Menu::GID));
+}
+
+#[test]
+fn test_build_many_reports_conflicts() {
+    // Same path, different pinned GIDs
+    let a = [NamespaceDef::new("Legacy", None).with_gid(bevy_tag::gid_with_segment(0, 0, 1))];
+    let b = [NamespaceDef::new("Legacy", None).with_gid(bevy_tag::gid_with_segment(0, 0, 2))];
+    let err = NamespaceRegistry::build_many(&[&a, &b]).unwrap_err();
+    assert!(err.contains("defined differently"), "{err}");
+
+    // Same path and GID, but different metadata
+    let a = [NamespaceDef::new("Shared", None).with_display_name("Shared")];
+    let b = [NamespaceDef::new("Shared", None).with_display_name("Common")];
+    let err = NamespaceRegistry::build_many(&[&a, &b]).unwrap_err();
+    assert!(err.contains("display_name differs"), "{err}");
+    let b = [NamespaceDef::new("Shared", None)
+        .with_display_name("Shared")
+        .with_data_type("f32")];
+    let err = NamespaceRegistry::build_many(&[&a, &b]).unwrap_err();
+    assert!(err.contains("data_type differs"), "{err}");
+
+    // Different paths hashing to one GID
+    let a = [NamespaceDef::new("B5bWGqX", None)];
+    let b = [NamespaceDef::new("Tt__G_r", None)];
+    let err = NamespaceRegistry::build_many(&[&a, &b]).unwrap_err();
+    assert!(err.contains("GID collision"), "{err}");
+
+    // Duplicates within one set are still errors
+    let a = [NamespaceDef::new("X", None), NamespaceDef::new("X", None)];
+    assert!(NamespaceRegistry::build_many(&[&a]).is_err());
+}
@@ -0,0 +1,65 @@
+//! Tests for localization keys.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Combat {
+            #[loc_key = "combat-attack-basic"]
+            Attack;
+            HeavyAttack;
+        }
+        Ui {
+            Fire_Ball;
+            HUDIcon;
+        }
+    }
+}
+
+#[test]
+fn test_explicit_and_derived_loc_keys() {
+    assert_eq!(Tags::Combat::Attack::Tag::LOC_KEY, "combat-attack-basic");
+    assert_eq!(Tags::Combat::HeavyAttack::Tag::LOC_KEY, "tag-combat-heavy-attack");
+    assert_eq!(Tags::Combat::Tag::LOC_KEY, "tag-combat");
+}
+
+#[test]
+fn test_macro_and_runtime_derive_same_key() {
+    assert_eq!(
+        Tags::Combat::HeavyAttack::Tag::LOC_KEY,
+        default_loc_key(Tags::Combat::HeavyAttack::PATH)
+    );
+}
+
+#[test]
+fn test_macro_and_runtime_agree_on_underscores_and_acronyms() {
+    assert_eq!(Tags::Ui::Fire_Ball::Tag::LOC_KEY, "tag-ui-fire-ball");
+    assert_eq!(Tags::Ui::HUDIcon::Tag::LOC_KEY, "tag-ui-hud-icon");
+    for def in Tags::DEFINITIONS.iter().filter(|d| d.path.starts_with("Ui")) {
+        assert_eq!(def.loc_key.unwrap(), default_loc_key(def.path), "{}", def.path);
+    }
+}
+
+#[test]
+fn test_loc_keys_extractable_from_definitions() {
+    let keys: Vec<_> = Tags::DEFINITIONS
+        .iter()
+        .filter(|d| d.path.starts_with("Combat"))
+        .map(|d| (d.loc_key.unwrap(), d.path))
+        .collect();
+    assert_eq!(
+        keys,
+        vec![
+            ("tag-combat", "Combat"),
+            ("combat-attack-basic", "Combat.Attack"),
+            ("tag-combat-heavy-attack", "Combat.HeavyAttack"),
+        ]
+    );
+
+    let reg = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    assert_eq!(
+        reg.loc_key_of(Tags::Combat::Attack::GID).as_deref(),
+        Some("combat-attack-basic")
+    );
+}
@@ -0,0 +1,41 @@
+//! Tests for the generated const path -> GID lookup.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Movement {
+            Idle;
+            Running;
+        }
+        MovementExtra;
+        Combat {
+            #[redirect = "Movement.Running"]
+            Charge;
+        }
+    }
+}
+
+#[test]
+fn test_lookup_matches_registry() {
+    let reg = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    for def in Tags::DEFINITIONS {
+        assert_eq!(Tags::lookup(def.path), reg.gid_of(def.path), "{}", def.path);
+    }
+}
+
+#[test]
+fn test_lookup_in_const_context() {
+    const IDLE: Option<GID> = Tags::lookup("Movement.Idle");
+    assert_eq!(IDLE, Some(Tags::Movement::Idle::GID));
+    assert_eq!(Tags::lookup("Movement.Walking"), None);
+    assert_eq!(Tags::lookup("Movement."), None);
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_lookup_resolves_redirects() {
+    assert_eq!(Tags::lookup("Combat.Charge"), Some(Tags::Movement::Running::GID));
+    assert_eq!(Tags::PATH_TABLE.len(), 6);
+}
@@ -75,6 +75,38 @@ fn test_data_type_association() {
     requires_movement_data::<GameplayTags::Movement::Dash::Tag>();
 }
 
+namespace! {
+    pub mod TypedTags {
+        Inventory<Vec<(u8, std::string::String)>>;
+        Scale<[f32; 3]>;
+    }
+}
+
+#[test]
+fn test_data_type_reaches_registry() {
+    let registry = NamespaceRegistry::build(GameplayTags::DEFINITIONS).unwrap();
+    assert_eq!(
+        registry.data_type_of(GameplayTags::HeavyAttack::GID),
+        Some("crate::AbilityData")
+    );
+    assert_eq!(
+        registry.data_type_of(GameplayTags::Movement::Dash::GID),
+        Some("crate::MovementData")
+    );
+    assert_eq!(registry.data_type_of(GameplayTags::BasicAttack::GID), None);
+    assert_eq!(
+        GameplayTags::STATIC_REGISTRY.data_type_of(GameplayTags::Status::GID),
+        Some("crate::AbilityData")
+    );
+
+    let typed = NamespaceRegistry::build(TypedTags::DEFINITIONS).unwrap();
+    assert_eq!(
+        typed.data_type_of(TypedTags::Inventory::GID),
+        Some("Vec<(u8, std::string::String)>")
+    );
+    assert_eq!(typed.data_type_of(TypedTags::Scale::GID), Some("[f32; 3]"));
+}
+
 #[test]
 fn test_gid_still_works() {
     // Ensure GID generation still works correctly
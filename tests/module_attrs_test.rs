@@ -0,0 +1,26 @@
+//! Tests for root-module attributes and extra `Tag` derives.
+
+bevy_tag::namespace!(derive(PartialOrd, Ord);
+    /// Tags with a doc comment on the root module.
+    #[allow(missing_docs)]
+    pub(crate) mod Tags {
+        Movement {
+            Idle;
+            Running;
+        }
+    }
+);
+
+#[test]
+fn test_extra_derives_on_every_tag() {
+    fn assert_ord<T: Ord>(_: T) {}
+    assert_ord(Tags::Movement::Tag);
+    assert_ord(Tags::Movement::Idle::Tag);
+    assert!(Tags::Movement::Running::Tag >= Tags::Movement::Running::Tag);
+}
+
+#[test]
+fn test_root_attributes_keep_module_usable() {
+    assert_eq!(Tags::Movement::Idle::PATH, "Movement.Idle");
+    assert_eq!(Tags::DEFINITIONS.len(), 3);
+}
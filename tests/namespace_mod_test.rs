@@ -0,0 +1,67 @@
+//! Tests for the `#[namespace_mod]` attribute form.
+
+#![allow(deprecated)]
+
+use bevy_tag::NamespaceRegistry;
+
+/// Gameplay tags, written as ordinary Rust modules.
+#[bevy_tag::namespace_mod]
+pub mod Tags {
+    pub mod Movement {
+        /// Standing still.
+        #[description]
+        pub mod Idle {}
+
+        /// Docs only, not registry metadata.
+        #[data(f32)]
+        pub mod Running {}
+    }
+
+    pub mod Combat {
+        #[display_name = "Heavy Attack"]
+        #[description = "A slow, powerful swing"]
+        pub mod HeavyAttack {}
+
+        #[deprecated(since = "0.2.0")]
+        #[redirect = "Combat.HeavyAttack"]
+        pub mod Smash {}
+    }
+}
+
+mod function_like {
+    bevy_tag::namespace! {
+        pub mod Tags {
+            Movement {
+                Idle;
+                Running<f32>;
+            }
+            Combat {
+                HeavyAttack;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_attribute_form_matches_function_like() {
+    assert_eq!(Tags::Movement::Idle::GID, function_like::Tags::Movement::Idle::GID);
+    assert_eq!(Tags::Combat::HeavyAttack::PATH, "Combat.HeavyAttack");
+    assert_eq!(Tags::NODE_COUNT, function_like::Tags::NODE_COUNT);
+}
+
+#[test]
+fn test_attribute_form_node_attributes() {
+    assert_eq!(Tags::Movement::Idle::Tag::DESCRIPTION, "Standing still.");
+    assert_eq!(Tags::Movement::Running::Tag::DESCRIPTION, "");
+    assert_eq!(Tags::Combat::HeavyAttack::Tag::DISPLAY_NAME, "Heavy Attack");
+    assert_eq!(Tags::Combat::Smash::GID, Tags::Combat::HeavyAttack::GID);
+
+    fn data_of<T: bevy_tag::HasData<Data = f32>>() {}
+    data_of::<Tags::Movement::Running::Tag>();
+}
+
+#[test]
+fn test_attribute_form_builds_registry() {
+    let reg = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    assert_eq!(reg.description_of(Tags::Movement::Idle::GID), Some("Standing still."));
+}
@@ -0,0 +1,41 @@
+//! Tests for `#[no_registry]` compile-time-only nodes.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Combat {
+            Attack;
+            #[no_registry]
+            Debug;
+        }
+        #[no_registry]
+        Editor {
+            Gizmo;
+        }
+    }
+}
+
+#[test]
+fn test_no_registry_nodes_keep_type_and_gid() {
+    assert_eq!(Tags::Combat::Debug::PATH, "Combat.Debug");
+    assert_eq!(Tags::Editor::Gizmo::PATH, "Editor.Gizmo");
+    assert!(gid_is_descendant_of(Tags::Combat::Debug::GID, Tags::Combat::GID));
+    assert!(gid_is_descendant_of(Tags::Editor::Gizmo::GID, Tags::Editor::GID));
+}
+
+#[test]
+fn test_no_registry_subtrees_left_out_of_definitions() {
+    let paths: Vec<_> = Tags::DEFINITIONS.iter().map(|d| d.path).collect();
+    assert_eq!(paths, vec!["Combat", "Combat.Attack"]);
+    assert_eq!(Tags::NODE_COUNT, 2);
+}
+
+#[test]
+fn test_registry_builds_without_no_registry_nodes() {
+    let reg = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    assert!(reg.contains("Combat.Attack"));
+    assert!(!reg.contains("Combat.Debug"));
+    assert_eq!(reg.path_of(Tags::Editor::Gizmo::GID), None);
+}
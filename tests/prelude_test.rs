@@ -0,0 +1,61 @@
+//! Tests for the generated leaf-tag prelude.
+
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Movement {
+            Idle;
+            Running;
+        }
+        Combat {
+            Attack;
+            Block;
+        }
+        Skill {
+            Attack;
+        }
+        #[deprecated]
+        Legacy {
+            Old;
+        }
+        #[redirect = "Movement.Running"]
+        Sprint;
+    }
+}
+
+mod gameplay {
+    use super::Tags::prelude::*;
+    use bevy_tag::NamespaceTag;
+
+    pub fn unique_leaf_paths() -> [&'static str; 3] {
+        [Idle::PATH, Running::PATH, Block::PATH]
+    }
+
+    pub fn ambiguous_leaf_paths() -> [&'static str; 2] {
+        [CombatAttack::PATH, <SkillAttack as NamespaceTag>::PATH]
+    }
+
+    pub fn unit_value() -> Idle {
+        Idle
+    }
+}
+
+#[test]
+fn test_unique_leaves_use_short_names() {
+    assert_eq!(
+        gameplay::unique_leaf_paths(),
+        ["Movement.Idle", "Movement.Running", "Combat.Block"]
+    );
+}
+
+#[test]
+fn test_ambiguous_leaves_use_full_path_names() {
+    assert_eq!(gameplay::ambiguous_leaf_paths(), ["Combat.Attack", "Skill.Attack"]);
+}
+
+#[test]
+fn test_prelude_exports_the_tag_type() {
+    assert_eq!(gameplay::unit_value(), Tags::Movement::Idle::Tag);
+    assert_eq!(gameplay::unit_value().to_string(), "Movement.Idle");
+}
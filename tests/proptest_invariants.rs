@@ -0,0 +1,149 @@
+//! Property tests for the GID layout, hashing and registry invariants.
+
+use std::collections::{BTreeSet, HashMap};
+
+use bevy_tag::*;
+use proptest::prelude::*;
+
+/// Bit position of the depth field.
+const DEPTH_SHIFT: u32 = 125;
+
+/// A valid path segment.
+fn segment() -> impl Strategy<Value = String> {
+    "[A-Z][A-Za-z0-9_]{0,7}"
+}
+
+/// A path of 1..=MAX_DEPTH segments.
+fn path() -> impl Strategy<Value = Vec<String>> {
+    prop::collection::vec(segment(), 1..=MAX_DEPTH)
+}
+
+fn hash_path(segments: &[String]) -> GID {
+    let bytes: Vec<&[u8]> = segments.iter().map(|s| s.as_bytes()).collect();
+    hierarchical_gid(&bytes)
+}
+
+/// Every path in `paths` plus all of its ancestors, parents first.
+fn closed_tree(paths: &[Vec<String>]) -> BTreeSet<String> {
+    paths
+        .iter()
+        .flat_map(|segments| (1..=segments.len()).map(|n| segments[..n].join(".")))
+        .collect()
+}
+
+fn definitions(paths: &BTreeSet<String>) -> Vec<NamespaceDef> {
+    // BTreeSet order puts every parent before its children
+    paths
+        .iter()
+        .map(|path| {
+            let path: &'static str = Box::leak(path.clone().into_boxed_str());
+            NamespaceDef::new(path, path.rsplit_once('.').map(|(parent, _)| parent))
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn depth_bits_hold_only_the_depth(segments in path()) {
+        let gid = hash_path(&segments);
+        prop_assert_eq!((gid >> DEPTH_SHIFT) as usize, segments.len() - 1);
+        prop_assert_eq!(depth_of(gid) as usize, segments.len() - 1);
+        prop_assert!(is_well_formed(gid));
+    }
+
+    #[test]
+    fn ancestors_follow_the_path(segments in path()) {
+        let gid = hash_path(&segments);
+        let mut expected_parent = None;
+        for n in 1..=segments.len() {
+            let ancestor = hash_path(&segments[..n]);
+            prop_assert!(gid_is_descendant_of(gid, ancestor));
+            prop_assert_eq!(parent_of(ancestor), expected_parent);
+            for level in 0..MAX_DEPTH {
+                let expected = if level < n { segment_of(gid, level) } else { 0 };
+                prop_assert_eq!(segment_of(ancestor, level), expected);
+            }
+            expected_parent = Some(ancestor);
+        }
+    }
+
+    #[test]
+    fn incremental_hashing_matches(segments in path()) {
+        let mut gid = ROOT_GID;
+        for (depth, segment) in segments.iter().enumerate() {
+            gid = child_gid(gid, depth as u8, segment.as_bytes());
+        }
+        prop_assert_eq!(gid, hash_path(&segments));
+    }
+
+    #[test]
+    fn siblings_share_only_their_parent(
+        parent in prop::collection::vec(segment(), 0..MAX_DEPTH),
+        a in segment(),
+        b in segment(),
+    ) {
+        let child = |name: &String| {
+            let mut segments = parent.clone();
+            segments.push(name.clone());
+            hash_path(&segments)
+        };
+        let (a, b) = (child(&a), child(&b));
+        // Segments are 13-21 bits wide, so distinct names can collide
+        prop_assume!(a != b);
+
+        prop_assert!(is_sibling(a, b));
+        prop_assert_eq!(parent_of(a), parent_of(b));
+        prop_assert!(!gid_is_descendant_of(a, b));
+        prop_assert!(!gid_is_descendant_of(b, a));
+    }
+
+    #[test]
+    fn pinned_segments_never_touch_the_depth_bits(
+        depth in 0..MAX_DEPTH,
+        seed in any::<u128>(),
+        parent_path in path(),
+    ) {
+        let parent = if depth == 0 {
+            ROOT_GID
+        } else {
+            hash_path(&parent_path.iter().cycle().take(depth).cloned().collect::<Vec<_>>())
+        };
+        let value = seed % ((1u128 << LEVEL_WIDTHS[depth]) - 1) + 1;
+        let gid = gid_with_segment(parent, depth as u8, value);
+        prop_assert_eq!((gid >> DEPTH_SHIFT) as usize, depth);
+        prop_assert_eq!(segment_of(gid, depth), value);
+        prop_assert!(is_well_formed(gid));
+    }
+
+    #[test]
+    fn register_after_build_keeps_existing_gids(
+        built in prop::collection::vec(path(), 1..24),
+        added in prop::collection::vec(path(), 1..24),
+    ) {
+        let defs = definitions(&closed_tree(&built));
+        // Random trees occasionally collide; that is `build`'s error to report
+        let Ok(mut registry) = NamespaceRegistry::build(&defs) else {
+            return Ok(());
+        };
+        let before: HashMap<String, GID> = registry
+            .entries()
+            .iter()
+            .map(|e| (e.path.clone(), e.gid))
+            .collect();
+
+        for segments in &added {
+            let _ = registry.register(&segments.join("."));
+        }
+
+        for (path, &gid) in &before {
+            prop_assert_eq!(registry.gid_of(path), Some(gid));
+            prop_assert_eq!(registry.path_of(gid), Some(path.as_str()));
+        }
+        for entry in registry.entries() {
+            if entry.is_dynamic {
+                let segments: Vec<String> = entry.path.split('.').map(str::to_string).collect();
+                prop_assert_eq!(entry.gid, hash_path(&segments));
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Tests for redirect chain resolution.
+
+#![allow(deprecated)]
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Equipment {
+            Blade;
+        }
+        Item {
+            // Item.Sword -> Weapon.Sword -> Equipment.Blade
+            #[redirect = "Weapon.Sword"]
+            Sword;
+        }
+        Weapon {
+            #[redirect = "Equipment.Blade"]
+            Sword;
+        }
+    }
+}
+
+#[test]
+fn test_chain_resolves_to_final_target() {
+    assert_eq!(Tags::Item::Sword::GID, Tags::Equipment::Blade::GID);
+    assert_eq!(Tags::Item::Sword::PATH, "Equipment.Blade");
+}
+
+#[test]
+fn test_chain_is_not_nested() {
+    // Both hops become a single Redirect to the canonical tag
+    let _: Redirect<Tags::Equipment::Blade::Tag> = Tags::Item::Sword::Tag::new();
+    let _: Redirect<Tags::Equipment::Blade::Tag> = Tags::Weapon::Sword::Tag::new();
+}
@@ -0,0 +1,76 @@
+//! Tests for serializing raw GIDs as hex or paths (`serde` feature).
+#![cfg(feature = "serde")]
+
+use bevy_tag::{GID, NamespaceRegistry, migrate::RedirectMap, serde_support::gid_path};
+use bevy_tag_macro::namespace;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+namespace! {
+    pub mod Tags {
+        Combat {
+            Attack;
+            #[segment = 7]
+            Block;
+        }
+    }
+}
+
+static REGISTRY: LazyLock<NamespaceRegistry> =
+    LazyLock::new(|| NamespaceRegistry::build(Tags::DEFINITIONS).unwrap());
+
+const OLD_PARRY: GID = 0x1234;
+
+static REDIRECTS: LazyLock<RedirectMap> = LazyLock::new(|| {
+    let mut redirects = RedirectMap::new();
+    redirects.insert_path("Combat.Parry", "Combat.Block");
+    redirects.insert(OLD_PARRY, Tags::Combat::Block::GID);
+    redirects
+});
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SaveSlot {
+    #[serde(with = "bevy_tag::serde_support::gid_path")]
+    stance: GID,
+    #[serde(with = "bevy_tag::serde_support::gid_hex")]
+    last: GID,
+}
+
+// One test, since the seeded registry is process-wide
+#[test]
+fn test_gid_path_uses_seeded_registry() {
+    let slot = SaveSlot {
+        stance: Tags::Combat::Attack::GID,
+        last: Tags::Combat::Attack::GID,
+    };
+    let hex = format!("0x{:032x}", Tags::Combat::Attack::GID);
+
+    // Before seeding: hex out, hashed paths in
+    let json = serde_json::to_string(&slot).unwrap();
+    assert_eq!(json, format!(r#"{{"stance":"{}","last":"{}"}}"#, hex, hex));
+    let parsed: SaveSlot =
+        serde_json::from_str(&format!(r#"{{"stance":"Combat.Attack","last":"{}"}}"#, hex)).unwrap();
+    assert_eq!(parsed, slot);
+
+    gid_path::seed(&REGISTRY);
+    let json = serde_json::to_string(&slot).unwrap();
+    assert_eq!(json, format!(r#"{{"stance":"Combat.Attack","last":"{}"}}"#, hex));
+    assert_eq!(serde_json::from_str::<SaveSlot>(&json).unwrap(), slot);
+
+    // Pinned GIDs resolve through the registry; unknown paths are rejected
+    let json = format!(r#"{{"stance":"Combat.Block","last":"{}"}}"#, hex);
+    let parsed: SaveSlot = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.stance, Tags::Combat::Block::GID);
+    let json = format!(r#"{{"stance":"Combat.Dodge","last":"{}"}}"#, hex);
+    assert!(serde_json::from_str::<SaveSlot>(&json).is_err());
+
+    // Redirected paths and GIDs load as their target; gid_hex is untouched
+    gid_path::seed_redirects(&REDIRECTS);
+    let old = format!("0x{:032x}", OLD_PARRY);
+    for stance in ["\"Combat.Parry\"", &format!("\"{}\"", old), "4660"] {
+        let json = format!(r#"{{"stance":{},"last":"{}"}}"#, stance, old);
+        let parsed: SaveSlot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.stance, Tags::Combat::Block::GID, "{}", stance);
+        assert_eq!(parsed.last, OLD_PARRY);
+    }
+}
@@ -0,0 +1,70 @@
+//! Tests for registry-scoped serialization of nested GIDs (`serde` feature).
+#![cfg(feature = "serde")]
+
+use bevy_tag::serde_support::{RegistrySeed, WithRegistry};
+use bevy_tag::{GID, NamespaceRegistry};
+use bevy_tag_macro::namespace;
+use serde::de::DeserializeSeed;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+namespace! {
+    pub mod Tags {
+        Status {
+            Stunned;
+            #[segment = 0x2A]
+            Burning;
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Effect {
+    #[serde(with = "bevy_tag::serde_support::gid_path")]
+    tag: GID,
+    turns: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Save {
+    party: BTreeMap<String, Vec<Effect>>,
+}
+
+fn save() -> Save {
+    let effects = vec![
+        Effect { tag: Tags::Status::Stunned::GID, turns: 1 },
+        Effect { tag: Tags::Status::Burning::GID, turns: 3 },
+    ];
+    Save { party: BTreeMap::from([("hero".to_string(), effects)]) }
+}
+
+#[test]
+fn test_nested_gids_roundtrip_as_paths() {
+    let registry = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    let save = save();
+
+    let json = serde_json::to_string(&WithRegistry::new(&registry, &save)).unwrap();
+    assert_eq!(
+        json,
+        r#"{"party":{"hero":[{"tag":"Status.Stunned","turns":1},{"tag":"Status.Burning","turns":3}]}}"#
+    );
+
+    let mut deserializer = serde_json::Deserializer::from_str(&json);
+    let back: Save = RegistrySeed::new(&registry).deserialize(&mut deserializer).unwrap();
+    assert_eq!(back, save);
+}
+
+#[test]
+fn test_scope_ends_with_the_call() {
+    let registry = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    serde_json::to_string(&WithRegistry::new(&registry, &save())).unwrap();
+
+    // Outside a scope (and without a seeded registry) GIDs fall back to hex
+    let json = serde_json::to_string(&save()).unwrap();
+    assert!(json.contains(&format!("\"0x{:032x}\"", Tags::Status::Stunned::GID)), "{}", json);
+
+    // Unknown paths are rejected inside a scope
+    let json = r#"{"party":{"hero":[{"tag":"Status.Frozen","turns":1}]}}"#;
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    assert!(RegistrySeed::<Save>::new(&registry).deserialize(&mut deserializer).is_err());
+}
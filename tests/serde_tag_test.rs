@@ -0,0 +1,52 @@
+//! Tests for serde support on generated tag types (`serde` feature).
+#![cfg(feature = "serde")]
+
+use bevy_tag_macro::namespace;
+use serde::{Deserialize, Serialize};
+
+namespace! {
+    pub mod Tags {
+        Equipment {
+            Weapon {
+                Blade;
+                Bow;
+            }
+        }
+        Legacy {
+            #[redirect = "Equipment.Weapon.Blade"]
+            OldSword;
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct WeaponConfig {
+    kind: Tags::Equipment::Weapon::Blade::Tag,
+    damage: u32,
+}
+
+#[test]
+fn test_tag_field_serializes_as_path() {
+    let config = WeaponConfig {
+        kind: Tags::Equipment::Weapon::Blade::Tag,
+        damage: 12,
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(json, r#"{"kind":"Equipment.Weapon.Blade","damage":12}"#);
+
+    let back: WeaponConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, config);
+}
+
+#[test]
+fn test_wrong_path_is_rejected() {
+    let json = r#"{"kind":"Equipment.Weapon.Bow","damage":12}"#;
+    assert!(serde_json::from_str::<WeaponConfig>(json).is_err());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_redirect_serializes_canonical_path() {
+    let json = serde_json::to_string(&Tags::Legacy::OldSword::Tag::new()).unwrap();
+    assert_eq!(json, "\"Equipment.Weapon.Blade\"");
+}
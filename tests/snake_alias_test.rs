@@ -0,0 +1,45 @@
+//! Tests for the hidden snake_case module aliases.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Movement {
+            Idle;
+            Running;
+        }
+        StatusEffect {
+            Stunned;
+        }
+        Type;
+        #[redirect = "Movement.Running"]
+        OldRun;
+    }
+}
+
+#[test]
+fn test_both_spellings_resolve_to_same_node() {
+    assert_eq!(Tags::movement::Idle::GID, Tags::Movement::Idle::GID);
+    assert_eq!(Tags::movement::idle::PATH, "Movement.Idle");
+    assert_eq!(Tags::status_effect::Stunned::GID, Tags::StatusEffect::Stunned::GID);
+}
+
+#[test]
+fn test_aliases_match_build_crate_paths() {
+    // bevy-tag-build emits `Tags::movement::Running` style paths
+    let tag = Tags::movement::Running::Tag;
+    assert_eq!(tag.to_string(), "Movement.Running");
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_redirect_alias() {
+    assert_eq!(Tags::old_run::GID, Tags::Movement::Running::GID);
+    assert_eq!(<Tags::old_run::Tag as NamespaceTag>::PATH, "Movement.Running");
+}
+
+#[test]
+fn test_keyword_names_keep_original_spelling() {
+    assert_eq!(Tags::Type::PATH, "Type");
+}
@@ -0,0 +1,121 @@
+//! Tests for the generated const `STATIC_REGISTRY`.
+
+use bevy_tag::*;
+use bevy_tag_macro::namespace;
+
+namespace! {
+    pub mod Tags {
+        Combat {
+            #[display_name = "Heavy Attack"]
+            #[description = "Slow, strong swing"]
+            HeavyAttack;
+            #[flags(Replicated)]
+            Block;
+            #[segment = 0x2A]
+            Parry;
+        }
+        Movement {
+            Idle;
+            #[deprecated(since = "0.2.0", note = "use Movement.Idle")]
+            Stand;
+            #[redirect = "Movement.Idle"]
+            Wait;
+        }
+        Debug {
+            #[cfg(any())]
+            GodMode;
+            NoClip;
+        }
+        #[no_registry]
+        Scratch;
+    }
+}
+
+const STATIC: StaticRegistry = Tags::STATIC_REGISTRY;
+
+#[test]
+fn matches_the_runtime_registry() {
+    let registry = NamespaceRegistry::build(Tags::DEFINITIONS).unwrap();
+    assert_eq!(STATIC.len(), registry.len());
+    assert_eq!(STATIC.tree_depth(), registry.tree_depth());
+
+    for entry in registry.entries() {
+        let gid = entry.gid;
+        assert_eq!(STATIC.gid_of(&entry.path), Some(gid), "{}", entry.path);
+        assert_eq!(STATIC.path_of(gid), registry.path_of(gid));
+        assert_eq!(STATIC.display_name_of(gid), registry.display_name_of(gid));
+        assert_eq!(STATIC.description_of(gid), registry.description_of(gid));
+        assert_eq!(STATIC.loc_key_of(gid), registry.loc_key_of(gid).as_deref());
+        assert_eq!(STATIC.flags_of(gid), registry.flags_of(gid));
+        assert_eq!(STATIC.deprecation_of(gid), registry.deprecation_of(gid));
+
+        let mut descendants: Vec<GID> = STATIC.descendants_of(gid).collect();
+        let mut expected = registry.descendants_of(gid);
+        descendants.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(descendants, expected, "{}", entry.path);
+    }
+    assert!(STATIC.entries().windows(2).all(|w| w[0].gid < w[1].gid));
+}
+
+#[test]
+#[allow(deprecated)]
+fn lookups_are_const() {
+    const IDLE: Option<GID> = STATIC.gid_of("Movement.Idle");
+    const { assert!(STATIC.contains("Combat.Parry")) };
+    const PARRY: Option<&StaticEntry> = STATIC.get(Tags::Combat::Parry::GID);
+    assert_eq!(IDLE, Some(Tags::Movement::Idle::GID));
+    assert_eq!(PARRY.map(|e| e.def.path), Some("Combat.Parry"));
+
+    // Only DEFINITIONS are registered: no redirects, cfg'd-out or
+    // `#[no_registry]` nodes
+    assert!(!STATIC.contains("Movement.Wait"));
+    assert!(!STATIC.contains("Debug.GodMode"));
+    assert!(!STATIC.contains("Scratch"));
+    assert!(!STATIC.contains_gid(Tags::Scratch::GID));
+}
+
+#[test]
+#[allow(deprecated)]
+fn descendant_and_flag_queries() {
+    assert_eq!(
+        STATIC.is_descendant_of_path("Combat.Block", "Combat"),
+        Some(true)
+    );
+    assert_eq!(
+        STATIC.is_descendant_of_path("Combat.Block", "Movement"),
+        Some(false)
+    );
+    assert_eq!(STATIC.is_descendant_of_path("Combat.Dodge", "Combat"), None);
+    assert!(STATIC.is_descendant_of(Tags::Debug::NoClip::GID, Tags::Debug::GID));
+
+    let replicated: Vec<GID> = STATIC.gids_with_flag(TagFlags::REPLICATED).collect();
+    assert_eq!(replicated, [Tags::Combat::Block::GID]);
+    assert!(STATIC.is_deprecated(Tags::Movement::Stand::GID));
+}
+
+#[test]
+fn sorts_hand_built_tables() {
+    const ENTRIES: [StaticEntry; 3] = StaticRegistry::sort_by_gid([
+        StaticEntry {
+            gid: 3,
+            def: NamespaceDef::new("A", None),
+        },
+        StaticEntry {
+            gid: 1,
+            def: NamespaceDef::new("C", None),
+        },
+        StaticEntry {
+            gid: 2,
+            def: NamespaceDef::new("B", None),
+        },
+    ]);
+    const INDEX: [usize; 3] = StaticRegistry::path_index(&ENTRIES);
+    const REGISTRY: StaticRegistry = StaticRegistry::new(&ENTRIES, &INDEX);
+
+    assert_eq!(ENTRIES.map(|e| e.gid), [1, 2, 3]);
+    assert_eq!(INDEX, [2, 1, 0]);
+    assert_eq!(REGISTRY.gid_of("B"), Some(2));
+    assert_eq!(REGISTRY.path_of(1u128), Some("C"));
+    assert_eq!(REGISTRY.gid_of("D"), None);
+}